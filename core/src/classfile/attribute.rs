@@ -1,10 +1,12 @@
-use super::constant_pool::ConstantPool;
+use super::constant_pool::{ConstantPool, ConstantPoolEntry};
 use super::error::Error;
+use super::flags::{InnerClassFlags, MethodParameterFlags};
 
 use crate::gc::Trace;
 use crate::reader::{FileData, Reader};
 use crate::string::JvmString;
 
+use alloc::boxed::Box;
 use alloc::vec::Vec;
 
 #[derive(Clone)]
@@ -14,7 +16,10 @@ pub struct Attribute {
 }
 
 impl Attribute {
-    pub fn read_from(data: &mut FileData<'_>, constant_pool: &ConstantPool) -> Result<Self, Error> {
+    pub(crate) fn read_from(
+        data: &mut FileData<'_>,
+        constant_pool: &ConstantPool,
+    ) -> Result<Self, Error> {
         let name_idx = data.read_u16_be()?;
         let name = constant_pool.get_utf8(name_idx)?;
 
@@ -31,6 +36,149 @@ impl Attribute {
     pub fn data(&self) -> &[u8] {
         &self.data
     }
+
+    /// Parse the data of this attribute according to its name, resolving any
+    /// constant pool references using the given constant pool. Attributes that
+    /// are not defined by the JVM specification (or that we don't yet know how
+    /// to parse) are returned as [`AttributeInfo::Unknown`].
+    ///
+    /// This method returns an [`Error`] if the attribute data is malformed.
+    pub fn parse(&self, constant_pool: &ConstantPool) -> Result<AttributeInfo, Error> {
+        let mut data = FileData::new(&self.data);
+
+        let info = match self.name.as_bytes() {
+            b"ConstantValue" => AttributeInfo::ConstantValue(data.read_u16_be()?),
+            b"Code" => AttributeInfo::Code(CodeAttribute::read_from(&mut data, constant_pool)?),
+            b"Exceptions" => AttributeInfo::Exceptions(read_class_list(&mut data, constant_pool)?),
+            b"InnerClasses" => {
+                let count = data.read_u16_be()?;
+                let mut inner_classes = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    inner_classes.push(InnerClass::read_from(&mut data, constant_pool)?);
+                }
+
+                AttributeInfo::InnerClasses(inner_classes.into_boxed_slice())
+            }
+            b"EnclosingMethod" => {
+                let class_idx = data.read_u16_be()?;
+                let method_idx = data.read_u16_be()?;
+
+                let class = constant_pool.get_class(class_idx)?;
+
+                // The method index is zero if the class is not immediately
+                // enclosed by a method or constructor
+                let method = if method_idx == 0 {
+                    None
+                } else {
+                    Some(constant_pool.get_name_and_type(method_idx)?)
+                };
+
+                AttributeInfo::EnclosingMethod { class, method }
+            }
+            b"Synthetic" => AttributeInfo::Synthetic,
+            b"Deprecated" => AttributeInfo::Deprecated,
+            b"Signature" => AttributeInfo::Signature(read_utf8(&mut data, constant_pool)?),
+            b"SourceFile" => AttributeInfo::SourceFile(read_utf8(&mut data, constant_pool)?),
+            b"LineNumberTable" => {
+                let count = data.read_u16_be()?;
+                let mut line_numbers = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    line_numbers.push(LineNumber {
+                        start_pc: data.read_u16_be()?,
+                        line_number: data.read_u16_be()?,
+                    });
+                }
+
+                AttributeInfo::LineNumberTable(line_numbers.into_boxed_slice())
+            }
+            b"LocalVariableTable" => {
+                AttributeInfo::LocalVariableTable(read_local_variables(&mut data, constant_pool)?)
+            }
+            b"LocalVariableTypeTable" => AttributeInfo::LocalVariableTypeTable(
+                read_local_variables(&mut data, constant_pool)?,
+            ),
+            b"BootstrapMethods" => {
+                let count = data.read_u16_be()?;
+                let mut bootstrap_methods = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    bootstrap_methods.push(BootstrapMethod::read_from(&mut data, constant_pool)?);
+                }
+
+                AttributeInfo::BootstrapMethods(bootstrap_methods.into_boxed_slice())
+            }
+            b"NestHost" => {
+                let class_idx = data.read_u16_be()?;
+
+                AttributeInfo::NestHost(constant_pool.get_class(class_idx)?)
+            }
+            b"NestMembers" => {
+                AttributeInfo::NestMembers(read_class_list(&mut data, constant_pool)?)
+            }
+            b"Record" => {
+                let count = data.read_u16_be()?;
+                let mut components = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    components.push(RecordComponent::read_from(&mut data, constant_pool)?);
+                }
+
+                AttributeInfo::Record(components.into_boxed_slice())
+            }
+            b"PermittedSubclasses" => {
+                AttributeInfo::PermittedSubclasses(read_class_list(&mut data, constant_pool)?)
+            }
+            b"MethodParameters" => {
+                let count = data.read_u8()?;
+                let mut parameters = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    let name_idx = data.read_u16_be()?;
+                    let flag_bits = data.read_u16_be()?;
+
+                    // A zero name index means the parameter has no name
+                    let name = if name_idx == 0 {
+                        None
+                    } else {
+                        Some(constant_pool.get_utf8(name_idx)?)
+                    };
+
+                    parameters.push(MethodParameter {
+                        name,
+                        flags: MethodParameterFlags::from_bits_truncate(flag_bits),
+                    });
+                }
+
+                AttributeInfo::MethodParameters(parameters.into_boxed_slice())
+            }
+            b"RuntimeVisibleAnnotations" => AttributeInfo::RuntimeVisibleAnnotations(
+                read_annotations(&mut data, constant_pool)?,
+            ),
+            b"RuntimeInvisibleAnnotations" => AttributeInfo::RuntimeInvisibleAnnotations(
+                read_annotations(&mut data, constant_pool)?,
+            ),
+            b"RuntimeVisibleParameterAnnotations" => {
+                AttributeInfo::RuntimeVisibleParameterAnnotations(read_parameter_annotations(
+                    &mut data,
+                    constant_pool,
+                )?)
+            }
+            b"RuntimeInvisibleParameterAnnotations" => {
+                AttributeInfo::RuntimeInvisibleParameterAnnotations(read_parameter_annotations(
+                    &mut data,
+                    constant_pool,
+                )?)
+            }
+            b"AnnotationDefault" => {
+                AttributeInfo::AnnotationDefault(ElementValue::read_from(&mut data, constant_pool)?)
+            }
+            _ => return Ok(AttributeInfo::Unknown),
+        };
+
+        // The attribute's length must match the length of its contents
+        if data.position() != data.len() {
+            return Err(Error::InvalidAttributeLength);
+        }
+
+        Ok(info)
+    }
 }
 
 impl Trace for Attribute {
@@ -38,3 +186,513 @@ impl Trace for Attribute {
         self.name.trace();
     }
 }
+
+/// The parsed contents of an [`Attribute`].
+pub enum AttributeInfo {
+    /// The constant pool index of the value of a constant field.
+    ConstantValue(u16),
+    Code(CodeAttribute),
+    /// The names of the checked exceptions a method is declared to throw.
+    Exceptions(Box<[JvmString]>),
+    InnerClasses(Box<[InnerClass]>),
+    /// The class enclosing a local or anonymous class, and the name and
+    /// descriptor of the enclosing method, if any.
+    EnclosingMethod {
+        class: JvmString,
+        method: Option<(JvmString, JvmString)>,
+    },
+    Synthetic,
+    Deprecated,
+    Signature(JvmString),
+    SourceFile(JvmString),
+    LineNumberTable(Box<[LineNumber]>),
+    LocalVariableTable(Box<[LocalVariable]>),
+    /// Like `LocalVariableTable`, but the `descriptor` of each entry is a
+    /// generic signature instead of a field descriptor.
+    LocalVariableTypeTable(Box<[LocalVariable]>),
+    BootstrapMethods(Box<[BootstrapMethod]>),
+    NestHost(JvmString),
+    NestMembers(Box<[JvmString]>),
+    Record(Box<[RecordComponent]>),
+    PermittedSubclasses(Box<[JvmString]>),
+    MethodParameters(Box<[MethodParameter]>),
+    RuntimeVisibleAnnotations(Box<[Annotation]>),
+    RuntimeInvisibleAnnotations(Box<[Annotation]>),
+    RuntimeVisibleParameterAnnotations(Box<[Box<[Annotation]>]>),
+    RuntimeInvisibleParameterAnnotations(Box<[Box<[Annotation]>]>),
+    AnnotationDefault(ElementValue),
+    Unknown,
+}
+
+impl Trace for AttributeInfo {
+    fn trace(&self) {
+        match self {
+            AttributeInfo::Code(code) => code.trace(),
+            AttributeInfo::Exceptions(classes)
+            | AttributeInfo::NestMembers(classes)
+            | AttributeInfo::PermittedSubclasses(classes) => classes.trace(),
+            AttributeInfo::InnerClasses(inner_classes) => inner_classes.trace(),
+            AttributeInfo::EnclosingMethod { class, method } => {
+                class.trace();
+                method.trace();
+            }
+            AttributeInfo::Signature(string)
+            | AttributeInfo::SourceFile(string)
+            | AttributeInfo::NestHost(string) => string.trace(),
+            AttributeInfo::LocalVariableTable(variables)
+            | AttributeInfo::LocalVariableTypeTable(variables) => variables.trace(),
+            AttributeInfo::Record(components) => components.trace(),
+            AttributeInfo::MethodParameters(parameters) => parameters.trace(),
+            AttributeInfo::RuntimeVisibleAnnotations(annotations)
+            | AttributeInfo::RuntimeInvisibleAnnotations(annotations) => annotations.trace(),
+            AttributeInfo::RuntimeVisibleParameterAnnotations(annotations)
+            | AttributeInfo::RuntimeInvisibleParameterAnnotations(annotations) => {
+                annotations.trace()
+            }
+            AttributeInfo::AnnotationDefault(value) => value.trace(),
+            AttributeInfo::ConstantValue(_)
+            | AttributeInfo::Synthetic
+            | AttributeInfo::Deprecated
+            | AttributeInfo::LineNumberTable(_)
+            | AttributeInfo::BootstrapMethods(_)
+            | AttributeInfo::Unknown => {}
+        }
+    }
+}
+
+pub struct CodeAttribute {
+    pub max_stack: u16,
+    pub max_locals: u16,
+    pub code: Box<[u8]>,
+    pub exception_table: Box<[ExceptionTableEntry]>,
+    pub attributes: Box<[Attribute]>,
+}
+
+impl CodeAttribute {
+    fn read_from(data: &mut FileData<'_>, constant_pool: &ConstantPool) -> Result<Self, Error> {
+        let max_stack = data.read_u16_be()?;
+        let max_locals = data.read_u16_be()?;
+
+        let code_length = data.read_u32_be()?;
+        let code = data.read_bytes(code_length as usize)?;
+
+        let exception_count = data.read_u16_be()?;
+        let mut exception_table = Vec::with_capacity(exception_count as usize);
+        for _ in 0..exception_count {
+            let start_pc = data.read_u16_be()?;
+            let end_pc = data.read_u16_be()?;
+            let handler_pc = data.read_u16_be()?;
+            let catch_type_idx = data.read_u16_be()?;
+
+            // A zero catch type index means the handler catches everything
+            let catch_type = if catch_type_idx == 0 {
+                None
+            } else {
+                Some(constant_pool.get_class(catch_type_idx)?)
+            };
+
+            exception_table.push(ExceptionTableEntry {
+                start_pc,
+                end_pc,
+                handler_pc,
+                catch_type,
+            });
+        }
+
+        let attributes = read_attributes(data, constant_pool)?;
+
+        Ok(Self {
+            max_stack,
+            max_locals,
+            code: code.into_boxed_slice(),
+            exception_table: exception_table.into_boxed_slice(),
+            attributes,
+        })
+    }
+}
+
+impl Trace for CodeAttribute {
+    fn trace(&self) {
+        self.exception_table.trace();
+        self.attributes.trace();
+    }
+}
+
+pub struct ExceptionTableEntry {
+    pub start_pc: u16,
+    pub end_pc: u16,
+    pub handler_pc: u16,
+    pub catch_type: Option<JvmString>,
+}
+
+impl Trace for ExceptionTableEntry {
+    fn trace(&self) {
+        self.catch_type.trace();
+    }
+}
+
+pub struct InnerClass {
+    pub inner_class: JvmString,
+    /// The class this class is a member of. This is `None` for local and
+    /// anonymous classes.
+    pub outer_class: Option<JvmString>,
+    /// The simple name of this class. This is `None` for anonymous classes.
+    pub inner_name: Option<JvmString>,
+    pub flags: InnerClassFlags,
+}
+
+impl InnerClass {
+    fn read_from(data: &mut FileData<'_>, constant_pool: &ConstantPool) -> Result<Self, Error> {
+        let inner_class_idx = data.read_u16_be()?;
+        let outer_class_idx = data.read_u16_be()?;
+        let inner_name_idx = data.read_u16_be()?;
+        let flag_bits = data.read_u16_be()?;
+
+        let inner_class = constant_pool.get_class(inner_class_idx)?;
+
+        let outer_class = if outer_class_idx == 0 {
+            None
+        } else {
+            Some(constant_pool.get_class(outer_class_idx)?)
+        };
+
+        let inner_name = if inner_name_idx == 0 {
+            None
+        } else {
+            Some(constant_pool.get_utf8(inner_name_idx)?)
+        };
+
+        Ok(Self {
+            inner_class,
+            outer_class,
+            inner_name,
+            flags: InnerClassFlags::from_bits_truncate(flag_bits),
+        })
+    }
+}
+
+impl Trace for InnerClass {
+    fn trace(&self) {
+        self.inner_class.trace();
+        self.outer_class.trace();
+        self.inner_name.trace();
+    }
+}
+
+pub struct LineNumber {
+    pub start_pc: u16,
+    pub line_number: u16,
+}
+
+pub struct LocalVariable {
+    pub start_pc: u16,
+    pub length: u16,
+    pub name: JvmString,
+    pub descriptor: JvmString,
+    pub index: u16,
+}
+
+impl Trace for LocalVariable {
+    fn trace(&self) {
+        self.name.trace();
+        self.descriptor.trace();
+    }
+}
+
+pub struct BootstrapMethod {
+    /// The constant pool index of the `MethodHandle` of the bootstrap method.
+    pub method_ref: u16,
+    /// The constant pool indices of the static arguments passed to the
+    /// bootstrap method.
+    pub arguments: Box<[u16]>,
+}
+
+impl BootstrapMethod {
+    fn read_from(data: &mut FileData<'_>, constant_pool: &ConstantPool) -> Result<Self, Error> {
+        let method_ref = data.read_u16_be()?;
+        if !matches!(
+            constant_pool.entry(method_ref)?,
+            ConstantPoolEntry::MethodHandle { .. }
+        ) {
+            return Err(Error::ConstantPoolTypeMismatch);
+        }
+
+        let argument_count = data.read_u16_be()?;
+        let mut arguments = Vec::with_capacity(argument_count as usize);
+        for _ in 0..argument_count {
+            let argument = data.read_u16_be()?;
+
+            // Make sure the argument index is valid
+            constant_pool.entry(argument)?;
+
+            arguments.push(argument);
+        }
+
+        Ok(Self {
+            method_ref,
+            arguments: arguments.into_boxed_slice(),
+        })
+    }
+}
+
+pub struct RecordComponent {
+    pub name: JvmString,
+    pub descriptor: JvmString,
+    pub attributes: Box<[Attribute]>,
+}
+
+impl RecordComponent {
+    fn read_from(data: &mut FileData<'_>, constant_pool: &ConstantPool) -> Result<Self, Error> {
+        let name = read_utf8(data, constant_pool)?;
+        let descriptor = read_utf8(data, constant_pool)?;
+        let attributes = read_attributes(data, constant_pool)?;
+
+        Ok(Self {
+            name,
+            descriptor,
+            attributes,
+        })
+    }
+}
+
+impl Trace for RecordComponent {
+    fn trace(&self) {
+        self.name.trace();
+        self.descriptor.trace();
+        self.attributes.trace();
+    }
+}
+
+pub struct MethodParameter {
+    pub name: Option<JvmString>,
+    pub flags: MethodParameterFlags,
+}
+
+impl Trace for MethodParameter {
+    fn trace(&self) {
+        self.name.trace();
+    }
+}
+
+#[derive(Clone)]
+pub struct Annotation {
+    /// The field descriptor of the annotation interface.
+    pub type_name: JvmString,
+    pub elements: Box<[(JvmString, ElementValue)]>,
+}
+
+impl Annotation {
+    fn read_from(data: &mut FileData<'_>, constant_pool: &ConstantPool) -> Result<Self, Error> {
+        let type_name = read_utf8(data, constant_pool)?;
+
+        let element_count = data.read_u16_be()?;
+        let mut elements = Vec::with_capacity(element_count as usize);
+        for _ in 0..element_count {
+            let name = read_utf8(data, constant_pool)?;
+            let value = ElementValue::read_from(data, constant_pool)?;
+
+            elements.push((name, value));
+        }
+
+        Ok(Self {
+            type_name,
+            elements: elements.into_boxed_slice(),
+        })
+    }
+}
+
+impl Trace for Annotation {
+    fn trace(&self) {
+        self.type_name.trace();
+        self.elements.trace();
+    }
+}
+
+#[derive(Clone)]
+pub enum ElementValue {
+    Byte(i8),
+    Char(u16),
+    Double(f64),
+    Float(f32),
+    Int(i32),
+    Long(i64),
+    Short(i16),
+    Boolean(bool),
+    String(JvmString),
+    /// An enum constant; the first string is the field descriptor of the enum
+    /// class and the second is the name of the constant.
+    Enum(JvmString, JvmString),
+    /// A class literal, stored as a return descriptor (e.g. `V` for
+    /// `void.class`).
+    Class(JvmString),
+    Annotation(Box<Annotation>),
+    Array(Box<[ElementValue]>),
+}
+
+impl ElementValue {
+    fn read_from(data: &mut FileData<'_>, constant_pool: &ConstantPool) -> Result<Self, Error> {
+        let tag = data.read_u8()?;
+
+        let value = match tag {
+            b'B' | b'C' | b'I' | b'S' | b'Z' => {
+                let const_idx = data.read_u16_be()?;
+                let ConstantPoolEntry::Integer { value } = constant_pool.entry(const_idx)? else {
+                    return Err(Error::ConstantPoolTypeMismatch);
+                };
+
+                match tag {
+                    b'B' => ElementValue::Byte(value as i8),
+                    b'C' => ElementValue::Char(value as u16),
+                    b'I' => ElementValue::Int(value),
+                    b'S' => ElementValue::Short(value as i16),
+                    b'Z' => ElementValue::Boolean(value != 0),
+                    _ => unreachable!(),
+                }
+            }
+            b'D' => {
+                let const_idx = data.read_u16_be()?;
+                let ConstantPoolEntry::Double { value } = constant_pool.entry(const_idx)? else {
+                    return Err(Error::ConstantPoolTypeMismatch);
+                };
+
+                ElementValue::Double(value)
+            }
+            b'F' => {
+                let const_idx = data.read_u16_be()?;
+                let ConstantPoolEntry::Float { value } = constant_pool.entry(const_idx)? else {
+                    return Err(Error::ConstantPoolTypeMismatch);
+                };
+
+                ElementValue::Float(value)
+            }
+            b'J' => {
+                let const_idx = data.read_u16_be()?;
+                let ConstantPoolEntry::Long { value } = constant_pool.entry(const_idx)? else {
+                    return Err(Error::ConstantPoolTypeMismatch);
+                };
+
+                ElementValue::Long(value)
+            }
+            b's' => ElementValue::String(read_utf8(data, constant_pool)?),
+            b'e' => {
+                let type_name = read_utf8(data, constant_pool)?;
+                let const_name = read_utf8(data, constant_pool)?;
+
+                ElementValue::Enum(type_name, const_name)
+            }
+            b'c' => ElementValue::Class(read_utf8(data, constant_pool)?),
+            b'@' => ElementValue::Annotation(Box::new(Annotation::read_from(data, constant_pool)?)),
+            b'[' => {
+                let count = data.read_u16_be()?;
+                let mut values = Vec::with_capacity(count as usize);
+                for _ in 0..count {
+                    values.push(ElementValue::read_from(data, constant_pool)?);
+                }
+
+                ElementValue::Array(values.into_boxed_slice())
+            }
+            _ => return Err(Error::InvalidElementValueTag),
+        };
+
+        Ok(value)
+    }
+}
+
+impl Trace for ElementValue {
+    fn trace(&self) {
+        match self {
+            ElementValue::String(string) | ElementValue::Class(string) => string.trace(),
+            ElementValue::Enum(type_name, const_name) => {
+                type_name.trace();
+                const_name.trace();
+            }
+            ElementValue::Annotation(annotation) => annotation.trace(),
+            ElementValue::Array(values) => values.trace(),
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn read_attributes(
+    data: &mut FileData<'_>,
+    constant_pool: &ConstantPool,
+) -> Result<Box<[Attribute]>, Error> {
+    let attribute_count = data.read_u16_be()?;
+    let mut attribute_list = Vec::with_capacity(attribute_count as usize);
+    for _ in 0..attribute_count {
+        attribute_list.push(Attribute::read_from(data, constant_pool)?);
+    }
+
+    Ok(attribute_list.into_boxed_slice())
+}
+
+fn read_utf8(data: &mut FileData<'_>, constant_pool: &ConstantPool) -> Result<JvmString, Error> {
+    let idx = data.read_u16_be()?;
+
+    constant_pool.get_utf8(idx)
+}
+
+fn read_class_list(
+    data: &mut FileData<'_>,
+    constant_pool: &ConstantPool,
+) -> Result<Box<[JvmString]>, Error> {
+    let count = data.read_u16_be()?;
+    let mut classes = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let class_idx = data.read_u16_be()?;
+        classes.push(constant_pool.get_class(class_idx)?);
+    }
+
+    Ok(classes.into_boxed_slice())
+}
+
+fn read_local_variables(
+    data: &mut FileData<'_>,
+    constant_pool: &ConstantPool,
+) -> Result<Box<[LocalVariable]>, Error> {
+    let count = data.read_u16_be()?;
+    let mut variables = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let start_pc = data.read_u16_be()?;
+        let length = data.read_u16_be()?;
+        let name = read_utf8(data, constant_pool)?;
+        let descriptor = read_utf8(data, constant_pool)?;
+        let index = data.read_u16_be()?;
+
+        variables.push(LocalVariable {
+            start_pc,
+            length,
+            name,
+            descriptor,
+            index,
+        });
+    }
+
+    Ok(variables.into_boxed_slice())
+}
+
+fn read_annotations(
+    data: &mut FileData<'_>,
+    constant_pool: &ConstantPool,
+) -> Result<Box<[Annotation]>, Error> {
+    let count = data.read_u16_be()?;
+    let mut annotations = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        annotations.push(Annotation::read_from(data, constant_pool)?);
+    }
+
+    Ok(annotations.into_boxed_slice())
+}
+
+fn read_parameter_annotations(
+    data: &mut FileData<'_>,
+    constant_pool: &ConstantPool,
+) -> Result<Box<[Box<[Annotation]>]>, Error> {
+    let count = data.read_u8()?;
+    let mut parameters = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        parameters.push(read_annotations(data, constant_pool)?);
+    }
+
+    Ok(parameters.into_boxed_slice())
+}
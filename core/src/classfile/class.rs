@@ -1,4 +1,4 @@
-use super::attribute::Attribute;
+use super::attribute::{Attribute, read_attributes};
 use super::constant_pool::{ConstantPool, read_constant_pool};
 use super::error::Error;
 use super::field::Field;
@@ -21,6 +21,9 @@ use alloc::vec::Vec;
 pub struct ClassFile(Gc<ClassFileData>);
 
 struct ClassFileData {
+    minor_version: u16,
+    major_version: u16,
+
     constant_pool: ConstantPool,
 
     flags: ClassFlags,
@@ -50,8 +53,8 @@ impl ClassFile {
             return Err(Error::InvalidMagic);
         }
 
-        let _minor_version = reader.read_u16_be()?;
-        let _major_version = reader.read_u16_be()?;
+        let minor_version = reader.read_u16_be()?;
+        let major_version = reader.read_u16_be()?;
 
         let constant_pool = read_constant_pool(gc_ctx, interner, &mut reader)?;
 
@@ -91,15 +94,13 @@ impl ClassFile {
             method_list.push(Method::read_from(&mut reader, &constant_pool)?);
         }
 
-        let attribute_count = reader.read_u16_be()?;
-        let mut attribute_list = Vec::with_capacity(attribute_count as usize);
-        for _ in 0..attribute_count {
-            attribute_list.push(Attribute::read_from(&mut reader, &constant_pool)?);
-        }
+        let attributes = read_attributes(&mut reader, &constant_pool)?;
 
         Ok(Self(Gc::new(
             gc_ctx,
             ClassFileData {
+                minor_version,
+                major_version,
                 constant_pool,
                 flags,
                 this_class,
@@ -107,12 +108,23 @@ impl ClassFile {
                 interfaces: interface_list.into_boxed_slice(),
                 fields: field_list.into_boxed_slice(),
                 methods: method_list.into_boxed_slice(),
-                attributes: attribute_list.into_boxed_slice(),
+                attributes,
             },
         )))
     }
 
-    pub(crate) fn constant_pool(&self) -> &ConstantPool {
+    /// The minor version of this class file.
+    pub fn minor_version(self) -> u16 {
+        self.0.minor_version
+    }
+
+    /// The major version of this class file (e.g. 52 for Java 8).
+    pub fn major_version(self) -> u16 {
+        self.0.major_version
+    }
+
+    /// The constant pool of this class file.
+    pub fn constant_pool(&self) -> &ConstantPool {
         &self.0.constant_pool
     }
 
@@ -136,15 +148,19 @@ impl ClassFile {
         &self.0.interfaces
     }
 
-    pub(crate) fn fields(&self) -> &[Field] {
+    /// The fields declared by this class.
+    pub fn fields(&self) -> &[Field] {
         &self.0.fields
     }
 
-    pub(crate) fn methods(&self) -> &[Method] {
+    /// The methods declared by this class.
+    pub fn methods(&self) -> &[Method] {
         &self.0.methods
     }
 
-    pub(crate) fn attributes(&self) -> &[Attribute] {
+    /// The attributes of this class. Use [`Attribute::parse`] to interpret
+    /// their contents.
+    pub fn attributes(&self) -> &[Attribute] {
         &self.0.attributes
     }
 }
@@ -6,22 +6,25 @@ use crate::string::{JvmString, JvmStringInterner};
 
 use alloc::vec::Vec;
 
-const PLACEHOLDER: u8 = 0;
-const UTF8: u8 = 1;
-const INTEGER: u8 = 3;
-const FLOAT: u8 = 4;
-const LONG: u8 = 5;
-const DOUBLE: u8 = 6;
-const CLASS: u8 = 7;
-const STRING: u8 = 8;
-const FIELD_REF: u8 = 9;
-const METHOD_REF: u8 = 10;
-const INTERFACE_METHOD_REF: u8 = 11;
-const NAME_AND_TYPE: u8 = 12;
-const METHOD_HANDLE: u8 = 15;
-const METHOD_TYPE: u8 = 16;
-const INVOKE_DYNAMIC: u8 = 18;
-
+pub(crate) const PLACEHOLDER: u8 = 0;
+pub(crate) const UTF8: u8 = 1;
+pub(crate) const INTEGER: u8 = 3;
+pub(crate) const FLOAT: u8 = 4;
+pub(crate) const LONG: u8 = 5;
+pub(crate) const DOUBLE: u8 = 6;
+pub(crate) const CLASS: u8 = 7;
+pub(crate) const STRING: u8 = 8;
+pub(crate) const FIELD_REF: u8 = 9;
+pub(crate) const METHOD_REF: u8 = 10;
+pub(crate) const INTERFACE_METHOD_REF: u8 = 11;
+pub(crate) const NAME_AND_TYPE: u8 = 12;
+pub(crate) const METHOD_HANDLE: u8 = 15;
+pub(crate) const METHOD_TYPE: u8 = 16;
+pub(crate) const DYNAMIC: u8 = 17;
+pub(crate) const INVOKE_DYNAMIC: u8 = 18;
+
+/// The constant pool of a class file. Entries are indexed starting at 1, as in
+/// the class file itself.
 pub struct ConstantPool {
     entries: Vec<ConstantPoolEntry>,
 }
@@ -158,8 +161,11 @@ impl ConstantPool {
                     self.ensure_entry_type(descriptor_idx, UTF8)?;
                 }
 
-                // InvokeDynamic must point to a NameAndType
-                ConstantPoolEntry::InvokeDynamic {
+                // Dynamic and InvokeDynamic must point to a NameAndType
+                ConstantPoolEntry::Dynamic {
+                    name_and_type_idx, ..
+                }
+                | ConstantPoolEntry::InvokeDynamic {
                     name_and_type_idx, ..
                 } => {
                     // Should we verify the `bootstrap_method_idx` here?
@@ -179,6 +185,13 @@ impl ConstantPool {
         }
     }
 
+    /// The number of entries in this constant pool, including the placeholder
+    /// entries following `Long` and `Double` entries. Valid indices range from
+    /// 1 to this number inclusive.
+    pub fn entry_count(&self) -> u16 {
+        self.entries.len() as u16
+    }
+
     pub fn entry(&self, index: u16) -> Result<ConstantPoolEntry, Error> {
         if index == 0 {
             Err(Error::ExpectedNonZero)
@@ -351,6 +364,10 @@ pub enum ConstantPoolEntry {
     MethodType {
         descriptor_idx: u16,
     },
+    Dynamic {
+        bootstrap_method_idx: u16,
+        name_and_type_idx: u16,
+    },
     InvokeDynamic {
         bootstrap_method_idx: u16,
         name_and_type_idx: u16,
@@ -387,6 +404,7 @@ impl ConstantPoolEntry {
             ConstantPoolEntry::NameAndType { .. } => NAME_AND_TYPE,
             ConstantPoolEntry::MethodHandle { .. } => METHOD_HANDLE,
             ConstantPoolEntry::MethodType { .. } => METHOD_TYPE,
+            ConstantPoolEntry::Dynamic { .. } => DYNAMIC,
             ConstantPoolEntry::InvokeDynamic { .. } => INVOKE_DYNAMIC,
         }
     }
@@ -517,6 +535,15 @@ fn read_constant_pool_entry(
 
             Ok(ConstantPoolEntry::MethodType { descriptor_idx })
         }
+        DYNAMIC => {
+            let bootstrap_method_idx = data.read_u16_be()?;
+            let name_and_type_idx = data.read_u16_be()?;
+
+            Ok(ConstantPoolEntry::Dynamic {
+                bootstrap_method_idx,
+                name_and_type_idx,
+            })
+        }
         INVOKE_DYNAMIC => {
             let bootstrap_method_idx = data.read_u16_be()?;
             let name_and_type_idx = data.read_u16_be()?;
@@ -530,7 +557,7 @@ fn read_constant_pool_entry(
     }
 }
 
-pub fn read_constant_pool(
+pub(crate) fn read_constant_pool(
     gc_ctx: GcCtx,
     interner: &mut JvmStringInterner,
     data: &mut FileData<'_>,
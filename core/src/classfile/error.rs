@@ -9,6 +9,8 @@ pub enum Error {
     ConstantPoolVerifyError,
     EndOfFile,
     ExpectedNonZero,
+    InvalidAttributeLength,
+    InvalidElementValueTag,
     InvalidMagic,
     InvalidString,
 }
@@ -22,3 +24,25 @@ impl From<ReadError> for Error {
         }
     }
 }
+
+/// An error encountered while writing a class file.
+#[derive(Debug)]
+pub enum WriteError {
+    AttributeTooLarge,
+    CodeTooLarge,
+    ConstantPoolOverflow,
+    FallsOffEndOfCode,
+    ForeignLabel,
+    IncompatibleStackTypes,
+    InconsistentStackHeight,
+    InvalidDescriptor,
+    InvalidOperand,
+    InvalidOpcode(u8),
+    LabelNotPlaced,
+    LabelPlacedTwice,
+    MissingCode,
+    StackUnderflow,
+    StringTooLong,
+    TooManyMembers,
+    UnexpectedCode,
+}
@@ -48,3 +48,31 @@ bitflags! {
         const SYNTHETIC    = 0x1000;
     }
 }
+
+bitflags! {
+    /// Flags modifying an inner class, as recorded in the `InnerClasses`
+    /// attribute of its outer class.
+    #[derive(Clone, Copy, Debug)]
+    pub struct InnerClassFlags: u16 {
+        const PUBLIC     = 0x0001;
+        const PRIVATE    = 0x0002;
+        const PROTECTED  = 0x0004;
+        const STATIC     = 0x0008;
+        const FINAL      = 0x0010;
+        const INTERFACE  = 0x0200;
+        const ABSTRACT   = 0x0400;
+        const SYNTHETIC  = 0x1000;
+        const ANNOTATION = 0x2000;
+        const ENUM       = 0x4000;
+    }
+}
+
+bitflags! {
+    /// Flags modifying a method parameter.
+    #[derive(Clone, Copy, Debug)]
+    pub struct MethodParameterFlags: u16 {
+        const FINAL     = 0x0010;
+        const SYNTHETIC = 0x1000;
+        const MANDATED  = 0x8000;
+    }
+}
@@ -1,4 +1,4 @@
-use super::attribute::Attribute;
+use super::attribute::{Attribute, read_attributes};
 use super::constant_pool::ConstantPool;
 use super::error::Error;
 use super::flags::MethodFlags;
@@ -8,8 +8,8 @@ use crate::reader::{FileData, Reader};
 use crate::string::JvmString;
 
 use alloc::boxed::Box;
-use alloc::vec::Vec;
 
+/// A method declared in a class file.
 pub struct Method {
     flags: MethodFlags,
     name: JvmString,
@@ -19,7 +19,10 @@ pub struct Method {
 }
 
 impl Method {
-    pub fn read_from(data: &mut FileData<'_>, constant_pool: &ConstantPool) -> Result<Self, Error> {
+    pub(crate) fn read_from(
+        data: &mut FileData<'_>,
+        constant_pool: &ConstantPool,
+    ) -> Result<Self, Error> {
         let flag_bits = data.read_u16_be()?;
         let flags = MethodFlags::from_bits_truncate(flag_bits);
 
@@ -29,17 +32,13 @@ impl Method {
         let descriptor_idx = data.read_u16_be()?;
         let descriptor = constant_pool.get_utf8(descriptor_idx)?;
 
-        let attribute_count = data.read_u16_be()?;
-        let mut attribute_list = Vec::with_capacity(attribute_count as usize);
-        for _ in 0..attribute_count {
-            attribute_list.push(Attribute::read_from(data, constant_pool)?);
-        }
+        let attributes = read_attributes(data, constant_pool)?;
 
         Ok(Self {
             flags,
             name,
             descriptor,
-            attributes: attribute_list.into_boxed_slice(),
+            attributes,
         })
     }
 
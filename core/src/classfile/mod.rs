@@ -1,7 +1,9 @@
-pub(crate) mod attribute;
-pub(crate) mod class;
-pub(crate) mod constant_pool;
-pub(crate) mod error;
-pub(crate) mod field;
-pub(crate) mod flags;
-pub(crate) mod method;
+pub mod attribute;
+pub mod class;
+pub mod constant_pool;
+pub mod error;
+pub mod field;
+pub mod flags;
+pub mod method;
+pub mod opcodes;
+pub mod writer;
@@ -0,0 +1,426 @@
+//! Opcodes of JVM instructions.
+
+pub const NOP: u8 = 0x00;
+pub const A_CONST_NULL: u8 = 0x01;
+pub const I_CONST_M1: u8 = 0x02;
+pub const I_CONST_0: u8 = 0x03;
+pub const I_CONST_1: u8 = 0x04;
+pub const I_CONST_2: u8 = 0x05;
+pub const I_CONST_3: u8 = 0x06;
+pub const I_CONST_4: u8 = 0x07;
+pub const I_CONST_5: u8 = 0x08;
+pub const L_CONST_0: u8 = 0x09;
+pub const L_CONST_1: u8 = 0x0A;
+pub const F_CONST_0: u8 = 0x0B;
+pub const F_CONST_1: u8 = 0x0C;
+pub const F_CONST_2: u8 = 0x0D;
+pub const D_CONST_0: u8 = 0x0E;
+pub const D_CONST_1: u8 = 0x0F;
+pub const B_I_PUSH: u8 = 0x10;
+pub const S_I_PUSH: u8 = 0x11;
+pub const LDC: u8 = 0x12;
+pub const LDC_W: u8 = 0x13;
+pub const LDC_2_W: u8 = 0x14;
+pub const I_LOAD: u8 = 0x15;
+pub const L_LOAD: u8 = 0x16;
+pub const F_LOAD: u8 = 0x17;
+pub const D_LOAD: u8 = 0x18;
+pub const A_LOAD: u8 = 0x19;
+pub const I_LOAD_0: u8 = 0x1A;
+pub const I_LOAD_1: u8 = 0x1B;
+pub const I_LOAD_2: u8 = 0x1C;
+pub const I_LOAD_3: u8 = 0x1D;
+pub const L_LOAD_0: u8 = 0x1E;
+pub const L_LOAD_1: u8 = 0x1F;
+pub const L_LOAD_2: u8 = 0x20;
+pub const L_LOAD_3: u8 = 0x21;
+pub const F_LOAD_0: u8 = 0x22;
+pub const F_LOAD_1: u8 = 0x23;
+pub const F_LOAD_2: u8 = 0x24;
+pub const F_LOAD_3: u8 = 0x25;
+pub const D_LOAD_0: u8 = 0x26;
+pub const D_LOAD_1: u8 = 0x27;
+pub const D_LOAD_2: u8 = 0x28;
+pub const D_LOAD_3: u8 = 0x29;
+pub const A_LOAD_0: u8 = 0x2A;
+pub const A_LOAD_1: u8 = 0x2B;
+pub const A_LOAD_2: u8 = 0x2C;
+pub const A_LOAD_3: u8 = 0x2D;
+pub const IA_LOAD: u8 = 0x2E;
+pub const LA_LOAD: u8 = 0x2F;
+pub const FA_LOAD: u8 = 0x30;
+pub const DA_LOAD: u8 = 0x31;
+pub const AA_LOAD: u8 = 0x32;
+pub const BA_LOAD: u8 = 0x33;
+pub const CA_LOAD: u8 = 0x34;
+pub const SA_LOAD: u8 = 0x35;
+pub const I_STORE: u8 = 0x36;
+pub const L_STORE: u8 = 0x37;
+pub const F_STORE: u8 = 0x38;
+pub const D_STORE: u8 = 0x39;
+pub const A_STORE: u8 = 0x3A;
+pub const I_STORE_0: u8 = 0x3B;
+pub const I_STORE_1: u8 = 0x3C;
+pub const I_STORE_2: u8 = 0x3D;
+pub const I_STORE_3: u8 = 0x3E;
+pub const L_STORE_0: u8 = 0x3F;
+pub const L_STORE_1: u8 = 0x40;
+pub const L_STORE_2: u8 = 0x41;
+pub const L_STORE_3: u8 = 0x42;
+pub const F_STORE_0: u8 = 0x43;
+pub const F_STORE_1: u8 = 0x44;
+pub const F_STORE_2: u8 = 0x45;
+pub const F_STORE_3: u8 = 0x46;
+pub const D_STORE_0: u8 = 0x47;
+pub const D_STORE_1: u8 = 0x48;
+pub const D_STORE_2: u8 = 0x49;
+pub const D_STORE_3: u8 = 0x4A;
+pub const A_STORE_0: u8 = 0x4B;
+pub const A_STORE_1: u8 = 0x4C;
+pub const A_STORE_2: u8 = 0x4D;
+pub const A_STORE_3: u8 = 0x4E;
+pub const IA_STORE: u8 = 0x4F;
+pub const LA_STORE: u8 = 0x50;
+pub const FA_STORE: u8 = 0x51;
+pub const DA_STORE: u8 = 0x52;
+pub const AA_STORE: u8 = 0x53;
+pub const BA_STORE: u8 = 0x54;
+pub const CA_STORE: u8 = 0x55;
+pub const SA_STORE: u8 = 0x56;
+pub const POP: u8 = 0x57;
+pub const POP_2: u8 = 0x58;
+pub const DUP: u8 = 0x59;
+pub const DUP_X1: u8 = 0x5A;
+pub const DUP_X2: u8 = 0x5B;
+pub const DUP_2: u8 = 0x5C;
+pub const DUP_2_X1: u8 = 0x5D;
+pub const DUP_2_X2: u8 = 0x5E;
+pub const SWAP: u8 = 0x5F;
+pub const I_ADD: u8 = 0x60;
+pub const L_ADD: u8 = 0x61;
+pub const F_ADD: u8 = 0x62;
+pub const D_ADD: u8 = 0x63;
+pub const I_SUB: u8 = 0x64;
+pub const L_SUB: u8 = 0x65;
+pub const F_SUB: u8 = 0x66;
+pub const D_SUB: u8 = 0x67;
+pub const I_MUL: u8 = 0x68;
+pub const L_MUL: u8 = 0x69;
+pub const F_MUL: u8 = 0x6A;
+pub const D_MUL: u8 = 0x6B;
+pub const I_DIV: u8 = 0x6C;
+pub const L_DIV: u8 = 0x6D;
+pub const F_DIV: u8 = 0x6E;
+pub const D_DIV: u8 = 0x6F;
+pub const I_REM: u8 = 0x70;
+pub const L_REM: u8 = 0x71;
+pub const F_REM: u8 = 0x72;
+pub const D_REM: u8 = 0x73;
+pub const I_NEG: u8 = 0x74;
+pub const L_NEG: u8 = 0x75;
+pub const F_NEG: u8 = 0x76;
+pub const D_NEG: u8 = 0x77;
+pub const I_SHL: u8 = 0x78;
+pub const L_SHL: u8 = 0x79;
+pub const I_SHR: u8 = 0x7A;
+pub const L_SHR: u8 = 0x7B;
+pub const I_USHR: u8 = 0x7C;
+pub const L_USHR: u8 = 0x7D;
+pub const I_AND: u8 = 0x7E;
+pub const L_AND: u8 = 0x7F;
+pub const I_OR: u8 = 0x80;
+pub const L_OR: u8 = 0x81;
+pub const I_XOR: u8 = 0x82;
+pub const L_XOR: u8 = 0x83;
+pub const I_INC: u8 = 0x84;
+pub const I2L: u8 = 0x85;
+pub const I2F: u8 = 0x86;
+pub const I2D: u8 = 0x87;
+pub const L2I: u8 = 0x88;
+pub const L2F: u8 = 0x89;
+pub const L2D: u8 = 0x8A;
+pub const F2I: u8 = 0x8B;
+pub const F2L: u8 = 0x8C;
+pub const F2D: u8 = 0x8D;
+pub const D2I: u8 = 0x8E;
+pub const D2L: u8 = 0x8F;
+pub const D2F: u8 = 0x90;
+pub const I2B: u8 = 0x91;
+pub const I2C: u8 = 0x92;
+pub const I2S: u8 = 0x93;
+pub const L_CMP: u8 = 0x94;
+pub const F_CMP_L: u8 = 0x95;
+pub const F_CMP_G: u8 = 0x96;
+pub const D_CMP_L: u8 = 0x97;
+pub const D_CMP_G: u8 = 0x98;
+pub const IF_EQ: u8 = 0x99;
+pub const IF_NE: u8 = 0x9A;
+pub const IF_LT: u8 = 0x9B;
+pub const IF_GE: u8 = 0x9C;
+pub const IF_GT: u8 = 0x9D;
+pub const IF_LE: u8 = 0x9E;
+pub const IF_I_CMP_EQ: u8 = 0x9F;
+pub const IF_I_CMP_NE: u8 = 0xA0;
+pub const IF_I_CMP_LT: u8 = 0xA1;
+pub const IF_I_CMP_GE: u8 = 0xA2;
+pub const IF_I_CMP_GT: u8 = 0xA3;
+pub const IF_I_CMP_LE: u8 = 0xA4;
+pub const IF_A_CMP_EQ: u8 = 0xA5;
+pub const IF_A_CMP_NE: u8 = 0xA6;
+pub const GOTO: u8 = 0xA7;
+pub const JSR: u8 = 0xA8;
+pub const RET: u8 = 0xA9;
+pub const TABLE_SWITCH: u8 = 0xAA;
+pub const LOOKUP_SWITCH: u8 = 0xAB;
+pub const I_RETURN: u8 = 0xAC;
+pub const L_RETURN: u8 = 0xAD;
+pub const F_RETURN: u8 = 0xAE;
+pub const D_RETURN: u8 = 0xAF;
+pub const A_RETURN: u8 = 0xB0;
+pub const RETURN: u8 = 0xB1;
+pub const GET_STATIC: u8 = 0xB2;
+pub const PUT_STATIC: u8 = 0xB3;
+pub const GET_FIELD: u8 = 0xB4;
+pub const PUT_FIELD: u8 = 0xB5;
+pub const INVOKE_VIRTUAL: u8 = 0xB6;
+pub const INVOKE_SPECIAL: u8 = 0xB7;
+pub const INVOKE_STATIC: u8 = 0xB8;
+pub const INVOKE_INTERFACE: u8 = 0xB9;
+pub const INVOKE_DYNAMIC: u8 = 0xBA;
+pub const NEW: u8 = 0xBB;
+pub const NEW_ARRAY: u8 = 0xBC;
+pub const A_NEW_ARRAY: u8 = 0xBD;
+pub const ARRAY_LENGTH: u8 = 0xBE;
+pub const A_THROW: u8 = 0xBF;
+pub const CHECK_CAST: u8 = 0xC0;
+pub const INSTANCE_OF: u8 = 0xC1;
+pub const MONITOR_ENTER: u8 = 0xC2;
+pub const MONITOR_EXIT: u8 = 0xC3;
+pub const WIDE: u8 = 0xC4;
+pub const MULTI_A_NEW_ARRAY: u8 = 0xC5;
+pub const IF_NULL: u8 = 0xC6;
+pub const IF_NON_NULL: u8 = 0xC7;
+pub const GOTO_W: u8 = 0xC8;
+pub const JSR_W: u8 = 0xC9;
+
+// Element type codes used by the `newarray` instruction
+pub const T_BOOLEAN: u8 = 4;
+pub const T_CHAR: u8 = 5;
+pub const T_FLOAT: u8 = 6;
+pub const T_DOUBLE: u8 = 7;
+pub const T_BYTE: u8 = 8;
+pub const T_SHORT: u8 = 9;
+pub const T_INT: u8 = 10;
+pub const T_LONG: u8 = 11;
+
+/// Returns the mnemonic of the given opcode, as used by `javap`, or `None` if
+/// the opcode isn't a valid JVM instruction.
+pub fn name(opcode: u8) -> Option<&'static str> {
+    let name = match opcode {
+        NOP => "nop",
+        A_CONST_NULL => "aconst_null",
+        I_CONST_M1 => "iconst_m1",
+        I_CONST_0 => "iconst_0",
+        I_CONST_1 => "iconst_1",
+        I_CONST_2 => "iconst_2",
+        I_CONST_3 => "iconst_3",
+        I_CONST_4 => "iconst_4",
+        I_CONST_5 => "iconst_5",
+        L_CONST_0 => "lconst_0",
+        L_CONST_1 => "lconst_1",
+        F_CONST_0 => "fconst_0",
+        F_CONST_1 => "fconst_1",
+        F_CONST_2 => "fconst_2",
+        D_CONST_0 => "dconst_0",
+        D_CONST_1 => "dconst_1",
+        B_I_PUSH => "bipush",
+        S_I_PUSH => "sipush",
+        LDC => "ldc",
+        LDC_W => "ldc_w",
+        LDC_2_W => "ldc2_w",
+        I_LOAD => "iload",
+        L_LOAD => "lload",
+        F_LOAD => "fload",
+        D_LOAD => "dload",
+        A_LOAD => "aload",
+        I_LOAD_0 => "iload_0",
+        I_LOAD_1 => "iload_1",
+        I_LOAD_2 => "iload_2",
+        I_LOAD_3 => "iload_3",
+        L_LOAD_0 => "lload_0",
+        L_LOAD_1 => "lload_1",
+        L_LOAD_2 => "lload_2",
+        L_LOAD_3 => "lload_3",
+        F_LOAD_0 => "fload_0",
+        F_LOAD_1 => "fload_1",
+        F_LOAD_2 => "fload_2",
+        F_LOAD_3 => "fload_3",
+        D_LOAD_0 => "dload_0",
+        D_LOAD_1 => "dload_1",
+        D_LOAD_2 => "dload_2",
+        D_LOAD_3 => "dload_3",
+        A_LOAD_0 => "aload_0",
+        A_LOAD_1 => "aload_1",
+        A_LOAD_2 => "aload_2",
+        A_LOAD_3 => "aload_3",
+        IA_LOAD => "iaload",
+        LA_LOAD => "laload",
+        FA_LOAD => "faload",
+        DA_LOAD => "daload",
+        AA_LOAD => "aaload",
+        BA_LOAD => "baload",
+        CA_LOAD => "caload",
+        SA_LOAD => "saload",
+        I_STORE => "istore",
+        L_STORE => "lstore",
+        F_STORE => "fstore",
+        D_STORE => "dstore",
+        A_STORE => "astore",
+        I_STORE_0 => "istore_0",
+        I_STORE_1 => "istore_1",
+        I_STORE_2 => "istore_2",
+        I_STORE_3 => "istore_3",
+        L_STORE_0 => "lstore_0",
+        L_STORE_1 => "lstore_1",
+        L_STORE_2 => "lstore_2",
+        L_STORE_3 => "lstore_3",
+        F_STORE_0 => "fstore_0",
+        F_STORE_1 => "fstore_1",
+        F_STORE_2 => "fstore_2",
+        F_STORE_3 => "fstore_3",
+        D_STORE_0 => "dstore_0",
+        D_STORE_1 => "dstore_1",
+        D_STORE_2 => "dstore_2",
+        D_STORE_3 => "dstore_3",
+        A_STORE_0 => "astore_0",
+        A_STORE_1 => "astore_1",
+        A_STORE_2 => "astore_2",
+        A_STORE_3 => "astore_3",
+        IA_STORE => "iastore",
+        LA_STORE => "lastore",
+        FA_STORE => "fastore",
+        DA_STORE => "dastore",
+        AA_STORE => "aastore",
+        BA_STORE => "bastore",
+        CA_STORE => "castore",
+        SA_STORE => "sastore",
+        POP => "pop",
+        POP_2 => "pop2",
+        DUP => "dup",
+        DUP_X1 => "dup_x1",
+        DUP_X2 => "dup_x2",
+        DUP_2 => "dup2",
+        DUP_2_X1 => "dup2_x1",
+        DUP_2_X2 => "dup2_x2",
+        SWAP => "swap",
+        I_ADD => "iadd",
+        L_ADD => "ladd",
+        F_ADD => "fadd",
+        D_ADD => "dadd",
+        I_SUB => "isub",
+        L_SUB => "lsub",
+        F_SUB => "fsub",
+        D_SUB => "dsub",
+        I_MUL => "imul",
+        L_MUL => "lmul",
+        F_MUL => "fmul",
+        D_MUL => "dmul",
+        I_DIV => "idiv",
+        L_DIV => "ldiv",
+        F_DIV => "fdiv",
+        D_DIV => "ddiv",
+        I_REM => "irem",
+        L_REM => "lrem",
+        F_REM => "frem",
+        D_REM => "drem",
+        I_NEG => "ineg",
+        L_NEG => "lneg",
+        F_NEG => "fneg",
+        D_NEG => "dneg",
+        I_SHL => "ishl",
+        L_SHL => "lshl",
+        I_SHR => "ishr",
+        L_SHR => "lshr",
+        I_USHR => "iushr",
+        L_USHR => "lushr",
+        I_AND => "iand",
+        L_AND => "land",
+        I_OR => "ior",
+        L_OR => "lor",
+        I_XOR => "ixor",
+        L_XOR => "lxor",
+        I_INC => "iinc",
+        I2L => "i2l",
+        I2F => "i2f",
+        I2D => "i2d",
+        L2I => "l2i",
+        L2F => "l2f",
+        L2D => "l2d",
+        F2I => "f2i",
+        F2L => "f2l",
+        F2D => "f2d",
+        D2I => "d2i",
+        D2L => "d2l",
+        D2F => "d2f",
+        I2B => "i2b",
+        I2C => "i2c",
+        I2S => "i2s",
+        L_CMP => "lcmp",
+        F_CMP_L => "fcmpl",
+        F_CMP_G => "fcmpg",
+        D_CMP_L => "dcmpl",
+        D_CMP_G => "dcmpg",
+        IF_EQ => "ifeq",
+        IF_NE => "ifne",
+        IF_LT => "iflt",
+        IF_GE => "ifge",
+        IF_GT => "ifgt",
+        IF_LE => "ifle",
+        IF_I_CMP_EQ => "if_icmpeq",
+        IF_I_CMP_NE => "if_icmpne",
+        IF_I_CMP_LT => "if_icmplt",
+        IF_I_CMP_GE => "if_icmpge",
+        IF_I_CMP_GT => "if_icmpgt",
+        IF_I_CMP_LE => "if_icmple",
+        IF_A_CMP_EQ => "if_acmpeq",
+        IF_A_CMP_NE => "if_acmpne",
+        GOTO => "goto",
+        JSR => "jsr",
+        RET => "ret",
+        TABLE_SWITCH => "tableswitch",
+        LOOKUP_SWITCH => "lookupswitch",
+        I_RETURN => "ireturn",
+        L_RETURN => "lreturn",
+        F_RETURN => "freturn",
+        D_RETURN => "dreturn",
+        A_RETURN => "areturn",
+        RETURN => "return",
+        GET_STATIC => "getstatic",
+        PUT_STATIC => "putstatic",
+        GET_FIELD => "getfield",
+        PUT_FIELD => "putfield",
+        INVOKE_VIRTUAL => "invokevirtual",
+        INVOKE_SPECIAL => "invokespecial",
+        INVOKE_STATIC => "invokestatic",
+        INVOKE_INTERFACE => "invokeinterface",
+        INVOKE_DYNAMIC => "invokedynamic",
+        NEW => "new",
+        NEW_ARRAY => "newarray",
+        A_NEW_ARRAY => "anewarray",
+        ARRAY_LENGTH => "arraylength",
+        A_THROW => "athrow",
+        CHECK_CAST => "checkcast",
+        INSTANCE_OF => "instanceof",
+        MONITOR_ENTER => "monitorenter",
+        MONITOR_EXIT => "monitorexit",
+        WIDE => "wide",
+        MULTI_A_NEW_ARRAY => "multianewarray",
+        IF_NULL => "ifnull",
+        IF_NON_NULL => "ifnonnull",
+        GOTO_W => "goto_w",
+        JSR_W => "jsr_w",
+        _ => return None,
+    };
+
+    Some(name)
+}
@@ -0,0 +1,2533 @@
+//! Writing class files.
+//!
+//! [`ClassWriter`] assembles a class file from its members, and
+//! [`MethodWriter`] assembles the bytecode of a single method. Branches refer
+//! to [`Label`]s, which are resolved (and widened to `goto_w` if needed) when
+//! the method is finished. `max_stack`, `max_locals`, and the `StackMapTable`
+//! attribute are computed from the bytecode.
+//!
+//! ```
+//! use rjvm_core::classfile::opcodes::*;
+//! use rjvm_core::classfile::writer::ClassWriter;
+//! use rjvm_core::{ClassFlags, MethodFlags};
+//!
+//! let mut writer = ClassWriter::new(ClassFlags::PUBLIC, "Sum", Some("java/lang/Object"));
+//!
+//! // static int sum(int n) { int total = 0; while (n > 0) { total += n--; } return total; }
+//! let mut method = writer.add_method(MethodFlags::STATIC, "sum", "(I)I");
+//! let loop_start = method.new_label();
+//! let loop_end = method.new_label();
+//!
+//! method.push_int(0)?;
+//! method.var_insn(I_STORE, 1)?;
+//! method.place_label(loop_start)?;
+//! method.var_insn(I_LOAD, 0)?;
+//! method.jump_insn(IF_LE, loop_end)?;
+//! method.var_insn(I_LOAD, 1)?;
+//! method.var_insn(I_LOAD, 0)?;
+//! method.insn(I_ADD)?;
+//! method.var_insn(I_STORE, 1)?;
+//! method.iinc_insn(0, -1)?;
+//! method.jump_insn(GOTO, loop_start)?;
+//! method.place_label(loop_end)?;
+//! method.var_insn(I_LOAD, 1)?;
+//! method.insn(I_RETURN)?;
+//! method.finish()?;
+//!
+//! let bytes = writer.into_bytes()?;
+//! assert_eq!(bytes[..4], [0xCA, 0xFE, 0xBA, 0xBE]);
+//! # Ok::<(), rjvm_core::ClassFileWriteError>(())
+//! ```
+
+use super::constant_pool::{
+    self, CLASS, DOUBLE, DYNAMIC, FIELD_REF, FLOAT, INTEGER, INTERFACE_METHOD_REF, LONG,
+    METHOD_HANDLE, METHOD_REF, METHOD_TYPE, NAME_AND_TYPE, STRING, UTF8,
+};
+use super::error::WriteError;
+use super::flags::{ClassFlags, FieldFlags, MethodFlags};
+use super::opcodes::*;
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use hashbrown::HashMap;
+
+const OBJECT: &str = "java/lang/Object";
+
+// The first class file version that requires a `StackMapTable` attribute.
+const STACK_MAP_MAJOR_VERSION: u16 = 50;
+
+// The ID given to the next `MethodWriter`, which its labels are tagged with.
+static NEXT_METHOD_WRITER_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A constant that can be loaded with `ldc` or passed to a bootstrap method.
+#[derive(Clone, Copy, Debug)]
+pub enum Constant<'a> {
+    Integer(i32),
+    Float(f32),
+    Long(i64),
+    Double(f64),
+    String(&'a str),
+    /// A class, given by its internal name or, for array classes, its descriptor.
+    Class(&'a str),
+    /// A method type, given by its method descriptor.
+    MethodType(&'a str),
+    MethodHandle(Handle<'a>),
+    /// A dynamically-computed constant, produced by calling `bootstrap` with
+    /// `args` the first time the constant is loaded.
+    Dynamic {
+        name: &'a str,
+        descriptor: &'a str,
+        bootstrap: Handle<'a>,
+        args: &'a [Constant<'a>],
+    },
+}
+
+/// The kind of a method handle, as stored in a `CONSTANT_MethodHandle` entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandleKind {
+    GetField = 1,
+    GetStatic = 2,
+    PutField = 3,
+    PutStatic = 4,
+    InvokeVirtual = 5,
+    InvokeStatic = 6,
+    InvokeSpecial = 7,
+    NewInvokeSpecial = 8,
+    InvokeInterface = 9,
+}
+
+/// A reference to a field or method, used for method handle constants and
+/// bootstrap methods.
+#[derive(Clone, Copy, Debug)]
+pub struct Handle<'a> {
+    pub kind: HandleKind,
+    pub owner: &'a str,
+    pub name: &'a str,
+    pub descriptor: &'a str,
+    /// Whether `owner` is an interface. This is only used for `InvokeStatic`
+    /// and `InvokeSpecial` handles, as other kinds imply it.
+    pub is_interface: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum EntryKey {
+    Utf8(String),
+    Integer(i32),
+    Float(u32),
+    Long(i64),
+    Double(u64),
+    Class(u16),
+    String(u16),
+    FieldRef(u16, u16),
+    MethodRef(u16, u16),
+    InterfaceMethodRef(u16, u16),
+    NameAndType(u16, u16),
+    MethodHandle(u8, u16),
+    MethodType(u16),
+    Dynamic(u16, u16),
+    InvokeDynamic(u16, u16),
+}
+
+/// The constant pool of a class file being written. Adding an entry that is
+/// already in the pool returns the index of the existing entry.
+///
+/// The pool also holds the class's bootstrap methods, as they are only ever
+/// referenced by constant pool entries.
+pub struct ConstantPoolWriter {
+    data: Vec<u8>,
+    next_index: u16,
+    entries: HashMap<EntryKey, u16>,
+
+    bootstrap_methods: Vec<(u16, Box<[u16]>)>,
+    bootstrap_method_indices: HashMap<(u16, Box<[u16]>), u16>,
+}
+
+impl ConstantPoolWriter {
+    fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            next_index: 1,
+            entries: HashMap::new(),
+            bootstrap_methods: Vec::new(),
+            bootstrap_method_indices: HashMap::new(),
+        }
+    }
+
+    /// The number of slots in the pool, as stored in `constant_pool_count`.
+    pub fn entry_count(&self) -> u16 {
+        self.next_index
+    }
+
+    fn add_entry(
+        &mut self,
+        key: EntryKey,
+        write: impl FnOnce(&mut Vec<u8>),
+    ) -> Result<u16, WriteError> {
+        if let Some(index) = self.entries.get(&key) {
+            return Ok(*index);
+        }
+
+        // Long and double entries take up two slots
+        let size = if matches!(key, EntryKey::Long(_) | EntryKey::Double(_)) {
+            2
+        } else {
+            1
+        };
+
+        let index = self.next_index;
+        if index as u32 + size > u16::MAX as u32 {
+            return Err(WriteError::ConstantPoolOverflow);
+        }
+
+        write(&mut self.data);
+        self.next_index += size as u16;
+        self.entries.insert(key, index);
+
+        Ok(index)
+    }
+
+    pub fn utf8(&mut self, string: &str) -> Result<u16, WriteError> {
+        if let Some(index) = self.entries.get(&EntryKey::Utf8(string.to_string())) {
+            return Ok(*index);
+        }
+
+        let bytes = encode_modified_utf8(string);
+        if bytes.len() > u16::MAX as usize {
+            return Err(WriteError::StringTooLong);
+        }
+
+        self.add_entry(EntryKey::Utf8(string.to_string()), |data| {
+            data.push(UTF8);
+            write_u16(data, bytes.len() as u16);
+            data.extend_from_slice(&bytes);
+        })
+    }
+
+    pub fn integer(&mut self, value: i32) -> Result<u16, WriteError> {
+        self.add_entry(EntryKey::Integer(value), |data| {
+            data.push(INTEGER);
+            write_u32(data, value as u32);
+        })
+    }
+
+    pub fn float(&mut self, value: f32) -> Result<u16, WriteError> {
+        // Floats are deduplicated by their bits, so that `0.0` and `-0.0` (and
+        // NaNs with different payloads) stay distinct
+        let bits = value.to_bits();
+
+        self.add_entry(EntryKey::Float(bits), |data| {
+            data.push(FLOAT);
+            write_u32(data, bits);
+        })
+    }
+
+    pub fn long(&mut self, value: i64) -> Result<u16, WriteError> {
+        self.add_entry(EntryKey::Long(value), |data| {
+            data.push(LONG);
+            data.extend_from_slice(&value.to_be_bytes());
+        })
+    }
+
+    pub fn double(&mut self, value: f64) -> Result<u16, WriteError> {
+        let bits = value.to_bits();
+
+        self.add_entry(EntryKey::Double(bits), |data| {
+            data.push(DOUBLE);
+            data.extend_from_slice(&bits.to_be_bytes());
+        })
+    }
+
+    /// Add a class entry. `name` is an internal name such as
+    /// `java/lang/String`, or an array descriptor such as `[I`.
+    pub fn class(&mut self, name: &str) -> Result<u16, WriteError> {
+        let name_idx = self.utf8(name)?;
+
+        self.add_entry(EntryKey::Class(name_idx), |data| {
+            data.push(CLASS);
+            write_u16(data, name_idx);
+        })
+    }
+
+    pub fn string(&mut self, string: &str) -> Result<u16, WriteError> {
+        let string_idx = self.utf8(string)?;
+
+        self.add_entry(EntryKey::String(string_idx), |data| {
+            data.push(STRING);
+            write_u16(data, string_idx);
+        })
+    }
+
+    pub fn name_and_type(&mut self, name: &str, descriptor: &str) -> Result<u16, WriteError> {
+        let name_idx = self.utf8(name)?;
+        let descriptor_idx = self.utf8(descriptor)?;
+
+        self.add_entry(EntryKey::NameAndType(name_idx, descriptor_idx), |data| {
+            data.push(NAME_AND_TYPE);
+            write_u16(data, name_idx);
+            write_u16(data, descriptor_idx);
+        })
+    }
+
+    pub fn field_ref(
+        &mut self,
+        owner: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<u16, WriteError> {
+        let class_idx = self.class(owner)?;
+        let name_and_type_idx = self.name_and_type(name, descriptor)?;
+
+        self.add_entry(EntryKey::FieldRef(class_idx, name_and_type_idx), |data| {
+            data.push(FIELD_REF);
+            write_u16(data, class_idx);
+            write_u16(data, name_and_type_idx);
+        })
+    }
+
+    pub fn method_ref(
+        &mut self,
+        owner: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<u16, WriteError> {
+        let class_idx = self.class(owner)?;
+        let name_and_type_idx = self.name_and_type(name, descriptor)?;
+
+        self.add_entry(EntryKey::MethodRef(class_idx, name_and_type_idx), |data| {
+            data.push(METHOD_REF);
+            write_u16(data, class_idx);
+            write_u16(data, name_and_type_idx);
+        })
+    }
+
+    pub fn interface_method_ref(
+        &mut self,
+        owner: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<u16, WriteError> {
+        let class_idx = self.class(owner)?;
+        let name_and_type_idx = self.name_and_type(name, descriptor)?;
+
+        self.add_entry(
+            EntryKey::InterfaceMethodRef(class_idx, name_and_type_idx),
+            |data| {
+                data.push(INTERFACE_METHOD_REF);
+                write_u16(data, class_idx);
+                write_u16(data, name_and_type_idx);
+            },
+        )
+    }
+
+    pub fn method_handle(&mut self, handle: &Handle<'_>) -> Result<u16, WriteError> {
+        let reference_idx = match handle.kind {
+            HandleKind::GetField
+            | HandleKind::GetStatic
+            | HandleKind::PutField
+            | HandleKind::PutStatic => {
+                self.field_ref(handle.owner, handle.name, handle.descriptor)?
+            }
+            HandleKind::InvokeInterface => {
+                self.interface_method_ref(handle.owner, handle.name, handle.descriptor)?
+            }
+            HandleKind::InvokeStatic | HandleKind::InvokeSpecial if handle.is_interface => {
+                self.interface_method_ref(handle.owner, handle.name, handle.descriptor)?
+            }
+            HandleKind::InvokeVirtual
+            | HandleKind::InvokeStatic
+            | HandleKind::InvokeSpecial
+            | HandleKind::NewInvokeSpecial => {
+                self.method_ref(handle.owner, handle.name, handle.descriptor)?
+            }
+        };
+
+        let kind = handle.kind as u8;
+        self.add_entry(EntryKey::MethodHandle(kind, reference_idx), |data| {
+            data.push(METHOD_HANDLE);
+            data.push(kind);
+            write_u16(data, reference_idx);
+        })
+    }
+
+    pub fn method_type(&mut self, descriptor: &str) -> Result<u16, WriteError> {
+        let descriptor_idx = self.utf8(descriptor)?;
+
+        self.add_entry(EntryKey::MethodType(descriptor_idx), |data| {
+            data.push(METHOD_TYPE);
+            write_u16(data, descriptor_idx);
+        })
+    }
+
+    /// Add an entry for a loadable constant.
+    pub fn constant(&mut self, constant: &Constant<'_>) -> Result<u16, WriteError> {
+        match *constant {
+            Constant::Integer(value) => self.integer(value),
+            Constant::Float(value) => self.float(value),
+            Constant::Long(value) => self.long(value),
+            Constant::Double(value) => self.double(value),
+            Constant::String(string) => self.string(string),
+            Constant::Class(name) => self.class(name),
+            Constant::MethodType(descriptor) => self.method_type(descriptor),
+            Constant::MethodHandle(handle) => self.method_handle(&handle),
+            Constant::Dynamic {
+                name,
+                descriptor,
+                bootstrap,
+                args,
+            } => self.dynamic(name, descriptor, &bootstrap, args),
+        }
+    }
+
+    /// Add a `CONSTANT_Dynamic` entry, along with its bootstrap method.
+    pub fn dynamic(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        bootstrap: &Handle<'_>,
+        args: &[Constant<'_>],
+    ) -> Result<u16, WriteError> {
+        let bootstrap_idx = self.bootstrap_method(bootstrap, args)?;
+        let name_and_type_idx = self.name_and_type(name, descriptor)?;
+
+        self.add_entry(
+            EntryKey::Dynamic(bootstrap_idx, name_and_type_idx),
+            |data| {
+                data.push(DYNAMIC);
+                write_u16(data, bootstrap_idx);
+                write_u16(data, name_and_type_idx);
+            },
+        )
+    }
+
+    /// Add a `CONSTANT_InvokeDynamic` entry, along with its bootstrap method.
+    pub fn invoke_dynamic(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        bootstrap: &Handle<'_>,
+        args: &[Constant<'_>],
+    ) -> Result<u16, WriteError> {
+        let bootstrap_idx = self.bootstrap_method(bootstrap, args)?;
+        let name_and_type_idx = self.name_and_type(name, descriptor)?;
+
+        self.add_entry(
+            EntryKey::InvokeDynamic(bootstrap_idx, name_and_type_idx),
+            |data| {
+                // The tag has the same name as the opcode
+                data.push(constant_pool::INVOKE_DYNAMIC);
+                write_u16(data, bootstrap_idx);
+                write_u16(data, name_and_type_idx);
+            },
+        )
+    }
+
+    /// Add a bootstrap method, returning its index in the `BootstrapMethods`
+    /// attribute.
+    fn bootstrap_method(
+        &mut self,
+        bootstrap: &Handle<'_>,
+        args: &[Constant<'_>],
+    ) -> Result<u16, WriteError> {
+        let handle_idx = self.method_handle(bootstrap)?;
+
+        let mut arg_indices = Vec::with_capacity(args.len());
+        for arg in args {
+            arg_indices.push(self.constant(arg)?);
+        }
+
+        if arg_indices.len() > u16::MAX as usize {
+            return Err(WriteError::TooManyMembers);
+        }
+
+        let key = (handle_idx, arg_indices.into_boxed_slice());
+        if let Some(index) = self.bootstrap_method_indices.get(&key) {
+            return Ok(*index);
+        }
+
+        let index = self.bootstrap_methods.len();
+        if index >= u16::MAX as usize {
+            return Err(WriteError::TooManyMembers);
+        }
+
+        self.bootstrap_methods.push(key.clone());
+        self.bootstrap_method_indices.insert(key, index as u16);
+
+        Ok(index as u16)
+    }
+}
+
+// Finds the superclass of a class, given its internal name.
+type SuperClassLookup = Box<dyn Fn(&str) -> Option<String>>;
+
+/// A class file being written.
+///
+/// Class files are written with version 52.0 (Java 8) unless another version
+/// is set with [`ClassWriter::set_version`].
+pub struct ClassWriter {
+    minor_version: u16,
+    major_version: u16,
+
+    constant_pool: ConstantPoolWriter,
+
+    flags: ClassFlags,
+    name: String,
+    super_class: Option<String>,
+    interfaces: Vec<u16>,
+
+    fields: Vec<Vec<u8>>,
+    methods: Vec<Vec<u8>>,
+    attributes: Vec<Vec<u8>>,
+
+    super_class_lookup: Option<SuperClassLookup>,
+}
+
+impl ClassWriter {
+    /// Create a writer for a class with the given flags, internal name, and
+    /// superclass. Only `java/lang/Object` has no superclass.
+    pub fn new(flags: ClassFlags, name: &str, super_class: Option<&str>) -> Self {
+        Self {
+            minor_version: 0,
+            major_version: 52,
+            constant_pool: ConstantPoolWriter::new(),
+            flags,
+            name: name.to_string(),
+            super_class: super_class.map(|s| s.to_string()),
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+            methods: Vec::new(),
+            attributes: Vec::new(),
+            super_class_lookup: None,
+        }
+    }
+
+    pub fn set_version(&mut self, major_version: u16, minor_version: u16) {
+        self.major_version = major_version;
+        self.minor_version = minor_version;
+    }
+
+    /// Set the function used to look up the superclass of a class while
+    /// computing stack map frames. When two different class types meet at a
+    /// branch target, their common superclass is found by walking up both
+    /// hierarchies. Without a lookup function (or if it returns `None` for a
+    /// class other than `java/lang/Object`), only the superclass of the class
+    /// being written is known, and other classes merge to `java/lang/Object`.
+    pub fn set_super_class_lookup(&mut self, lookup: impl Fn(&str) -> Option<String> + 'static) {
+        self.super_class_lookup = Some(Box::new(lookup));
+    }
+
+    pub fn constant_pool(&mut self) -> &mut ConstantPoolWriter {
+        &mut self.constant_pool
+    }
+
+    pub fn add_interface(&mut self, name: &str) -> Result<(), WriteError> {
+        let interface_idx = self.constant_pool.class(name)?;
+        self.interfaces.push(interface_idx);
+
+        Ok(())
+    }
+
+    /// Start writing a field. The field is added to the class when
+    /// [`FieldWriter::finish`] is called.
+    pub fn add_field(
+        &mut self,
+        flags: FieldFlags,
+        name: &str,
+        descriptor: &str,
+    ) -> FieldWriter<'_> {
+        FieldWriter {
+            class: self,
+            flags,
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Start writing a method. The method is added to the class when
+    /// [`MethodWriter::finish`] is called.
+    pub fn add_method(
+        &mut self,
+        flags: MethodFlags,
+        name: &str,
+        descriptor: &str,
+    ) -> MethodWriter<'_> {
+        MethodWriter {
+            class: self,
+            id: NEXT_METHOD_WRITER_ID.fetch_add(1, Ordering::Relaxed),
+            flags,
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+            insns: Vec::new(),
+            labels: Vec::new(),
+            try_catch_blocks: Vec::new(),
+            line_numbers: Vec::new(),
+            exceptions: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Add an attribute to the class. Any constant pool indices in `data`
+    /// should come from [`ClassWriter::constant_pool`].
+    pub fn add_attribute(&mut self, name: &str, data: &[u8]) -> Result<(), WriteError> {
+        let attribute = write_attribute(&mut self.constant_pool, name, data)?;
+        self.attributes.push(attribute);
+
+        Ok(())
+    }
+
+    /// Finish writing the class, returning the bytes of the class file.
+    pub fn into_bytes(mut self) -> Result<Vec<u8>, WriteError> {
+        let this_class_idx = self.constant_pool.class(&self.name)?;
+        let super_class_idx = match &self.super_class {
+            Some(super_class) => self.constant_pool.class(super_class)?,
+            None => 0,
+        };
+
+        if !self.constant_pool.bootstrap_methods.is_empty() {
+            let bootstrap_methods = core::mem::take(&mut self.constant_pool.bootstrap_methods);
+
+            let mut data = Vec::new();
+            write_u16(&mut data, bootstrap_methods.len() as u16);
+            for (handle_idx, args) in bootstrap_methods {
+                write_u16(&mut data, handle_idx);
+                write_u16(&mut data, args.len() as u16);
+                for arg in args {
+                    write_u16(&mut data, arg);
+                }
+            }
+
+            self.add_attribute("BootstrapMethods", &data)?;
+        }
+
+        let mut bytes = Vec::new();
+        write_u32(&mut bytes, 0xCAFEBABE);
+        write_u16(&mut bytes, self.minor_version);
+        write_u16(&mut bytes, self.major_version);
+
+        write_u16(&mut bytes, self.constant_pool.entry_count());
+        bytes.extend_from_slice(&self.constant_pool.data);
+
+        write_u16(&mut bytes, self.flags.bits());
+        write_u16(&mut bytes, this_class_idx);
+        write_u16(&mut bytes, super_class_idx);
+
+        write_u16(&mut bytes, member_count(self.interfaces.len())?);
+        for interface_idx in self.interfaces {
+            write_u16(&mut bytes, interface_idx);
+        }
+
+        for members in [self.fields, self.methods, self.attributes] {
+            write_u16(&mut bytes, member_count(members.len())?);
+            for member in members {
+                bytes.extend_from_slice(&member);
+            }
+        }
+
+        Ok(bytes)
+    }
+
+    // Find the superclass of `class` while computing frames.
+    fn lookup_super_class(&self, class: &str) -> Option<String> {
+        if class == self.name {
+            self.super_class.clone()
+        } else if class == OBJECT {
+            None
+        } else {
+            self.super_class_lookup
+                .as_ref()
+                .and_then(|lookup| lookup(class))
+        }
+    }
+
+    // Find the most specific class that both `first` and `second` (which are
+    // internal names or array descriptors) are assignable to.
+    fn common_super_class(&self, first: &str, second: &str) -> String {
+        if first == second {
+            return first.to_string();
+        }
+
+        if first.starts_with('[') || second.starts_with('[') {
+            // Arrays of references are merged by merging their component
+            // types; anything else only has `Object` in common
+            let first_component = first.strip_prefix('[').and_then(reference_component);
+            let second_component = second.strip_prefix('[').and_then(reference_component);
+
+            return match (first_component, second_component) {
+                (Some(first_component), Some(second_component)) => {
+                    let component = self.common_super_class(first_component, second_component);
+
+                    if component.starts_with('[') {
+                        format!("[{}", component)
+                    } else {
+                        format!("[L{};", component)
+                    }
+                }
+                _ => OBJECT.to_string(),
+            };
+        }
+
+        let mut first_hierarchy = vec![first.to_string()];
+        while let Some(super_class) = self.lookup_super_class(first_hierarchy.last().unwrap()) {
+            first_hierarchy.push(super_class);
+        }
+
+        let mut current = Some(second.to_string());
+        while let Some(class) = current {
+            if first_hierarchy.contains(&class) {
+                return class;
+            }
+
+            current = self.lookup_super_class(&class);
+        }
+
+        OBJECT.to_string()
+    }
+}
+
+/// A field being written. The field is added to its class when
+/// [`FieldWriter::finish`] is called.
+pub struct FieldWriter<'a> {
+    class: &'a mut ClassWriter,
+
+    flags: FieldFlags,
+    name: String,
+    descriptor: String,
+
+    attributes: Vec<Vec<u8>>,
+}
+
+impl FieldWriter<'_> {
+    pub fn constant_pool(&mut self) -> &mut ConstantPoolWriter {
+        &mut self.class.constant_pool
+    }
+
+    /// Set the initial value of a static field with a `ConstantValue`
+    /// attribute.
+    pub fn set_constant_value(&mut self, value: Constant<'_>) -> Result<(), WriteError> {
+        let value_idx = self.class.constant_pool.constant(&value)?;
+
+        self.add_attribute("ConstantValue", &value_idx.to_be_bytes())
+    }
+
+    pub fn add_attribute(&mut self, name: &str, data: &[u8]) -> Result<(), WriteError> {
+        let attribute = write_attribute(&mut self.class.constant_pool, name, data)?;
+        self.attributes.push(attribute);
+
+        Ok(())
+    }
+
+    pub fn finish(self) -> Result<(), WriteError> {
+        let field = write_member(
+            &mut self.class.constant_pool,
+            self.flags.bits(),
+            &self.name,
+            &self.descriptor,
+            &self.attributes,
+        )?;
+        self.class.fields.push(field);
+
+        Ok(())
+    }
+}
+
+/// A position in the bytecode of a method, used as the target of branches and
+/// the bounds of exception handlers. Labels are created with
+/// [`MethodWriter::new_label`] and placed with [`MethodWriter::place_label`].
+/// A label can only be used with the `MethodWriter` that created it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Label {
+    // The ID of the `MethodWriter` that created this label
+    method_id: usize,
+
+    index: usize,
+}
+
+// An instruction whose operands have been added to the constant pool, but
+// whose branch offsets have not yet been resolved.
+enum Insn {
+    // An instruction with no operands
+    Simple(u8),
+
+    // `bipush` or `sipush`
+    Push(u8, i16),
+
+    NewArray(u8),
+    Var(u8, u16),
+    IInc(u16, i16),
+
+    // `new`, `anewarray`, `checkcast`, or `instanceof`, with the name of the
+    // class
+    Type(u8, u16, String),
+
+    // A field instruction, with the descriptor of the field
+    Field(u8, u16, String),
+
+    Method {
+        opcode: u8,
+        cpool_idx: u16,
+        name: String,
+        descriptor: String,
+    },
+
+    InvokeDynamic(u16, String),
+
+    // `ldc`, with the type of the constant
+    Ldc(u16, VerificationType),
+
+    Jump(u8, Label),
+    TableSwitch(i32, Label, Box<[Label]>),
+    LookupSwitch(Label, Box<[(i32, Label)]>),
+    MultiANewArray(u16, String, u8),
+}
+
+struct TryCatchBlock {
+    start: Label,
+    end: Label,
+    handler: Label,
+    catch_type_idx: u16,
+    catch_type: Option<String>,
+}
+
+/// A method being written. The method is added to its class when
+/// [`MethodWriter::finish`] is called.
+///
+/// Instructions are added with the `*_insn` methods, which take opcodes from
+/// [`opcodes`][super::opcodes]. Local variable and constant instructions are
+/// given in their general form (`iload`, `ldc`); the shortest encoding (such as
+/// `iload_1`, `ldc_w`, or a `wide` prefix) is picked automatically.
+pub struct MethodWriter<'a> {
+    class: &'a mut ClassWriter,
+
+    // A unique ID, used to check that labels belong to this method
+    id: usize,
+
+    flags: MethodFlags,
+    name: String,
+    descriptor: String,
+
+    insns: Vec<Insn>,
+
+    // The index of the instruction each label is placed before
+    labels: Vec<Option<usize>>,
+
+    try_catch_blocks: Vec<TryCatchBlock>,
+    line_numbers: Vec<(usize, u16)>,
+    exceptions: Vec<u16>,
+    attributes: Vec<Vec<u8>>,
+}
+
+impl MethodWriter<'_> {
+    pub fn constant_pool(&mut self) -> &mut ConstantPoolWriter {
+        &mut self.class.constant_pool
+    }
+
+    pub fn new_label(&mut self) -> Label {
+        self.labels.push(None);
+
+        Label {
+            method_id: self.id,
+            index: self.labels.len() - 1,
+        }
+    }
+
+    /// Place `label` before the next instruction.
+    pub fn place_label(&mut self, label: Label) -> Result<(), WriteError> {
+        let slot = self.label_slot(label)?;
+        if self.labels[slot].is_some() {
+            return Err(WriteError::LabelPlacedTwice);
+        }
+
+        self.labels[slot] = Some(self.insns.len());
+
+        Ok(())
+    }
+
+    /// Record that the next instruction was compiled from the given source
+    /// line, for the `LineNumberTable` attribute.
+    pub fn line_number(&mut self, line: u16) {
+        self.line_numbers.push((self.insns.len(), line));
+    }
+
+    /// Add an instruction that takes no operands, such as `iadd` or `areturn`.
+    pub fn insn(&mut self, opcode: u8) -> Result<(), WriteError> {
+        if !is_simple_opcode(opcode) {
+            return Err(WriteError::InvalidOpcode(opcode));
+        }
+
+        self.insns.push(Insn::Simple(opcode));
+
+        Ok(())
+    }
+
+    /// Add a `bipush` or `sipush` instruction.
+    pub fn int_insn(&mut self, opcode: u8, value: i16) -> Result<(), WriteError> {
+        let in_range = match opcode {
+            B_I_PUSH => i8::try_from(value).is_ok(),
+            S_I_PUSH => true,
+            _ => return Err(WriteError::InvalidOpcode(opcode)),
+        };
+
+        if !in_range {
+            return Err(WriteError::InvalidOperand);
+        }
+
+        self.insns.push(Insn::Push(opcode, value));
+
+        Ok(())
+    }
+
+    /// Push an int constant, using the shortest instruction that can hold it.
+    pub fn push_int(&mut self, value: i32) -> Result<(), WriteError> {
+        if (-1..=5).contains(&value) {
+            self.insn((I_CONST_0 as i32 + value) as u8)
+        } else if let Ok(value) = i8::try_from(value) {
+            self.int_insn(B_I_PUSH, value as i16)
+        } else if let Ok(value) = i16::try_from(value) {
+            self.int_insn(S_I_PUSH, value)
+        } else {
+            self.ldc_insn(Constant::Integer(value))
+        }
+    }
+
+    /// Add a `newarray` instruction creating an array of the given element
+    /// type, which is one of the `T_*` constants in
+    /// [`opcodes`][super::opcodes].
+    pub fn new_array_insn(&mut self, element_type: u8) -> Result<(), WriteError> {
+        if !(T_BOOLEAN..=T_LONG).contains(&element_type) {
+            return Err(WriteError::InvalidOperand);
+        }
+
+        self.insns.push(Insn::NewArray(element_type));
+
+        Ok(())
+    }
+
+    /// Add an instruction that loads or stores a local variable.
+    pub fn var_insn(&mut self, opcode: u8, index: u16) -> Result<(), WriteError> {
+        if !matches!(opcode, I_LOAD..=A_LOAD | I_STORE..=A_STORE) {
+            return Err(WriteError::InvalidOpcode(opcode));
+        }
+
+        self.insns.push(Insn::Var(opcode, index));
+
+        Ok(())
+    }
+
+    pub fn iinc_insn(&mut self, index: u16, increment: i16) -> Result<(), WriteError> {
+        self.insns.push(Insn::IInc(index, increment));
+
+        Ok(())
+    }
+
+    /// Add a `new`, `anewarray`, `checkcast`, or `instanceof` instruction.
+    pub fn type_insn(&mut self, opcode: u8, class: &str) -> Result<(), WriteError> {
+        if !matches!(opcode, NEW | A_NEW_ARRAY | CHECK_CAST | INSTANCE_OF) {
+            return Err(WriteError::InvalidOpcode(opcode));
+        }
+
+        let class_idx = self.class.constant_pool.class(class)?;
+        self.insns
+            .push(Insn::Type(opcode, class_idx, class.to_string()));
+
+        Ok(())
+    }
+
+    pub fn field_insn(
+        &mut self,
+        opcode: u8,
+        owner: &str,
+        name: &str,
+        descriptor: &str,
+    ) -> Result<(), WriteError> {
+        if !matches!(opcode, GET_STATIC..=PUT_FIELD) {
+            return Err(WriteError::InvalidOpcode(opcode));
+        }
+
+        parse_field_descriptor(descriptor)?;
+
+        let field_idx = self
+            .class
+            .constant_pool
+            .field_ref(owner, name, descriptor)?;
+        self.insns
+            .push(Insn::Field(opcode, field_idx, descriptor.to_string()));
+
+        Ok(())
+    }
+
+    /// Add an `invokevirtual`, `invokespecial`, `invokestatic`, or
+    /// `invokeinterface` instruction. `is_interface` is whether `owner` is an
+    /// interface; it is ignored for `invokeinterface`.
+    pub fn method_insn(
+        &mut self,
+        opcode: u8,
+        owner: &str,
+        name: &str,
+        descriptor: &str,
+        is_interface: bool,
+    ) -> Result<(), WriteError> {
+        if !matches!(opcode, INVOKE_VIRTUAL..=INVOKE_INTERFACE) {
+            return Err(WriteError::InvalidOpcode(opcode));
+        }
+
+        parse_method_descriptor(descriptor)?;
+
+        let constant_pool = &mut self.class.constant_pool;
+        let method_idx = if is_interface || opcode == INVOKE_INTERFACE {
+            constant_pool.interface_method_ref(owner, name, descriptor)?
+        } else {
+            constant_pool.method_ref(owner, name, descriptor)?
+        };
+
+        self.insns.push(Insn::Method {
+            opcode,
+            cpool_idx: method_idx,
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+        });
+
+        Ok(())
+    }
+
+    pub fn invoke_dynamic_insn(
+        &mut self,
+        name: &str,
+        descriptor: &str,
+        bootstrap: &Handle<'_>,
+        args: &[Constant<'_>],
+    ) -> Result<(), WriteError> {
+        parse_method_descriptor(descriptor)?;
+
+        let call_site_idx = self
+            .class
+            .constant_pool
+            .invoke_dynamic(name, descriptor, bootstrap, args)?;
+        self.insns
+            .push(Insn::InvokeDynamic(call_site_idx, descriptor.to_string()));
+
+        Ok(())
+    }
+
+    /// Add an `ldc` instruction (or `ldc_w` or `ldc2_w`, as needed) loading
+    /// the given constant.
+    pub fn ldc_insn(&mut self, constant: Constant<'_>) -> Result<(), WriteError> {
+        let constant_type = match constant {
+            Constant::Integer(_) => VerificationType::Integer,
+            Constant::Float(_) => VerificationType::Float,
+            Constant::Long(_) => VerificationType::Long,
+            Constant::Double(_) => VerificationType::Double,
+            Constant::String(_) => VerificationType::object("java/lang/String"),
+            Constant::Class(_) => VerificationType::object("java/lang/Class"),
+            Constant::MethodType(_) => VerificationType::object("java/lang/invoke/MethodType"),
+            Constant::MethodHandle(_) => VerificationType::object("java/lang/invoke/MethodHandle"),
+            Constant::Dynamic { descriptor, .. } => {
+                VerificationType::from_descriptor(parse_field_descriptor(descriptor)?)
+            }
+        };
+
+        let constant_idx = self.class.constant_pool.constant(&constant)?;
+        self.insns.push(Insn::Ldc(constant_idx, constant_type));
+
+        Ok(())
+    }
+
+    /// Add a branch instruction. `jsr` is not supported.
+    pub fn jump_insn(&mut self, opcode: u8, label: Label) -> Result<(), WriteError> {
+        if !matches!(opcode, IF_EQ..=GOTO | IF_NULL | IF_NON_NULL) {
+            return Err(WriteError::InvalidOpcode(opcode));
+        }
+
+        self.insns.push(Insn::Jump(opcode, label));
+
+        Ok(())
+    }
+
+    /// Add a `tableswitch` instruction. `labels[i]` is the target for the
+    /// value `min + i`.
+    pub fn table_switch_insn(
+        &mut self,
+        min: i32,
+        default: Label,
+        labels: &[Label],
+    ) -> Result<(), WriteError> {
+        let in_range = i32::try_from(labels.len())
+            .ok()
+            .and_then(|count| min.checked_add(count - 1));
+        if labels.is_empty() || in_range.is_none() {
+            return Err(WriteError::InvalidOperand);
+        }
+
+        self.insns
+            .push(Insn::TableSwitch(min, default, labels.into()));
+
+        Ok(())
+    }
+
+    /// Add a `lookupswitch` instruction. The cases may be given in any order.
+    pub fn lookup_switch_insn(
+        &mut self,
+        default: Label,
+        cases: &[(i32, Label)],
+    ) -> Result<(), WriteError> {
+        let mut cases = cases.to_vec();
+        cases.sort_by_key(|(key, _)| *key);
+
+        if cases.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+            return Err(WriteError::InvalidOperand);
+        }
+
+        self.insns
+            .push(Insn::LookupSwitch(default, cases.into_boxed_slice()));
+
+        Ok(())
+    }
+
+    pub fn multi_a_new_array_insn(
+        &mut self,
+        descriptor: &str,
+        dimensions: u8,
+    ) -> Result<(), WriteError> {
+        let array_dimensions = descriptor.bytes().take_while(|b| *b == b'[').count();
+        if dimensions == 0 || array_dimensions < dimensions as usize {
+            return Err(WriteError::InvalidOperand);
+        }
+
+        parse_field_descriptor(descriptor)?;
+
+        let class_idx = self.class.constant_pool.class(descriptor)?;
+        self.insns.push(Insn::MultiANewArray(
+            class_idx,
+            descriptor.to_string(),
+            dimensions,
+        ));
+
+        Ok(())
+    }
+
+    /// Add an exception handler covering the instructions between `start`
+    /// (inclusive) and `end` (exclusive). A `catch_type` of `None` catches
+    /// every exception. Handlers are searched in the order they were added.
+    pub fn try_catch_block(
+        &mut self,
+        start: Label,
+        end: Label,
+        handler: Label,
+        catch_type: Option<&str>,
+    ) -> Result<(), WriteError> {
+        let catch_type_idx = match catch_type {
+            Some(catch_type) => self.class.constant_pool.class(catch_type)?,
+            None => 0,
+        };
+
+        self.try_catch_blocks.push(TryCatchBlock {
+            start,
+            end,
+            handler,
+            catch_type_idx,
+            catch_type: catch_type.map(|c| c.to_string()),
+        });
+
+        Ok(())
+    }
+
+    /// Declare that the method throws the given checked exception class, for
+    /// the `Exceptions` attribute.
+    pub fn add_exception(&mut self, class: &str) -> Result<(), WriteError> {
+        let class_idx = self.class.constant_pool.class(class)?;
+        self.exceptions.push(class_idx);
+
+        Ok(())
+    }
+
+    pub fn add_attribute(&mut self, name: &str, data: &[u8]) -> Result<(), WriteError> {
+        let attribute = write_attribute(&mut self.class.constant_pool, name, data)?;
+        self.attributes.push(attribute);
+
+        Ok(())
+    }
+
+    /// Finish writing the method, resolving its labels and computing its
+    /// frames, and add it to the class.
+    ///
+    /// Abstract and native methods must not have any instructions; all other
+    /// methods must have at least one.
+    pub fn finish(mut self) -> Result<(), WriteError> {
+        let has_code = !self.insns.is_empty();
+        let needs_code = !self
+            .flags
+            .intersects(MethodFlags::ABSTRACT | MethodFlags::NATIVE);
+
+        match (has_code, needs_code) {
+            (true, false) => return Err(WriteError::UnexpectedCode),
+            (false, true) => return Err(WriteError::MissingCode),
+            _ => {}
+        }
+
+        let mut attributes = Vec::new();
+        if has_code {
+            let code = self.write_code()?;
+            attributes.push(write_attribute(
+                &mut self.class.constant_pool,
+                "Code",
+                &code,
+            )?);
+        }
+
+        if !self.exceptions.is_empty() {
+            let mut data = Vec::new();
+            write_u16(&mut data, member_count(self.exceptions.len())?);
+            for exception_idx in &self.exceptions {
+                write_u16(&mut data, *exception_idx);
+            }
+
+            attributes.push(write_attribute(
+                &mut self.class.constant_pool,
+                "Exceptions",
+                &data,
+            )?);
+        }
+
+        attributes.append(&mut self.attributes);
+
+        let method = write_member(
+            &mut self.class.constant_pool,
+            self.flags.bits(),
+            &self.name,
+            &self.descriptor,
+            &attributes,
+        )?;
+        self.class.methods.push(method);
+
+        Ok(())
+    }
+
+    // Get the index of the instruction a label was placed before.
+    fn label_index(&self, label: Label) -> Result<usize, WriteError> {
+        self.labels[self.label_slot(label)?].ok_or(WriteError::LabelNotPlaced)
+    }
+
+    // Get the index of a label in `self.labels`, checking that it was created
+    // by this method writer.
+    fn label_slot(&self, label: Label) -> Result<usize, WriteError> {
+        if label.method_id == self.id {
+            Ok(label.index)
+        } else {
+            Err(WriteError::ForeignLabel)
+        }
+    }
+
+    // Produce the data of the `Code` attribute.
+    fn write_code(&mut self) -> Result<Vec<u8>, WriteError> {
+        let layout = self.layout()?;
+        let analysis = self.analyze(&layout)?;
+
+        let mut code = Vec::with_capacity(layout.offsets[self.insns.len()]);
+        for (index, insn) in self.insns.iter().enumerate() {
+            self.write_insn(&mut code, &layout, index, insn)?;
+        }
+
+        // Unreachable code has no frame to verify it against, so replace it
+        // with `nop`s followed by an `athrow`. Each such range is given a frame
+        // with a `Throwable` on the stack, which verifies for any instructions
+        // that follow it.
+        let mut frames = Vec::new();
+        let mut max_stack = analysis.max_stack;
+        let mut index = 0;
+        while index < self.insns.len() {
+            if analysis.frames[index].is_some() {
+                if analysis.frame_points[index] {
+                    frames.push((index, analysis.frames[index].clone().unwrap()));
+                }
+
+                index += 1;
+                continue;
+            }
+
+            let start = index;
+            while index < self.insns.len() && analysis.frames[index].is_none() {
+                index += 1;
+            }
+
+            let start_offset = layout.offsets[start];
+            let end_offset = layout.offsets[index];
+            code[start_offset..end_offset - 1].fill(NOP);
+            code[end_offset - 1] = A_THROW;
+
+            frames.push((
+                start,
+                Frame {
+                    locals: Vec::new(),
+                    stack: vec![VerificationType::object("java/lang/Throwable")],
+                },
+            ));
+            max_stack = max_stack.max(1);
+        }
+
+        let mut data = Vec::new();
+        write_u16(&mut data, max_stack);
+        write_u16(&mut data, self.max_locals()?);
+        write_u32(&mut data, code.len() as u32);
+        data.extend_from_slice(&code);
+
+        // Exception handlers shouldn't cover the unreachable code, as it may
+        // not be compatible with the handler's frame. Split each handler's
+        // range into the runs of reachable instructions it covers.
+        let mut exception_table = Vec::new();
+        for block in &self.try_catch_blocks {
+            let start = self.label_index(block.start)?;
+            let end = self.label_index(block.end)?;
+            let handler = self.label_index(block.handler)?;
+
+            let mut index = start;
+            while index < end {
+                if analysis.frames[index].is_none() {
+                    index += 1;
+                    continue;
+                }
+
+                let run_start = index;
+                while index < end && analysis.frames[index].is_some() {
+                    index += 1;
+                }
+
+                exception_table.push((
+                    layout.offsets[run_start] as u16,
+                    layout.offsets[index] as u16,
+                    layout.offsets[handler] as u16,
+                    block.catch_type_idx,
+                ));
+            }
+        }
+
+        write_u16(&mut data, member_count(exception_table.len())?);
+        for (start_pc, end_pc, handler_pc, catch_type_idx) in exception_table {
+            write_u16(&mut data, start_pc);
+            write_u16(&mut data, end_pc);
+            write_u16(&mut data, handler_pc);
+            write_u16(&mut data, catch_type_idx);
+        }
+
+        let mut attributes = Vec::new();
+        if self.class.major_version >= STACK_MAP_MAJOR_VERSION && !frames.is_empty() {
+            let stack_map_table = self.write_stack_map_table(&layout, &frames)?;
+            attributes.push(write_attribute(
+                &mut self.class.constant_pool,
+                "StackMapTable",
+                &stack_map_table,
+            )?);
+        }
+
+        if !self.line_numbers.is_empty() {
+            let mut line_number_table = Vec::new();
+            write_u16(
+                &mut line_number_table,
+                member_count(self.line_numbers.len())?,
+            );
+            for (index, line) in &self.line_numbers {
+                write_u16(&mut line_number_table, layout.offsets[*index] as u16);
+                write_u16(&mut line_number_table, *line);
+            }
+
+            attributes.push(write_attribute(
+                &mut self.class.constant_pool,
+                "LineNumberTable",
+                &line_number_table,
+            )?);
+        }
+
+        write_u16(&mut data, member_count(attributes.len())?);
+        for attribute in attributes {
+            data.extend_from_slice(&attribute);
+        }
+
+        Ok(data)
+    }
+
+    // The number of local variable slots used by the arguments and
+    // instructions of this method.
+    fn max_locals(&self) -> Result<u16, WriteError> {
+        let mut max_locals = self.initial_frame()?.locals.len();
+
+        for insn in &self.insns {
+            let used = match insn {
+                Insn::Var(L_LOAD | D_LOAD | L_STORE | D_STORE, index) => *index as usize + 2,
+                Insn::Var(_, index) | Insn::IInc(index, _) => *index as usize + 1,
+                _ => 0,
+            };
+
+            max_locals = max_locals.max(used);
+        }
+
+        u16::try_from(max_locals).map_err(|_| WriteError::InvalidOperand)
+    }
+
+    // Pick the encoding of each branch and compute the offset of each
+    // instruction. Branches start out short and are widened until every
+    // offset fits.
+    fn layout(&self) -> Result<Layout, WriteError> {
+        let mut layout = Layout {
+            offsets: vec![0; self.insns.len() + 1],
+            wide_jumps: vec![false; self.insns.len()],
+        };
+
+        loop {
+            let mut offset = 0;
+            for (index, insn) in self.insns.iter().enumerate() {
+                layout.offsets[index] = offset;
+                offset += insn_size(insn, offset, layout.wide_jumps[index]);
+            }
+
+            layout.offsets[self.insns.len()] = offset;
+
+            if offset > u16::MAX as usize {
+                return Err(WriteError::CodeTooLarge);
+            }
+
+            let mut changed = false;
+            for (index, insn) in self.insns.iter().enumerate() {
+                if let Insn::Jump(_, label) = insn {
+                    let target = layout.offsets[self.label_index(*label)?] as isize;
+                    let branch_offset = target - layout.offsets[index] as isize;
+
+                    if !layout.wide_jumps[index] && i16::try_from(branch_offset).is_err() {
+                        layout.wide_jumps[index] = true;
+                        changed = true;
+                    }
+                }
+            }
+
+            if !changed {
+                return Ok(layout);
+            }
+        }
+    }
+
+    fn write_insn(
+        &self,
+        code: &mut Vec<u8>,
+        layout: &Layout,
+        index: usize,
+        insn: &Insn,
+    ) -> Result<(), WriteError> {
+        let offset = layout.offsets[index];
+        let branch_offset = |label: Label| -> Result<i32, WriteError> {
+            Ok(layout.offsets[self.label_index(label)?] as i32 - offset as i32)
+        };
+
+        match insn {
+            Insn::Simple(opcode) => code.push(*opcode),
+            Insn::Push(opcode, value) => {
+                code.push(*opcode);
+                if *opcode == B_I_PUSH {
+                    code.push(*value as u8);
+                } else {
+                    write_u16(code, *value as u16);
+                }
+            }
+            Insn::NewArray(element_type) => {
+                code.push(NEW_ARRAY);
+                code.push(*element_type);
+            }
+            Insn::Var(opcode, index) => {
+                if *index <= 3 {
+                    // `iload_0` and friends
+                    let short_opcode = if *opcode <= A_LOAD {
+                        I_LOAD_0 + (opcode - I_LOAD) * 4
+                    } else {
+                        I_STORE_0 + (opcode - I_STORE) * 4
+                    };
+
+                    code.push(short_opcode + *index as u8);
+                } else if *index <= u8::MAX as u16 {
+                    code.push(*opcode);
+                    code.push(*index as u8);
+                } else {
+                    code.push(WIDE);
+                    code.push(*opcode);
+                    write_u16(code, *index);
+                }
+            }
+            Insn::IInc(index, increment) => {
+                if *index <= u8::MAX as u16 && i8::try_from(*increment).is_ok() {
+                    code.push(I_INC);
+                    code.push(*index as u8);
+                    code.push(*increment as u8);
+                } else {
+                    code.push(WIDE);
+                    code.push(I_INC);
+                    write_u16(code, *index);
+                    write_u16(code, *increment as u16);
+                }
+            }
+            Insn::Type(opcode, cpool_idx, _)
+            | Insn::Field(opcode, cpool_idx, _)
+            | Insn::Method {
+                opcode, cpool_idx, ..
+            } => {
+                code.push(*opcode);
+                write_u16(code, *cpool_idx);
+
+                if let Insn::Method {
+                    opcode: INVOKE_INTERFACE,
+                    descriptor,
+                    ..
+                } = insn
+                {
+                    let (args, _) = parse_method_descriptor(descriptor)?;
+                    let arg_slots: usize = args.iter().map(|a| descriptor_size(a)).sum();
+
+                    code.push(arg_slots as u8 + 1);
+                    code.push(0);
+                }
+            }
+            Insn::InvokeDynamic(cpool_idx, _) => {
+                code.push(INVOKE_DYNAMIC);
+                write_u16(code, *cpool_idx);
+                write_u16(code, 0);
+            }
+            Insn::Ldc(cpool_idx, constant_type) => {
+                if constant_type.is_wide() {
+                    code.push(LDC_2_W);
+                    write_u16(code, *cpool_idx);
+                } else if *cpool_idx <= u8::MAX as u16 {
+                    code.push(LDC);
+                    code.push(*cpool_idx as u8);
+                } else {
+                    code.push(LDC_W);
+                    write_u16(code, *cpool_idx);
+                }
+            }
+            Insn::Jump(opcode, label) => {
+                if !layout.wide_jumps[index] {
+                    code.push(*opcode);
+                    write_u16(code, branch_offset(*label)? as u16);
+                } else if *opcode == GOTO {
+                    code.push(GOTO_W);
+                    write_u32(code, branch_offset(*label)? as u32);
+                } else {
+                    // Conditional branches have no wide form, so branch over
+                    // a `goto_w` with the opposite condition instead
+                    code.push(opposite_condition(*opcode));
+                    write_u16(code, 8);
+                    code.push(GOTO_W);
+                    write_u32(code, (branch_offset(*label)? - 3) as u32);
+                }
+            }
+            Insn::TableSwitch(min, default, labels) => {
+                code.push(TABLE_SWITCH);
+                code.resize(code.len() + switch_padding(offset), 0);
+
+                write_u32(code, branch_offset(*default)? as u32);
+                write_u32(code, *min as u32);
+                write_u32(code, (*min + labels.len() as i32 - 1) as u32);
+                for label in labels {
+                    write_u32(code, branch_offset(*label)? as u32);
+                }
+            }
+            Insn::LookupSwitch(default, cases) => {
+                code.push(LOOKUP_SWITCH);
+                code.resize(code.len() + switch_padding(offset), 0);
+
+                write_u32(code, branch_offset(*default)? as u32);
+                write_u32(code, cases.len() as u32);
+                for (key, label) in cases {
+                    write_u32(code, *key as u32);
+                    write_u32(code, branch_offset(*label)? as u32);
+                }
+            }
+            Insn::MultiANewArray(cpool_idx, _, dimensions) => {
+                code.push(MULTI_A_NEW_ARRAY);
+                write_u16(code, *cpool_idx);
+                code.push(*dimensions);
+            }
+        }
+
+        Ok(())
+    }
+
+    // The frame at the start of the method, holding its arguments.
+    fn initial_frame(&self) -> Result<Frame, WriteError> {
+        let mut locals = Vec::new();
+
+        if !self.flags.contains(MethodFlags::STATIC) {
+            if self.name == "<init>" && self.class.name != OBJECT {
+                locals.push(VerificationType::UninitializedThis);
+            } else {
+                locals.push(VerificationType::object(&self.class.name));
+            }
+        }
+
+        let (args, _) = parse_method_descriptor(&self.descriptor)?;
+        for arg in args {
+            let arg_type = VerificationType::from_descriptor(arg);
+            let is_wide = arg_type.is_wide();
+
+            locals.push(arg_type);
+            if is_wide {
+                locals.push(VerificationType::Top);
+            }
+        }
+
+        Ok(Frame {
+            locals,
+            stack: Vec::new(),
+        })
+    }
+
+    // Compute the frame before each reachable instruction by abstract
+    // interpretation, merging frames where control flow meets.
+    fn analyze(&self, layout: &Layout) -> Result<Analysis, WriteError> {
+        let insn_count = self.insns.len();
+
+        let mut handlers = Vec::with_capacity(self.try_catch_blocks.len());
+        for block in &self.try_catch_blocks {
+            let catch_type = match &block.catch_type {
+                Some(catch_type) => VerificationType::object(catch_type),
+                None => VerificationType::object("java/lang/Throwable"),
+            };
+
+            handlers.push((
+                self.label_index(block.start)?,
+                self.label_index(block.end)?,
+                self.label_index(block.handler)?,
+                catch_type,
+            ));
+        }
+
+        // Frames are needed at branch targets, exception handlers, and
+        // instructions that follow an unconditional branch
+        let mut frame_points = vec![false; insn_count];
+        for (index, insn) in self.insns.iter().enumerate() {
+            for target in self.branch_targets(insn)? {
+                if target >= insn_count {
+                    return Err(WriteError::FallsOffEndOfCode);
+                }
+
+                frame_points[target] = true;
+            }
+
+            // A widened conditional branch also branches to the next
+            // instruction, over its `goto_w`
+            let is_wide_condition = matches!(insn, Insn::Jump(opcode, _) if *opcode != GOTO)
+                && layout.wide_jumps[index];
+
+            if (!falls_through(insn) || is_wide_condition) && index + 1 < insn_count {
+                frame_points[index + 1] = true;
+            }
+        }
+
+        for (_, _, handler, _) in &handlers {
+            if *handler >= insn_count {
+                return Err(WriteError::FallsOffEndOfCode);
+            }
+
+            frame_points[*handler] = true;
+        }
+
+        let mut frames: Vec<Option<Frame>> = vec![None; insn_count];
+        frames[0] = Some(self.initial_frame()?);
+
+        let mut max_stack = 0;
+        let mut worklist = vec![0];
+        while let Some(index) = worklist.pop() {
+            let insn = &self.insns[index];
+            let frame = frames[index].clone().unwrap();
+
+            let mut next_frame = frame.clone();
+            self.execute(insn, index, &mut next_frame)?;
+            max_stack = max_stack.max(next_frame.stack.len());
+
+            // An exception can be thrown before or after the instruction
+            // changes the locals, so handlers have to accept both
+            for (start, end, handler, catch_type) in &handlers {
+                if (*start..*end).contains(&index) {
+                    let mut handler_frame = Frame {
+                        locals: frame.locals.clone(),
+                        stack: vec![catch_type.clone()],
+                    };
+                    self.merge_locals(&mut handler_frame.locals, &next_frame.locals);
+
+                    if self.merge_into(&mut frames[*handler], &handler_frame)? {
+                        worklist.push(*handler);
+                    }
+                }
+            }
+
+            let mut successors = self.branch_targets(insn)?;
+            if falls_through(insn) {
+                if index + 1 >= insn_count {
+                    return Err(WriteError::FallsOffEndOfCode);
+                }
+
+                successors.push(index + 1);
+            }
+
+            for successor in successors {
+                if self.merge_into(&mut frames[successor], &next_frame)? {
+                    worklist.push(successor);
+                }
+            }
+        }
+
+        Ok(Analysis {
+            frames,
+            frame_points,
+            max_stack: u16::try_from(max_stack.max(1)).map_err(|_| WriteError::InvalidOperand)?,
+        })
+    }
+
+    // The indices of the instructions that `insn` may branch to.
+    fn branch_targets(&self, insn: &Insn) -> Result<Vec<usize>, WriteError> {
+        match insn {
+            Insn::Jump(_, label) => Ok(vec![self.label_index(*label)?]),
+            Insn::TableSwitch(_, default, labels) => core::iter::once(default)
+                .chain(labels.iter())
+                .map(|label| self.label_index(*label))
+                .collect(),
+            Insn::LookupSwitch(default, cases) => core::iter::once(default)
+                .chain(cases.iter().map(|(_, label)| label))
+                .map(|label| self.label_index(*label))
+                .collect(),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    // Merge `frame` into the frame stored for an instruction, returning
+    // whether the stored frame changed.
+    fn merge_into(&self, target: &mut Option<Frame>, frame: &Frame) -> Result<bool, WriteError> {
+        let Some(target) = target else {
+            *target = Some(frame.clone());
+            return Ok(true);
+        };
+
+        if target.stack.len() != frame.stack.len() {
+            return Err(WriteError::InconsistentStackHeight);
+        }
+
+        let mut changed = self.merge_locals(&mut target.locals, &frame.locals);
+
+        for (target_type, other_type) in target.stack.iter_mut().zip(&frame.stack) {
+            let merged = self
+                .merge_types(target_type, other_type)
+                .ok_or(WriteError::IncompatibleStackTypes)?;
+
+            if merged != *target_type {
+                *target_type = merged;
+                changed = true;
+            }
+        }
+
+        Ok(changed)
+    }
+
+    // Merge `other` into `locals`. Locals that are incompatible become
+    // unusable, and locals that aren't set on both sides are dropped.
+    fn merge_locals(&self, locals: &mut Vec<VerificationType>, other: &[VerificationType]) -> bool {
+        let mut changed = false;
+
+        if locals.len() > other.len() {
+            locals.truncate(other.len());
+            changed = true;
+        }
+
+        for (local, other_local) in locals.iter_mut().zip(other) {
+            let merged = self
+                .merge_types(local, other_local)
+                .unwrap_or(VerificationType::Top);
+
+            if merged != *local {
+                *local = merged;
+                changed = true;
+            }
+        }
+
+        // The second half of a long or double is only usable together with
+        // its first half
+        for i in 1..locals.len() {
+            if locals[i - 1].is_wide() && locals[i] != VerificationType::Top {
+                locals[i] = VerificationType::Top;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    // Find the type that both `first` and `second` can be used as, if any.
+    fn merge_types(
+        &self,
+        first: &VerificationType,
+        second: &VerificationType,
+    ) -> Option<VerificationType> {
+        match (first, second) {
+            _ if first == second => Some(first.clone()),
+            (VerificationType::Null, VerificationType::Object(_)) => Some(second.clone()),
+            (VerificationType::Object(_), VerificationType::Null) => Some(first.clone()),
+            (VerificationType::Object(first), VerificationType::Object(second)) => Some(
+                VerificationType::Object(self.class.common_super_class(first, second)),
+            ),
+            _ => None,
+        }
+    }
+
+    // Apply the effect of `insn` (at index `index`) to `frame`.
+    fn execute(&self, insn: &Insn, index: usize, frame: &mut Frame) -> Result<(), WriteError> {
+        match insn {
+            Insn::Simple(opcode) => execute_simple(*opcode, frame)?,
+            Insn::Push(..) => frame.push(VerificationType::Integer),
+            Insn::NewArray(element_type) => {
+                frame.pop(1)?;
+
+                let descriptor = match *element_type {
+                    T_BOOLEAN => "[Z",
+                    T_CHAR => "[C",
+                    T_FLOAT => "[F",
+                    T_DOUBLE => "[D",
+                    T_BYTE => "[B",
+                    T_SHORT => "[S",
+                    T_INT => "[I",
+                    T_LONG => "[J",
+                    _ => unreachable!("Element type was checked when adding instruction"),
+                };
+                frame.push(VerificationType::object(descriptor));
+            }
+            Insn::Var(opcode, index) => {
+                let index = *index as usize;
+
+                match *opcode {
+                    I_LOAD => frame.push(VerificationType::Integer),
+                    L_LOAD => frame.push(VerificationType::Long),
+                    F_LOAD => frame.push(VerificationType::Float),
+                    D_LOAD => frame.push(VerificationType::Double),
+                    A_LOAD => {
+                        let local = frame.locals.get(index).cloned();
+                        frame.push(local.unwrap_or(VerificationType::Top));
+                    }
+                    L_STORE | D_STORE => {
+                        frame.pop(2)?;
+                        let local_type = if *opcode == L_STORE {
+                            VerificationType::Long
+                        } else {
+                            VerificationType::Double
+                        };
+
+                        frame.set_local(index, local_type);
+                    }
+                    _ => {
+                        let value = frame.pop(1)?.remove(0);
+                        frame.set_local(index, value);
+                    }
+                }
+            }
+            Insn::IInc(..) => {}
+            Insn::Type(opcode, _, class) => match *opcode {
+                NEW => frame.push(VerificationType::Uninitialized(index)),
+                A_NEW_ARRAY => {
+                    frame.pop(1)?;
+
+                    let descriptor = if class.starts_with('[') {
+                        format!("[{}", class)
+                    } else {
+                        format!("[L{};", class)
+                    };
+                    frame.push(VerificationType::Object(descriptor));
+                }
+                CHECK_CAST => {
+                    frame.pop(1)?;
+                    frame.push(VerificationType::object(class));
+                }
+                _ => {
+                    frame.pop(1)?;
+                    frame.push(VerificationType::Integer);
+                }
+            },
+            Insn::Field(opcode, _, descriptor) => {
+                let field_type = VerificationType::from_descriptor(descriptor);
+                let size = descriptor_size(descriptor);
+
+                match *opcode {
+                    GET_STATIC => frame.push(field_type),
+                    PUT_STATIC => {
+                        frame.pop(size)?;
+                    }
+                    GET_FIELD => {
+                        frame.pop(1)?;
+                        frame.push(field_type);
+                    }
+                    _ => {
+                        frame.pop(size + 1)?;
+                    }
+                }
+            }
+            Insn::Method {
+                opcode,
+                name,
+                descriptor,
+                ..
+            } => {
+                let (args, return_type) = parse_method_descriptor(descriptor)?;
+                frame.pop(args.iter().map(|a| descriptor_size(a)).sum())?;
+
+                if *opcode != INVOKE_STATIC {
+                    let receiver = frame.pop(1)?.remove(0);
+
+                    // Calling a constructor initializes every copy of the
+                    // object
+                    let initialized = match &receiver {
+                        VerificationType::UninitializedThis if name == "<init>" => {
+                            Some(VerificationType::object(&self.class.name))
+                        }
+                        VerificationType::Uninitialized(new_index) if name == "<init>" => {
+                            match &self.insns[*new_index] {
+                                Insn::Type(NEW, _, class) => Some(VerificationType::object(class)),
+                                _ => unreachable!("Uninitialized types come from `new`"),
+                            }
+                        }
+                        _ => None,
+                    };
+
+                    if let Some(initialized) = initialized {
+                        for value in frame.locals.iter_mut().chain(frame.stack.iter_mut()) {
+                            if *value == receiver {
+                                *value = initialized.clone();
+                            }
+                        }
+                    }
+                }
+
+                if return_type != "V" {
+                    frame.push(VerificationType::from_descriptor(return_type));
+                }
+            }
+            Insn::InvokeDynamic(_, descriptor) => {
+                let (args, return_type) = parse_method_descriptor(descriptor)?;
+                frame.pop(args.iter().map(|a| descriptor_size(a)).sum())?;
+
+                if return_type != "V" {
+                    frame.push(VerificationType::from_descriptor(return_type));
+                }
+            }
+            Insn::Ldc(_, constant_type) => frame.push(constant_type.clone()),
+            Insn::Jump(opcode, _) => match *opcode {
+                IF_EQ..=IF_LE | IF_NULL | IF_NON_NULL => {
+                    frame.pop(1)?;
+                }
+                IF_I_CMP_EQ..=IF_A_CMP_NE => {
+                    frame.pop(2)?;
+                }
+                _ => {}
+            },
+            Insn::TableSwitch(..) | Insn::LookupSwitch(..) => {
+                frame.pop(1)?;
+            }
+            Insn::MultiANewArray(_, descriptor, dimensions) => {
+                frame.pop(*dimensions as usize)?;
+                frame.push(VerificationType::object(descriptor));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_stack_map_table(
+        &mut self,
+        layout: &Layout,
+        frames: &[(usize, Frame)],
+    ) -> Result<Vec<u8>, WriteError> {
+        let mut data = Vec::new();
+        write_u16(&mut data, member_count(frames.len())?);
+
+        let mut previous_locals = self.initial_frame()?.compressed_locals();
+        let mut previous_offset = None;
+        for (index, frame) in frames {
+            let offset = layout.offsets[*index];
+
+            // The first frame's offset is stored as-is, and each later
+            // frame's offset is stored relative to the previous frame plus one
+            let offset_delta = match previous_offset {
+                Some(previous_offset) => offset - previous_offset - 1,
+                None => offset,
+            } as u16;
+            previous_offset = Some(offset);
+
+            let locals = frame.compressed_locals();
+            let stack = compress_types(&frame.stack);
+
+            let same_locals = locals == previous_locals;
+            if same_locals && stack.is_empty() {
+                if offset_delta < 64 {
+                    // same_frame
+                    data.push(offset_delta as u8);
+                } else {
+                    // same_frame_extended
+                    data.push(251);
+                    write_u16(&mut data, offset_delta);
+                }
+            } else if same_locals && stack.len() == 1 {
+                if offset_delta < 64 {
+                    // same_locals_1_stack_item_frame
+                    data.push(64 + offset_delta as u8);
+                } else {
+                    // same_locals_1_stack_item_frame_extended
+                    data.push(247);
+                    write_u16(&mut data, offset_delta);
+                }
+
+                self.write_verification_type(&mut data, layout, &stack[0])?;
+            } else if stack.is_empty()
+                && locals.len() < previous_locals.len()
+                && previous_locals.len() - locals.len() <= 3
+                && previous_locals.starts_with(&locals)
+            {
+                // chop_frame
+                data.push(251 - (previous_locals.len() - locals.len()) as u8);
+                write_u16(&mut data, offset_delta);
+            } else if stack.is_empty()
+                && locals.len() > previous_locals.len()
+                && locals.len() - previous_locals.len() <= 3
+                && locals.starts_with(&previous_locals)
+            {
+                // append_frame
+                data.push(251 + (locals.len() - previous_locals.len()) as u8);
+                write_u16(&mut data, offset_delta);
+
+                for local in &locals[previous_locals.len()..] {
+                    self.write_verification_type(&mut data, layout, local)?;
+                }
+            } else {
+                // full_frame
+                data.push(255);
+                write_u16(&mut data, offset_delta);
+
+                write_u16(&mut data, member_count(locals.len())?);
+                for local in &locals {
+                    self.write_verification_type(&mut data, layout, local)?;
+                }
+
+                write_u16(&mut data, member_count(stack.len())?);
+                for value in &stack {
+                    self.write_verification_type(&mut data, layout, value)?;
+                }
+            }
+
+            previous_locals = locals;
+        }
+
+        Ok(data)
+    }
+
+    fn write_verification_type(
+        &mut self,
+        data: &mut Vec<u8>,
+        layout: &Layout,
+        verification_type: &VerificationType,
+    ) -> Result<(), WriteError> {
+        match verification_type {
+            VerificationType::Top => data.push(0),
+            VerificationType::Integer => data.push(1),
+            VerificationType::Float => data.push(2),
+            VerificationType::Double => data.push(3),
+            VerificationType::Long => data.push(4),
+            VerificationType::Null => data.push(5),
+            VerificationType::UninitializedThis => data.push(6),
+            VerificationType::Object(class) => {
+                let class_idx = self.class.constant_pool.class(class)?;
+
+                data.push(7);
+                write_u16(data, class_idx);
+            }
+            VerificationType::Uninitialized(new_index) => {
+                data.push(8);
+                write_u16(data, layout.offsets[*new_index] as u16);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+struct Layout {
+    // The offset of each instruction, followed by the length of the code
+    offsets: Vec<usize>,
+
+    // Whether each branch instruction needs a 32-bit offset
+    wide_jumps: Vec<bool>,
+}
+
+struct Analysis {
+    // The frame before each instruction, or `None` if it is unreachable
+    frames: Vec<Option<Frame>>,
+
+    // Whether each instruction needs an entry in the `StackMapTable`
+    frame_points: Vec<bool>,
+
+    max_stack: u16,
+}
+
+// The type of a local variable or stack slot, as used by the verifier. Longs
+// and doubles take up two slots, the second of which is `Top`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum VerificationType {
+    Top,
+    Integer,
+    Float,
+    Long,
+    Double,
+    Null,
+    UninitializedThis,
+
+    // An object created by the `new` instruction at the given index that
+    // hasn't had its constructor called yet
+    Uninitialized(usize),
+
+    // An instance of a class, given by its internal name or array descriptor
+    Object(String),
+}
+
+impl VerificationType {
+    fn object(class: &str) -> Self {
+        VerificationType::Object(class.to_string())
+    }
+
+    // Get the type of values of the given (valid) field descriptor.
+    fn from_descriptor(descriptor: &str) -> Self {
+        match descriptor.as_bytes()[0] {
+            b'Z' | b'B' | b'C' | b'S' | b'I' => VerificationType::Integer,
+            b'F' => VerificationType::Float,
+            b'J' => VerificationType::Long,
+            b'D' => VerificationType::Double,
+            b'L' => VerificationType::object(&descriptor[1..descriptor.len() - 1]),
+            _ => VerificationType::object(descriptor),
+        }
+    }
+
+    fn is_wide(&self) -> bool {
+        matches!(self, VerificationType::Long | VerificationType::Double)
+    }
+}
+
+#[derive(Clone)]
+struct Frame {
+    locals: Vec<VerificationType>,
+    stack: Vec<VerificationType>,
+}
+
+impl Frame {
+    fn push(&mut self, value: VerificationType) {
+        let is_wide = value.is_wide();
+
+        self.stack.push(value);
+        if is_wide {
+            self.stack.push(VerificationType::Top);
+        }
+    }
+
+    // Pop `slots` stack slots, returning them in the order they were pushed.
+    fn pop(&mut self, slots: usize) -> Result<Vec<VerificationType>, WriteError> {
+        if self.stack.len() < slots {
+            return Err(WriteError::StackUnderflow);
+        }
+
+        Ok(self.stack.split_off(self.stack.len() - slots))
+    }
+
+    fn set_local(&mut self, index: usize, value: VerificationType) {
+        let is_wide = value.is_wide();
+        let end = index + if is_wide { 2 } else { 1 };
+
+        if self.locals.len() < end {
+            self.locals.resize(end, VerificationType::Top);
+        }
+
+        // Overwriting the second half of a long or double invalidates it
+        if index > 0 && self.locals[index - 1].is_wide() {
+            self.locals[index - 1] = VerificationType::Top;
+        }
+
+        self.locals[index] = value;
+        if is_wide {
+            self.locals[index + 1] = VerificationType::Top;
+        }
+    }
+
+    // Get the locals in the form used by the `StackMapTable`, without the
+    // second halves of longs and doubles or any trailing `Top`s.
+    fn compressed_locals(&self) -> Vec<VerificationType> {
+        let mut end = self.locals.len();
+        while end > 0 && self.locals[end - 1] == VerificationType::Top {
+            end -= 1;
+        }
+
+        compress_types(&self.locals[..end])
+    }
+}
+
+// Remove the second halves of longs and doubles from a list of types.
+fn compress_types(types: &[VerificationType]) -> Vec<VerificationType> {
+    let mut compressed = Vec::with_capacity(types.len());
+
+    let mut i = 0;
+    while i < types.len() {
+        compressed.push(types[i].clone());
+        i += if types[i].is_wide() { 2 } else { 1 };
+    }
+
+    compressed
+}
+
+fn is_simple_opcode(opcode: u8) -> bool {
+    matches!(
+        opcode,
+        NOP..=D_CONST_1
+            | IA_LOAD..=SA_LOAD
+            | IA_STORE..=L_XOR
+            | I2L..=D_CMP_G
+            | I_RETURN..=RETURN
+            | ARRAY_LENGTH
+            | A_THROW
+            | MONITOR_ENTER
+            | MONITOR_EXIT
+    )
+}
+
+// Apply the effect of an instruction with no operands to `frame`.
+fn execute_simple(opcode: u8, frame: &mut Frame) -> Result<(), WriteError> {
+    use VerificationType::{Double, Float, Integer, Long, Null};
+
+    // Pop `slots` slots, then push a value of type `result`, if any
+    let mut pop_push = |slots: usize, result: Option<VerificationType>| {
+        frame.pop(slots)?;
+        if let Some(result) = result {
+            frame.push(result);
+        }
+
+        Ok(())
+    };
+
+    match opcode {
+        NOP => Ok(()),
+        A_CONST_NULL => pop_push(0, Some(Null)),
+        I_CONST_M1..=I_CONST_5 => pop_push(0, Some(Integer)),
+        L_CONST_0 | L_CONST_1 => pop_push(0, Some(Long)),
+        F_CONST_0..=F_CONST_2 => pop_push(0, Some(Float)),
+        D_CONST_0 | D_CONST_1 => pop_push(0, Some(Double)),
+
+        IA_LOAD | BA_LOAD | CA_LOAD | SA_LOAD => pop_push(2, Some(Integer)),
+        LA_LOAD => pop_push(2, Some(Long)),
+        FA_LOAD => pop_push(2, Some(Float)),
+        DA_LOAD => pop_push(2, Some(Double)),
+        AA_LOAD => {
+            let array = frame.pop(2)?.remove(0);
+
+            let component = match array {
+                VerificationType::Object(descriptor) if descriptor.starts_with('[') => {
+                    VerificationType::from_descriptor(&descriptor[1..])
+                }
+                _ => Null,
+            };
+            frame.push(component);
+
+            Ok(())
+        }
+
+        IA_STORE | FA_STORE | AA_STORE | BA_STORE | CA_STORE | SA_STORE => pop_push(3, None),
+        LA_STORE | DA_STORE => pop_push(4, None),
+
+        POP => pop_push(1, None),
+        POP_2 => pop_push(2, None),
+        DUP | DUP_X1 | DUP_X2 | DUP_2 | DUP_2_X1 | DUP_2_X2 | SWAP => {
+            // These work on slots regardless of their types, so they're
+            // described by which of the popped slots are pushed back, in order
+            let (popped, pushed): (usize, &[usize]) = match opcode {
+                DUP => (1, &[0, 0]),
+                DUP_X1 => (2, &[1, 0, 1]),
+                DUP_X2 => (3, &[2, 0, 1, 2]),
+                DUP_2 => (2, &[0, 1, 0, 1]),
+                DUP_2_X1 => (3, &[1, 2, 0, 1, 2]),
+                DUP_2_X2 => (4, &[2, 3, 0, 1, 2, 3]),
+                _ => (2, &[1, 0]),
+            };
+
+            let values = frame.pop(popped)?;
+            frame
+                .stack
+                .extend(pushed.iter().map(|i| values[*i].clone()));
+
+            Ok(())
+        }
+
+        I_ADD | I_SUB | I_MUL | I_DIV | I_REM | I_SHL | I_SHR | I_USHR | I_AND | I_OR | I_XOR => {
+            pop_push(2, Some(Integer))
+        }
+        L_ADD | L_SUB | L_MUL | L_DIV | L_REM | L_AND | L_OR | L_XOR => pop_push(4, Some(Long)),
+        L_SHL | L_SHR | L_USHR => pop_push(3, Some(Long)),
+        F_ADD | F_SUB | F_MUL | F_DIV | F_REM => pop_push(2, Some(Float)),
+        D_ADD | D_SUB | D_MUL | D_DIV | D_REM => pop_push(4, Some(Double)),
+        I_NEG => pop_push(1, Some(Integer)),
+        L_NEG => pop_push(2, Some(Long)),
+        F_NEG => pop_push(1, Some(Float)),
+        D_NEG => pop_push(2, Some(Double)),
+
+        I2L => pop_push(1, Some(Long)),
+        I2F => pop_push(1, Some(Float)),
+        I2D => pop_push(1, Some(Double)),
+        L2I => pop_push(2, Some(Integer)),
+        L2F => pop_push(2, Some(Float)),
+        L2D => pop_push(2, Some(Double)),
+        F2I => pop_push(1, Some(Integer)),
+        F2L => pop_push(1, Some(Long)),
+        F2D => pop_push(1, Some(Double)),
+        D2I => pop_push(2, Some(Integer)),
+        D2L => pop_push(2, Some(Long)),
+        D2F => pop_push(2, Some(Float)),
+        I2B | I2C | I2S => pop_push(1, Some(Integer)),
+
+        L_CMP | D_CMP_L | D_CMP_G => pop_push(4, Some(Integer)),
+        F_CMP_L | F_CMP_G => pop_push(2, Some(Integer)),
+
+        I_RETURN | F_RETURN | A_RETURN => pop_push(1, None),
+        L_RETURN | D_RETURN => pop_push(2, None),
+        RETURN => Ok(()),
+
+        ARRAY_LENGTH => pop_push(1, Some(Integer)),
+        A_THROW | MONITOR_ENTER | MONITOR_EXIT => pop_push(1, None),
+
+        _ => unreachable!("Opcode was checked when adding instruction"),
+    }
+}
+
+// Whether execution can continue to the next instruction after `insn`.
+fn falls_through(insn: &Insn) -> bool {
+    match insn {
+        Insn::Simple(opcode) => !matches!(*opcode, I_RETURN..=RETURN | A_THROW),
+        Insn::Jump(opcode, _) => *opcode != GOTO,
+        Insn::TableSwitch(..) | Insn::LookupSwitch(..) => false,
+        _ => true,
+    }
+}
+
+fn opposite_condition(opcode: u8) -> u8 {
+    match opcode {
+        // These come in pairs of opposite conditions
+        IF_EQ..=IF_A_CMP_NE => {
+            if (opcode - IF_EQ).is_multiple_of(2) {
+                opcode + 1
+            } else {
+                opcode - 1
+            }
+        }
+        IF_NULL => IF_NON_NULL,
+        IF_NON_NULL => IF_NULL,
+        _ => unreachable!("Only called with conditional branches"),
+    }
+}
+
+// The padding after a switch opcode at `offset`, which aligns its operands to
+// a multiple of four bytes from the start of the code.
+fn switch_padding(offset: usize) -> usize {
+    (4 - (offset + 1) % 4) % 4
+}
+
+// The size of the encoding of `insn` at `offset`.
+fn insn_size(insn: &Insn, offset: usize, wide_jump: bool) -> usize {
+    match insn {
+        Insn::Simple(_) => 1,
+        Insn::Push(opcode, _) => {
+            if *opcode == B_I_PUSH {
+                2
+            } else {
+                3
+            }
+        }
+        Insn::NewArray(_) => 2,
+        Insn::Var(_, index) => match *index {
+            0..=3 => 1,
+            4..=0xFF => 2,
+            _ => 4,
+        },
+        Insn::IInc(index, increment) => {
+            if *index <= u8::MAX as u16 && i8::try_from(*increment).is_ok() {
+                3
+            } else {
+                6
+            }
+        }
+        Insn::Type(..) | Insn::Field(..) => 3,
+        Insn::Method { opcode, .. } => {
+            if *opcode == INVOKE_INTERFACE {
+                5
+            } else {
+                3
+            }
+        }
+        Insn::InvokeDynamic(..) => 5,
+        Insn::Ldc(cpool_idx, constant_type) => {
+            if constant_type.is_wide() || *cpool_idx > u8::MAX as u16 {
+                3
+            } else {
+                2
+            }
+        }
+        Insn::Jump(opcode, _) => match (wide_jump, *opcode) {
+            (false, _) => 3,
+            (true, GOTO) => 5,
+            (true, _) => 8,
+        },
+        Insn::TableSwitch(_, _, labels) => 1 + switch_padding(offset) + 12 + 4 * labels.len(),
+        Insn::LookupSwitch(_, cases) => 1 + switch_padding(offset) + 8 + 8 * cases.len(),
+        Insn::MultiANewArray(..) => 4,
+    }
+}
+
+// The number of slots taken up by a value of the given field descriptor.
+fn descriptor_size(descriptor: &str) -> usize {
+    if matches!(descriptor, "J" | "D") {
+        2
+    } else {
+        1
+    }
+}
+
+// Get the component type of an array descriptor's component, as an internal
+// name or array descriptor, if it is a reference type.
+fn reference_component(component: &str) -> Option<&str> {
+    if component.starts_with('[') {
+        Some(component)
+    } else {
+        component
+            .strip_prefix('L')
+            .and_then(|c| c.strip_suffix(';'))
+    }
+}
+
+// Get the length of the field descriptor at the start of `descriptor`.
+fn field_descriptor_length(descriptor: &str) -> Result<usize, WriteError> {
+    let bytes = descriptor.as_bytes();
+    let dimensions = bytes.iter().take_while(|b| **b == b'[').count();
+
+    match bytes.get(dimensions) {
+        Some(b'Z' | b'B' | b'C' | b'S' | b'I' | b'F' | b'J' | b'D') => Ok(dimensions + 1),
+        Some(b'L') => {
+            let end = descriptor[dimensions..]
+                .find(';')
+                .ok_or(WriteError::InvalidDescriptor)?;
+
+            // The class name can't be empty
+            if end == 1 {
+                Err(WriteError::InvalidDescriptor)
+            } else {
+                Ok(dimensions + end + 1)
+            }
+        }
+        _ => Err(WriteError::InvalidDescriptor),
+    }
+}
+
+// Check that `descriptor` is a single field descriptor.
+fn parse_field_descriptor(descriptor: &str) -> Result<&str, WriteError> {
+    if field_descriptor_length(descriptor)? == descriptor.len() {
+        Ok(descriptor)
+    } else {
+        Err(WriteError::InvalidDescriptor)
+    }
+}
+
+// Split a method descriptor into the descriptors of its arguments and its
+// return type, which is `V` for `void` methods.
+fn parse_method_descriptor(descriptor: &str) -> Result<(Vec<&str>, &str), WriteError> {
+    let mut rest = descriptor
+        .strip_prefix('(')
+        .ok_or(WriteError::InvalidDescriptor)?;
+
+    let mut args = Vec::new();
+    while !rest.starts_with(')') {
+        let length = field_descriptor_length(rest)?;
+        args.push(&rest[..length]);
+        rest = &rest[length..];
+    }
+
+    let return_type = &rest[1..];
+    if return_type != "V" {
+        parse_field_descriptor(return_type)?;
+    }
+
+    Ok((args, return_type))
+}
+
+// Encode a string in the "modified UTF-8" used by class files, where the null
+// character takes up two bytes and supplementary characters are encoded as
+// surrogate pairs.
+fn encode_modified_utf8(string: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(string.len());
+
+    for character in string.encode_utf16() {
+        let character = character as u32;
+
+        if character != 0 && character < 0x80 {
+            bytes.push(character as u8);
+        } else if character < 0x800 {
+            bytes.push(0xC0 | (character >> 6) as u8);
+            bytes.push(0x80 | (character & 0x3F) as u8);
+        } else {
+            bytes.push(0xE0 | (character >> 12) as u8);
+            bytes.push(0x80 | ((character >> 6) & 0x3F) as u8);
+            bytes.push(0x80 | (character & 0x3F) as u8);
+        }
+    }
+
+    bytes
+}
+
+fn write_u16(data: &mut Vec<u8>, value: u16) {
+    data.extend_from_slice(&value.to_be_bytes());
+}
+
+fn write_u32(data: &mut Vec<u8>, value: u32) {
+    data.extend_from_slice(&value.to_be_bytes());
+}
+
+fn member_count(count: usize) -> Result<u16, WriteError> {
+    u16::try_from(count).map_err(|_| WriteError::TooManyMembers)
+}
+
+fn write_attribute(
+    constant_pool: &mut ConstantPoolWriter,
+    name: &str,
+    data: &[u8],
+) -> Result<Vec<u8>, WriteError> {
+    let name_idx = constant_pool.utf8(name)?;
+    let length = u32::try_from(data.len()).map_err(|_| WriteError::AttributeTooLarge)?;
+
+    let mut attribute = Vec::with_capacity(data.len() + 6);
+    write_u16(&mut attribute, name_idx);
+    write_u32(&mut attribute, length);
+    attribute.extend_from_slice(data);
+
+    Ok(attribute)
+}
+
+// Write a `field_info` or `method_info` structure.
+fn write_member(
+    constant_pool: &mut ConstantPoolWriter,
+    flags: u16,
+    name: &str,
+    descriptor: &str,
+    attributes: &[Vec<u8>],
+) -> Result<Vec<u8>, WriteError> {
+    let name_idx = constant_pool.utf8(name)?;
+    let descriptor_idx = constant_pool.utf8(descriptor)?;
+
+    let mut member = Vec::new();
+    write_u16(&mut member, flags);
+    write_u16(&mut member, name_idx);
+    write_u16(&mut member, descriptor_idx);
+
+    write_u16(&mut member, member_count(attributes.len())?);
+    for attribute in attributes {
+        member.extend_from_slice(attribute);
+    }
+
+    Ok(member)
+}
@@ -26,6 +26,16 @@ impl Jar {
         self.0.jar_file.has_file(file_name)
     }
 
+    /// Returns the names of all files in the JAR, in no particular order.
+    /// Names that aren't valid UTF-8 are decoded lossily.
+    pub fn file_names(self) -> Vec<String> {
+        self.0
+            .jar_file
+            .file_names()
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect()
+    }
+
     /// Reads a file with the given name from the JAR.
     ///
     /// This method makes no attempt to normalize the provided name as a path
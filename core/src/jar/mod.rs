@@ -2,3 +2,4 @@ mod jar;
 mod read_zip;
 
 pub use jar::Jar;
+pub use read_zip::{ZipFile, ZipReadError};
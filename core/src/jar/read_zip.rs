@@ -13,6 +13,8 @@ const CDFH_MAGIC: u32 = 0x02014b50;
 // Local file header magic
 const LFH_MAGIC: u32 = 0x04034b50;
 
+/// A zip archive. Unlike [`Jar`](super::Jar), this isn't managed by the
+/// garbage collector, so it can be used before a `Context` exists.
 pub struct ZipFile {
     data: Vec<u8>,
 
@@ -20,6 +22,7 @@ pub struct ZipFile {
 }
 
 impl ZipFile {
+    /// Read the central directory of the zip archive `data`.
     pub fn new(data: Vec<u8>) -> Result<Self, ZipReadError> {
         let mut reader = FileData::new(&data);
 
@@ -30,11 +33,19 @@ impl ZipFile {
         Ok(Self { data, records })
     }
 
+    /// Checks whether the archive contains a file with the given name.
     pub fn has_file(&self, file_name: &String) -> bool {
         let record_name = Box::from(file_name.as_bytes());
         self.records.contains_key(&record_name)
     }
 
+    /// Returns the names of all files in the archive, in no particular order.
+    /// The names of directory entries, if the archive has any, end in `/`.
+    pub fn file_names(&self) -> impl Iterator<Item = &[u8]> {
+        self.records.keys().map(|name| &**name)
+    }
+
+    /// Reads and decompresses a file with the given name from the archive.
     pub fn read_file(&self, file_name: &String) -> Result<Vec<u8>, ZipReadError> {
         let record_name = Box::from(file_name.as_bytes());
         if let Some(record) = self.records.get(&record_name) {
@@ -123,7 +134,8 @@ struct FileRecord {
 fn read_eocd<'a>(reader: &mut FileData<'a>) -> Result<(usize, usize), ReadError> {
     // assume the "comment" is of 0 length :P
     // That means we can just seek to `(end - 0x16)`
-    reader.seek(reader.len() - 0x16)?;
+    let eocd_position = reader.len().checked_sub(0x16).ok_or(ReadError::EndOfFile)?;
+    reader.seek(eocd_position)?;
 
     // We should be at the start of the EOCD now
     let magic = reader.read_u32_le()?;
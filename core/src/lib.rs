@@ -3,7 +3,7 @@
 #[macro_use]
 extern crate alloc;
 
-mod classfile;
+pub mod classfile;
 mod gc;
 mod jar;
 mod reader;
@@ -11,21 +11,30 @@ mod runtime;
 mod string;
 mod utils;
 
+pub use crate::classfile::class::ClassFile;
 pub use crate::classfile::error::Error as ClassFileError;
+pub use crate::classfile::error::WriteError as ClassFileWriteError;
 pub use crate::classfile::flags::{ClassFlags, FieldFlags, MethodFlags};
-pub use crate::gc::{Gc, GcCtx};
-pub use crate::jar::Jar;
+pub use crate::gc::{Gc, GcCtx, Trace};
+pub use crate::jar::{Jar, ZipFile, ZipReadError};
 pub use crate::reader::ReadError;
 pub use crate::runtime::array::Array;
 pub use crate::runtime::class::{Class, PrimitiveType};
+pub use crate::runtime::class_builder::ClassBuilder;
+pub use crate::runtime::console::ConsoleBackend;
 pub use crate::runtime::context::Context;
 pub use crate::runtime::descriptor::{
     Descriptor, MethodDescriptor, ResolvedDescriptor, ResolvedMethodDescriptor,
 };
-pub use crate::runtime::error::Error;
+pub use crate::runtime::embed::{FromJava, IntoJava, IntoJavaArgs};
+pub use crate::runtime::error::{Error, StackFrameInfo, ThrowableInfo};
 pub use crate::runtime::field::FieldTemplate;
 pub use crate::runtime::loader::{ClassLoader, LoaderBackend, ResourceLoadSource};
 pub use crate::runtime::method::{Method, NativeMethod};
+pub use crate::runtime::native::{
+    CapturingNativeFunction, NativeArg, NativeFunction, NativeRegistrationError, NativeReturn,
+};
 pub use crate::runtime::object::Object;
+pub use crate::runtime::security::{Operation, SecurityPolicy};
 pub use crate::runtime::value::Value;
 pub use crate::string::JvmString;
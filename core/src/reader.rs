@@ -157,6 +157,28 @@ fn fix_extended_utf8(data: &[u8]) -> Vec<u8> {
             }
         }
 
+        if i + 5 < data.len() {
+            // Supplementary characters are encoded as a surrogate pair, with
+            // each surrogate encoded separately as three bytes. Rust doesn't
+            // support decoding surrogates, so combine them into a single
+            // four-byte character here.
+            let high = &data[i..i + 3];
+            let low = &data[i + 3..i + 6];
+            if high[0] == 0xED && high[1] & 0xF0 == 0xA0 && low[0] == 0xED && low[1] & 0xF0 == 0xB0
+            {
+                let high = ((high[1] as u32 & 0x0F) << 6) | (high[2] as u32 & 0x3F);
+                let low = ((low[1] as u32 & 0x0F) << 6) | (low[2] as u32 & 0x3F);
+                let code_point = 0x10000 + ((high << 10) | low);
+
+                result.push(0xF0 | (code_point >> 18) as u8);
+                result.push(0x80 | ((code_point >> 12) & 0x3F) as u8);
+                result.push(0x80 | ((code_point >> 6) & 0x3F) as u8);
+                result.push(0x80 | (code_point & 0x3F) as u8);
+                i += 6;
+                continue;
+            }
+        }
+
         result.push(data[i]);
         i += 1;
     }
@@ -33,12 +33,14 @@ pub struct BuiltinClasses {
     pub java_lang_arithmetic_exception: Class,
     pub java_lang_array_index_oob_exception: Class,
     pub java_lang_array_store_exception: Class,
+    pub java_lang_bootstrap_method_error: Class,
     pub java_lang_class_cast_exception: Class,
     pub java_lang_class_circularity_error: Class,
     pub java_lang_class_format_error: Class,
     pub java_lang_exception: Class,
     pub java_lang_exception_in_initializer_error: Class,
     pub java_lang_illegal_access_error: Class,
+    pub java_lang_illegal_argument_exception: Class,
     pub java_lang_incompatible_class_change_error: Class,
     pub java_lang_instantiation_error: Class,
     pub java_lang_instantiation_exception: Class,
@@ -53,6 +55,7 @@ pub struct BuiltinClasses {
     pub java_lang_reflect_method: Class,
     pub java_lang_stack_trace_element: Class,
     pub java_lang_system: Class,
+    pub java_lang_unsupported_class_version_error: Class,
     pub java_lang_verify_error: Class,
 }
 
@@ -69,12 +72,14 @@ impl BuiltinClasses {
             java_lang_arithmetic_exception: object_class,
             java_lang_array_index_oob_exception: object_class,
             java_lang_array_store_exception: object_class,
+            java_lang_bootstrap_method_error: object_class,
             java_lang_class_cast_exception: object_class,
             java_lang_class_circularity_error: object_class,
             java_lang_class_format_error: object_class,
             java_lang_exception: object_class,
             java_lang_exception_in_initializer_error: object_class,
             java_lang_illegal_access_error: object_class,
+            java_lang_illegal_argument_exception: object_class,
             java_lang_incompatible_class_change_error: object_class,
             java_lang_instantiation_error: object_class,
             java_lang_instantiation_exception: object_class,
@@ -89,6 +94,7 @@ impl BuiltinClasses {
             java_lang_reflect_method: object_class,
             java_lang_stack_trace_element: object_class,
             java_lang_system: object_class,
+            java_lang_unsupported_class_version_error: object_class,
             java_lang_verify_error: object_class,
         }
     }
@@ -106,12 +112,14 @@ impl BuiltinClasses {
                 ("java/lang/ArithmeticException", java_lang_arithmetic_exception),
                 ("java/lang/ArrayIndexOutOfBoundsException", java_lang_array_index_oob_exception),
                 ("java/lang/ArrayStoreException", java_lang_array_store_exception),
+                ("java/lang/BootstrapMethodError", java_lang_bootstrap_method_error),
                 ("java/lang/ClassCastException", java_lang_class_cast_exception),
                 ("java/lang/ClassCircularityError", java_lang_class_circularity_error),
                 ("java/lang/ClassFormatError", java_lang_class_format_error),
                 ("java/lang/Exception", java_lang_exception),
                 ("java/lang/ExceptionInInitializerError", java_lang_exception_in_initializer_error),
                 ("java/lang/IllegalAccessError", java_lang_illegal_access_error),
+                ("java/lang/IllegalArgumentException", java_lang_illegal_argument_exception),
                 ("java/lang/IncompatibleClassChangeError", java_lang_incompatible_class_change_error),
                 ("java/lang/InstantiationError", java_lang_instantiation_error),
                 ("java/lang/InstantiationException", java_lang_instantiation_exception),
@@ -126,6 +134,7 @@ impl BuiltinClasses {
                 ("java/lang/reflect/Method", java_lang_reflect_method),
                 ("java/lang/StackTraceElement", java_lang_stack_trace_element),
                 ("java/lang/System", java_lang_system),
+                ("java/lang/UnsupportedClassVersionError", java_lang_unsupported_class_version_error),
                 ("java/lang/VerifyError", java_lang_verify_error),
             ]
         );
@@ -191,12 +200,14 @@ impl Trace for BuiltinClasses {
         self.java_lang_arithmetic_exception.trace();
         self.java_lang_array_index_oob_exception.trace();
         self.java_lang_array_store_exception.trace();
+        self.java_lang_bootstrap_method_error.trace();
         self.java_lang_class_cast_exception.trace();
         self.java_lang_class_circularity_error.trace();
         self.java_lang_class_format_error.trace();
         self.java_lang_exception.trace();
         self.java_lang_exception_in_initializer_error.trace();
         self.java_lang_illegal_access_error.trace();
+        self.java_lang_illegal_argument_exception.trace();
         self.java_lang_incompatible_class_change_error.trace();
         self.java_lang_instantiation_error.trace();
         self.java_lang_instantiation_exception.trace();
@@ -211,6 +222,7 @@ impl Trace for BuiltinClasses {
         self.java_lang_reflect_method.trace();
         self.java_lang_stack_trace_element.trace();
         self.java_lang_system.trace();
+        self.java_lang_unsupported_class_version_error.trace();
         self.java_lang_verify_error.trace();
     }
 }
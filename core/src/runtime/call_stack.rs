@@ -8,6 +8,7 @@ use super::method::Method;
 use super::object::Object;
 use super::value::Value;
 
+use crate::classfile::flags::MethodFlags;
 use crate::gc::Trace;
 
 use alloc::vec::Vec;
@@ -35,6 +36,14 @@ impl CallStack {
         self.entries.pop();
     }
 
+    pub fn top(&self) -> Option<Method> {
+        self.entries.last().copied()
+    }
+
+    pub fn nth_from_top(&self, n: usize) -> Option<Method> {
+        self.entries.iter().rev().nth(n).copied()
+    }
+
     // This needs to do some hacky stuff to remove the error initializer frames
     // to make the call stack look correct
     pub fn get_entries(&self, skip_count: usize) -> Vec<Method> {
@@ -97,11 +106,20 @@ impl CallStack {
             Value::Object(Some(context.str_to_string(&entry.name()))),
         );
 
-        // TODO set these three fields properly
+        // TODO set the file name and line number properly. As in Java, a line
+        // number of -2 indicates a native method, and -1 an unknown line.
+
+        let is_native = entry.flags().contains(MethodFlags::NATIVE);
 
         instance.set_field(STACK_TRACE_ELEMENT_FILE_FIELD, Value::Object(None));
-        instance.set_field(STACK_TRACE_ELEMENT_LINE_FIELD, Value::Integer(0));
-        instance.set_field(STACK_TRACE_ELEMENT_IS_NATIVE_FIELD, Value::Integer(0));
+        instance.set_field(
+            STACK_TRACE_ELEMENT_LINE_FIELD,
+            Value::Integer(if is_native { -2 } else { -1 }),
+        );
+        instance.set_field(
+            STACK_TRACE_ELEMENT_IS_NATIVE_FIELD,
+            Value::Integer(is_native as i32),
+        );
 
         instance
     }
@@ -3,26 +3,29 @@ use super::descriptor::{Descriptor, MethodDescriptor, ResolvedDescriptor};
 use super::error::Error;
 use super::field::Field;
 use super::loader::ClassLoader;
-use super::method::Method;
+use super::method::{Method, NativeMethod};
 use super::object::{Object, array_clone_method};
-use super::read_macros::read_u16_be;
 use super::value::Value;
 use super::vtable::{InstanceMethodVTable, VTable};
 
+use crate::classfile::attribute::AttributeInfo;
 use crate::classfile::class::ClassFile;
 use crate::classfile::flags::{ClassFlags, FieldFlags, MethodFlags};
 use crate::gc::{Gc, GcCtx, Trace};
-use crate::reader::{FileData, Reader};
 use crate::string::JvmString;
 use crate::utils::CompactBitSet;
 
 use alloc::boxed::Box;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
-use core::cell::{Cell, OnceCell};
+use core::cell::{Cell, OnceCell, RefCell};
 use core::fmt;
 use core::hash::{Hash, Hasher};
-use hashbrown::HashSet;
+use hashbrown::{HashMap, HashSet};
+
+// The range of class file versions that can be loaded: Java 1.1 to Java 21.
+const MIN_MAJOR_VERSION: u16 = 45;
+const MAX_MAJOR_VERSION: u16 = 65;
 
 /// The representation of a Java class.
 ///
@@ -63,6 +66,13 @@ struct ClassData {
 
     clinit_method: Cell<Option<Method>>,
     clinit_stage: Cell<ClinitStage>,
+
+    // The host of the nest that this class is a member of, lazily resolved
+    nest_host: OnceCell<Class>,
+
+    // The values of the constant pool entries of this class that are resolved
+    // by running Java code, by constant pool index.
+    resolved_constants: RefCell<HashMap<u16, Value>>,
 }
 
 struct MethodData {
@@ -101,6 +111,18 @@ impl Class {
         let class_file = ClassFile::from_data(context.gc_ctx(), &mut context.interner(), data);
         let class_file = class_file.map_err(|e| Error::from_class_file_error(context, e))?;
 
+        let major_version = class_file.major_version();
+        if !(MIN_MAJOR_VERSION..=MAX_MAJOR_VERSION).contains(&major_version) {
+            return Err(context.unsupported_class_version_error(&format!(
+                "{} has been compiled by an unsupported version of Java (class file version {}.{}), only class file versions {}.0 to {}.0 are recognized",
+                class_file.this_class().replace('/', "."),
+                major_version,
+                class_file.minor_version(),
+                MIN_MAJOR_VERSION,
+                MAX_MAJOR_VERSION,
+            )));
+        }
+
         let class = Self::from_class_file_partial(context, loader, class_file)?;
         class.load_methods(context, class_file)?;
 
@@ -189,6 +211,8 @@ impl Class {
                 method_data: OnceCell::new(),
                 clinit_method: Cell::new(None),
                 clinit_stage: Cell::new(ClinitStage::NotStarted),
+                nest_host: OnceCell::new(),
+                resolved_constants: RefCell::new(HashMap::new()),
             },
         ));
 
@@ -402,6 +426,8 @@ impl Class {
 
                 clinit_method: Cell::new(None),
                 clinit_stage: Cell::new(ClinitStage::Completed),
+                nest_host: OnceCell::new(),
+                resolved_constants: RefCell::new(HashMap::new()),
             },
         ));
 
@@ -450,6 +476,244 @@ impl Class {
         class
     }
 
+    /// Create a class named `name` that extends `super_class` and implements
+    /// all of `interfaces`. Every abstract interface method that isn't already
+    /// implemented by `super_class` is implemented by the native method
+    /// `handler`, which can call [`Context::current_method`] to find out which
+    /// method it was called as.
+    ///
+    /// Like [`Class::from_data`], this method will not register the created
+    /// `Class` in the `ClassLoader`'s registry.
+    pub fn for_interface_impl(
+        context: &Context,
+        loader: ClassLoader,
+        name: JvmString,
+        super_class: Class,
+        interfaces: &[Class],
+        handler: NativeMethod,
+    ) -> Result<Self, Error> {
+        Self::for_synthetic_impl(
+            context,
+            loader,
+            name,
+            super_class,
+            interfaces,
+            handler,
+            false,
+        )
+    }
+
+    /// Create a proxy class named `name` that extends `super_class` and
+    /// implements all of `interfaces`, as used by `java.lang.reflect.Proxy`.
+    /// Unlike [`Class::for_interface_impl`], every interface method (including
+    /// default methods) and the `equals`, `hashCode`, and `toString` methods of
+    /// `java.lang.Object` are implemented by the native method `handler`.
+    ///
+    /// Like [`Class::from_data`], this method will not register the created
+    /// `Class` in the `ClassLoader`'s registry.
+    pub fn for_proxy(
+        context: &Context,
+        loader: ClassLoader,
+        name: JvmString,
+        super_class: Class,
+        interfaces: &[Class],
+        handler: NativeMethod,
+    ) -> Result<Self, Error> {
+        Self::for_synthetic_impl(
+            context,
+            loader,
+            name,
+            super_class,
+            interfaces,
+            handler,
+            true,
+        )
+    }
+
+    fn for_synthetic_impl(
+        context: &Context,
+        loader: ClassLoader,
+        name: JvmString,
+        super_class: Class,
+        interfaces: &[Class],
+        handler: NativeMethod,
+        is_proxy: bool,
+    ) -> Result<Self, Error> {
+        if super_class.is_final() || super_class.is_interface() {
+            return Err(context.incompatible_class_change_error(&format!(
+                "class {} cannot inherit from {}",
+                name,
+                super_class.name()
+            )));
+        }
+
+        if interfaces.iter().any(|i| !i.is_interface()) {
+            return Err(
+                context.incompatible_class_change_error("Class cannot implement non-interface")
+            );
+        }
+
+        let mut all_interfaces = HashSet::new();
+        let mut class_queue = interfaces.to_vec();
+        class_queue.push(super_class);
+        all_interfaces.extend(interfaces.iter().copied());
+
+        while let Some(class) = class_queue.pop() {
+            for interface in class.own_interfaces() {
+                if all_interfaces.insert(*interface) {
+                    class_queue.push(*interface);
+                }
+            }
+
+            if let Some(super_class) = class.super_class() {
+                class_queue.push(super_class);
+            }
+        }
+
+        let class = Self(Gc::new(
+            context.gc_ctx,
+            ClassData {
+                class_file: None,
+                loader: Some(loader),
+
+                flags: ClassFlags::PUBLIC | ClassFlags::FINAL | ClassFlags::SYNTHETIC,
+
+                name,
+                super_class: Some(super_class),
+
+                object: OnceCell::new(),
+
+                own_interfaces: interfaces.to_vec().into_boxed_slice(),
+                all_interfaces: all_interfaces.iter().copied().collect::<Box<[_]>>(),
+
+                array_value_type: None,
+                primitive_type: None,
+
+                method_data: OnceCell::new(),
+
+                // The superclass still needs to be initialized
+                clinit_method: Cell::new(None),
+                clinit_stage: Cell::new(ClinitStage::NotStarted),
+                nest_host: OnceCell::new(),
+                resolved_constants: RefCell::new(HashMap::new()),
+            },
+        ));
+
+        let super_vtable = super_class.instance_method_vtable();
+
+        let native_method = |key: (JvmString, MethodDescriptor)| {
+            Method::for_native(
+                context.gc_ctx,
+                handler,
+                key.1,
+                key.1.physical_arg_count(),
+                MethodFlags::PUBLIC | MethodFlags::FINAL,
+                key.0,
+                class,
+            )
+        };
+
+        let mut instance_methods: Vec<((JvmString, MethodDescriptor), Method)> = Vec::new();
+
+        if is_proxy {
+            // Proxy classes also dispatch these methods of `java.lang.Object`
+            // to the handler
+            for key in super_vtable.mapping().keys() {
+                let descriptor = key.1.to_string();
+                let is_proxied = matches!(
+                    (&*key.0, &*descriptor),
+                    ("equals", "(Ljava/lang/Object;)Z")
+                        | ("hashCode", "()I")
+                        | ("toString", "()Ljava/lang/String;")
+                );
+
+                if is_proxied {
+                    instance_methods.push((*key, native_method(*key)));
+                }
+            }
+        }
+
+        for interface in &class.0.all_interfaces {
+            let interface_vtable = interface.instance_method_vtable();
+
+            for (key, index) in interface_vtable.mapping().iter() {
+                if (!is_proxy && super_vtable.lookup(*key).is_some())
+                    || instance_methods.iter().any(|(name, _)| name == key)
+                {
+                    // Already implemented by the superclass or by an earlier
+                    // interface
+                    continue;
+                }
+
+                let interface_method = interface_vtable.get_element(*index);
+
+                // The vtables of interfaces also contain the methods of
+                // `java.lang.Object`, which are inherited from the superclass
+                if !interface_method.class().is_interface() {
+                    continue;
+                }
+
+                let flags = interface_method.flags();
+                let method = if !flags.contains(MethodFlags::PRIVATE)
+                    && (is_proxy || flags.contains(MethodFlags::ABSTRACT))
+                {
+                    native_method(*key)
+                } else {
+                    // Default and private methods are inherited as-is
+                    interface_method
+                };
+
+                instance_methods.push((*key, method));
+            }
+        }
+
+        let instance_fields = super_class.instance_fields();
+        let is_object_iter = instance_fields
+            .iter()
+            .map(|f| !f.descriptor().is_primitive());
+        let instance_object_fields =
+            CompactBitSet::from_iter(is_object_iter, instance_fields.len());
+
+        let method_data = MethodData {
+            static_field_vtable: VTable::from_parent_and_keys(
+                context.gc_ctx,
+                Some(class),
+                Some(super_class.static_field_vtable()),
+                Vec::new(),
+            ),
+            static_fields: super_class.static_fields().to_vec().into_boxed_slice(),
+            instance_field_vtable: VTable::from_parent_and_keys(
+                context.gc_ctx,
+                None,
+                Some(super_class.instance_field_vtable()),
+                Vec::new(),
+            ),
+            instance_fields: instance_fields.to_vec().into_boxed_slice(),
+            instance_object_fields,
+            static_method_vtable: VTable::from_parent_and_keys(
+                context.gc_ctx,
+                Some(class),
+                Some(super_class.static_method_vtable()),
+                Vec::new(),
+            ),
+            static_methods: super_class.static_methods().to_vec().into_boxed_slice(),
+            instance_method_vtable: InstanceMethodVTable::from_parent_and_keys(
+                context.gc_ctx,
+                class,
+                Some(super_vtable),
+                instance_methods,
+            ),
+        };
+
+        class
+            .0
+            .method_data
+            .set(method_data)
+            .expect("Method data not yet initialized");
+
+        Ok(class)
+    }
+
     // Creates a builtin class for one of the primitive types.
     pub(crate) fn for_primitive(gc_ctx: GcCtx, primitive_type: PrimitiveType) -> Self {
         let class = Self(Gc::new(
@@ -475,6 +739,8 @@ impl Class {
 
                 clinit_method: Cell::new(None),
                 clinit_stage: Cell::new(ClinitStage::Completed),
+                nest_host: OnceCell::new(),
+                resolved_constants: RefCell::new(HashMap::new()),
             },
         ));
 
@@ -498,8 +764,9 @@ impl Class {
         class
     }
 
-    /// The class file that this class was loaded from.
-    pub(crate) fn class_file(&self) -> &Option<ClassFile> {
+    /// The class file that this class was loaded from, or `None` for array and
+    /// primitive classes.
+    pub fn class_file(&self) -> &Option<ClassFile> {
         &self.0.class_file
     }
 
@@ -638,6 +905,23 @@ impl Class {
         })
     }
 
+    /// The cached value of the constant pool entry at `index`, if it has
+    /// already been resolved with [`Class::set_resolved_constant`].
+    pub(crate) fn resolved_constant(self, index: u16) -> Option<Value> {
+        self.0.resolved_constants.borrow().get(&index).copied()
+    }
+
+    /// Cache the value of the constant pool entry at `index`. If the entry was
+    /// already resolved, the existing value is kept and returned.
+    pub(crate) fn set_resolved_constant(self, index: u16, value: Value) -> Value {
+        *self
+            .0
+            .resolved_constants
+            .borrow_mut()
+            .entry(index)
+            .or_insert(value)
+    }
+
     /// Whether this the given class is one of the superclasses of this class.
     /// This will return `false` if the given class is the same class as the
     /// class this method is called on.
@@ -722,32 +1006,26 @@ impl Class {
             .expect("Class with class file must have loader");
 
         for attribute in attributes {
-            if &*attribute.name() == "InnerClasses" {
-                let mut data = FileData::new(attribute.data());
+            // Other attributes aren't needed here, and must be ignored if
+            // they're malformed
+            if &*attribute.name() != "InnerClasses" {
+                continue;
+            }
 
-                let num_inner_classes = read_u16_be!(context, data);
-                for _ in 0..num_inner_classes {
-                    let inner_class_cls_index = read_u16_be!(context, data);
-                    let outer_class_cls_index = read_u16_be!(context, data);
-                    let _inner_class_name_index = read_u16_be!(context, data);
-                    let _inner_class_access_flags = read_u16_be!(context, data);
+            let attribute = attribute
+                .parse(constant_pool)
+                .map_err(|e| Error::from_class_file_error(context, e))?;
 
-                    if outer_class_cls_index == 0 {
+            if let AttributeInfo::InnerClasses(inner_classes) = attribute {
+                for inner_class in inner_classes {
+                    let Some(outer_class_name) = inner_class.outer_class else {
                         // This is a local ("anonymous"?) class defined in a
                         // method, not a `static class`
                         continue;
-                    }
-
-                    let inner_class_name = constant_pool
-                        .get_class(inner_class_cls_index)
-                        .map_err(|e| Error::from_class_file_error(context, e))?;
+                    };
 
                     // TODO is this if-check necessary?
-                    if inner_class_name == self.name() {
-                        let outer_class_name = constant_pool
-                            .get_class(outer_class_cls_index)
-                            .map_err(|e| Error::from_class_file_error(context, e))?;
-
+                    if inner_class.inner_class == self.name() {
                         let class = loader.lookup_class(context, outer_class_name)?;
 
                         return Ok(Some(class));
@@ -759,6 +1037,85 @@ impl Class {
         Ok(None)
     }
 
+    /// The host of the nest that this class is a member of. Classes without a
+    /// `NestHost` attribute are the hosts of their own nest, as are classes
+    /// whose nest host can't be loaded, is in a different runtime package, or
+    /// doesn't list them as a member. The host is resolved once, on the first
+    /// call.
+    pub fn nest_host(self, context: &Context) -> Class {
+        if let Some(host) = self.0.nest_host.get() {
+            return *host;
+        }
+
+        // Loading the host can run Java code, which could resolve it first
+        let host = self.resolve_nest_host(context);
+
+        *self.0.nest_host.get_or_init(|| host)
+    }
+
+    fn resolve_nest_host(self, context: &Context) -> Class {
+        let (Some(class_file), Some(loader)) = (self.class_file(), self.loader()) else {
+            return self;
+        };
+
+        let constant_pool = class_file.constant_pool();
+
+        let host_name = class_file
+            .attributes()
+            .iter()
+            .filter(|attribute| &*attribute.name() == "NestHost")
+            .find_map(|attribute| match attribute.parse(constant_pool) {
+                Ok(AttributeInfo::NestHost(host_name)) => Some(host_name),
+                _ => None,
+            });
+
+        let Some(host_name) = host_name else {
+            return self;
+        };
+
+        // Nest hosts must be in the same runtime package (JVMS 5.4.4)
+        match loader.lookup_class(context, host_name) {
+            Ok(host) if host.in_same_runtime_package(self) && host.has_nest_member(self) => host,
+            _ => self,
+        }
+    }
+
+    // Whether this class and `other` were loaded by the same loader and have
+    // the same package name.
+    fn in_same_runtime_package(self, other: Class) -> bool {
+        fn package_name(name: &str) -> &str {
+            name.rsplit_once('/').map_or("", |(package, _)| package)
+        }
+
+        self.loader() == other.loader() && package_name(&self.name()) == package_name(&other.name())
+    }
+
+    // Whether the `NestMembers` attribute of this class lists `member`.
+    fn has_nest_member(self, member: Class) -> bool {
+        let Some(class_file) = self.class_file() else {
+            return false;
+        };
+
+        let constant_pool = class_file.constant_pool();
+
+        class_file
+            .attributes()
+            .iter()
+            .filter(|attribute| &*attribute.name() == "NestMembers")
+            .any(|attribute| match attribute.parse(constant_pool) {
+                Ok(AttributeInfo::NestMembers(members)) => {
+                    members.iter().any(|name| *name == member.name())
+                }
+                _ => false,
+            })
+    }
+
+    /// Whether this class and `other` are members of the same nest, and so can
+    /// access each other's private members.
+    pub fn is_nestmate_of(self, context: &Context, other: Class) -> bool {
+        self == other || self.nest_host(context) == other.nest_host(context)
+    }
+
     /// Run the class initializer for this class.
     pub fn run_clinit(self, context: &Context) -> Result<(), Error> {
         match self.0.clinit_stage.get() {
@@ -866,6 +1223,8 @@ impl Trace for ClassData {
         method_data.instance_method_vtable.trace();
 
         self.clinit_method.trace();
+        self.nest_host.trace();
+        self.resolved_constants.trace();
     }
 }
 
@@ -0,0 +1,262 @@
+// Defining classes from Rust. A `ClassBuilder` writes a class file for the
+// class with `ClassWriter` and loads it like any other class, so the class is
+// visible to reflection as usual; its methods are all `native` methods whose
+// implementations are bound directly to the created `Method`s rather than
+// being registered in the `Context`'s native mappings. Abstract methods have no
+// implementation, and are written to the class file as they are.
+
+use super::class::Class;
+use super::context::Context;
+use super::descriptor::MethodDescriptor;
+use super::error::Error;
+use super::loader::ClassLoader;
+use super::method::{Method, NativeMethod};
+use super::native::{NativeFunction, NativeHandler, SignatureCheck};
+
+use crate::classfile::error::WriteError;
+use crate::classfile::flags::{ClassFlags, FieldFlags, MethodFlags};
+use crate::classfile::writer::ClassWriter;
+use crate::string::JvmString;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// A builder for a class whose methods are all implemented in Rust, without
+/// needing the bytes of a class file. For example:
+///
+/// ```ignore
+/// fn bridge_version(_context: &Context, _args: &[Value]) -> Result<Option<Value>, Error> {
+///     Ok(Some(Value::Integer(3)))
+/// }
+///
+/// let mut builder = ClassBuilder::new(ClassFlags::PUBLIC, "HostBridge");
+/// builder.add_field(FieldFlags::PUBLIC | FieldFlags::STATIC, "counter", "I");
+/// builder.add_method(
+///     MethodFlags::PUBLIC | MethodFlags::STATIC,
+///     "version",
+///     "()I",
+///     bridge_version,
+/// );
+///
+/// let class = builder.define(context, context.system_loader())?;
+/// ```
+///
+/// Methods added with [`ClassBuilder::add_method`] or
+/// [`ClassBuilder::add_typed_method`] are made `native` and implemented by the
+/// given function, which receives the receiver as its first argument for
+/// instance methods. The methods aren't registered with
+/// [`Context::register_native_mappings`], so classes of the same name can be
+/// defined with different implementations in different loaders.
+pub struct ClassBuilder {
+    flags: ClassFlags,
+    name: String,
+    super_class: String,
+    interfaces: Vec<String>,
+
+    fields: Vec<(FieldFlags, String, String)>,
+    methods: Vec<BuilderMethod>,
+}
+
+struct BuilderMethod {
+    flags: MethodFlags,
+    name: String,
+    descriptor: String,
+
+    // The implementation of the method, along with the check of its signature
+    // for typed native methods, or `None` for abstract methods
+    implementation: Option<(NativeHandler, Option<SignatureCheck>)>,
+}
+
+impl ClassBuilder {
+    /// Create a builder for a class with the given flags and internal name
+    /// (e.g. `com/example/HostBridge`), extending `java/lang/Object`.
+    pub fn new(flags: ClassFlags, name: &str) -> Self {
+        Self {
+            flags,
+            name: name.to_string(),
+            super_class: "java/lang/Object".to_string(),
+            interfaces: Vec::new(),
+            fields: Vec::new(),
+            methods: Vec::new(),
+        }
+    }
+
+    /// Set the internal name of the superclass of the class.
+    pub fn set_super_class(&mut self, name: &str) {
+        self.super_class = name.to_string();
+    }
+
+    /// Add an interface, by internal name, to those the class implements.
+    pub fn add_interface(&mut self, name: &str) {
+        self.interfaces.push(name.to_string());
+    }
+
+    /// Add a field with the given flags, name, and descriptor (e.g.
+    /// `Ljava/lang/String;`). Fields are initialized to their default value.
+    pub fn add_field(&mut self, flags: FieldFlags, name: &str, descriptor: &str) {
+        self.fields
+            .push((flags, name.to_string(), descriptor.to_string()));
+    }
+
+    /// Add a method with the given flags, name, and descriptor (e.g.
+    /// `(ILjava/lang/String;)V`), implemented by `method`. The method is
+    /// always `native`; constructors can be added with the name `<init>`, and
+    /// a class initializer with the name `<clinit>`.
+    pub fn add_method(
+        &mut self,
+        flags: MethodFlags,
+        name: &str,
+        descriptor: &str,
+        method: NativeMethod,
+    ) {
+        self.push_method(
+            flags | MethodFlags::NATIVE,
+            name,
+            descriptor,
+            Some((NativeHandler::untyped(method), None)),
+        );
+    }
+
+    /// Add a method implemented by a typed Rust function, as for
+    /// [`Context::register_static_native`] and
+    /// [`Context::register_instance_native`]; whether the method is static is
+    /// taken from `flags`. The signature of `function` is checked when the
+    /// class is defined.
+    pub fn add_typed_method<Args, R, F: NativeFunction<Args, R>>(
+        &mut self,
+        flags: MethodFlags,
+        name: &str,
+        descriptor: &str,
+        function: F,
+    ) {
+        let is_static = flags.contains(MethodFlags::STATIC);
+
+        self.push_method(
+            flags | MethodFlags::NATIVE,
+            name,
+            descriptor,
+            Some((
+                NativeHandler::typed(function, is_static),
+                Some(F::check_signature),
+            )),
+        );
+    }
+
+    /// Add an `abstract` method without an implementation, such as a method of
+    /// an interface.
+    pub fn add_abstract_method(&mut self, flags: MethodFlags, name: &str, descriptor: &str) {
+        self.push_method(flags | MethodFlags::ABSTRACT, name, descriptor, None);
+    }
+
+    fn push_method(
+        &mut self,
+        flags: MethodFlags,
+        name: &str,
+        descriptor: &str,
+        implementation: Option<(NativeHandler, Option<SignatureCheck>)>,
+    ) {
+        self.methods.push(BuilderMethod {
+            flags,
+            name: name.to_string(),
+            descriptor: descriptor.to_string(),
+            implementation,
+        });
+    }
+
+    /// Create the class and register it in the registry of `loader`. The
+    /// superclass and interfaces of the class are looked up with `loader`.
+    ///
+    /// This returns a `ClassFormatError` if a name or descriptor is invalid or
+    /// two methods have the same name and descriptor, and a `LinkageError` if
+    /// the signature of a typed method doesn't match its descriptor or
+    /// `loader` already has a class of the same name.
+    pub fn define(self, context: &Context, loader: ClassLoader) -> Result<Class, Error> {
+        for (i, method) in self.methods.iter().enumerate() {
+            let is_duplicate = self.methods[..i]
+                .iter()
+                .any(|other| other.name == method.name && other.descriptor == method.descriptor);
+            if is_duplicate {
+                return Err(context.class_format_error(&format!(
+                    "{}: Duplicate method {}{}",
+                    self.name, method.name, method.descriptor
+                )));
+            }
+        }
+
+        let data = self
+            .write_class_file()
+            .map_err(|e| context.class_format_error(&format!("{}: {:?}", self.name, e)))?;
+
+        let class = Class::from_data(context, loader, &data)?;
+
+        for method in &self.methods {
+            let Some((handler, check)) = &method.implementation else {
+                continue;
+            };
+
+            let created_method = own_method(context, class, method)?;
+            if let Some(check) = check {
+                let is_static = method.flags.contains(MethodFlags::STATIC);
+                check(class.name(), created_method.descriptor(), is_static)
+                    .map_err(|e| context.linkage_error(&e.to_string()))?;
+            }
+
+            created_method.set_native(handler.clone());
+        }
+
+        loader.define_class(context, class)?;
+
+        Ok(class)
+    }
+
+    fn write_class_file(&self) -> Result<Vec<u8>, WriteError> {
+        let mut writer = ClassWriter::new(self.flags, &self.name, Some(&self.super_class));
+
+        for interface in &self.interfaces {
+            writer.add_interface(interface)?;
+        }
+
+        for (flags, name, descriptor) in &self.fields {
+            writer.add_field(*flags, name, descriptor).finish()?;
+        }
+
+        for method in &self.methods {
+            writer
+                .add_method(method.flags, &method.name, &method.descriptor)
+                .finish()?;
+        }
+
+        writer.into_bytes()
+    }
+}
+
+// Find the method declared by `class` for `method`, which was just loaded from
+// the class file written by the builder.
+fn own_method(context: &Context, class: Class, method: &BuilderMethod) -> Result<Method, Error> {
+    let name = JvmString::new(context.gc_ctx(), method.name.clone());
+    let descriptor = JvmString::new(context.gc_ctx(), method.descriptor.clone());
+    let descriptor = MethodDescriptor::from_string(context, descriptor)?;
+
+    let created_method = if method.flags.contains(MethodFlags::STATIC) {
+        class
+            .static_method_vtable()
+            .lookup_own((name, descriptor))
+            .map(|i| class.get_static_method(i))
+    } else {
+        let vtable = class.instance_method_vtable();
+        vtable
+            .lookup((name, descriptor))
+            .map(|i| vtable.get_element(i))
+            .filter(|created_method| created_method.class() == class)
+    };
+
+    created_method.ok_or_else(|| {
+        context.class_format_error(&format!(
+            "{}: Method {}{} was not declared",
+            class.name(),
+            method.name,
+            method.descriptor
+        ))
+    })
+}
@@ -0,0 +1,94 @@
+// The standard streams of the JVM. `System.in`, `System.out`, and `System.err`
+// read from and write to the `ConsoleBackend` of the `Context`, which is
+// provided by the embedder.
+
+use super::context::Context;
+
+use alloc::rc::Rc;
+
+/// A trait that describes a type that provides the standard streams of the
+/// JVM: the bytes written to `System.out` and `System.err` are passed to it,
+/// and the bytes read from `System.in` are supplied by it.
+///
+/// It is set with [`Context::set_console_backend`]. By default, output is
+/// discarded and `System.in` is empty. For example, a backend that captures
+/// the output of the program could be written as so:
+///
+/// ```ignore
+/// #[derive(Default)]
+/// struct CapturingConsole {
+///     stdout: RefCell<Vec<u8>>,
+///     stderr: RefCell<Vec<u8>>,
+/// }
+///
+/// impl ConsoleBackend for CapturingConsole {
+///     fn write_stdout(&self, data: &[u8]) {
+///         self.stdout.borrow_mut().extend_from_slice(data);
+///     }
+///
+///     fn write_stderr(&self, data: &[u8]) {
+///         self.stderr.borrow_mut().extend_from_slice(data);
+///     }
+///
+///     fn read_stdin(&self, _buf: &mut [u8]) -> usize {
+///         0
+///     }
+/// }
+///
+/// let console = Rc::new(CapturingConsole::default());
+/// context.set_console_backend(console.clone());
+///
+/// // Run the program...
+///
+/// assert_eq!(&*console.stdout.borrow(), b"Hello, world!\n");
+/// ```
+pub trait ConsoleBackend {
+    /// Write bytes written to `System.out`.
+    fn write_stdout(&self, data: &[u8]);
+
+    /// Write bytes written to `System.err`.
+    fn write_stderr(&self, data: &[u8]);
+
+    /// Flush any bytes written to `System.out` that are buffered.
+    fn flush_stdout(&self) {}
+
+    /// Flush any bytes written to `System.err` that are buffered.
+    fn flush_stderr(&self) {}
+
+    /// Read bytes from `System.in` into `buf`, returning the number of bytes
+    /// read. This should block until at least one byte is available, and
+    /// return 0 only once the end of the input has been reached.
+    fn read_stdin(&self, buf: &mut [u8]) -> usize;
+
+    /// The number of bytes that can be read from `System.in` without
+    /// blocking.
+    fn stdin_available(&self) -> usize {
+        0
+    }
+}
+
+// The console backend used until one is set by the embedder.
+pub(crate) struct NullConsoleBackend;
+
+impl ConsoleBackend for NullConsoleBackend {
+    fn write_stdout(&self, _data: &[u8]) {}
+
+    fn write_stderr(&self, _data: &[u8]) {}
+
+    fn read_stdin(&self, _buf: &mut [u8]) -> usize {
+        0
+    }
+}
+
+impl Context {
+    /// Set the backend that provides the standard streams of the JVM,
+    /// replacing the current one. See [`ConsoleBackend`].
+    pub fn set_console_backend(&self, backend: Rc<dyn ConsoleBackend>) {
+        *self.console_backend.borrow_mut() = backend;
+    }
+
+    /// The backend that provides the standard streams of the JVM.
+    pub fn console_backend(&self) -> Rc<dyn ConsoleBackend> {
+        self.console_backend.borrow().clone()
+    }
+}
@@ -0,0 +1,613 @@
+// Resolution of loadable constant pool entries and call sites. Method handles,
+// method types, dynamically-computed constants, and the targets of call sites
+// are created by Java code, and are cached on the class whose constant pool
+// contains them so that every use of one of these entries produces the same
+// value.
+
+use super::class::Class;
+use super::context::Context;
+use super::descriptor::{
+    Descriptor, MethodDescriptor, ResolvedDescriptor, ResolvedMethodDescriptor,
+};
+use super::error::Error;
+use super::field::FieldTemplate;
+use super::method_handle::{natives_class, natives_method};
+use super::object::Object;
+use super::value::Value;
+
+use crate::classfile::attribute::{AttributeInfo, BootstrapMethod};
+use crate::classfile::constant_pool::{ConstantPool, ConstantPoolEntry, MethodHandle};
+use crate::string::JvmString;
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+// The reference kinds of `CONSTANT_MethodHandle` entries, as passed to
+// `MethodHandleNatives.linkMethodHandleConstant`.
+const REF_GET_FIELD: i32 = 1;
+const REF_GET_STATIC: i32 = 2;
+const REF_PUT_FIELD: i32 = 3;
+const REF_PUT_STATIC: i32 = 4;
+const REF_INVOKE_VIRTUAL: i32 = 5;
+const REF_INVOKE_STATIC: i32 = 6;
+const REF_INVOKE_SPECIAL: i32 = 7;
+const REF_NEW_INVOKE_SPECIAL: i32 = 8;
+const REF_INVOKE_INTERFACE: i32 = 9;
+
+/// Load the value of a constant that doesn't need to be cached: a string,
+/// number, or class.
+pub(crate) fn load_constant(
+    context: &Context,
+    class: Class,
+    entry: ConstantPoolEntry,
+) -> Result<Value, Error> {
+    let loader = class
+        .loader()
+        .expect("Class with constant pool should have loader");
+
+    let class_file = class
+        .class_file()
+        .expect("Class with constant pool should have class file");
+    let constant_pool = class_file.constant_pool();
+
+    Ok(match entry {
+        ConstantPoolEntry::String { string_idx } => {
+            let string = constant_pool
+                .get_utf8(string_idx)
+                .expect("Should refer to valid entry");
+
+            let string_chars = string.encode_utf16().collect::<Vec<_>>();
+
+            let string_obj = context.create_string(&string_chars);
+
+            // All string literals are interned
+            let string_obj = context.intern_string_obj(string_obj);
+
+            Value::Object(Some(string_obj))
+        }
+        ConstantPoolEntry::Integer { value } => Value::Integer(value),
+        ConstantPoolEntry::Float { value } => Value::Float(value),
+        ConstantPoolEntry::Long { value } => Value::Long(value),
+        ConstantPoolEntry::Double { value } => Value::Double(value),
+        ConstantPoolEntry::Class { name_idx } => {
+            let class_name = constant_pool
+                .get_utf8(name_idx)
+                .expect("Should refer to valid entry");
+
+            let class = loader.lookup_class(context, class_name)?;
+
+            Value::Object(Some(class.get_or_init_object(context)))
+        }
+        _ => return Err(context.verify_error("Constant pool entry is not loadable")),
+    })
+}
+
+/// Resolve the loadable constant at `index` in the constant pool of `class`.
+/// `MethodHandle`, `MethodType`, and `Dynamic` entries are only resolved once;
+/// later calls return the cached value.
+pub(crate) fn resolve_constant(
+    context: &Context,
+    class: Class,
+    index: u16,
+) -> Result<Value, Error> {
+    if let Some(value) = class.resolved_constant(index) {
+        return Ok(value);
+    }
+
+    let class_file = class
+        .class_file()
+        .expect("Class with constant pool should have class file");
+    let constant_pool = class_file.constant_pool();
+
+    let entry = constant_pool
+        .entry(index)
+        .map_err(|e| Error::from_class_file_error(context, e))?;
+
+    let value = match entry {
+        ConstantPoolEntry::MethodHandle { method_handle } => {
+            Value::Object(Some(method_handle_constant(context, class, method_handle)?))
+        }
+        ConstantPoolEntry::MethodType { descriptor_idx } => {
+            let descriptor = constant_pool
+                .get_utf8(descriptor_idx)
+                .expect("Should refer to valid entry");
+
+            Value::Object(Some(method_type_constant(context, class, descriptor)?))
+        }
+        ConstantPoolEntry::Dynamic {
+            bootstrap_method_idx,
+            name_and_type_idx,
+        } => dynamic_constant(context, class, bootstrap_method_idx, name_and_type_idx)?,
+        _ => return load_constant(context, class, entry),
+    };
+
+    // A bootstrap method could have resolved this entry already, in which case
+    // the first value is used
+    Ok(class.set_resolved_constant(index, value))
+}
+
+/// The type of the value of the loadable constant at `index`. This is a class
+/// descriptor for every entry except numbers and dynamic constants.
+pub(crate) fn constant_type(
+    context: &Context,
+    constant_pool: &ConstantPool,
+    index: u16,
+) -> Result<Descriptor, Error> {
+    let entry = constant_pool
+        .entry(index)
+        .map_err(|e| Error::from_class_file_error(context, e))?;
+
+    let class_name = match entry {
+        ConstantPoolEntry::Integer { .. } => return Ok(Descriptor::Integer),
+        ConstantPoolEntry::Float { .. } => return Ok(Descriptor::Float),
+        ConstantPoolEntry::Long { .. } => return Ok(Descriptor::Long),
+        ConstantPoolEntry::Double { .. } => return Ok(Descriptor::Double),
+        ConstantPoolEntry::Dynamic {
+            name_and_type_idx, ..
+        } => {
+            let (_, descriptor) = constant_pool
+                .get_name_and_type(name_and_type_idx)
+                .map_err(|e| Error::from_class_file_error(context, e))?;
+
+            let descriptor = Descriptor::from_string(context, descriptor)?;
+            if matches!(descriptor, Descriptor::Void) {
+                return Err(context.class_format_error("Dynamic constant cannot be void"));
+            }
+
+            return Ok(descriptor);
+        }
+        ConstantPoolEntry::String { .. } => "java/lang/String",
+        ConstantPoolEntry::Class { .. } => "java/lang/Class",
+        ConstantPoolEntry::MethodHandle { .. } => "java/lang/invoke/MethodHandle",
+        ConstantPoolEntry::MethodType { .. } => "java/lang/invoke/MethodType",
+        _ => return Err(context.verify_error("Constant pool entry is not loadable")),
+    };
+
+    let class_name = JvmString::new(context.gc_ctx, class_name.to_string());
+
+    Ok(Descriptor::Class(class_name))
+}
+
+// Create the `java.lang.invoke.MethodHandle` for a `MethodHandle` entry of the
+// constant pool of `class`.
+fn method_handle_constant(
+    context: &Context,
+    class: Class,
+    method_handle: MethodHandle,
+) -> Result<Object, Error> {
+    let loader = class
+        .loader()
+        .expect("Class with constant pool should have loader");
+
+    let class_file = class
+        .class_file()
+        .expect("Class with constant pool should have class file");
+    let constant_pool = class_file.constant_pool();
+
+    let natives_class = natives_class(context)?;
+    natives_class.run_clinit(context)?;
+
+    let (kind, ref_class, member) = match method_handle {
+        MethodHandle::GetField(idx)
+        | MethodHandle::GetStatic(idx)
+        | MethodHandle::PutField(idx)
+        | MethodHandle::PutStatic(idx) => {
+            let (class_name, field_name, descriptor) = constant_pool
+                .get_field_ref(idx)
+                .map_err(|e| Error::from_class_file_error(context, e))?;
+
+            let ref_class = loader.lookup_class(context, class_name)?;
+            let descriptor = Descriptor::from_string(context, descriptor)?;
+            let key = (field_name, descriptor);
+
+            let (kind, field) = match method_handle {
+                MethodHandle::GetField(_) | MethodHandle::PutField(_) => {
+                    let kind = if matches!(method_handle, MethodHandle::GetField(_)) {
+                        REF_GET_FIELD
+                    } else {
+                        REF_PUT_FIELD
+                    };
+
+                    let slot = ref_class
+                        .instance_field_vtable()
+                        .lookup(key)
+                        .ok_or_else(|| context.no_such_field_error())?;
+
+                    (
+                        kind,
+                        FieldTemplate::for_instance_field(context, ref_class, slot as usize),
+                    )
+                }
+                _ => {
+                    let kind = if matches!(method_handle, MethodHandle::GetStatic(_)) {
+                        REF_GET_STATIC
+                    } else {
+                        REF_PUT_STATIC
+                    };
+
+                    let slot = ref_class
+                        .static_field_vtable()
+                        .lookup(key)
+                        .ok_or_else(|| context.no_such_field_error())?;
+
+                    (
+                        kind,
+                        FieldTemplate::for_static_field(context, ref_class, slot as usize),
+                    )
+                }
+            };
+
+            (kind, ref_class, field.object())
+        }
+        MethodHandle::InvokeVirtual(idx)
+        | MethodHandle::InvokeStatic(idx)
+        | MethodHandle::InvokeSpecial(idx)
+        | MethodHandle::NewInvokeSpecial(idx)
+        | MethodHandle::InvokeInterface(idx) => {
+            let (class_name, method_name, descriptor) = constant_pool
+                .get_any_method_ref(idx)
+                .map_err(|e| Error::from_class_file_error(context, e))?;
+
+            let ref_class = loader.lookup_class(context, class_name)?;
+            let descriptor = MethodDescriptor::from_string(context, descriptor)?;
+            let key = (method_name, descriptor);
+
+            let method = if matches!(method_handle, MethodHandle::InvokeStatic(_)) {
+                let vtable = ref_class.static_method_vtable();
+                vtable.lookup(key).map(|i| ref_class.get_static_method(i))
+            } else {
+                let vtable = ref_class.instance_method_vtable();
+                vtable.lookup(key).map(|i| vtable.get_element(i))
+            };
+
+            // Constructors aren't inherited
+            let method = method.filter(|m| {
+                !matches!(method_handle, MethodHandle::NewInvokeSpecial(_))
+                    || m.class() == ref_class
+            });
+
+            let method = method.ok_or_else(|| {
+                let message = format!("{}.{}()", class_name, method_name);
+                context.no_such_method_error(&message)
+            })?;
+
+            let kind = match method_handle {
+                MethodHandle::InvokeVirtual(_) => REF_INVOKE_VIRTUAL,
+                MethodHandle::InvokeStatic(_) => REF_INVOKE_STATIC,
+                MethodHandle::InvokeSpecial(_) => REF_INVOKE_SPECIAL,
+                MethodHandle::NewInvokeSpecial(_) => REF_NEW_INVOKE_SPECIAL,
+                _ => REF_INVOKE_INTERFACE,
+            };
+
+            // The handle suppresses access checks on its member, so it can't
+            // share the member object returned by reflection
+            (kind, ref_class, method.create_object(context))
+        }
+    };
+
+    // Like `invokespecial`, an `invokeSpecial` handle can only be called on
+    // instances of the class containing the constant, so that class is the
+    // type of its receiver
+    let ref_class = if kind == REF_INVOKE_SPECIAL {
+        class
+    } else {
+        ref_class
+    };
+
+    let ref_class_object = ref_class.get_or_init_object(context);
+
+    let link_method = natives_method(
+        context,
+        natives_class,
+        "linkMethodHandleConstant",
+        "(ILjava/lang/Class;Ljava/lang/reflect/AccessibleObject;)Ljava/lang/invoke/MethodHandle;",
+    )?;
+
+    let result = context
+        .exec_method(
+            link_method,
+            &[
+                Value::Integer(kind),
+                Value::Object(Some(ref_class_object)),
+                Value::Object(Some(member)),
+            ],
+        )?
+        .expect("linkMethodHandleConstant returns a value");
+
+    Ok(result
+        .object()
+        .expect("linkMethodHandleConstant returns non-null"))
+}
+
+// Create the `java.lang.invoke.MethodType` for a `MethodType` entry of the
+// constant pool of `class`, with the given descriptor.
+fn method_type_constant(
+    context: &Context,
+    class: Class,
+    descriptor: JvmString,
+) -> Result<Object, Error> {
+    let loader = class
+        .loader()
+        .expect("Class with constant pool should have loader");
+
+    let descriptor = MethodDescriptor::from_string(context, descriptor)?;
+    let resolved = ResolvedMethodDescriptor::from_method_descriptor(context, loader, descriptor)?;
+
+    let method_type_class = context.bootstrap_loader().lookup_class(
+        context,
+        JvmString::new(context.gc_ctx, "java/lang/invoke/MethodType".to_string()),
+    )?;
+    method_type_class.run_clinit(context)?;
+
+    let return_type = resolved.return_type().reflection_class(context);
+    let parameter_types = resolved
+        .args()
+        .iter()
+        .map(|arg| Some(arg.reflection_class(context).get_or_init_object(context)))
+        .collect::<Box<_>>();
+
+    let parameter_types =
+        Object::obj_array(context, context.builtins().java_lang_class, parameter_types);
+
+    let method_type = natives_method(
+        context,
+        method_type_class,
+        "methodType",
+        "(Ljava/lang/Class;[Ljava/lang/Class;)Ljava/lang/invoke/MethodType;",
+    )?;
+
+    let result = context
+        .exec_method(
+            method_type,
+            &[
+                Value::Object(Some(return_type.get_or_init_object(context))),
+                Value::Object(Some(parameter_types)),
+            ],
+        )?
+        .expect("methodType returns a value");
+
+    Ok(result.object().expect("methodType returns non-null"))
+}
+
+// Compute the value of a `Dynamic` entry of the constant pool of `class` by
+// calling its bootstrap method.
+fn dynamic_constant(
+    context: &Context,
+    class: Class,
+    bootstrap_method_idx: u16,
+    name_and_type_idx: u16,
+) -> Result<Value, Error> {
+    let loader = class
+        .loader()
+        .expect("Class with constant pool should have loader");
+
+    let class_file = class
+        .class_file()
+        .expect("Class with constant pool should have class file");
+    let constant_pool = class_file.constant_pool();
+
+    let (name, descriptor) = constant_pool
+        .get_name_and_type(name_and_type_idx)
+        .map_err(|e| Error::from_class_file_error(context, e))?;
+
+    let descriptor = Descriptor::from_string(context, descriptor)?;
+    if matches!(descriptor, Descriptor::Void) {
+        return Err(context.class_format_error("Dynamic constant cannot be void"));
+    }
+
+    let bootstrap_method = bootstrap_method(context, class, bootstrap_method_idx)?;
+
+    let (handle, static_args) = resolve_bootstrap_method(context, class, &bootstrap_method)?;
+
+    let value_class =
+        ResolvedDescriptor::from_descriptor(context, loader, descriptor)?.reflection_class(context);
+
+    let natives_class = natives_class(context)?;
+    natives_class.run_clinit(context)?;
+
+    let link_method = natives_method(
+        context,
+        natives_class,
+        "linkDynamicConstant",
+        "(Ljava/lang/Class;Ljava/lang/invoke/MethodHandle;Ljava/lang/String;Ljava/lang/Class;[Ljava/lang/Object;)Ljava/lang/Object;",
+    )?;
+
+    let args_array = box_bootstrap_arguments(context, static_args)?;
+
+    let name_chars = name.encode_utf16().collect::<Vec<_>>();
+    let name = context.intern_string_obj(context.create_string(&name_chars));
+
+    let result = context
+        .exec_method(
+            link_method,
+            &[
+                Value::Object(Some(class.get_or_init_object(context))),
+                handle,
+                Value::Object(Some(name)),
+                Value::Object(Some(value_class.get_or_init_object(context))),
+                Value::Object(Some(args_array)),
+            ],
+        )?
+        .expect("linkDynamicConstant returns a value");
+
+    // The result was checked against the type of the constant by Java code
+    if descriptor.is_primitive() {
+        let boxed = result.object().unwrap();
+
+        Ok(boxed.get_field(0))
+    } else {
+        Ok(result)
+    }
+}
+
+/// Resolve the `InvokeDynamic` entry at `index` in the constant pool of
+/// `class` to the target of its call site, by calling its bootstrap method.
+/// Like other entries resolved by Java code, the result is cached on `class`.
+pub(crate) fn resolve_call_site(
+    context: &Context,
+    class: Class,
+    index: u16,
+) -> Result<Object, Error> {
+    if let Some(value) = class.resolved_constant(index) {
+        return Ok(value.object().unwrap());
+    }
+
+    let class_file = class
+        .class_file()
+        .expect("Class with constant pool should have class file");
+    let constant_pool = class_file.constant_pool();
+
+    let entry = constant_pool
+        .entry(index)
+        .map_err(|e| Error::from_class_file_error(context, e))?;
+
+    let ConstantPoolEntry::InvokeDynamic {
+        bootstrap_method_idx,
+        name_and_type_idx,
+    } = entry
+    else {
+        return Err(context.verify_error("Expected InvokeDynamic constant"));
+    };
+
+    let (name, descriptor) = constant_pool
+        .get_name_and_type(name_and_type_idx)
+        .map_err(|e| Error::from_class_file_error(context, e))?;
+
+    let bootstrap_method = bootstrap_method(context, class, bootstrap_method_idx)?;
+
+    let (handle, static_args) = resolve_bootstrap_method(context, class, &bootstrap_method)?;
+
+    let natives_class = natives_class(context)?;
+    natives_class.run_clinit(context)?;
+
+    let link_method = natives_method(
+        context,
+        natives_class,
+        "linkCallSite",
+        "(Ljava/lang/Class;Ljava/lang/invoke/MethodHandle;Ljava/lang/String;Ljava/lang/invoke/MethodType;[Ljava/lang/Object;)Ljava/lang/invoke/MethodHandle;",
+    )?;
+
+    // This runs Java code, so it must be created before the arguments are
+    // boxed. Nothing else runs Java code before it's passed to `linkCallSite`.
+    let call_site_type = method_type_constant(context, class, descriptor)?;
+
+    let args_array = box_bootstrap_arguments(context, static_args)?;
+
+    let name_chars = name.encode_utf16().collect::<Vec<_>>();
+    let name = context.intern_string_obj(context.create_string(&name_chars));
+
+    let result = context
+        .exec_method(
+            link_method,
+            &[
+                Value::Object(Some(class.get_or_init_object(context))),
+                handle,
+                Value::Object(Some(name)),
+                Value::Object(Some(call_site_type)),
+                Value::Object(Some(args_array)),
+            ],
+        )?
+        .expect("linkCallSite returns a value");
+
+    // The bootstrap method could have linked this call site already, in which
+    // case the first target is used
+    let target = class.set_resolved_constant(index, result);
+
+    Ok(target.object().expect("linkCallSite returns non-null"))
+}
+
+// Resolve the handle and static arguments of a bootstrap method, along with
+// the type of each argument, and initialize the classes used to box them.
+//
+// This can run Java code. The results are either cached on `class` or
+// otherwise kept alive, so they won't be collected before the bootstrap
+// method is called.
+fn resolve_bootstrap_method(
+    context: &Context,
+    class: Class,
+    bootstrap_method: &BootstrapMethod,
+) -> Result<(Value, Vec<(Value, Descriptor)>), Error> {
+    let class_file = class
+        .class_file()
+        .expect("Class with constant pool should have class file");
+    let constant_pool = class_file.constant_pool();
+
+    let handle = resolve_constant(context, class, bootstrap_method.method_ref)?;
+
+    let mut static_args = Vec::with_capacity(bootstrap_method.arguments.len());
+    for arg_idx in &bootstrap_method.arguments {
+        let value = resolve_constant(context, class, *arg_idx)?;
+        let value_type = constant_type(context, constant_pool, *arg_idx)?;
+
+        static_args.push((value, value_type));
+    }
+
+    // Initialize the classes used to box the arguments before creating any
+    // objects, as that may run Java code
+    for (_, value_type) in &static_args {
+        if value_type.is_primitive() {
+            value_type.box_class(context)?.run_clinit(context)?;
+        }
+    }
+
+    Ok((handle, static_args))
+}
+
+// Box the static arguments of a bootstrap method into an `Object[]`. This
+// doesn't run any Java code.
+fn box_bootstrap_arguments(
+    context: &Context,
+    static_args: Vec<(Value, Descriptor)>,
+) -> Result<Object, Error> {
+    let mut boxed_args = Vec::with_capacity(static_args.len());
+    for (value, value_type) in static_args {
+        if value_type.is_primitive() {
+            let boxed = Object::from_class(context.gc_ctx, value_type.box_class(context)?);
+            boxed.set_field(0, value);
+
+            boxed_args.push(Some(boxed));
+        } else {
+            boxed_args.push(value.object());
+        }
+    }
+
+    Ok(Object::obj_array(
+        context,
+        context.object_class(),
+        boxed_args.into_boxed_slice(),
+    ))
+}
+
+/// The bootstrap method at `index` in the `BootstrapMethods` attribute of the
+/// class file of `class`.
+pub(crate) fn bootstrap_method(
+    context: &Context,
+    class: Class,
+    index: u16,
+) -> Result<BootstrapMethod, Error> {
+    let class_file = class
+        .class_file()
+        .expect("Class with constant pool should have class file");
+    let constant_pool = class_file.constant_pool();
+
+    for attribute in class_file.attributes() {
+        if &*attribute.name() != "BootstrapMethods" {
+            continue;
+        }
+
+        let attribute = attribute
+            .parse(constant_pool)
+            .map_err(|e| Error::from_class_file_error(context, e))?;
+
+        if let AttributeInfo::BootstrapMethods(bootstrap_methods) = attribute
+            && let Some(bootstrap_method) =
+                bootstrap_methods.into_vec().into_iter().nth(index as usize)
+        {
+            return Ok(bootstrap_method);
+        }
+    }
+
+    Err(context.class_format_error("Invalid bootstrap method index"))
+}
@@ -1,32 +1,39 @@
 use super::builtins::{BuiltinClasses, PrimitiveArrayClasses};
 use super::call_stack::CallStack;
 use super::class::{Class, PrimitiveType};
+use super::console::{ConsoleBackend, NullConsoleBackend};
 use super::descriptor::MethodDescriptor;
 use super::error::Error;
 use super::field::FieldTemplate;
 use super::intern::StringObjectInterner;
 use super::loader::{ClassLoader, LoaderBackend, ResourceLoadSource};
 use super::method::{Method, NativeMethod};
+use super::native::{
+    CapturingNativeFunction, NativeFunction, NativeHandler, NativeRegistrationError, SignatureCheck,
+};
 use super::object::Object;
+use super::security::SecurityPolicy;
 use super::value::Value;
 
+use crate::classfile::class::ClassFile;
+use crate::classfile::error::Error as ClassFileError;
 use crate::gc::{Gc, GcCtx, Trace};
 use crate::jar::Jar;
 use crate::string::{JvmString, JvmStringInterner};
 use crate::utils::Syncable;
 
 use alloc::boxed::Box;
+use alloc::rc::Rc;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::cell::{Cell, OnceCell, Ref, RefCell, RefMut};
 use hashbrown::HashMap;
 
 // Various magic fields
-pub const OBJECT_TO_STRING_METHOD: u32 = 2;
-
 pub const THROWABLE_MESSAGE_FIELD: u32 = 0;
 pub const THROWABLE_STACK_TRACE_FIELD: u32 = 1;
 pub const THROWABLE_CAUSE_FIELD: u32 = 2;
+pub const THROWABLE_SUPPRESSED_FIELD: u32 = 3;
 
 pub const STRING_DATA_FIELD: u32 = 0;
 
@@ -72,6 +79,18 @@ pub struct Context {
     // system classes).
     loader_backend: Gc<Box<dyn LoaderBackend>>,
 
+    // The backend that provides the standard streams (stdin, stdout, and
+    // stderr). This can be replaced using `Context::set_console_backend`.
+    pub(crate) console_backend: RefCell<Rc<dyn ConsoleBackend>>,
+
+    // The policy deciding which operations the program may perform. This can
+    // be replaced using `Context::set_security_policy`.
+    pub(crate) security_policy: RefCell<SecurityPolicy>,
+
+    // The size of the call stack when each call to `Context::run_privileged`
+    // that is currently running started, innermost last.
+    pub(crate) privileged_frames: RefCell<Vec<usize>>,
+
     // The "bootstrap" class loader
     bootstrap_loader: ClassLoader,
 
@@ -108,7 +127,7 @@ pub struct Context {
     primitive_classes: HashMap<PrimitiveType, Class>,
 
     // Native method mappings
-    native_mapping: RefCell<HashMap<(JvmString, JvmString, MethodDescriptor), NativeMethod>>,
+    native_mapping: RefCell<HashMap<(JvmString, JvmString, MethodDescriptor), NativeHandler>>,
 
     // Values currently in locals or stacks of interpreter frames
     frame_data: Box<[Cell<Value>]>,
@@ -242,6 +261,9 @@ impl Context {
 
         Self {
             loader_backend,
+            console_backend: RefCell::new(Rc::new(NullConsoleBackend)),
+            security_policy: RefCell::new(SecurityPolicy::allow_all()),
+            privileged_frames: RefCell::new(Vec::new()),
             bootstrap_loader,
             system_loader: OnceCell::new(),
             java_classes: RefCell::new(Vec::new()),
@@ -308,7 +330,7 @@ impl Context {
             let descriptor =
                 MethodDescriptor::from_string(self, descriptor_name).expect("Valid descriptor");
 
-            let method = mapping.1;
+            let method = NativeHandler::untyped(mapping.1);
 
             self.native_mapping
                 .borrow_mut()
@@ -316,6 +338,155 @@ impl Context {
         }
     }
 
+    /// Register a typed Rust function as the implementation of a static Java
+    /// `native` method.
+    ///
+    /// The method is identified by a string of the same format as for
+    /// [`Context::register_native_mappings`]. Unlike the functions registered
+    /// by that method, `function` takes each argument of the Java method as a
+    /// Rust value and returns a Rust value; see [`NativeFunction`] for the
+    /// supported types. For example:
+    ///
+    /// ```ignore
+    /// fn math_pow(_context: &Context, base: f64, exp: f64) -> Result<f64, Error> {
+    ///     Ok(libm::pow(base, exp))
+    /// }
+    ///
+    /// context.register_static_native("java/lang/Math.pow.(DD)D", math_pow)?;
+    /// ```
+    ///
+    /// The signature of `function` is checked against the descriptor of the
+    /// Java method, and an error is returned if they don't match. If the Java
+    /// method turns out not to be static, loading its class will fail with a
+    /// `LinkageError` describing a [`NativeRegistrationError::StaticMismatch`].
+    ///
+    /// `function` isn't traced by the garbage collector, so it can't be a
+    /// closure with captures. Use [`Context::register_static_native_with`] to
+    /// pass values to it.
+    pub fn register_static_native<Args, R, F: NativeFunction<Args, R>>(
+        &self,
+        name: &str,
+        function: F,
+    ) -> Result<(), NativeRegistrationError> {
+        self.register_typed_native(
+            name,
+            true,
+            F::check_signature,
+            NativeHandler::typed(function, true),
+        )
+    }
+
+    /// Register a typed Rust function as the implementation of an instance
+    /// Java `native` method. This is like [`Context::register_static_native`],
+    /// but `function` takes the receiver before the arguments of the method:
+    ///
+    /// ```ignore
+    /// fn object_hash_code(_context: &Context, this: Object) -> Result<i32, Error> {
+    ///     Ok(0) // Do some calculations to determine the hash code
+    /// }
+    ///
+    /// context.register_instance_native("java/lang/Object.hashCode.()I", object_hash_code)?;
+    /// ```
+    pub fn register_instance_native<Args, R, F: NativeFunction<Args, R>>(
+        &self,
+        name: &str,
+        function: F,
+    ) -> Result<(), NativeRegistrationError> {
+        self.register_typed_native(
+            name,
+            false,
+            F::check_signature,
+            NativeHandler::typed(function, false),
+        )
+    }
+
+    /// Register a typed Rust function as the implementation of a static Java
+    /// `native` method, like [`Context::register_static_native`], passing it a
+    /// reference to `captures` after the `Context`:
+    ///
+    /// ```ignore
+    /// fn get_name(_context: &Context, name: &Object) -> Result<Object, Error> {
+    ///     Ok(*name)
+    /// }
+    ///
+    /// context.register_static_native_with("Config.getName.()Ljava/lang/String;", name, get_name)?;
+    /// ```
+    ///
+    /// `captures` is traced by the garbage collector for as long as the native
+    /// method is registered, so it may hold objects.
+    pub fn register_static_native_with<T, Args, R, F>(
+        &self,
+        name: &str,
+        captures: T,
+        function: F,
+    ) -> Result<(), NativeRegistrationError>
+    where
+        T: Trace + 'static,
+        F: CapturingNativeFunction<T, Args, R>,
+    {
+        self.register_typed_native(
+            name,
+            true,
+            F::check_signature,
+            NativeHandler::typed_with(captures, function, true),
+        )
+    }
+
+    /// Register a typed Rust function as the implementation of an instance
+    /// Java `native` method, passing it a reference to `captures` as for
+    /// [`Context::register_static_native_with`].
+    pub fn register_instance_native_with<T, Args, R, F>(
+        &self,
+        name: &str,
+        captures: T,
+        function: F,
+    ) -> Result<(), NativeRegistrationError>
+    where
+        T: Trace + 'static,
+        F: CapturingNativeFunction<T, Args, R>,
+    {
+        self.register_typed_native(
+            name,
+            false,
+            F::check_signature,
+            NativeHandler::typed_with(captures, function, false),
+        )
+    }
+
+    fn register_typed_native(
+        &self,
+        name: &str,
+        is_static: bool,
+        check_signature: SignatureCheck,
+        handler: NativeHandler,
+    ) -> Result<(), NativeRegistrationError> {
+        let name_parts = name.split(".").collect::<Vec<_>>();
+        let [class_name, method_name, descriptor_name] = name_parts[..] else {
+            return Err(NativeRegistrationError::InvalidName(name.to_string()));
+        };
+
+        let class_name = JvmString::new(self.gc_ctx, class_name.to_string());
+        let method_name = JvmString::new(self.gc_ctx, method_name.to_string());
+        let descriptor_name = JvmString::new(self.gc_ctx, descriptor_name.to_string());
+
+        // Natives are registered before the builtin classes are loaded, so
+        // this can't create a `ClassFormatError` for invalid descriptors
+        let descriptor = MethodDescriptor::new_from_string(
+            self.gc_ctx,
+            &mut self.interner(),
+            descriptor_name,
+        )
+        .map_err(|_| NativeRegistrationError::InvalidDescriptor(descriptor_name.to_string()))?;
+
+        check_signature(class_name, descriptor, is_static)?;
+
+        self.native_mapping
+            .borrow_mut()
+            .insert((class_name, method_name, descriptor), handler);
+
+        Ok(())
+    }
+
     /// Execute the provided [`Method`].
     ///
     /// This method ensures that none of the values passed in `args` can be
@@ -361,11 +532,11 @@ impl Context {
         class_name: JvmString,
         method_name: JvmString,
         method_descriptor: MethodDescriptor,
-    ) -> Option<NativeMethod> {
+    ) -> Option<NativeHandler> {
         self.native_mapping
             .borrow()
             .get(&(class_name, method_name, method_descriptor))
-            .copied()
+            .cloned()
     }
 
     pub(crate) fn loader_backend(&self) -> Gc<Box<dyn LoaderBackend>> {
@@ -473,6 +644,13 @@ impl Context {
         self.interner.borrow_mut()
     }
 
+    /// Parse a [`ClassFile`] from data without loading it as a class. This is
+    /// intended for tools that want to inspect class files; use
+    /// [`Class::from_data`] to actually load a class.
+    pub fn parse_class_file(&self, data: &[u8]) -> Result<ClassFile, ClassFileError> {
+        ClassFile::from_data(self.gc_ctx, &mut self.interner(), data)
+    }
+
     /// Intern a Java `String` [`Object`].
     ///
     /// This uses the VM's interned Java `String`s set, which is also used for
@@ -493,6 +671,25 @@ impl Context {
         self.call_stack.borrow().len()
     }
 
+    /// The method currently being executed, i.e. the top entry of the VM call
+    /// stack. When called from a native method, this returns the [`Method`]
+    /// that the native method is implementing.
+    ///
+    /// This method will panic if no method is being executed.
+    pub fn current_method(&self) -> Method {
+        self.call_stack
+            .borrow()
+            .top()
+            .expect("A method should be executing")
+    }
+
+    /// The method `depth` calls below the method currently being executed, or
+    /// `None` if the call stack isn't that deep. A `depth` of 0 returns the
+    /// same method as [`Context::current_method`].
+    pub fn caller_method(&self, depth: usize) -> Option<Method> {
+        self.call_stack.borrow().nth_from_top(depth)
+    }
+
     pub(crate) fn frame_data(&self) -> &[Cell<Value>] {
         &self.frame_data
     }
@@ -735,6 +932,24 @@ impl Context {
         Error(exception_instance)
     }
 
+    /// Create a `BootstrapMethodError` instance.
+    #[inline(never)]
+    #[cold]
+    pub fn bootstrap_method_error(&self, message: &str) -> Error {
+        let error_class = self.builtins().java_lang_bootstrap_method_error;
+
+        let error_instance = error_class.new_instance(self.gc_ctx);
+        self.fill_stack_trace(error_instance);
+
+        // Set the `message` field
+        error_instance.set_field(
+            THROWABLE_MESSAGE_FIELD,
+            Value::Object(Some(self.str_to_string(message))),
+        );
+
+        Error(error_instance)
+    }
+
     /// Create a `ClassCastException` instance.
     #[inline(never)]
     pub fn class_cast_exception(&self) -> Error {
@@ -791,6 +1006,24 @@ impl Context {
         Error(exception_instance)
     }
 
+    /// Create an `IllegalArgumentException` instance.
+    #[inline(never)]
+    #[cold]
+    pub fn illegal_argument_exception(&self, message: &str) -> Error {
+        let exception_class = self.builtins().java_lang_illegal_argument_exception;
+
+        let exception_instance = exception_class.new_instance(self.gc_ctx);
+        self.fill_stack_trace(exception_instance);
+
+        // Set the `message` field
+        exception_instance.set_field(
+            THROWABLE_MESSAGE_FIELD,
+            Value::Object(Some(self.str_to_string(message))),
+        );
+
+        Error(exception_instance)
+    }
+
     /// Create an `IncompatibleClassChangeError` instance.
     #[inline(never)]
     #[cold]
@@ -928,6 +1161,24 @@ impl Context {
         Error(exception_instance)
     }
 
+    /// Create an `UnsupportedClassVersionError` instance.
+    #[inline(never)]
+    #[cold]
+    pub fn unsupported_class_version_error(&self, message: &str) -> Error {
+        let error_class = self.builtins().java_lang_unsupported_class_version_error;
+
+        let error_instance = error_class.new_instance(self.gc_ctx);
+        self.fill_stack_trace(error_instance);
+
+        // Set the `message` field
+        error_instance.set_field(
+            THROWABLE_MESSAGE_FIELD,
+            Value::Object(Some(self.str_to_string(message))),
+        );
+
+        Error(error_instance)
+    }
+
     /// Create a `VerifyError` instance.
     #[inline(never)]
     #[cold]
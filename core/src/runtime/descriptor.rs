@@ -193,6 +193,28 @@ impl Descriptor {
     pub fn is_primitive(self) -> bool {
         !matches!(self, Descriptor::Class(_) | Descriptor::Array(_))
     }
+
+    /// The wrapper class that values of this primitive descriptor are boxed
+    /// in, such as `java.lang.Integer` for `Descriptor::Integer`. This method
+    /// will panic if called on a `Descriptor::Class`, `Descriptor::Array`, or
+    /// `Descriptor::Void`.
+    pub fn box_class(self, context: &Context) -> Result<Class, Error> {
+        let class_name = match self {
+            Descriptor::Boolean => "java/lang/Boolean",
+            Descriptor::Byte => "java/lang/Byte",
+            Descriptor::Character => "java/lang/Character",
+            Descriptor::Short => "java/lang/Short",
+            Descriptor::Integer => "java/lang/Integer",
+            Descriptor::Long => "java/lang/Long",
+            Descriptor::Float => "java/lang/Float",
+            Descriptor::Double => "java/lang/Double",
+            _ => panic!("Only primitive descriptors have box classes"),
+        };
+
+        let class_name = JvmString::new(context.gc_ctx, String::from(class_name));
+
+        context.bootstrap_loader().lookup_class(context, class_name)
+    }
 }
 
 impl Trace for Descriptor {
@@ -591,9 +613,10 @@ pub(crate) enum MethodDescParseError {
 /// one `ResolvedDescriptor` for the return type.
 ///
 /// See [`MethodDescriptor`] for the not-yet-resolved version of this struct.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct ResolvedMethodDescriptor(Gc<ResolvedMethodDescriptorData>);
 
+#[derive(Debug)]
 struct ResolvedMethodDescriptorData {
     args: Box<[ResolvedDescriptor]>,
     physical_arg_count: u8,
@@ -601,7 +624,7 @@ struct ResolvedMethodDescriptorData {
 }
 
 impl ResolvedMethodDescriptor {
-    pub(crate) fn from_method_descriptor(
+    pub fn from_method_descriptor(
         context: &Context,
         loader: ClassLoader,
         descriptor: MethodDescriptor,
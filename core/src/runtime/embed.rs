@@ -0,0 +1,804 @@
+// A higher-level API for embedders to call Java methods and access fields by
+// name, converting arguments and results between Rust and Java types.
+//
+// Arguments are converted to Java values before anything else happens, and are
+// kept on the frame stack while classes are resolved, since resolving a class
+// can run Java code (and so trigger a GC). Results are converted without
+// running Java code. As with `Context::exec_method`, objects returned to Rust
+// aren't kept alive once Java code runs again.
+
+use super::class::Class;
+use super::context::{Context, THROWABLE_CAUSE_FIELD};
+use super::descriptor::{Descriptor, MethodDescriptor, ResolvedDescriptor};
+use super::error::Error;
+use super::loader::ClassLoader;
+use super::method::Method;
+use super::object::Object;
+use super::value::Value;
+
+use crate::classfile::flags::MethodFlags;
+use crate::string::JvmString;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::any::type_name;
+
+/// A Rust value that can be passed to Java code.
+///
+/// This is implemented for the primitive types (`bool`, `i8`, `u16` for
+/// `char`, `i16`, `i32`, `i64`, `f32` and `f64`), for [`Object`] and
+/// `Option<Object>`, for `&str` and `String`, and for `Vec`s of any of these,
+/// which become Java arrays.
+pub trait IntoJava {
+    /// Convert this value to a Java value. This returns the type of the value,
+    /// or `None` for `null`, which can be passed as any reference type.
+    ///
+    /// This may allocate objects, but must never run Java code.
+    fn into_java(self, context: &Context) -> (Value, Option<ResolvedDescriptor>);
+
+    /// The Java type of every value converted from this type, if it's always
+    /// the same. This is used as the element type of arrays created from
+    /// `Vec`s, including empty ones.
+    fn java_type(_context: &Context) -> Option<ResolvedDescriptor>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// A Rust value that can be created from a value returned by Java code.
+///
+/// This is implemented for `()` (for `void` methods), the primitive types,
+/// [`Object`] and `Option<Object>`, `String` and `Option<String>`, and `Vec`s
+/// of any of these, which are created from Java arrays.
+pub trait FromJava: Sized {
+    /// Convert `value`, of the Java type `ty`, to this type. `value` is `None`
+    /// only when `ty` is `void`.
+    ///
+    /// This must never run Java code.
+    fn from_java(
+        context: &Context,
+        value: Option<Value>,
+        ty: ResolvedDescriptor,
+    ) -> Result<Self, Error>;
+}
+
+/// A list of arguments for a Java method. This is implemented for tuples of up
+/// to eight [`IntoJava`] values; use `()` for methods without arguments and
+/// `(value,)` for methods with a single argument.
+pub trait IntoJavaArgs {
+    /// Convert each argument to a Java value, along with its type.
+    fn into_java_args(self, context: &Context) -> Vec<(Value, Option<ResolvedDescriptor>)>;
+}
+
+macro_rules! impl_into_java_args {
+    ($($name:ident),*) => {
+        impl<$($name: IntoJava),*> IntoJavaArgs for ($($name,)*) {
+            #[allow(non_snake_case, unused_variables)]
+            fn into_java_args(self, context: &Context) -> Vec<(Value, Option<ResolvedDescriptor>)> {
+                let ($($name,)*) = self;
+
+                vec![$($name.into_java(context)),*]
+            }
+        }
+    };
+}
+
+impl_into_java_args!();
+impl_into_java_args!(A);
+impl_into_java_args!(A, B);
+impl_into_java_args!(A, B, C);
+impl_into_java_args!(A, B, C, D);
+impl_into_java_args!(A, B, C, D, E);
+impl_into_java_args!(A, B, C, D, E, F);
+impl_into_java_args!(A, B, C, D, E, F, G);
+impl_into_java_args!(A, B, C, D, E, F, G, H);
+
+macro_rules! impl_primitive {
+    ($rust_type:ty, $descriptor:ident, $to_value:expr, $from_value:expr) => {
+        impl IntoJava for $rust_type {
+            fn into_java(self, _context: &Context) -> (Value, Option<ResolvedDescriptor>) {
+                ($to_value(self), Some(ResolvedDescriptor::$descriptor))
+            }
+
+            fn java_type(_context: &Context) -> Option<ResolvedDescriptor> {
+                Some(ResolvedDescriptor::$descriptor)
+            }
+        }
+
+        impl FromJava for $rust_type {
+            fn from_java(
+                context: &Context,
+                value: Option<Value>,
+                ty: ResolvedDescriptor,
+            ) -> Result<Self, Error> {
+                match value {
+                    Some(value) if ty == ResolvedDescriptor::$descriptor => Ok($from_value(value)),
+                    _ => Err(conversion_error::<Self>(context, ty)),
+                }
+            }
+        }
+    };
+}
+
+impl_primitive!(
+    bool,
+    Boolean,
+    |v: bool| Value::Integer(v as i32),
+    |v: Value| v.int() != 0
+);
+impl_primitive!(
+    i8,
+    Byte,
+    |v: i8| Value::Integer(v as i32),
+    |v: Value| v.int() as i8
+);
+impl_primitive!(
+    u16,
+    Character,
+    |v: u16| Value::Integer(v as i32),
+    |v: Value| v.int() as u16
+);
+impl_primitive!(
+    i16,
+    Short,
+    |v: i16| Value::Integer(v as i32),
+    |v: Value| v.int() as i16
+);
+impl_primitive!(i32, Integer, Value::Integer, |v: Value| v.int());
+impl_primitive!(i64, Long, Value::Long, |v: Value| v.long());
+impl_primitive!(f32, Float, Value::Float, |v: Value| v.float());
+impl_primitive!(f64, Double, Value::Double, |v: Value| v.double());
+
+impl IntoJava for Option<Object> {
+    fn into_java(self, _context: &Context) -> (Value, Option<ResolvedDescriptor>) {
+        (Value::Object(self), self.map(type_of_object))
+    }
+}
+
+impl IntoJava for Object {
+    fn into_java(self, context: &Context) -> (Value, Option<ResolvedDescriptor>) {
+        Some(self).into_java(context)
+    }
+}
+
+impl IntoJava for &str {
+    fn into_java(self, context: &Context) -> (Value, Option<ResolvedDescriptor>) {
+        let chars = self.encode_utf16().collect::<Vec<_>>();
+
+        Some(context.create_string(&chars)).into_java(context)
+    }
+
+    fn java_type(context: &Context) -> Option<ResolvedDescriptor> {
+        Some(ResolvedDescriptor::Class(
+            context.builtins().java_lang_string,
+        ))
+    }
+}
+
+impl IntoJava for String {
+    fn into_java(self, context: &Context) -> (Value, Option<ResolvedDescriptor>) {
+        self.as_str().into_java(context)
+    }
+
+    fn java_type(context: &Context) -> Option<ResolvedDescriptor> {
+        <&str>::java_type(context)
+    }
+}
+
+impl<T: IntoJava> IntoJava for Vec<T> {
+    fn into_java(self, context: &Context) -> (Value, Option<ResolvedDescriptor>) {
+        let values = self
+            .into_iter()
+            .map(|element| element.into_java(context))
+            .collect::<Vec<_>>();
+
+        // Arrays of objects are created with the most specific class shared
+        // by their elements, or `Object` if they have none
+        let element_type = T::java_type(context)
+            .unwrap_or_else(|| ResolvedDescriptor::Class(common_class(context, &values)));
+
+        let array = match element_type {
+            ResolvedDescriptor::Boolean | ResolvedDescriptor::Byte => {
+                let data = values.iter().map(|(v, _)| v.int() as i8).collect();
+                if element_type == ResolvedDescriptor::Boolean {
+                    Object::bool_array(context, data)
+                } else {
+                    Object::byte_array(context, data)
+                }
+            }
+            ResolvedDescriptor::Character => {
+                let data = values.iter().map(|(v, _)| v.int() as u16).collect();
+                Object::char_array(context, data)
+            }
+            ResolvedDescriptor::Short => {
+                let data = values.iter().map(|(v, _)| v.int() as i16).collect();
+                Object::short_array(context, data)
+            }
+            ResolvedDescriptor::Integer => {
+                let data = values.iter().map(|(v, _)| v.int()).collect();
+                Object::int_array(context, data)
+            }
+            ResolvedDescriptor::Long => {
+                let data = values.iter().map(|(v, _)| v.long()).collect();
+                Object::long_array(context, data)
+            }
+            ResolvedDescriptor::Float => {
+                let data = values.iter().map(|(v, _)| v.float()).collect();
+                Object::float_array(context, data)
+            }
+            ResolvedDescriptor::Double => {
+                let data = values.iter().map(|(v, _)| v.double()).collect();
+                Object::double_array(context, data)
+            }
+            ResolvedDescriptor::Class(class) | ResolvedDescriptor::Array(class) => {
+                let data = values.iter().map(|(v, _)| v.object()).collect();
+                Object::obj_array(context, class, data)
+            }
+            ResolvedDescriptor::Void => unreachable!("Values can't be void"),
+        };
+
+        Some(array).into_java(context)
+    }
+
+    fn java_type(context: &Context) -> Option<ResolvedDescriptor> {
+        let element_type = T::java_type(context)?;
+
+        Some(ResolvedDescriptor::Array(ClassLoader::array_class_for(
+            context,
+            element_type,
+        )))
+    }
+}
+
+impl FromJava for () {
+    fn from_java(
+        context: &Context,
+        value: Option<Value>,
+        ty: ResolvedDescriptor,
+    ) -> Result<Self, Error> {
+        match value {
+            None => Ok(()),
+            Some(_) => Err(conversion_error::<Self>(context, ty)),
+        }
+    }
+}
+
+impl FromJava for Option<Object> {
+    fn from_java(
+        context: &Context,
+        value: Option<Value>,
+        ty: ResolvedDescriptor,
+    ) -> Result<Self, Error> {
+        match value {
+            Some(value) if ty.class().is_some() => Ok(value.object()),
+            _ => Err(conversion_error::<Self>(context, ty)),
+        }
+    }
+}
+
+impl FromJava for Object {
+    fn from_java(
+        context: &Context,
+        value: Option<Value>,
+        ty: ResolvedDescriptor,
+    ) -> Result<Self, Error> {
+        Option::<Object>::from_java(context, value, ty)?
+            .ok_or_else(|| context.null_pointer_exception())
+    }
+}
+
+impl FromJava for Option<String> {
+    fn from_java(
+        context: &Context,
+        value: Option<Value>,
+        ty: ResolvedDescriptor,
+    ) -> Result<Self, Error> {
+        let Some(object) = Option::<Object>::from_java(context, value, ty)? else {
+            return Ok(None);
+        };
+
+        if object.class() != context.builtins().java_lang_string {
+            return Err(conversion_error::<Self>(context, type_of_object(object)));
+        }
+
+        Ok(Some(Context::string_object_to_string(object)))
+    }
+}
+
+impl FromJava for String {
+    fn from_java(
+        context: &Context,
+        value: Option<Value>,
+        ty: ResolvedDescriptor,
+    ) -> Result<Self, Error> {
+        Option::<String>::from_java(context, value, ty)?
+            .ok_or_else(|| context.null_pointer_exception())
+    }
+}
+
+impl<T: FromJava> FromJava for Vec<T> {
+    fn from_java(
+        context: &Context,
+        value: Option<Value>,
+        ty: ResolvedDescriptor,
+    ) -> Result<Self, Error> {
+        let array = Object::from_java(context, value, ty)?;
+
+        // The elements are converted based on the class of the array itself,
+        // which may be more specific than `ty`
+        let Some(element_type) = array.class().array_value_type() else {
+            return Err(conversion_error::<Self>(context, type_of_object(array)));
+        };
+
+        let data = array.array_data();
+        let values = match element_type {
+            ResolvedDescriptor::Boolean | ResolvedDescriptor::Byte => data
+                .as_byte_array()
+                .iter()
+                .map(|e| Value::Integer(e.get() as i32))
+                .collect::<Vec<_>>(),
+            ResolvedDescriptor::Character => data
+                .as_char_array()
+                .iter()
+                .map(|e| Value::Integer(e.get() as i32))
+                .collect(),
+            ResolvedDescriptor::Short => data
+                .as_short_array()
+                .iter()
+                .map(|e| Value::Integer(e.get() as i32))
+                .collect(),
+            ResolvedDescriptor::Integer => data
+                .as_int_array()
+                .iter()
+                .map(|e| Value::Integer(e.get()))
+                .collect(),
+            ResolvedDescriptor::Long => data
+                .as_long_array()
+                .iter()
+                .map(|e| Value::Long(e.get()))
+                .collect(),
+            ResolvedDescriptor::Float => data
+                .as_float_array()
+                .iter()
+                .map(|e| Value::Float(e.get()))
+                .collect(),
+            ResolvedDescriptor::Double => data
+                .as_double_array()
+                .iter()
+                .map(|e| Value::Double(e.get()))
+                .collect(),
+            ResolvedDescriptor::Class(_) | ResolvedDescriptor::Array(_) => data
+                .as_object_array()
+                .iter()
+                .map(|e| Value::Object(e.get()))
+                .collect(),
+            ResolvedDescriptor::Void => unreachable!("Arrays can't be of void"),
+        };
+
+        values
+            .into_iter()
+            .map(|value| T::from_java(context, Some(value), element_type))
+            .collect()
+    }
+}
+
+impl Context {
+    /// Call the static method with the given name and descriptor on the class
+    /// named `class_name`, which is looked up with the system loader. For
+    /// example:
+    ///
+    /// ```ignore
+    /// let result: i64 = context.call_static(
+    ///     "com/acme/Foo",
+    ///     "bar",
+    ///     "(ILjava/lang/String;)J",
+    ///     (5, "five"),
+    /// )?;
+    /// ```
+    ///
+    /// If the method throws, or the arguments or return type don't match its
+    /// descriptor, this returns the thrown error.
+    pub fn call_static<R: FromJava>(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        descriptor: &str,
+        args: impl IntoJavaArgs,
+    ) -> Result<R, Error> {
+        let args = args.into_java_args(self);
+
+        let class = with_rooted(self, &args, || {
+            let class_name = JvmString::new(self.gc_ctx(), class_name.to_string());
+
+            self.system_loader().lookup_class(self, class_name)
+        })?;
+
+        class.call_static_with(self, method_name, descriptor, args)
+    }
+
+    /// Create an instance of the `Throwable` class named `class_name`, which
+    /// is looked up with the bootstrap loader, by calling its `(String)`
+    /// constructor with `message`. This is intended to be returned as the
+    /// error of a native method. For example:
+    ///
+    /// ```ignore
+    /// return Err(context.throw_new("java/io/FileNotFoundException", &path));
+    /// ```
+    ///
+    /// If the class can't be loaded, isn't a `Throwable`, or can't be
+    /// instantiated, or if its constructor throws, this returns the error
+    /// thrown while creating it instead.
+    pub fn throw_new(&self, class_name: &str, message: &str) -> Error {
+        self.create_throwable(class_name, message)
+            .unwrap_or_else(|error| error)
+    }
+
+    /// Like [`Context::throw_new`], but also set the cause of the created
+    /// throwable to `cause`, which must be a `Throwable`.
+    pub fn throw_with_cause(&self, class_name: &str, message: &str, cause: Object) -> Error {
+        let cause_value = [(Value::Object(Some(cause)), None)];
+
+        // The constructor can run a GC, so the cause must be kept alive
+        match with_rooted(self, &cause_value, || {
+            self.create_throwable(class_name, message)
+        }) {
+            Ok(Error(throwable)) => {
+                throwable.set_field(THROWABLE_CAUSE_FIELD, Value::Object(Some(cause)));
+
+                Error(throwable)
+            }
+            Err(error) => error,
+        }
+    }
+
+    fn create_throwable(&self, class_name: &str, message: &str) -> Result<Error, Error> {
+        let class_name = JvmString::new(self.gc_ctx(), class_name.to_string());
+        let class = self.bootstrap_loader().lookup_class(self, class_name)?;
+
+        if !class.check_cast(self.builtins().java_lang_throwable) {
+            return Err(self.class_cast_exception());
+        }
+
+        let throwable = class.new_object(self, "(Ljava/lang/String;)V", (message,))?;
+
+        Ok(Error(throwable))
+    }
+}
+
+impl Class {
+    /// Call the static method of this class with the given name and
+    /// descriptor. See [`Context::call_static`].
+    pub fn call_static<R: FromJava>(
+        self,
+        context: &Context,
+        method_name: &str,
+        descriptor: &str,
+        args: impl IntoJavaArgs,
+    ) -> Result<R, Error> {
+        let args = args.into_java_args(context);
+
+        self.call_static_with(context, method_name, descriptor, args)
+    }
+
+    fn call_static_with<R: FromJava>(
+        self,
+        context: &Context,
+        method_name: &str,
+        descriptor: &str,
+        args: Vec<(Value, Option<ResolvedDescriptor>)>,
+    ) -> Result<R, Error> {
+        let (name, method_descriptor) = parse_member(context, method_name, descriptor)?;
+
+        let method = self
+            .static_method_vtable()
+            .lookup((name, method_descriptor))
+            .map(|slot| self.get_static_method(slot))
+            .ok_or_else(|| no_such_method(context, self, method_name, descriptor))?;
+
+        invoke(context, method, None, args)
+    }
+
+    /// Create a new instance of this class, calling the constructor with the
+    /// given descriptor. For example:
+    ///
+    /// ```ignore
+    /// let list = class.new_object(context, "(I)V", (16,))?;
+    /// ```
+    pub fn new_object(
+        self,
+        context: &Context,
+        descriptor: &str,
+        args: impl IntoJavaArgs,
+    ) -> Result<Object, Error> {
+        let args = args.into_java_args(context);
+
+        if self.cant_instantiate() {
+            return Err(context.instantiation_exception());
+        }
+
+        let (name, method_descriptor) = parse_member(context, "<init>", descriptor)?;
+
+        let method_vtable = self.instance_method_vtable();
+        let constructor = method_vtable
+            .lookup((name, method_descriptor))
+            .map(|slot| method_vtable.get_element(slot))
+            .filter(|method| method.class() == self)
+            .ok_or_else(|| no_such_method(context, self, "<init>", descriptor))?;
+
+        // The class must be initialized before the instance is created
+        with_rooted(context, &args, || self.run_clinit(context))?;
+
+        let instance = self.new_instance(context.gc_ctx());
+
+        let () = invoke(context, constructor, Some(instance), args)?;
+
+        Ok(instance)
+    }
+}
+
+impl Object {
+    /// Call the instance method of this object with the given name and
+    /// descriptor. The method is looked up from the class of this object, so
+    /// overriding methods are called. For example:
+    ///
+    /// ```ignore
+    /// let length: i32 = string.call_virtual(context, "length", "()I", ())?;
+    /// ```
+    pub fn call_virtual<R: FromJava>(
+        self,
+        context: &Context,
+        method_name: &str,
+        descriptor: &str,
+        args: impl IntoJavaArgs,
+    ) -> Result<R, Error> {
+        let args = args.into_java_args(context);
+
+        let class = self.class();
+        let (name, method_descriptor) = parse_member(context, method_name, descriptor)?;
+
+        let method_vtable = class.instance_method_vtable();
+        let method = method_vtable
+            .lookup((name, method_descriptor))
+            .map(|slot| method_vtable.get_element(slot))
+            .ok_or_else(|| no_such_method(context, class, method_name, descriptor))?;
+
+        invoke(context, method, Some(self), args)
+    }
+
+    /// Read the instance field of this object named `field_name`. If the
+    /// class of this object and one of its superclasses both declare a field
+    /// with that name, the field of the subclass is read.
+    pub fn get_field_by_name<R: FromJava>(
+        self,
+        context: &Context,
+        field_name: &str,
+    ) -> Result<R, Error> {
+        let (slot, field_type) = self.resolve_field(context, field_name, &[])?;
+
+        R::from_java(context, Some(self.get_field(slot)), field_type)
+    }
+
+    /// Write `value` to the instance field of this object named `field_name`.
+    /// See [`Object::get_field_by_name`].
+    pub fn set_field_by_name(
+        self,
+        context: &Context,
+        field_name: &str,
+        value: impl IntoJava,
+    ) -> Result<(), Error> {
+        let value = value.into_java(context);
+
+        let (slot, field_type) = self.resolve_field(context, field_name, &[value])?;
+        check_type(context, value, field_type)?;
+
+        self.set_field(slot, value.0);
+
+        Ok(())
+    }
+
+    // Find the slot and type of the field named `field_name`, keeping `values`
+    // alive while its type is resolved.
+    fn resolve_field(
+        self,
+        context: &Context,
+        field_name: &str,
+        values: &[(Value, Option<ResolvedDescriptor>)],
+    ) -> Result<(u32, ResolvedDescriptor), Error> {
+        let class = self.class();
+        if class.array_value_type().is_some() {
+            return Err(context.no_such_field_error());
+        }
+
+        // Fields of subclasses come after those of their superclasses
+        let (slot, field) = class
+            .instance_fields()
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, field)| &*field.name() == field_name)
+            .ok_or_else(|| context.no_such_field_error())?;
+
+        let loader = field.defining_class().loader();
+
+        let mut rooted = values.to_vec();
+        rooted.push((Value::Object(Some(self)), None));
+
+        let field_type = with_rooted(context, &rooted, || {
+            resolve_type(context, loader, field.descriptor())
+        })?;
+
+        Ok((slot as u32, field_type))
+    }
+}
+
+// Check the arguments against the descriptor of `method`, then call it with
+// `receiver`, if it's an instance method, and convert the result.
+fn invoke<R: FromJava>(
+    context: &Context,
+    method: Method,
+    receiver: Option<Object>,
+    args: Vec<(Value, Option<ResolvedDescriptor>)>,
+) -> Result<R, Error> {
+    if method.flags().contains(MethodFlags::ABSTRACT) {
+        return Err(context.incompatible_class_change_error(&format!(
+            "{}.{}{} is abstract",
+            method.class().name(),
+            method.name(),
+            method.descriptor()
+        )));
+    }
+
+    let mut rooted = args.clone();
+    if let Some(receiver) = receiver {
+        rooted.push((Value::Object(Some(receiver)), None));
+    }
+
+    let resolved = with_rooted(context, &rooted, || {
+        method.get_or_init_resolved_descriptor(context)
+    })?;
+
+    if args.len() != resolved.args().len() {
+        return Err(context.illegal_argument_exception(&format!(
+            "Method {}.{} takes {} arguments, but {} were passed",
+            method.class().dot_name(),
+            method.name(),
+            resolved.args().len(),
+            args.len()
+        )));
+    }
+
+    let mut values = Vec::with_capacity(method.physical_arg_count() as usize);
+    if let Some(receiver) = receiver {
+        values.push(Value::Object(Some(receiver)));
+    }
+
+    for (arg, arg_type) in args.into_iter().zip(resolved.args()) {
+        check_type(context, arg, *arg_type)?;
+
+        values.push(arg.0);
+
+        // Wide values take up two slots
+        if matches!(
+            arg_type,
+            ResolvedDescriptor::Long | ResolvedDescriptor::Double
+        ) {
+            values.push(Value::Integer(0));
+        }
+    }
+
+    let result = context.exec_method(method, &values)?;
+
+    R::from_java(context, result, resolved.return_type())
+}
+
+// Keep `values` alive while running `f`, which may run Java code.
+pub(crate) fn with_rooted<T>(
+    context: &Context,
+    values: &[(Value, Option<ResolvedDescriptor>)],
+    f: impl FnOnce() -> T,
+) -> T {
+    let frame_index = context.frame_index();
+    let frame_data = context.frame_data();
+
+    let initial_index = frame_index.get();
+    for (i, (value, _)) in values.iter().enumerate() {
+        frame_data[initial_index + i].set(*value);
+    }
+    frame_index.set(initial_index + values.len());
+
+    let result = f();
+
+    frame_index.set(initial_index);
+
+    result
+}
+
+// Check that a value converted by `IntoJava` can be passed as `expected`.
+fn check_type(
+    context: &Context,
+    (_, actual): (Value, Option<ResolvedDescriptor>),
+    expected: ResolvedDescriptor,
+) -> Result<(), Error> {
+    let matches = match (actual, expected.class()) {
+        // `null` can be passed as any reference type
+        (None, expected_class) => expected_class.is_some(),
+        (Some(actual), Some(expected_class)) => actual
+            .class()
+            .is_some_and(|actual_class| actual_class.check_cast(expected_class)),
+        (Some(actual), None) => actual == expected,
+    };
+
+    if matches {
+        Ok(())
+    } else {
+        let actual = actual.map_or("null".to_string(), |ty| ty.to_string());
+
+        Err(context.illegal_argument_exception(&format!(
+            "Cannot pass value of type {} as {}",
+            actual, expected
+        )))
+    }
+}
+
+fn type_of_object(object: Object) -> ResolvedDescriptor {
+    let class = object.class();
+    if class.array_value_type().is_some() {
+        ResolvedDescriptor::Array(class)
+    } else {
+        ResolvedDescriptor::Class(class)
+    }
+}
+
+// The most specific class shared by every non-null element of `values`.
+fn common_class(context: &Context, values: &[(Value, Option<ResolvedDescriptor>)]) -> Class {
+    let mut classes = values
+        .iter()
+        .filter_map(|(_, ty)| ty.and_then(|ty| ty.class()));
+
+    let Some(mut common) = classes.next() else {
+        return context.object_class();
+    };
+
+    for class in classes {
+        while !class.check_cast(common) {
+            common = common.super_class().unwrap_or(context.object_class());
+        }
+    }
+
+    common
+}
+
+fn conversion_error<T>(context: &Context, ty: ResolvedDescriptor) -> Error {
+    context.illegal_argument_exception(&format!(
+        "Cannot convert value of type {} to {}",
+        ty,
+        type_name::<T>()
+    ))
+}
+
+fn parse_member(
+    context: &Context,
+    name: &str,
+    descriptor: &str,
+) -> Result<(JvmString, MethodDescriptor), Error> {
+    let name = JvmString::new(context.gc_ctx(), name.to_string());
+    let descriptor = JvmString::new(context.gc_ctx(), descriptor.to_string());
+
+    Ok((name, MethodDescriptor::from_string(context, descriptor)?))
+}
+
+fn no_such_method(context: &Context, class: Class, name: &str, descriptor: &str) -> Error {
+    context.no_such_method_error(&format!("{}.{}{}", class.dot_name(), name, descriptor))
+}
+
+fn resolve_type(
+    context: &Context,
+    loader: Option<ClassLoader>,
+    descriptor: Descriptor,
+) -> Result<ResolvedDescriptor, Error> {
+    let loader = loader.unwrap_or(context.bootstrap_loader());
+
+    ResolvedDescriptor::from_descriptor(context, loader, descriptor)
+}
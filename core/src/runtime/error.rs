@@ -1,11 +1,20 @@
+use super::class::Class;
 use super::context::Context;
-use super::context::{OBJECT_TO_STRING_METHOD, THROWABLE_CAUSE_FIELD, THROWABLE_STACK_TRACE_FIELD};
+use super::context::{
+    STACK_TRACE_ELEMENT_DECL_CLASS_FIELD, STACK_TRACE_ELEMENT_FILE_FIELD,
+    STACK_TRACE_ELEMENT_IS_NATIVE_FIELD, STACK_TRACE_ELEMENT_LINE_FIELD,
+    STACK_TRACE_ELEMENT_METHOD_FIELD, THROWABLE_CAUSE_FIELD, THROWABLE_MESSAGE_FIELD,
+    THROWABLE_STACK_TRACE_FIELD, THROWABLE_SUPPRESSED_FIELD,
+};
+use super::embed::with_rooted;
 use super::object::Object;
 use super::value::Value;
 
 use crate::classfile::error::Error as ClassFileError;
+use crate::string::JvmString;
 
-use alloc::string::String;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 use core::fmt;
 
@@ -28,6 +37,8 @@ impl Error {
             ClassFileError::ConstantPoolVerifyError => "Constant pool failed verification",
             ClassFileError::EndOfFile => "Truncated class file",
             ClassFileError::ExpectedNonZero => "Illegal zero constant pool index",
+            ClassFileError::InvalidAttributeLength => "Attribute length does not match contents",
+            ClassFileError::InvalidElementValueTag => "Illegal annotation element value tag",
             ClassFileError::InvalidMagic => "Invalid magic value",
             ClassFileError::InvalidString => "Illegal UTF8 string",
         };
@@ -39,6 +50,8 @@ impl Error {
             ClassFileError::ConstantPoolVerifyError => Context::verify_error,
             ClassFileError::EndOfFile => Context::class_format_error,
             ClassFileError::ExpectedNonZero => Context::verify_error,
+            ClassFileError::InvalidAttributeLength => Context::class_format_error,
+            ClassFileError::InvalidElementValueTag => Context::class_format_error,
             ClassFileError::InvalidMagic => Context::class_format_error,
             ClassFileError::InvalidString => Context::class_format_error,
         };
@@ -53,105 +66,187 @@ impl Error {
         self.0.class().dot_name()
     }
 
-    /// Formats this `Error` as a string. This will include the `Error`'s
-    /// message, cause, and stack trace information.
+    /// Returns the detail message of this thrown error, if it has one. This
+    /// reads the message directly from the error object and doesn't run any
+    /// Java code, so overrides of `Throwable.getMessage` are not respected.
+    pub fn message(&self) -> Option<String> {
+        let message = self.0.get_field(THROWABLE_MESSAGE_FIELD).object()?;
+
+        Some(Context::string_object_to_string(message))
+    }
+
+    /// Formats this `Error` as a string, as printed by
+    /// `Throwable.printStackTrace`. This will include the `Error`'s message,
+    /// stack trace, suppressed exceptions and cause.
     pub fn display(&self, context: &Context) -> String {
-        let mut result_string = String::new();
+        let error_object = self.0;
+        let rooted = [(Value::Object(Some(error_object)), None)];
+
+        let result: Result<Vec<u8>, Error> = with_rooted(context, &rooted, || {
+            let output = lookup_class(context, "java/io/ByteArrayOutputStream")?.new_object(
+                context,
+                "()V",
+                (),
+            )?;
+            let print_stream = lookup_class(context, "java/io/PrintStream")?.new_object(
+                context,
+                "(Ljava/io/OutputStream;)V",
+                (output,),
+            )?;
+
+            // The print stream keeps the output stream alive
+            let () = error_object.call_virtual(
+                context,
+                "printStackTrace",
+                "(Ljava/io/PrintStream;)V",
+                (print_stream,),
+            )?;
+
+            let bytes: Vec<i8> = output.call_virtual(context, "toByteArray", "()[B", ())?;
+
+            Ok(bytes.into_iter().map(|b| b as u8).collect::<Vec<_>>())
+        });
+
+        match result {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) => format!("(error while displaying error): {}", e.display_infallible()),
+        }
+    }
 
+    /// Returns structured information about this `Error`: its class, message,
+    /// stack trace, cause and suppressed exceptions. This is intended for
+    /// embedders that want to present errors themselves rather than display
+    /// them as [`Error::display`] does.
+    ///
+    /// The message is retrieved by calling `Throwable.getLocalizedMessage`,
+    /// falling back to the detail message if that throws. A cause or
+    /// suppressed exception that already appears in the returned
+    /// `ThrowableInfo` (a circular reference) is omitted.
+    pub fn info(&self, context: &Context) -> ThrowableInfo {
         let error_object = self.0;
+        let rooted = [(Value::Object(Some(error_object)), None)];
 
-        // TODO we should just be calling `Throwable.printStackTrace(PrintStream)`
-        // instead of duplicating all the logic
-
-        // Call `toString` on the error object
-        let to_string_method = error_object
-            .class()
-            .instance_method_vtable()
-            .get_element(OBJECT_TO_STRING_METHOD);
-
-        let args = &[Value::Object(Some(error_object))];
-        let result = context.exec_method(to_string_method, args);
-        let value = match result {
-            Ok(value) => value.unwrap(),
-            Err(e) => {
-                return format!("(error while displaying error): {}", e.display_infallible());
-            }
-        };
+        // Everything else reachable from the error is kept alive by it
+        with_rooted(context, &rooted, || {
+            throwable_info(context, error_object, &mut Vec::new())
+        })
+    }
+}
 
-        let string_obj = value.object();
+/// Structured information about a Java `Throwable`, as returned by
+/// [`Error::info`].
+#[derive(Clone, Debug)]
+pub struct ThrowableInfo {
+    /// The name of the class of the throwable, such as
+    /// `java.lang.NullPointerException`.
+    pub class_name: String,
 
-        if let Some(string_obj) = string_obj {
-            result_string.push_str(&Context::string_object_to_string(string_obj));
-        } else {
-            result_string.push_str("null");
-        }
+    /// The message of the throwable, if it has one.
+    pub message: Option<String>,
 
-        result_string.push('\n');
-
-        // Get cause now- the stack trace code can cause a GC, which would
-        // result in the error object being collected, which we don't want to
-        // happen before we retrieve the cause
-        let cause = error_object.get_field(THROWABLE_CAUSE_FIELD).object();
-        // If cause is equal to the error, there is no cause
-        let cause_info = cause
-            .filter(|cause| !cause.ptr_eq(error_object))
-            .map(|cause| {
-                let mut result = String::new();
-                result.push_str("Caused by: ");
-
-                let cause_error = Error(cause);
-                result.push_str(&cause_error.display(context));
-
-                result
-            });
-
-        // Now write the stack trace, if it exists. (TODO: Shouldn't it
-        // always exist?)
-        // This is a little complicated because we need to interact with
-        // Java code a lot.
-
-        let stack_trace = error_object.get_field(THROWABLE_STACK_TRACE_FIELD).object();
-        if let Some(stack_trace) = stack_trace {
-            let mut result = Vec::new();
-
-            let stack_trace = stack_trace.array_data().as_object_array();
-
-            // Now we have the array of stack trace elements.
-            for stack_trace_element in stack_trace {
-                let stack_trace_element = stack_trace_element.get().unwrap();
-
-                let to_string_method = stack_trace_element
-                    .class()
-                    .instance_method_vtable()
-                    .get_element(OBJECT_TO_STRING_METHOD);
-
-                let args = &[Value::Object(Some(stack_trace_element))];
-                // We know exactly what `StackTraceElement.toString` does
-                let stringified = context
-                    .exec_method(to_string_method, args)
-                    .unwrap()
-                    .unwrap()
-                    .object()
-                    .unwrap();
-                let stringified = Context::unwrap_string(stringified);
-
-                // "\tat "
-                result.push(b'\t' as u16);
-                result.push(b'a' as u16);
-                result.push(b't' as u16);
-                result.push(b' ' as u16);
-
-                result.extend_from_slice(&stringified);
-
-                result.push(b'\n' as u16);
-            }
-            result_string.push_str(&String::from_utf16_lossy(&result));
-        }
+    /// The frames of the throwable's stack trace, starting with the most
+    /// recent call.
+    pub frames: Vec<StackFrameInfo>,
+
+    /// The cause of the throwable, if it has one.
+    pub cause: Option<Box<ThrowableInfo>>,
+
+    /// The exceptions that were suppressed in order to throw this one, such
+    /// as by a `try`-with-resources statement.
+    pub suppressed: Vec<ThrowableInfo>,
+}
+
+/// A single frame of the stack trace of a [`ThrowableInfo`].
+#[derive(Clone, Debug)]
+pub struct StackFrameInfo {
+    /// The name of the class declaring the method, such as `java.lang.Object`.
+    pub class_name: String,
+
+    /// The name of the method.
+    pub method_name: String,
 
-        if let Some(cause_info) = cause_info {
-            result_string.push_str(&cause_info);
+    /// The name of the source file of the method, if known.
+    pub file_name: Option<String>,
+
+    /// The line number in the source file, if known.
+    pub line_number: Option<u32>,
+
+    /// Whether the method is a `native` method.
+    pub is_native: bool,
+}
+
+// Collect the information about `throwable`. `seen` is the list of
+// throwables already visited, which are omitted if they're encountered again.
+fn throwable_info(context: &Context, throwable: Object, seen: &mut Vec<Object>) -> ThrowableInfo {
+    seen.push(throwable);
+
+    let message = throwable
+        .call_virtual(context, "getLocalizedMessage", "()Ljava/lang/String;", ())
+        .unwrap_or_else(|_| Error(throwable).message());
+
+    let frames = object_array(throwable.get_field(THROWABLE_STACK_TRACE_FIELD))
+        .into_iter()
+        .map(stack_frame_info)
+        .collect();
+
+    let mut suppressed = Vec::new();
+    for exception in object_array(throwable.get_field(THROWABLE_SUPPRESSED_FIELD)) {
+        if !seen.iter().any(|s| s.ptr_eq(exception)) {
+            suppressed.push(throwable_info(context, exception, seen));
         }
+    }
 
-        result_string
+    // If cause is equal to the throwable, there is no cause
+    let cause = throwable
+        .get_field(THROWABLE_CAUSE_FIELD)
+        .object()
+        .filter(|cause| !seen.iter().any(|s| s.ptr_eq(*cause)))
+        .map(|cause| Box::new(throwable_info(context, cause, seen)));
+
+    ThrowableInfo {
+        class_name: throwable.class().dot_name(),
+        message,
+        frames,
+        cause,
+        suppressed,
     }
 }
+
+fn stack_frame_info(element: Object) -> StackFrameInfo {
+    let string_field = |field| {
+        element
+            .get_field(field)
+            .object()
+            .map(Context::string_object_to_string)
+    };
+
+    let line_number = element.get_field(STACK_TRACE_ELEMENT_LINE_FIELD).int();
+
+    StackFrameInfo {
+        class_name: string_field(STACK_TRACE_ELEMENT_DECL_CLASS_FIELD).unwrap_or_default(),
+        method_name: string_field(STACK_TRACE_ELEMENT_METHOD_FIELD).unwrap_or_default(),
+        file_name: string_field(STACK_TRACE_ELEMENT_FILE_FIELD),
+        line_number: u32::try_from(line_number).ok(),
+        is_native: element.get_field(STACK_TRACE_ELEMENT_IS_NATIVE_FIELD).int() != 0,
+    }
+}
+
+// The non-null elements of the object array `value`, which may be null.
+fn object_array(value: Value) -> Vec<Object> {
+    let Some(array) = value.object() else {
+        return Vec::new();
+    };
+
+    array
+        .array_data()
+        .as_object_array()
+        .iter()
+        .filter_map(|element| element.get())
+        .collect()
+}
+
+fn lookup_class(context: &Context, class_name: &str) -> Result<Class, Error> {
+    let class_name = JvmString::new(context.gc_ctx(), class_name.to_string());
+
+    context.bootstrap_loader().lookup_class(context, class_name)
+}
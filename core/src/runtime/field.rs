@@ -3,15 +3,14 @@ use super::context::Context;
 use super::descriptor::{Descriptor, ResolvedDescriptor};
 use super::error::Error;
 use super::object::Object;
-use super::read_macros::read_u16_be;
 use super::value::Value;
 
+use crate::classfile::attribute::AttributeInfo;
 use crate::classfile::class::ClassFile;
 use crate::classfile::constant_pool::ConstantPoolEntry;
 use crate::classfile::field::Field as ClassFileField;
 use crate::classfile::flags::FieldFlags;
 use crate::gc::{Gc, Trace};
-use crate::reader::{FileData, Reader};
 use crate::string::JvmString;
 
 use alloc::vec::Vec;
@@ -111,11 +110,20 @@ fn field_constant_value(
     class_file: ClassFile,
     field: &ClassFileField,
 ) -> Result<Option<Value>, Error> {
+    let constant_pool = class_file.constant_pool();
+
     for attribute in field.attributes() {
-        if attribute.name().as_bytes() == b"ConstantValue" {
-            let mut data = FileData::new(attribute.data());
-            let cpool_index = read_u16_be!(context, data);
-            let constant_pool = class_file.constant_pool();
+        // Other attributes aren't needed here, and must be ignored if they're
+        // malformed
+        if &*attribute.name() != "ConstantValue" {
+            continue;
+        }
+
+        let attribute = attribute
+            .parse(constant_pool)
+            .map_err(|e| Error::from_class_file_error(context, e))?;
+
+        if let AttributeInfo::ConstantValue(cpool_index) = attribute {
             let cpool_entry = constant_pool
                 .entry(cpool_index)
                 .map_err(|e| Error::from_class_file_error(context, e))?;
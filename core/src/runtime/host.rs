@@ -0,0 +1,157 @@
+// Implementing Java interfaces with Rust code. For each interface, a class is
+// synthesized whose abstract methods are all implemented by `host_method`,
+// which calls the `HostCallback` stored in the receiver object.
+
+use super::class::Class;
+use super::context::Context;
+use super::error::Error;
+use super::method::Method;
+use super::object::Object;
+use super::value::Value;
+
+use crate::gc::Trace;
+use crate::string::JvmString;
+
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::ToString;
+
+/// The Rust implementation of the methods of an object created by
+/// [`Context::implement_interface`].
+pub(crate) trait HostCallback: Trace {
+    fn call(
+        &self,
+        context: &Context,
+        method: Method,
+        args: &[Value],
+    ) -> Result<Option<Value>, Error>;
+}
+
+// A closure along with the values it captures that must be kept alive.
+struct ClosureCallback<T, F> {
+    captures: T,
+    callback: F,
+}
+
+impl<T, F> HostCallback for ClosureCallback<T, F>
+where
+    T: Trace,
+    F: Fn(&Context, &T, Method, &[Value]) -> Result<Option<Value>, Error>,
+{
+    fn call(
+        &self,
+        context: &Context,
+        method: Method,
+        args: &[Value],
+    ) -> Result<Option<Value>, Error> {
+        (self.callback)(context, &self.captures, method, args)
+    }
+}
+
+impl<T: Trace, F> Trace for ClosureCallback<T, F> {
+    fn trace(&self) {
+        self.captures.trace();
+    }
+}
+
+impl Trace for Rc<dyn HostCallback> {
+    fn trace(&self) {
+        (**self).trace();
+    }
+}
+
+impl Context {
+    /// Create an object implementing the interface named `interface_name`,
+    /// which is looked up with the system loader, whose abstract methods are
+    /// all implemented by the Rust closure `callback`. Default methods and the
+    /// methods of `java.lang.Object` are inherited as usual. For example:
+    ///
+    /// ```ignore
+    /// let comparator = context.implement_interface(
+    ///     "java/util/Comparator",
+    ///     (),
+    ///     |context, _captures, _method, args| {
+    ///         let a: i32 = args[0].object().unwrap().call_virtual(context, "hashCode", "()I", ())?;
+    ///         let b: i32 = args[1].object().unwrap().call_virtual(context, "hashCode", "()I", ())?;
+    ///
+    ///         Ok(Some(Value::Integer(i32::cmp(&a, &b) as i32)))
+    ///     },
+    /// )?;
+    /// ```
+    ///
+    /// `callback` is called with the method that was called, which can be
+    /// used to tell the methods of the interface apart, and its arguments,
+    /// not including the receiver. As for native methods, `long` and `double`
+    /// arguments take up two slots, and `callback` must return a value of the
+    /// method's return type, or `None` for `void` methods.
+    ///
+    /// The garbage collector can't see the values captured by a Rust closure,
+    /// so any objects used by `callback` must be passed as `captures` instead,
+    /// which is kept alive as long as the created object is; `callback`
+    /// receives a reference to it.
+    pub fn implement_interface<T, F>(
+        &self,
+        interface_name: &str,
+        captures: T,
+        callback: F,
+    ) -> Result<Object, Error>
+    where
+        T: Trace + 'static,
+        F: Fn(&Context, &T, Method, &[Value]) -> Result<Option<Value>, Error> + 'static,
+    {
+        let callback: Rc<dyn HostCallback> = Rc::new(ClosureCallback { captures, callback });
+
+        // Keep the captures alive while classes are loaded and initialized
+        let holder = Object::host_object(self.gc_ctx(), self.object_class(), callback.clone());
+        let rooted = [(Value::Object(Some(holder)), None)];
+
+        let class = super::embed::with_rooted(self, &rooted, || {
+            let interface_name = JvmString::new(self.gc_ctx(), interface_name.to_string());
+            let interface = self.system_loader().lookup_class(self, interface_name)?;
+
+            let class = host_class_for(self, interface)?;
+            class.run_clinit(self)?;
+
+            Ok(class)
+        })?;
+
+        Ok(Object::host_object(self.gc_ctx(), class, callback))
+    }
+}
+
+// Returns the class used to implement the given interface with a Rust
+// callback, creating it if it doesn't exist yet.
+fn host_class_for(context: &Context, interface: Class) -> Result<Class, Error> {
+    let loader = interface.loader().expect("Interface should have loader");
+
+    let impl_name = format!("{}$$HostImpl", interface.name());
+    let impl_name = JvmString::new(context.gc_ctx(), impl_name);
+
+    if let Some(impl_class) = loader.find_loaded_class(impl_name) {
+        return Ok(impl_class);
+    }
+
+    let impl_class = Class::for_interface_impl(
+        context,
+        loader,
+        impl_name,
+        context.object_class(),
+        &[interface],
+        host_method,
+    )?;
+    loader.define_class(context, impl_class)?;
+
+    Ok(impl_class)
+}
+
+// The native implementation of every abstract method of a host class.
+fn host_method(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let receiver = args[0].object().unwrap();
+
+    let callback = receiver
+        .host_callback()
+        .expect("Instances of host classes are created with a callback");
+
+    callback.call(context, context.current_method(), &args[1..])
+}
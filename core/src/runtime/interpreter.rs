@@ -1,11 +1,16 @@
 use super::class::Class;
+use super::constant;
 use super::context::Context;
-use super::descriptor::{MethodDescriptor, ResolvedDescriptor};
+use super::descriptor::{
+    Descriptor, MethodDescriptor, ResolvedDescriptor, ResolvedMethodDescriptor,
+};
 use super::error::Error;
 use super::loader::ClassLoader;
 use super::method::{Exception, Method};
+use super::method_handle;
 use super::object::Object;
 use super::op::{ArrayType, Op};
+use super::string_concat::{self, ConcatPart, StringConcatInfo};
 use super::value::Value;
 
 use crate::classfile::constant_pool::ConstantPoolEntry;
@@ -146,6 +151,7 @@ impl<'a> Interpreter<'a> {
                 Op::FConst(val) => self.op_f_const(*val),
                 Op::DConst(val) => self.op_d_const(*val),
                 Op::Ldc(entry) => self.op_ldc(**entry),
+                Op::LdcResolved(index, descriptor) => self.op_ldc_resolved(*index, **descriptor),
                 Op::LoadLong(long) => self.op_load_long(*long),
                 Op::LoadDouble(long) => self.op_load_double(*long),
                 Op::ILoad(index) => self.op_i_load(*index),
@@ -299,6 +305,17 @@ impl<'a> Interpreter<'a> {
                     invoke_interface.name,
                     invoke_interface.descriptor,
                 ),
+                Op::InvokePolymorphic(invoke_polymorphic) => self.op_invoke_polymorphic(
+                    invoke_polymorphic.exact,
+                    invoke_polymorphic.descriptor,
+                    invoke_polymorphic.resolved,
+                ),
+                Op::InvokeDynamic(invoke_dynamic) => self.op_invoke_dynamic(
+                    invoke_dynamic.index,
+                    invoke_dynamic.descriptor,
+                    invoke_dynamic.resolved,
+                ),
+                Op::StringConcat(string_concat) => self.op_string_concat(string_concat),
                 Op::New(class) => self.op_new(*class),
                 Op::NewArray(array_type) => self.op_new_array(*array_type),
                 Op::ANewArray(class) => self.op_a_new_array(*class),
@@ -375,47 +392,25 @@ impl<'a> Interpreter<'a> {
     }
 
     fn op_ldc(&mut self, cpool_entry: ConstantPoolEntry) -> Result<ControlFlow, Error> {
-        let class = self.method.class();
-
-        let loader = class
-            .loader()
-            .expect("Bytecode method running from class with loader");
-
-        let class_file = class
-            .class_file()
-            .expect("Bytecode method running from class with classfile");
-        let constant_pool = class_file.constant_pool();
-
-        let result = match cpool_entry {
-            ConstantPoolEntry::String { string_idx } => {
-                let string = constant_pool
-                    .get_utf8(string_idx)
-                    .expect("Should refer to valid entry");
+        let result = constant::load_constant(self.context, self.method.class(), cpool_entry)?;
 
-                let string_chars = string.encode_utf16().collect::<Vec<_>>();
-
-                let string_obj = self.context.create_string(&string_chars);
-
-                // All string literals are interned
-                let string_obj = self.context.intern_string_obj(string_obj);
-
-                Value::Object(Some(string_obj))
-            }
-            ConstantPoolEntry::Integer { value } => Value::Integer(value),
-            ConstantPoolEntry::Float { value } => Value::Float(value),
-            ConstantPoolEntry::Class { name_idx } => {
-                let class_name = constant_pool
-                    .get_utf8(name_idx)
-                    .expect("Should refer to valid entry");
+        self.stack_push(result);
 
-                let class = loader.lookup_class(self.context, class_name)?;
+        Ok(ControlFlow::Continue)
+    }
 
-                Value::Object(Some(class.get_or_init_object(self.context)))
-            }
-            _ => unimplemented!(),
-        };
+    fn op_ldc_resolved(
+        &mut self,
+        index: u16,
+        descriptor: Descriptor,
+    ) -> Result<ControlFlow, Error> {
+        let result = constant::resolve_constant(self.context, self.method.class(), index)?;
 
-        self.stack_push(result);
+        if descriptor.is_wide() {
+            self.stack_push_wide(result);
+        } else {
+            self.stack_push(result);
+        }
 
         Ok(ControlFlow::Continue)
     }
@@ -930,7 +925,7 @@ impl<'a> Interpreter<'a> {
         let int1 = self.stack_pop().int();
         let int2 = self.stack_pop().int();
 
-        self.stack_push(Value::Integer(int1 + int2));
+        self.stack_push(Value::Integer(int1.wrapping_add(int2)));
 
         Ok(ControlFlow::Continue)
     }
@@ -939,7 +934,7 @@ impl<'a> Interpreter<'a> {
         let int1 = self.stack_pop_wide().long();
         let int2 = self.stack_pop_wide().long();
 
-        self.stack_push_wide(Value::Long(int1 + int2));
+        self.stack_push_wide(Value::Long(int1.wrapping_add(int2)));
 
         Ok(ControlFlow::Continue)
     }
@@ -966,7 +961,7 @@ impl<'a> Interpreter<'a> {
         let int1 = self.stack_pop().int();
         let int2 = self.stack_pop().int();
 
-        self.stack_push(Value::Integer(int2 - int1));
+        self.stack_push(Value::Integer(int2.wrapping_sub(int1)));
 
         Ok(ControlFlow::Continue)
     }
@@ -975,7 +970,7 @@ impl<'a> Interpreter<'a> {
         let int1 = self.stack_pop_wide().long();
         let int2 = self.stack_pop_wide().long();
 
-        self.stack_push_wide(Value::Long(int2 - int1));
+        self.stack_push_wide(Value::Long(int2.wrapping_sub(int1)));
 
         Ok(ControlFlow::Continue)
     }
@@ -1002,7 +997,7 @@ impl<'a> Interpreter<'a> {
         let int1 = self.stack_pop().int();
         let int2 = self.stack_pop().int();
 
-        self.stack_push(Value::Integer(int1 * int2));
+        self.stack_push(Value::Integer(int1.wrapping_mul(int2)));
 
         Ok(ControlFlow::Continue)
     }
@@ -1011,7 +1006,7 @@ impl<'a> Interpreter<'a> {
         let int1 = self.stack_pop_wide().long();
         let int2 = self.stack_pop_wide().long();
 
-        self.stack_push_wide(Value::Long(int1 * int2));
+        self.stack_push_wide(Value::Long(int1.wrapping_mul(int2)));
 
         Ok(ControlFlow::Continue)
     }
@@ -1041,7 +1036,7 @@ impl<'a> Interpreter<'a> {
         if int1 == 0 {
             Err(self.context.arithmetic_exception())
         } else {
-            self.stack_push(Value::Integer(int2 / int1));
+            self.stack_push(Value::Integer(int2.wrapping_div(int1)));
 
             Ok(ControlFlow::Continue)
         }
@@ -1054,7 +1049,7 @@ impl<'a> Interpreter<'a> {
         if long1 == 0 {
             Err(self.context.arithmetic_exception())
         } else {
-            self.stack_push_wide(Value::Long(long2 / long1));
+            self.stack_push_wide(Value::Long(long2.wrapping_div(long1)));
 
             Ok(ControlFlow::Continue)
         }
@@ -1085,7 +1080,7 @@ impl<'a> Interpreter<'a> {
         if int1 == 0 {
             Err(self.context.arithmetic_exception())
         } else {
-            self.stack_push(Value::Integer(int2 % int1));
+            self.stack_push(Value::Integer(int2.wrapping_rem(int1)));
 
             Ok(ControlFlow::Continue)
         }
@@ -1098,7 +1093,7 @@ impl<'a> Interpreter<'a> {
         if long1 == 0 {
             Err(self.context.arithmetic_exception())
         } else {
-            self.stack_push_wide(Value::Long(long2 % long1));
+            self.stack_push_wide(Value::Long(long2.wrapping_rem(long1)));
 
             Ok(ControlFlow::Continue)
         }
@@ -1125,7 +1120,7 @@ impl<'a> Interpreter<'a> {
     fn op_i_neg(&mut self) -> Result<ControlFlow, Error> {
         let int = self.stack_pop().int();
 
-        self.stack_push(Value::Integer(-int));
+        self.stack_push(Value::Integer(int.wrapping_neg()));
 
         Ok(ControlFlow::Continue)
     }
@@ -1133,7 +1128,7 @@ impl<'a> Interpreter<'a> {
     fn op_l_neg(&mut self) -> Result<ControlFlow, Error> {
         let long = self.stack_pop_wide().long();
 
-        self.stack_push_wide(Value::Long(-long));
+        self.stack_push_wide(Value::Long(long.wrapping_neg()));
 
         Ok(ControlFlow::Continue)
     }
@@ -1265,7 +1260,7 @@ impl<'a> Interpreter<'a> {
     fn op_i_inc(&mut self, index: usize, amount: i32) -> Result<ControlFlow, Error> {
         let loaded = self.local_reg(index).int();
 
-        self.set_local_reg(index, Value::Integer(loaded + amount));
+        self.set_local_reg(index, Value::Integer(loaded.wrapping_add(amount)));
 
         Ok(ControlFlow::Continue)
     }
@@ -2061,6 +2056,122 @@ impl<'a> Interpreter<'a> {
         }
     }
 
+    fn op_invoke_polymorphic(
+        &mut self,
+        exact: bool,
+        descriptor: MethodDescriptor,
+        resolved: ResolvedMethodDescriptor,
+    ) -> Result<ControlFlow, Error> {
+        let receiver = self
+            .stack_peek(descriptor.physical_arg_count() as usize)
+            .object();
+
+        if receiver.is_none() {
+            return Err(self.context.null_pointer_exception());
+        }
+
+        // This can run Java code, so it needs to happen while the arguments
+        // are still on the stack
+        method_handle::init_invoke_classes(self.context, descriptor)?;
+
+        let mut args = Vec::with_capacity(descriptor.args().len() + 1);
+        for arg in descriptor.args().iter().rev() {
+            if arg.is_wide() {
+                args.push(self.stack_pop_wide());
+            } else {
+                args.push(self.stack_pop());
+            }
+        }
+
+        // Receiver
+        args.push(self.stack_pop());
+        args.reverse();
+
+        let result =
+            method_handle::invoke_polymorphic(self.context, exact, descriptor, resolved, &args)?;
+
+        if let Some(result) = result {
+            if descriptor.return_type().is_wide() {
+                self.stack_push_wide(result);
+            } else {
+                self.stack_push(result);
+            }
+        }
+
+        Ok(ControlFlow::Continue)
+    }
+
+    fn op_invoke_dynamic(
+        &mut self,
+        index: u16,
+        descriptor: MethodDescriptor,
+        resolved: ResolvedMethodDescriptor,
+    ) -> Result<ControlFlow, Error> {
+        // Linking the call site and initializing classes can run Java code, so
+        // this needs to happen while the arguments are still on the stack. The
+        // target is kept alive by the cache of the current class.
+        let target = constant::resolve_call_site(self.context, self.method.class(), index)?;
+        method_handle::init_invoke_classes(self.context, descriptor)?;
+
+        let mut args = Vec::with_capacity(descriptor.args().len() + 1);
+        for arg in descriptor.args().iter().rev() {
+            if arg.is_wide() {
+                args.push(self.stack_pop_wide());
+            } else {
+                args.push(self.stack_pop());
+            }
+        }
+
+        // The target is invoked like a handle at an `invokeExact` call site
+        args.push(Value::Object(Some(target)));
+        args.reverse();
+
+        let result =
+            method_handle::invoke_polymorphic(self.context, true, descriptor, resolved, &args)?;
+
+        if let Some(result) = result {
+            if descriptor.return_type().is_wide() {
+                self.stack_push_wide(result);
+            } else {
+                self.stack_push(result);
+            }
+        }
+
+        Ok(ControlFlow::Continue)
+    }
+
+    fn op_string_concat(&mut self, string_concat: &StringConcatInfo) -> Result<ControlFlow, Error> {
+        // Linking the call site can run Java code, so this needs to happen
+        // while the arguments are still on the stack
+        let parts = string_concat.parts(self.context, self.method.class())?;
+
+        // This does an allocation; we should increment the gc counter
+        self.context.increment_gc_counter();
+
+        // Converting objects to strings can run Java code, so the arguments
+        // stay on the stack until the result is built
+        let mut chars = Vec::new();
+        for part in parts {
+            match part {
+                ConcatPart::Literal(literal) => chars.extend_from_slice(literal),
+                ConcatPart::Argument(descriptor, depth) => {
+                    let value = self.stack_peek(*depth);
+
+                    string_concat::append_value(self.context, &mut chars, value, *descriptor)?;
+                }
+            }
+        }
+
+        for _ in 0..string_concat.descriptor.physical_arg_count() {
+            self.stack_pop();
+        }
+
+        let result = self.context.create_string(&chars);
+        self.stack_push(Value::Object(Some(result)));
+
+        Ok(ControlFlow::Continue)
+    }
+
     fn op_new(&mut self, class: Class) -> Result<ControlFlow, Error> {
         // This does an allocation; we should increment the gc counter
         self.context.increment_gc_counter();
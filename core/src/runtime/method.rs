@@ -4,6 +4,7 @@ use super::descriptor::{MethodDescriptor, ResolvedMethodDescriptor};
 use super::error::Error;
 use super::interpreter::Interpreter;
 use super::loader::ClassLoader;
+use super::native::NativeHandler;
 use super::object::Object;
 use super::op::Op;
 use super::read_macros::read_u16_be;
@@ -18,6 +19,7 @@ use crate::reader::{FileData, Reader};
 use crate::string::JvmString;
 
 use alloc::boxed::Box;
+use alloc::string::ToString;
 use alloc::vec::Vec;
 use core::cell::{OnceCell, RefCell};
 use core::fmt;
@@ -87,6 +89,12 @@ impl Method {
             let native_method = context.get_native_method(class.name(), method.name(), descriptor);
 
             if let Some(native_method) = native_method {
+                let is_static = method.flags().contains(MethodFlags::STATIC);
+                let method_name = format!("{}.{}{}", class.name(), method.name(), descriptor);
+                if let Err(error) = native_method.check_static(method_name, is_static) {
+                    return Err(context.linkage_error(&error.to_string()));
+                }
+
                 MethodInfo::Native(native_method)
             } else {
                 // We don't want to panic right away, as the method might not
@@ -146,11 +154,16 @@ impl Method {
                 class,
                 object: OnceCell::new(),
                 resolved_descriptor: OnceCell::new(),
-                method_info: RefCell::new(MethodInfo::Native(method)),
+                method_info: RefCell::new(MethodInfo::Native(NativeHandler::untyped(method))),
             },
         ))
     }
 
+    /// Replace the implementation of this `native` method with `method`.
+    pub(crate) fn set_native(self, method: NativeHandler) {
+        *self.0.method_info.borrow_mut() = MethodInfo::Native(method);
+    }
+
     /// Internal method for executing a method- `pub(crate)` so it's not
     /// accidentally called by user code.
     ///
@@ -187,7 +200,7 @@ impl Method {
                         &frame_data[(current_position - physical_arg_count)..current_position];
                     let args = args_slice.iter().map(|a| a.get()).collect::<Vec<_>>();
 
-                    native_method(context, &args)
+                    native_method.call(context, &args)
                 }
                 MethodInfo::NativeNotFound => {
                     panic!(
@@ -240,23 +253,39 @@ impl Method {
         Ok(())
     }
 
+    /// Parses and verifies the bytecode of this method, resolving the classes
+    /// it references, if this hasn't been done already. This normally happens
+    /// the first time the method is called.
+    ///
+    /// Methods without bytecode (such as native or abstract methods) always
+    /// verify successfully.
+    pub fn verify(self, context: &Context) -> Result<(), Error> {
+        self.parse_info(context)
+    }
+
     /// Returns an instance of `java.lang.reflect.Executable` for this `Method`.
     /// If such an instance has not yet been created, this will create and cache
     /// it.
     pub fn get_or_init_object(self, context: &Context) -> Object {
-        *self.0.object.get_or_init(|| {
-            let id = context.add_executable_object(self);
+        *self.0.object.get_or_init(|| self.create_object(context))
+    }
 
-            let object = if self.0.name.as_bytes() == b"<init>" {
-                Object::constructor_object(context)
-            } else {
-                Object::method_object(context)
-            };
+    /// Creates a new instance of `java.lang.reflect.Executable` for this
+    /// `Method`, separate from the one returned by
+    /// [`Method::get_or_init_object`]. Changes to its accessibility flag don't
+    /// affect any other instance.
+    pub fn create_object(self, context: &Context) -> Object {
+        let id = context.add_executable_object(self);
 
-            object.set_field(0, Value::Integer(id));
+        let object = if self.0.name.as_bytes() == b"<init>" {
+            Object::constructor_object(context)
+        } else {
+            Object::method_object(context)
+        };
 
-            object
-        })
+        object.set_field(0, Value::Integer(id));
+
+        object
     }
 
     /// Returns a [`ResolvedMethodDescriptor`] for this `Method`'s signature. If
@@ -389,7 +418,7 @@ impl Hash for Method {
 enum MethodInfo {
     Bytecode(BytecodeMethodInfo),
     BytecodeUnparsed(Vec<u8>),
-    Native(NativeMethod),
+    Native(NativeHandler),
     NativeNotFound,
     Empty,
 }
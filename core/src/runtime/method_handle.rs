@@ -0,0 +1,172 @@
+// Support for `java.lang.invoke`. Calls to the signature-polymorphic methods
+// of `MethodHandle` box their arguments and pass them to Java code, which
+// adapts them to the type of the handle and unboxes the result.
+
+use super::class::Class;
+use super::context::Context;
+use super::descriptor::{Descriptor, MethodDescriptor, ResolvedMethodDescriptor};
+use super::error::Error;
+use super::method::Method;
+use super::object::Object;
+use super::value::Value;
+
+use crate::classfile::flags::MethodFlags;
+use crate::string::JvmString;
+
+use alloc::boxed::Box;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+/// Whether a method reference to `name` in `class` refers to a signature
+/// polymorphic method. As specified in the JVMS, these are the native varargs
+/// methods of `java.lang.invoke.MethodHandle` that take an `Object[]`.
+pub(crate) fn is_signature_polymorphic(
+    context: &Context,
+    class: Class,
+    name: JvmString,
+) -> Result<bool, Error> {
+    if &*class.name() != "java/lang/invoke/MethodHandle" {
+        return Ok(false);
+    }
+
+    let descriptor = JvmString::new(
+        context.gc_ctx,
+        "([Ljava/lang/Object;)Ljava/lang/Object;".to_string(),
+    );
+    let descriptor = MethodDescriptor::from_string(context, descriptor)?;
+
+    let vtable = class.instance_method_vtable();
+    let Some(slot) = vtable.lookup((name, descriptor)) else {
+        return Ok(false);
+    };
+
+    let flags = vtable.get_element(slot).flags();
+
+    Ok(flags.contains(MethodFlags::NATIVE | MethodFlags::VARARGS))
+}
+
+/// Initialize the classes used by `invoke_polymorphic` to call a handle with
+/// the given call site descriptor. This may run Java code, so it must be
+/// called before the arguments are taken off the stack.
+pub(crate) fn init_invoke_classes(
+    context: &Context,
+    descriptor: MethodDescriptor,
+) -> Result<(), Error> {
+    natives_class(context)?.run_clinit(context)?;
+
+    let return_type = descriptor.return_type();
+    for descriptor in descriptor.args().iter().chain([&return_type]) {
+        if descriptor.is_primitive() && !matches!(descriptor, Descriptor::Void) {
+            descriptor.box_class(context)?.run_clinit(context)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Invoke a method handle from a call site of `invoke` or `invokeExact` with
+/// the given descriptor. `args` are the arguments of the call, starting with
+/// the handle itself, and `init_invoke_classes` must have already been called.
+///
+/// The arguments are no longer on the stack at this point, so no Java code is
+/// run before they're passed to `MethodHandleNatives`, where they're rooted.
+pub(crate) fn invoke_polymorphic(
+    context: &Context,
+    exact: bool,
+    descriptor: MethodDescriptor,
+    resolved: ResolvedMethodDescriptor,
+    args: &[Value],
+) -> Result<Option<Value>, Error> {
+    let natives_class = natives_class(context)?;
+
+    let invoke_method = natives_method(
+        context,
+        natives_class,
+        "invokeFromCallSite",
+        "(Ljava/lang/invoke/MethodHandle;ZLjava/lang/Class;[Ljava/lang/Class;[Ljava/lang/Object;)Ljava/lang/Object;",
+    )?;
+
+    let return_type = resolved.return_type().reflection_class(context);
+    let parameter_types = resolved
+        .args()
+        .iter()
+        .map(|arg| Some(arg.reflection_class(context).get_or_init_object(context)))
+        .collect::<Box<_>>();
+
+    let parameter_types =
+        Object::obj_array(context, context.builtins().java_lang_class, parameter_types);
+
+    // The first argument is the handle being invoked
+    let mut boxed_args = Vec::with_capacity(args.len() - 1);
+    for (value, arg_type) in args[1..].iter().zip(descriptor.args()) {
+        if arg_type.is_primitive() {
+            let boxed = Object::from_class(context.gc_ctx, arg_type.box_class(context)?);
+            boxed.set_field(0, *value);
+
+            boxed_args.push(Some(boxed));
+        } else {
+            boxed_args.push(value.object());
+        }
+    }
+
+    let args_array = Object::obj_array(
+        context,
+        context.object_class(),
+        boxed_args.into_boxed_slice(),
+    );
+
+    let result = context
+        .exec_method(
+            invoke_method,
+            &[
+                args[0],
+                Value::Integer(exact as i32),
+                Value::Object(Some(return_type.get_or_init_object(context))),
+                Value::Object(Some(parameter_types)),
+                Value::Object(Some(args_array)),
+            ],
+        )?
+        .expect("invokeFromCallSite returns a value");
+
+    // The result was converted to the return type of the call site by Java
+    // code, so primitive results are never null
+    match descriptor.return_type() {
+        Descriptor::Void => Ok(None),
+        return_type if return_type.is_primitive() => {
+            let boxed = result.object().unwrap();
+
+            Ok(Some(boxed.get_field(0)))
+        }
+        _ => Ok(Some(result)),
+    }
+}
+
+pub(super) fn natives_class(context: &Context) -> Result<Class, Error> {
+    let natives_name = JvmString::new(
+        context.gc_ctx,
+        "java/lang/invoke/MethodHandleNatives".to_string(),
+    );
+
+    context
+        .bootstrap_loader()
+        .lookup_class(context, natives_name)
+}
+
+// Find a static method of a class that the VM calls into.
+pub(super) fn natives_method(
+    context: &Context,
+    class: Class,
+    name: &str,
+    descriptor: &str,
+) -> Result<Method, Error> {
+    let name = JvmString::new(context.gc_ctx, name.to_string());
+    let descriptor = JvmString::new(context.gc_ctx, descriptor.to_string());
+    let descriptor = MethodDescriptor::from_string(context, descriptor)?;
+
+    let slot = class
+        .static_method_vtable()
+        .lookup((name, descriptor))
+        .expect("VM-called method should exist");
+
+    Ok(class.get_static_method(slot))
+}
@@ -2,17 +2,26 @@ pub(crate) mod array;
 pub(crate) mod builtins;
 pub(crate) mod call_stack;
 pub(crate) mod class;
+pub(crate) mod class_builder;
+pub(crate) mod console;
+pub(crate) mod constant;
 pub(crate) mod context;
 pub(crate) mod descriptor;
+pub(crate) mod embed;
 pub(crate) mod error;
 pub(crate) mod field;
+pub(crate) mod host;
 pub(crate) mod intern;
 pub(crate) mod interpreter;
 pub(crate) mod loader;
 pub(crate) mod method;
+pub(crate) mod method_handle;
+pub(crate) mod native;
 pub(crate) mod object;
 pub(crate) mod op;
 pub(crate) mod read_macros;
+pub(crate) mod security;
+pub(crate) mod string_concat;
 pub(crate) mod value;
 pub(crate) mod verify;
 pub(crate) mod vtable;
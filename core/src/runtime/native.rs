@@ -0,0 +1,524 @@
+// Typed native methods. Rather than taking a slice of untyped `Value`s, a typed
+// native method is a Rust function taking each argument of the Java method as a
+// Rust value. Its signature is checked against the Java descriptor when it's
+// registered, and arguments and the return value are converted automatically.
+
+use super::context::Context;
+use super::descriptor::{Descriptor, MethodDescriptor};
+use super::error::Error;
+use super::method::NativeMethod;
+use super::object::Object;
+use super::value::Value;
+
+use crate::gc::Trace;
+use crate::string::JvmString;
+
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use core::any::type_name;
+use core::fmt;
+
+/// A Rust value that can be taken as an argument by a typed native method.
+///
+/// This is implemented for the primitive types (`bool`, `i8`, `u16` for
+/// `char`, `i16`, `i32`, `i64`, `f32` and `f64`), for `Option<Object>`, and for
+/// [`Object`], which throws a `NullPointerException` instead of calling the
+/// native method when `null` is passed.
+pub trait NativeArg: Sized {
+    /// Whether arguments of the Java type `descriptor` can be converted to
+    /// this type.
+    fn matches(descriptor: Descriptor) -> bool;
+
+    /// Whether arguments of this type take up two slots (`long` and `double`).
+    fn is_wide() -> bool {
+        false
+    }
+
+    /// Convert `value` to this type. `value` is always of a type for which
+    /// [`NativeArg::matches`] returned `true`.
+    fn from_value(context: &Context, value: Value) -> Result<Self, Error>;
+}
+
+/// A Rust value that can be returned by a typed native method.
+///
+/// This is implemented for `()` (for `void` methods), the primitive types,
+/// [`Object`] and `Option<Object>`.
+pub trait NativeReturn {
+    /// Whether values of this type can be returned as the Java type
+    /// `descriptor`.
+    fn matches(descriptor: Descriptor) -> bool;
+
+    /// Convert this value to a Java value, or `None` for `()`.
+    fn into_value(self) -> Option<Value>;
+}
+
+macro_rules! impl_primitive {
+    ($ty:ty, $descriptor:ident, $wide:expr, |$value:ident| $from:expr, |$this:ident| $into:expr) => {
+        impl NativeArg for $ty {
+            fn matches(descriptor: Descriptor) -> bool {
+                matches!(descriptor, Descriptor::$descriptor)
+            }
+
+            fn is_wide() -> bool {
+                $wide
+            }
+
+            fn from_value(_context: &Context, $value: Value) -> Result<Self, Error> {
+                Ok($from)
+            }
+        }
+
+        impl NativeReturn for $ty {
+            fn matches(descriptor: Descriptor) -> bool {
+                matches!(descriptor, Descriptor::$descriptor)
+            }
+
+            fn into_value(self) -> Option<Value> {
+                let $this = self;
+                Some($into)
+            }
+        }
+    };
+}
+
+impl_primitive!(bool, Boolean, false, |v| v.int() != 0, |b| Value::Integer(
+    b as i32
+));
+impl_primitive!(i8, Byte, false, |v| v.int() as i8, |b| Value::Integer(
+    b as i32
+));
+impl_primitive!(u16, Character, false, |v| v.int() as u16, |c| {
+    Value::Integer(c as i32)
+});
+impl_primitive!(i16, Short, false, |v| v.int() as i16, |s| Value::Integer(
+    s as i32
+));
+impl_primitive!(i32, Integer, false, |v| v.int(), |i| Value::Integer(i));
+impl_primitive!(i64, Long, true, |v| v.long(), |l| Value::Long(l));
+impl_primitive!(f32, Float, false, |v| v.float(), |f| Value::Float(f));
+impl_primitive!(f64, Double, true, |v| v.double(), |d| Value::Double(d));
+
+impl NativeArg for Object {
+    fn matches(descriptor: Descriptor) -> bool {
+        matches!(descriptor, Descriptor::Class(_) | Descriptor::Array(_))
+    }
+
+    fn from_value(context: &Context, value: Value) -> Result<Self, Error> {
+        value
+            .object()
+            .ok_or_else(|| context.null_pointer_exception())
+    }
+}
+
+impl NativeReturn for Object {
+    fn matches(descriptor: Descriptor) -> bool {
+        matches!(descriptor, Descriptor::Class(_) | Descriptor::Array(_))
+    }
+
+    fn into_value(self) -> Option<Value> {
+        Some(Value::Object(Some(self)))
+    }
+}
+
+impl NativeArg for Option<Object> {
+    fn matches(descriptor: Descriptor) -> bool {
+        matches!(descriptor, Descriptor::Class(_) | Descriptor::Array(_))
+    }
+
+    fn from_value(_context: &Context, value: Value) -> Result<Self, Error> {
+        Ok(value.object())
+    }
+}
+
+impl NativeReturn for Option<Object> {
+    fn matches(descriptor: Descriptor) -> bool {
+        matches!(descriptor, Descriptor::Class(_) | Descriptor::Array(_))
+    }
+
+    fn into_value(self) -> Option<Value> {
+        Some(Value::Object(self))
+    }
+}
+
+impl NativeReturn for () {
+    fn matches(descriptor: Descriptor) -> bool {
+        matches!(descriptor, Descriptor::Void)
+    }
+
+    fn into_value(self) -> Option<Value> {
+        None
+    }
+}
+
+/// An error encountered while registering a typed native method, because the
+/// signature of the Rust function doesn't match the Java method.
+#[derive(Debug)]
+pub enum NativeRegistrationError {
+    /// The method name wasn't of the form `ClassName.methodName.(descriptor)`.
+    InvalidName(String),
+
+    /// The descriptor of the method couldn't be parsed.
+    InvalidDescriptor(String),
+
+    /// The Rust function takes `found` arguments, but the Java method takes
+    /// `expected` arguments (plus the receiver, for instance methods).
+    ArgumentCount { expected: usize, found: usize },
+
+    /// The method was registered as a static method but is declared as an
+    /// instance method, or the other way around. This can only be detected
+    /// once the class declaring the method is loaded.
+    StaticMismatch {
+        method: String,
+        registered_static: bool,
+    },
+
+    /// The Rust function's argument at `index` (counting the receiver, if any)
+    /// is of type `found`, which can't hold the Java type `expected`.
+    ArgumentType {
+        index: usize,
+        expected: String,
+        found: &'static str,
+    },
+
+    /// The Rust function returns the type `found`, which can't be returned as
+    /// the Java type `expected`.
+    ReturnType {
+        expected: String,
+        found: &'static str,
+    },
+}
+
+impl fmt::Display for NativeRegistrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(f, "Invalid native method name {}", name),
+            Self::InvalidDescriptor(descriptor) => {
+                write!(f, "Invalid method descriptor {}", descriptor)
+            }
+            Self::ArgumentCount { expected, found } => write!(
+                f,
+                "Native function takes {} arguments, but the method takes {}",
+                found, expected
+            ),
+            Self::StaticMismatch {
+                method,
+                registered_static,
+            } => write!(
+                f,
+                "Native method for {} was registered as {}",
+                method,
+                if *registered_static {
+                    "a static method"
+                } else {
+                    "an instance method"
+                }
+            ),
+            Self::ArgumentType {
+                index,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Argument {} of type {} can't be passed to native function as {}",
+                index, expected, found
+            ),
+            Self::ReturnType { expected, found } => write!(
+                f,
+                "Native function returns {}, which can't be returned as {}",
+                found, expected
+            ),
+        }
+    }
+}
+
+/// A Rust function that can be registered as a typed native method with
+/// [`Context::register_static_native`] or
+/// [`Context::register_instance_native`].
+///
+/// This is implemented for functions taking a `&Context` followed by up to
+/// eight [`NativeArg`]s and returning `Result<R, Error>`, where `R` is a
+/// [`NativeReturn`]. For instance methods, the first argument after the
+/// `Context` is the receiver.
+///
+/// Native functions aren't traced by the garbage collector, so they must be
+/// `fn` items or closures that don't capture anything; registering a closure
+/// with captures fails to compile. Values needed by the function can be passed
+/// to it with [`Context::register_static_native_with`] instead, as a
+/// [`CapturingNativeFunction`].
+pub trait NativeFunction<Args, R>: 'static {
+    /// Check the signature of this function against the Java method of class
+    /// `class_name` with the given descriptor, which takes a receiver before
+    /// its arguments unless `is_static` is set.
+    fn check_signature(
+        class_name: JvmString,
+        descriptor: MethodDescriptor,
+        is_static: bool,
+    ) -> Result<(), NativeRegistrationError>;
+
+    /// Decode `args` and call this function, converting its result.
+    fn call(&self, context: &Context, args: &[Value]) -> Result<Option<Value>, Error>;
+}
+
+/// A Rust function that can be registered as a typed native method with
+/// [`Context::register_static_native_with`] or
+/// [`Context::register_instance_native_with`], which is given a reference to
+/// values of type `T` after the `Context`.
+///
+/// This is implemented for the same functions as [`NativeFunction`], with the
+/// extra `&T` argument. The values are traced by the garbage collector along
+/// with the native method, so they may hold objects.
+pub trait CapturingNativeFunction<T, Args, R>: 'static {
+    /// Check the signature of this function, as for
+    /// [`NativeFunction::check_signature`].
+    fn check_signature(
+        class_name: JvmString,
+        descriptor: MethodDescriptor,
+        is_static: bool,
+    ) -> Result<(), NativeRegistrationError>;
+
+    /// Decode `args` and call this function with `captures`, converting its
+    /// result.
+    fn call(&self, context: &Context, captures: &T, args: &[Value])
+    -> Result<Option<Value>, Error>;
+}
+
+// Native functions are stored without being traced, so they can't capture
+// anything that might be a GC pointer. Fails to compile for any function type
+// that isn't zero-sized, which rules out closures with captures.
+fn assert_no_captures<F>() {
+    const {
+        assert!(
+            core::mem::size_of::<F>() == 0,
+            "Native functions can't capture values; pass them to `register_*_native_with` instead"
+        )
+    }
+}
+
+// The check for a single argument or return type: whether the Rust type can
+// hold the Java type, and the name of the Rust type.
+type TypeCheck = (fn(Descriptor) -> bool, &'static str);
+
+fn check_signature(
+    class_name: JvmString,
+    descriptor: MethodDescriptor,
+    is_static: bool,
+    args: &[TypeCheck],
+    return_type: TypeCheck,
+) -> Result<(), NativeRegistrationError> {
+    let java_args = descriptor.args();
+
+    // Instance methods take the receiver before the other arguments
+    let receiver_count = if is_static { 0 } else { 1 };
+    if args.len() != java_args.len() + receiver_count {
+        return Err(NativeRegistrationError::ArgumentCount {
+            expected: java_args.len(),
+            found: args.len(),
+        });
+    }
+
+    if !is_static {
+        let (matches, found) = args[0];
+        let receiver = Descriptor::Class(class_name);
+        if !matches(receiver) {
+            return Err(NativeRegistrationError::ArgumentType {
+                index: 0,
+                expected: receiver.to_string(),
+                found,
+            });
+        }
+    }
+
+    let rust_args = &args[receiver_count..];
+    for (i, ((matches, found), java_arg)) in rust_args.iter().zip(java_args).enumerate() {
+        if !matches(*java_arg) {
+            return Err(NativeRegistrationError::ArgumentType {
+                index: i + receiver_count,
+                expected: java_arg.to_string(),
+                found,
+            });
+        }
+    }
+
+    let (matches, found) = return_type;
+    if !matches(descriptor.return_type()) {
+        return Err(NativeRegistrationError::ReturnType {
+            expected: descriptor.return_type().to_string(),
+            found,
+        });
+    }
+
+    Ok(())
+}
+
+macro_rules! impl_native_function {
+    ($($ty:ident $var:ident),*) => {
+        impl<F, R, $($ty),*> NativeFunction<($($ty,)*), R> for F
+        where
+            F: Fn(&Context, $($ty),*) -> Result<R, Error> + 'static,
+            R: NativeReturn,
+            $($ty: NativeArg),*
+        {
+            fn check_signature(
+                class_name: JvmString,
+                descriptor: MethodDescriptor,
+                is_static: bool,
+            ) -> Result<(), NativeRegistrationError> {
+                assert_no_captures::<F>();
+
+                check_signature(
+                    class_name,
+                    descriptor,
+                    is_static,
+                    &[$(($ty::matches, type_name::<$ty>())),*],
+                    (R::matches, type_name::<R>()),
+                )
+            }
+
+            #[allow(unused_mut, unused_variables, unused_assignments)]
+            fn call(&self, context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+                let mut position = 0;
+                $(
+                    let $var = $ty::from_value(context, args[position])?;
+                    position += if $ty::is_wide() { 2 } else { 1 };
+                )*
+
+                self(context, $($var),*).map(R::into_value)
+            }
+        }
+
+        impl<T, F, R, $($ty),*> CapturingNativeFunction<T, ($($ty,)*), R> for F
+        where
+            F: Fn(&Context, &T, $($ty),*) -> Result<R, Error> + 'static,
+            R: NativeReturn,
+            $($ty: NativeArg),*
+        {
+            fn check_signature(
+                class_name: JvmString,
+                descriptor: MethodDescriptor,
+                is_static: bool,
+            ) -> Result<(), NativeRegistrationError> {
+                assert_no_captures::<F>();
+
+                check_signature(
+                    class_name,
+                    descriptor,
+                    is_static,
+                    &[$(($ty::matches, type_name::<$ty>())),*],
+                    (R::matches, type_name::<R>()),
+                )
+            }
+
+            #[allow(unused_mut, unused_variables, unused_assignments)]
+            fn call(
+                &self,
+                context: &Context,
+                captures: &T,
+                args: &[Value],
+            ) -> Result<Option<Value>, Error> {
+                let mut position = 0;
+                $(
+                    let $var = $ty::from_value(context, args[position])?;
+                    position += if $ty::is_wide() { 2 } else { 1 };
+                )*
+
+                self(context, captures, $($var),*).map(R::into_value)
+            }
+        }
+    };
+}
+
+impl_native_function!();
+impl_native_function!(A a);
+impl_native_function!(A a, B b);
+impl_native_function!(A a, B b, C c);
+impl_native_function!(A a, B b, C c, D d);
+impl_native_function!(A a, B b, C c, D d, E e);
+impl_native_function!(A a, B b, C c, D d, E e, G g);
+impl_native_function!(A a, B b, C c, D d, E e, G g, H h);
+impl_native_function!(A a, B b, C c, D d, E e, G g, H h, I i);
+
+// The signature check of a typed native function, run when it's registered or
+// when the class it's added to is defined.
+pub(crate) type SignatureCheck =
+    fn(JvmString, MethodDescriptor, bool) -> Result<(), NativeRegistrationError>;
+
+type BoxedNativeMethod = dyn Fn(&Context, &[Value]) -> Result<Option<Value>, Error>;
+
+/// A registered native method, either untyped or typed.
+#[derive(Clone)]
+pub(crate) struct NativeHandler {
+    function: Rc<BoxedNativeMethod>,
+
+    // The values passed to a typed native method registered with captures,
+    // which the function itself holds as well.
+    captures: Option<Rc<dyn Trace>>,
+
+    // For typed native methods, whether the Rust function expects the Java
+    // method to be static. This is `None` for untyped native methods.
+    is_static: Option<bool>,
+}
+
+impl NativeHandler {
+    pub(crate) fn untyped(method: NativeMethod) -> Self {
+        Self {
+            function: Rc::new(method),
+            captures: None,
+            is_static: None,
+        }
+    }
+
+    pub(crate) fn typed<Args, R, F: NativeFunction<Args, R>>(function: F, is_static: bool) -> Self {
+        Self {
+            function: Rc::new(move |context, args| function.call(context, args)),
+            captures: None,
+            is_static: Some(is_static),
+        }
+    }
+
+    pub(crate) fn typed_with<T, Args, R, F>(captures: T, function: F, is_static: bool) -> Self
+    where
+        T: Trace + 'static,
+        F: CapturingNativeFunction<T, Args, R>,
+    {
+        let captures = Rc::new(captures);
+        let traced_captures = captures.clone();
+
+        Self {
+            function: Rc::new(move |context, args| function.call(context, &captures, args)),
+            captures: Some(traced_captures),
+            is_static: Some(is_static),
+        }
+    }
+
+    /// Check that this handler can implement `method`, which is or isn't
+    /// static.
+    pub(crate) fn check_static(
+        &self,
+        method: String,
+        is_static: bool,
+    ) -> Result<(), NativeRegistrationError> {
+        match self.is_static {
+            Some(registered_static) if registered_static != is_static => {
+                Err(NativeRegistrationError::StaticMismatch {
+                    method,
+                    registered_static,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn call(&self, context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+        (self.function)(context, args)
+    }
+}
+
+// The functions of native methods can't capture anything, so only the values
+// registered alongside them can hold GC pointers
+impl Trace for NativeHandler {
+    fn trace(&self) {
+        if let Some(captures) = &self.captures {
+            captures.trace();
+        }
+    }
+}
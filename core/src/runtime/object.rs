@@ -3,12 +3,14 @@ use super::class::Class;
 use super::context::Context;
 use super::descriptor::ResolvedDescriptor;
 use super::error::Error;
+use super::host::HostCallback;
 use super::loader::ClassLoader;
 use super::value::Value;
 
 use crate::gc::{Gc, GcCtx, Trace};
 
 use alloc::boxed::Box;
+use alloc::rc::Rc;
 use core::cell::Cell;
 use core::fmt;
 use core::hash::{Hash, Hasher};
@@ -46,6 +48,25 @@ impl Object {
         ))
     }
 
+    /// Allocates an `Object` that is an instance of the passed [`Class`], and
+    /// whose methods are implemented by `callback`. This method does not call
+    /// any `<init>` method.
+    pub(crate) fn host_object(gc_ctx: GcCtx, class: Class, callback: Rc<dyn HostCallback>) -> Self {
+        let fields = class
+            .instance_fields()
+            .iter()
+            .map(|f| Cell::new(f.value()))
+            .collect::<Box<_>>();
+
+        Self(Gc::new(
+            gc_ctx,
+            ObjectData {
+                class,
+                data: FieldOrArrayData::HostFields(fields, callback),
+            },
+        ))
+    }
+
     /// Creates a new instance of `java.lang.Class`. The caller is
     /// responsible for making it a valid `Class` object (see how
     /// `Class::get_or_init_object` does it).
@@ -299,7 +320,7 @@ impl Object {
     /// panic if called on an `Object` that represents an array.
     pub fn get_field(self, field_idx: u32) -> Value {
         match &self.0.data {
-            FieldOrArrayData::Fields(fields) => {
+            FieldOrArrayData::Fields(fields) | FieldOrArrayData::HostFields(fields, _) => {
                 let field = &fields[field_idx as usize];
                 field.get()
             }
@@ -311,7 +332,7 @@ impl Object {
     /// panic if called on an `Object` that represents an array.
     pub fn set_field(self, field_idx: u32, value: Value) {
         match &self.0.data {
-            FieldOrArrayData::Fields(fields) => {
+            FieldOrArrayData::Fields(fields) | FieldOrArrayData::HostFields(fields, _) => {
                 let field = &fields[field_idx as usize];
                 field.set(value);
             }
@@ -323,7 +344,9 @@ impl Object {
     /// called on an `Object` that represents a class instance.
     pub fn array_data(&self) -> &Array {
         match &self.0.data {
-            FieldOrArrayData::Fields(_) => panic!("Expected an array"),
+            FieldOrArrayData::Fields(_) | FieldOrArrayData::HostFields(..) => {
+                panic!("Expected an array")
+            }
             FieldOrArrayData::Array(array) => array,
         }
     }
@@ -332,11 +355,22 @@ impl Object {
     /// panic if called on an `Object` that represents a class instance.
     pub fn array_length(self) -> usize {
         match &self.0.data {
-            FieldOrArrayData::Fields(_) => panic!("Cannot get length of object"),
+            FieldOrArrayData::Fields(_) | FieldOrArrayData::HostFields(..) => {
+                panic!("Cannot get length of object")
+            }
             FieldOrArrayData::Array(array) => array.len(),
         }
     }
 
+    /// The Rust implementation of the methods of this object, if it was
+    /// created by [`Context::implement_interface`].
+    pub(crate) fn host_callback(self) -> Option<Rc<dyn HostCallback>> {
+        match &self.0.data {
+            FieldOrArrayData::HostFields(_, callback) => Some(callback.clone()),
+            _ => None,
+        }
+    }
+
     /// Allocates a new object, as a clone of this object.
     ///
     /// For class instances, this method will create a shallow copy of each of
@@ -401,7 +435,7 @@ impl Trace for ObjectData {
         self.class.trace();
 
         match &self.data {
-            FieldOrArrayData::Fields(data) => {
+            FieldOrArrayData::Fields(data) | FieldOrArrayData::HostFields(data, _) => {
                 // We're not sure here exactly which of the fields are objects,
                 // so we need to retrieve some information to tell.
 
@@ -421,6 +455,10 @@ impl Trace for ObjectData {
             }
             FieldOrArrayData::Array(data) => data.trace(),
         }
+
+        if let FieldOrArrayData::HostFields(_, callback) = &self.data {
+            callback.trace();
+        }
     }
 }
 
@@ -428,6 +466,10 @@ impl Trace for ObjectData {
 enum FieldOrArrayData {
     Fields(Box<[Cell<Value>]>),
     Array(Array),
+
+    // The fields of an object created by `Context::implement_interface`, along
+    // with the Rust implementation of its methods
+    HostFields(Box<[Cell<Value>]>, Rc<dyn HostCallback>),
 }
 
 // "[Ljava/lang/Object;".clone()Ljava/lang/Object;
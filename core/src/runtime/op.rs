@@ -1,18 +1,25 @@
 use super::class::Class;
+use super::constant;
 use super::context::Context;
-use super::descriptor::{Descriptor, MethodDescriptor, ResolvedDescriptor};
+use super::descriptor::{
+    Descriptor, MethodDescriptor, ResolvedDescriptor, ResolvedMethodDescriptor,
+};
 use super::error::Error;
 use super::method::Method;
+use super::method_handle;
 use super::read_macros::{read_u8, read_u16_be, read_u32_be};
+use super::string_concat::{self, ConcatPart, StringConcatInfo};
 
 use crate::classfile::constant_pool::{ConstantPool, ConstantPoolEntry};
-use crate::classfile::flags::FieldFlags;
+use crate::classfile::flags::{FieldFlags, MethodFlags};
+use crate::classfile::opcodes::*;
 use crate::gc::{Gc, Trace};
 use crate::reader::{FileData, Reader};
 use crate::string::JvmString;
 
 use alloc::boxed::Box;
 use alloc::vec::Vec;
+use core::cell::OnceCell;
 use hashbrown::HashMap;
 
 #[derive(Clone, Debug)]
@@ -26,6 +33,10 @@ pub enum Op {
     FConst(f32),
     DConst(f64),
     Ldc(Gc<ConstantPoolEntry>),
+    // A constant that must be resolved by running Java code: a method handle,
+    // a method type, or a dynamic constant. This holds its constant pool index
+    // and the type of its value.
+    LdcResolved(u16, Gc<Descriptor>),
     LoadLong(i64),
     LoadDouble(f64),
 
@@ -185,6 +196,9 @@ pub enum Op {
     InvokeSpecial(Method),
     InvokeStatic(Method),
     InvokeInterface(Box<InvokeInterfaceInfo>),
+    InvokePolymorphic(Box<InvokePolymorphicInfo>),
+    InvokeDynamic(Box<InvokeDynamicInfo>),
+    StringConcat(Box<StringConcatInfo>),
 
     // Memory allocation
     New(Class),
@@ -231,6 +245,9 @@ impl Trace for Op {
             Op::Ldc(entry) => {
                 entry.trace();
             }
+            Op::LdcResolved(_, descriptor) => {
+                descriptor.trace();
+            }
             Op::LoadLong(_) => {}
             Op::LoadDouble(_) => {}
             Op::ILoad(_) => {}
@@ -390,6 +407,22 @@ impl Trace for Op {
                 invoke_interface.name.trace();
                 invoke_interface.descriptor.trace();
             }
+            Op::InvokePolymorphic(invoke_polymorphic) => {
+                invoke_polymorphic.descriptor.trace();
+                invoke_polymorphic.resolved.trace();
+            }
+            Op::InvokeDynamic(invoke_dynamic) => {
+                invoke_dynamic.descriptor.trace();
+                invoke_dynamic.resolved.trace();
+            }
+            Op::StringConcat(string_concat) => {
+                string_concat.descriptor.trace();
+                for part in string_concat.parts.get().into_iter().flatten() {
+                    if let ConcatPart::Argument(descriptor, _) = part {
+                        descriptor.trace();
+                    }
+                }
+            }
             Op::New(class) => {
                 class.trace();
             }
@@ -421,206 +454,6 @@ impl Trace for Op {
     }
 }
 
-const NOP: u8 = 0x00;
-const A_CONST_NULL: u8 = 0x01;
-const I_CONST_M1: u8 = 0x02;
-const I_CONST_0: u8 = 0x03;
-const I_CONST_1: u8 = 0x04;
-const I_CONST_2: u8 = 0x05;
-const I_CONST_3: u8 = 0x06;
-const I_CONST_4: u8 = 0x07;
-const I_CONST_5: u8 = 0x08;
-const L_CONST_0: u8 = 0x09;
-const L_CONST_1: u8 = 0x0A;
-const F_CONST_0: u8 = 0x0B;
-const F_CONST_1: u8 = 0x0C;
-const F_CONST_2: u8 = 0x0D;
-const D_CONST_0: u8 = 0x0E;
-const D_CONST_1: u8 = 0x0F;
-const B_I_PUSH: u8 = 0x10;
-const S_I_PUSH: u8 = 0x11;
-const LDC: u8 = 0x12;
-const LDC_W: u8 = 0x13;
-const LDC_2_W: u8 = 0x14;
-const I_LOAD: u8 = 0x15;
-const L_LOAD: u8 = 0x16;
-const F_LOAD: u8 = 0x17;
-const D_LOAD: u8 = 0x18;
-const A_LOAD: u8 = 0x19;
-const I_LOAD_0: u8 = 0x1A;
-const I_LOAD_1: u8 = 0x1B;
-const I_LOAD_2: u8 = 0x1C;
-const I_LOAD_3: u8 = 0x1D;
-const L_LOAD_0: u8 = 0x1E;
-const L_LOAD_1: u8 = 0x1F;
-const L_LOAD_2: u8 = 0x20;
-const L_LOAD_3: u8 = 0x21;
-const F_LOAD_0: u8 = 0x22;
-const F_LOAD_1: u8 = 0x23;
-const F_LOAD_2: u8 = 0x24;
-const F_LOAD_3: u8 = 0x25;
-const D_LOAD_0: u8 = 0x26;
-const D_LOAD_1: u8 = 0x27;
-const D_LOAD_2: u8 = 0x28;
-const D_LOAD_3: u8 = 0x29;
-const A_LOAD_0: u8 = 0x2A;
-const A_LOAD_1: u8 = 0x2B;
-const A_LOAD_2: u8 = 0x2C;
-const A_LOAD_3: u8 = 0x2D;
-const IA_LOAD: u8 = 0x2E;
-const LA_LOAD: u8 = 0x2F;
-const FA_LOAD: u8 = 0x30;
-const DA_LOAD: u8 = 0x31;
-const AA_LOAD: u8 = 0x32;
-const BA_LOAD: u8 = 0x33;
-const CA_LOAD: u8 = 0x34;
-const SA_LOAD: u8 = 0x35;
-const I_STORE: u8 = 0x36;
-const L_STORE: u8 = 0x37;
-const F_STORE: u8 = 0x38;
-const D_STORE: u8 = 0x39;
-const A_STORE: u8 = 0x3A;
-const I_STORE_0: u8 = 0x3B;
-const I_STORE_1: u8 = 0x3C;
-const I_STORE_2: u8 = 0x3D;
-const I_STORE_3: u8 = 0x3E;
-const L_STORE_0: u8 = 0x3F;
-const L_STORE_1: u8 = 0x40;
-const L_STORE_2: u8 = 0x41;
-const L_STORE_3: u8 = 0x42;
-const F_STORE_0: u8 = 0x43;
-const F_STORE_1: u8 = 0x44;
-const F_STORE_2: u8 = 0x45;
-const F_STORE_3: u8 = 0x46;
-const D_STORE_0: u8 = 0x47;
-const D_STORE_1: u8 = 0x48;
-const D_STORE_2: u8 = 0x49;
-const D_STORE_3: u8 = 0x4A;
-const A_STORE_0: u8 = 0x4B;
-const A_STORE_1: u8 = 0x4C;
-const A_STORE_2: u8 = 0x4D;
-const A_STORE_3: u8 = 0x4E;
-const IA_STORE: u8 = 0x4F;
-const LA_STORE: u8 = 0x50;
-const FA_STORE: u8 = 0x51;
-const DA_STORE: u8 = 0x52;
-const AA_STORE: u8 = 0x53;
-const BA_STORE: u8 = 0x54;
-const CA_STORE: u8 = 0x55;
-const SA_STORE: u8 = 0x56;
-const POP: u8 = 0x57;
-const POP_2: u8 = 0x58;
-const DUP: u8 = 0x59;
-const DUP_X1: u8 = 0x5A;
-const DUP_X2: u8 = 0x5B;
-const DUP_2: u8 = 0x5C;
-const DUP_2_X2: u8 = 0x5E;
-const SWAP: u8 = 0x5F;
-const I_ADD: u8 = 0x60;
-const L_ADD: u8 = 0x61;
-const F_ADD: u8 = 0x62;
-const D_ADD: u8 = 0x63;
-const I_SUB: u8 = 0x64;
-const L_SUB: u8 = 0x65;
-const F_SUB: u8 = 0x66;
-const D_SUB: u8 = 0x67;
-const I_MUL: u8 = 0x68;
-const L_MUL: u8 = 0x69;
-const F_MUL: u8 = 0x6A;
-const D_MUL: u8 = 0x6B;
-const I_DIV: u8 = 0x6C;
-const L_DIV: u8 = 0x6D;
-const F_DIV: u8 = 0x6E;
-const D_DIV: u8 = 0x6F;
-const I_REM: u8 = 0x70;
-const L_REM: u8 = 0x71;
-const F_REM: u8 = 0x72;
-const D_REM: u8 = 0x73;
-const I_NEG: u8 = 0x74;
-const L_NEG: u8 = 0x75;
-const F_NEG: u8 = 0x76;
-const D_NEG: u8 = 0x77;
-const I_SHL: u8 = 0x78;
-const L_SHL: u8 = 0x79;
-const I_SHR: u8 = 0x7A;
-const L_SHR: u8 = 0x7B;
-const I_USHR: u8 = 0x7C;
-const L_USHR: u8 = 0x7D;
-const I_AND: u8 = 0x7E;
-const L_AND: u8 = 0x7F;
-const I_OR: u8 = 0x80;
-const L_OR: u8 = 0x81;
-const I_XOR: u8 = 0x82;
-const L_XOR: u8 = 0x83;
-const I_INC: u8 = 0x84;
-const I2L: u8 = 0x85;
-const I2F: u8 = 0x86;
-const I2D: u8 = 0x87;
-const L2I: u8 = 0x88;
-const L2F: u8 = 0x89;
-const L2D: u8 = 0x8A;
-const F2I: u8 = 0x8B;
-const F2L: u8 = 0x8C;
-const F2D: u8 = 0x8D;
-const D2I: u8 = 0x8E;
-const D2L: u8 = 0x8F;
-const D2F: u8 = 0x90;
-const I2B: u8 = 0x91;
-const I2C: u8 = 0x92;
-const I2S: u8 = 0x93;
-const L_CMP: u8 = 0x94;
-const F_CMP_L: u8 = 0x95;
-const F_CMP_G: u8 = 0x96;
-const D_CMP_L: u8 = 0x97;
-const D_CMP_G: u8 = 0x98;
-const IF_EQ: u8 = 0x99;
-const IF_NE: u8 = 0x9A;
-const IF_LT: u8 = 0x9B;
-const IF_GE: u8 = 0x9C;
-const IF_GT: u8 = 0x9D;
-const IF_LE: u8 = 0x9E;
-const IF_I_CMP_EQ: u8 = 0x9F;
-const IF_I_CMP_NE: u8 = 0xA0;
-const IF_I_CMP_LT: u8 = 0xA1;
-const IF_I_CMP_GE: u8 = 0xA2;
-const IF_I_CMP_GT: u8 = 0xA3;
-const IF_I_CMP_LE: u8 = 0xA4;
-const IF_A_CMP_EQ: u8 = 0xA5;
-const IF_A_CMP_NE: u8 = 0xA6;
-const GOTO: u8 = 0xA7;
-const JSR: u8 = 0xA8;
-const RET: u8 = 0xA9;
-const TABLE_SWITCH: u8 = 0xAA;
-const LOOKUP_SWITCH: u8 = 0xAB;
-const I_RETURN: u8 = 0xAC;
-const L_RETURN: u8 = 0xAD;
-const F_RETURN: u8 = 0xAE;
-const D_RETURN: u8 = 0xAF;
-const A_RETURN: u8 = 0xB0;
-const RETURN: u8 = 0xB1;
-const GET_STATIC: u8 = 0xB2;
-const PUT_STATIC: u8 = 0xB3;
-const GET_FIELD: u8 = 0xB4;
-const PUT_FIELD: u8 = 0xB5;
-const INVOKE_VIRTUAL: u8 = 0xB6;
-const INVOKE_SPECIAL: u8 = 0xB7;
-const INVOKE_STATIC: u8 = 0xB8;
-const INVOKE_INTERFACE: u8 = 0xB9;
-const NEW: u8 = 0xBB;
-const NEW_ARRAY: u8 = 0xBC;
-const A_NEW_ARRAY: u8 = 0xBD;
-const ARRAY_LENGTH: u8 = 0xBE;
-const A_THROW: u8 = 0xBF;
-const CHECK_CAST: u8 = 0xC0;
-const INSTANCE_OF: u8 = 0xC1;
-const MONITOR_ENTER: u8 = 0xC2;
-const MONITOR_EXIT: u8 = 0xC3;
-const MULTI_A_NEW_ARRAY: u8 = 0xC5;
-const IF_NULL: u8 = 0xC6;
-const IF_NON_NULL: u8 = 0xC7;
-const GOTO_W: u8 = 0xC8;
-const JSR_W: u8 = 0xC9;
-
 impl Op {
     pub fn read_ops(
         context: &Context,
@@ -758,32 +591,18 @@ impl Op {
             }
             LDC => {
                 let constant_pool_idx = read_u8!(context, data);
-                let entry = constant_pool
-                    .entry(constant_pool_idx as u16)
-                    .map_err(|e| Error::from_class_file_error(context, e))?;
 
-                Op::Ldc(Gc::new(context.gc_ctx, entry))
+                ldc_op(context, constant_pool, constant_pool_idx as u16, false)?
             }
             LDC_W => {
                 let constant_pool_idx = read_u16_be!(context, data);
-                let entry = constant_pool
-                    .entry(constant_pool_idx)
-                    .map_err(|e| Error::from_class_file_error(context, e))?;
 
-                Op::Ldc(Gc::new(context.gc_ctx, entry))
+                ldc_op(context, constant_pool, constant_pool_idx, false)?
             }
             LDC_2_W => {
                 let constant_pool_idx = read_u16_be!(context, data);
-                let entry = constant_pool
-                    .entry(constant_pool_idx)
-                    .map_err(|e| Error::from_class_file_error(context, e))?;
 
-                match entry {
-                    ConstantPoolEntry::Long { value } => Op::LoadLong(value),
-                    ConstantPoolEntry::Double { value } => Op::LoadDouble(value),
-                    // TODO error handling
-                    _ => panic!("Ldc2 only works on Double and Long"),
-                }
+                ldc_op(context, constant_pool, constant_pool_idx, true)?
             }
             I_LOAD => {
                 let local_idx = read_u8!(context, data);
@@ -1188,7 +1007,9 @@ impl Op {
 
                 // TODO "package-private" and "protected" access control
                 let flags = field.flags();
-                if flags.contains(FieldFlags::PRIVATE) && method.class() != class {
+                if flags.contains(FieldFlags::PRIVATE)
+                    && !method.class().is_nestmate_of(context, class)
+                {
                     return Err(context.illegal_access_error());
                 }
 
@@ -1223,7 +1044,9 @@ impl Op {
 
                 // TODO "package-private" and "protected" access control
                 let flags = field.flags();
-                if flags.contains(FieldFlags::PRIVATE) && method.class() != class {
+                if flags.contains(FieldFlags::PRIVATE)
+                    && !method.class().is_nestmate_of(context, class)
+                {
                     return Err(context.illegal_access_error());
                 }
 
@@ -1256,7 +1079,9 @@ impl Op {
 
                 // TODO "package-private" and "protected" access control
                 let flags = class.get_instance_field(field_slot).flags();
-                if flags.contains(FieldFlags::PRIVATE) && method.class() != class {
+                if flags.contains(FieldFlags::PRIVATE)
+                    && !method.class().is_nestmate_of(context, class)
+                {
                     return Err(context.illegal_access_error());
                 }
 
@@ -1285,7 +1110,9 @@ impl Op {
 
                 // TODO "package-private" and "protected" access control
                 let flags = class.get_instance_field(field_slot).flags();
-                if flags.contains(FieldFlags::PRIVATE) && method.class() != class {
+                if flags.contains(FieldFlags::PRIVATE)
+                    && !method.class().is_nestmate_of(context, class)
+                {
                     return Err(context.illegal_access_error());
                 }
 
@@ -1308,6 +1135,22 @@ impl Op {
 
                 let descriptor = MethodDescriptor::from_string(context, descriptor_name)?;
 
+                // Calls to `MethodHandle.invoke` and `invokeExact` can have any
+                // descriptor, and don't resolve to a method
+                if method_handle::is_signature_polymorphic(context, class, method_name)? {
+                    let resolved = ResolvedMethodDescriptor::from_method_descriptor(
+                        context, loader, descriptor,
+                    )?;
+
+                    let invoke_polymorphic = InvokePolymorphicInfo {
+                        exact: &*method_name == "invokeExact",
+                        descriptor,
+                        resolved,
+                    };
+
+                    return Ok((Op::InvokePolymorphic(Box::new(invoke_polymorphic)), None));
+                }
+
                 let method_index = class
                     .instance_method_vtable()
                     .lookup((method_name, descriptor))
@@ -1316,6 +1159,13 @@ impl Op {
                         context.no_such_method_error(&message)
                     })?;
 
+                // Private methods are never overridden, so calls to them
+                // (which nestmates make with `invokevirtual`) aren't virtual
+                let resolved_method = class.instance_method_vtable().get_element(method_index);
+                if resolved_method.flags().contains(MethodFlags::PRIVATE) {
+                    return Ok((Op::InvokeSpecial(resolved_method), None));
+                }
+
                 // TODO access control?
 
                 let physical_arg_count = descriptor.physical_arg_count();
@@ -1413,6 +1263,16 @@ impl Op {
                 // This should always be zero.
                 let _ = read_u8!(context, data);
 
+                // Private interface methods are called directly, like private
+                // methods of classes
+                let method_vtable = class.instance_method_vtable();
+                if let Some(method_slot) = method_vtable.lookup((method_name, descriptor)) {
+                    let method = method_vtable.get_element(method_slot);
+                    if method.class() == class && method.flags().contains(MethodFlags::PRIVATE) {
+                        return Ok((Op::InvokeSpecial(method), None));
+                    }
+                }
+
                 // TODO access control?
 
                 let invoke_interface = InvokeInterfaceInfo {
@@ -1423,6 +1283,64 @@ impl Op {
 
                 Op::InvokeInterface(Box::new(invoke_interface))
             }
+            INVOKE_DYNAMIC => {
+                let call_site_idx = read_u16_be!(context, data);
+
+                // These should always be zero.
+                let _ = read_u8!(context, data);
+                let _ = read_u8!(context, data);
+
+                let entry = constant_pool
+                    .entry(call_site_idx)
+                    .map_err(|e| Error::from_class_file_error(context, e))?;
+
+                let ConstantPoolEntry::InvokeDynamic {
+                    bootstrap_method_idx,
+                    name_and_type_idx,
+                } = entry
+                else {
+                    return Err(context.verify_error("Expected InvokeDynamic constant"));
+                };
+
+                let (_, descriptor_name) = constant_pool
+                    .get_name_and_type(name_and_type_idx)
+                    .map_err(|e| Error::from_class_file_error(context, e))?;
+
+                let descriptor = MethodDescriptor::from_string(context, descriptor_name)?;
+
+                let class = method.class();
+                let bootstrap_method =
+                    constant::bootstrap_method(context, class, bootstrap_method_idx)?;
+
+                // String concatenation is implemented directly, instead of
+                // calling its bootstrap method. Like other call sites, it's
+                // linked when it's first executed.
+                let concat_bootstrap =
+                    string_concat::concat_bootstrap(context, class, &bootstrap_method)?;
+
+                if let Some(bootstrap) = concat_bootstrap {
+                    let string_concat = StringConcatInfo {
+                        bootstrap,
+                        bootstrap_method_idx,
+                        descriptor,
+                        parts: OnceCell::new(),
+                    };
+
+                    Op::StringConcat(Box::new(string_concat))
+                } else {
+                    let resolved = ResolvedMethodDescriptor::from_method_descriptor(
+                        context, loader, descriptor,
+                    )?;
+
+                    let invoke_dynamic = InvokeDynamicInfo {
+                        index: call_site_idx,
+                        descriptor,
+                        resolved,
+                    };
+
+                    Op::InvokeDynamic(Box::new(invoke_dynamic))
+                }
+            }
             NEW => {
                 let class_idx = read_u16_be!(context, data);
                 let class_name = constant_pool
@@ -1435,14 +1353,14 @@ impl Op {
             }
             NEW_ARRAY => {
                 let array_type = match read_u8!(context, data) {
-                    4 => ArrayType::Boolean,
-                    5 => ArrayType::Char,
-                    6 => ArrayType::Float,
-                    7 => ArrayType::Double,
-                    8 => ArrayType::Byte,
-                    9 => ArrayType::Short,
-                    10 => ArrayType::Int,
-                    11 => ArrayType::Long,
+                    T_BOOLEAN => ArrayType::Boolean,
+                    T_CHAR => ArrayType::Char,
+                    T_FLOAT => ArrayType::Float,
+                    T_DOUBLE => ArrayType::Double,
+                    T_BYTE => ArrayType::Byte,
+                    T_SHORT => ArrayType::Short,
+                    T_INT => ArrayType::Int,
+                    T_LONG => ArrayType::Long,
                     _ => return Err(context.verify_error("Invalid array type")),
                 };
 
@@ -1482,6 +1400,32 @@ impl Op {
             }
             MONITOR_ENTER => Op::MonitorEnter,
             MONITOR_EXIT => Op::MonitorExit,
+            WIDE => {
+                // `wide` extends the local variable index (and the `iinc`
+                // constant) of the following instruction to 16 bits
+                let opcode = read_u8!(context, data);
+                let local_idx = read_u16_be!(context, data) as usize;
+
+                match opcode {
+                    I_LOAD => Op::ILoad(local_idx),
+                    L_LOAD => Op::LLoad(local_idx),
+                    F_LOAD => Op::FLoad(local_idx),
+                    D_LOAD => Op::DLoad(local_idx),
+                    A_LOAD => Op::ALoad(local_idx),
+                    I_STORE => Op::IStore(local_idx),
+                    L_STORE => Op::LStore(local_idx),
+                    F_STORE => Op::FStore(local_idx),
+                    D_STORE => Op::DStore(local_idx),
+                    A_STORE => Op::AStore(local_idx),
+                    RET => Op::Ret(local_idx),
+                    I_INC => {
+                        let constant = read_u16_be!(context, data) as i16;
+
+                        Op::IInc(local_idx, constant as i32)
+                    }
+                    _ => return Err(context.verify_error("Invalid wide instruction")),
+                }
+            }
             MULTI_A_NEW_ARRAY => {
                 let class_idx = read_u16_be!(context, data);
                 let class_name = constant_pool
@@ -1540,12 +1484,17 @@ impl Op {
 
                 Op::Jsr(((data_position as isize) + offset) as usize)
             }
-            other => unimplemented!(
-                "Unimplemented opcode {} ({}.{})",
-                other,
-                method.class().name(),
-                method.name()
-            ),
+            other => {
+                // This includes valid opcodes that aren't implemented yet, so
+                // that tools checking bytecode can report them instead of
+                // crashing
+                return Err(context.verify_error(&format!(
+                    "Unimplemented opcode {} ({}.{})",
+                    other,
+                    method.class().name(),
+                    method.name()
+                )));
+            }
         };
 
         Ok((result, None))
@@ -1555,6 +1504,7 @@ impl Op {
         matches!(
             self,
             Op::Ldc(_)
+                | Op::LdcResolved(_, _)
                 | Op::IaLoad
                 | Op::LaLoad
                 | Op::FaLoad
@@ -1584,6 +1534,9 @@ impl Op {
                 | Op::InvokeSpecial(_)
                 | Op::InvokeStatic(_)
                 | Op::InvokeInterface(_)
+                | Op::InvokePolymorphic(_)
+                | Op::InvokeDynamic(_)
+                | Op::StringConcat(_)
                 | Op::NewArray(_)
                 | Op::ANewArray(_)
                 | Op::ArrayLength
@@ -1597,6 +1550,45 @@ impl Op {
     }
 }
 
+// Create the op for an `ldc`, `ldc_w`, or (if `wide` is set) `ldc2_w` of the
+// constant pool entry at `index`.
+fn ldc_op(
+    context: &Context,
+    constant_pool: &ConstantPool,
+    index: u16,
+    wide: bool,
+) -> Result<Op, Error> {
+    let entry = constant_pool
+        .entry(index)
+        .map_err(|e| Error::from_class_file_error(context, e))?;
+
+    match entry {
+        ConstantPoolEntry::Long { value } if wide => Ok(Op::LoadLong(value)),
+        ConstantPoolEntry::Double { value } if wide => Ok(Op::LoadDouble(value)),
+        ConstantPoolEntry::String { .. }
+        | ConstantPoolEntry::Integer { .. }
+        | ConstantPoolEntry::Float { .. }
+        | ConstantPoolEntry::Class { .. }
+            if !wide =>
+        {
+            Ok(Op::Ldc(Gc::new(context.gc_ctx, entry)))
+        }
+        ConstantPoolEntry::MethodHandle { .. }
+        | ConstantPoolEntry::MethodType { .. }
+        | ConstantPoolEntry::Dynamic { .. } => {
+            let descriptor = constant::constant_type(context, constant_pool, index)?;
+
+            // Only `ldc2_w` can load long and double constants
+            if descriptor.is_wide() != wide {
+                return Err(context.verify_error("Wrong ldc instruction for constant type"));
+            }
+
+            Ok(Op::LdcResolved(index, Gc::new(context.gc_ctx, descriptor)))
+        }
+        _ => Err(context.verify_error("Wrong ldc instruction for constant type")),
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct TableSwitchInfo {
     pub low_int: i32,
@@ -1617,6 +1609,20 @@ pub(crate) struct InvokeInterfaceInfo {
     pub descriptor: MethodDescriptor,
 }
 
+#[derive(Clone, Debug)]
+pub(crate) struct InvokePolymorphicInfo {
+    pub exact: bool,
+    pub descriptor: MethodDescriptor,
+    pub resolved: ResolvedMethodDescriptor,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct InvokeDynamicInfo {
+    pub index: u16,
+    pub descriptor: MethodDescriptor,
+    pub resolved: ResolvedMethodDescriptor,
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct MultiANewArrayInfo {
     pub class: ResolvedDescriptor,
@@ -0,0 +1,212 @@
+// Restricting what the program running in the VM can do. The natives that
+// access files, the environment, or the process call
+// `Context::check_permission` before doing so, which consults the
+// `SecurityPolicy` set by the embedder.
+
+use super::context::Context;
+use super::error::Error;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// An operation that can be allowed or denied by a [`SecurityPolicy`]. Each
+/// operation is checked against a target, which is matched against the
+/// prefixes of the rules of the policy.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    /// Reading a file or its metadata, or listing a directory. The target is
+    /// the absolute path of the file.
+    ReadFile,
+
+    /// Creating or writing to a file. The target is the absolute path of the
+    /// file.
+    WriteFile,
+
+    /// Deleting a file, or moving it elsewhere. The target is the absolute
+    /// path of the file.
+    DeleteFile,
+
+    /// Exiting the process. The target is the status code (e.g. `0`).
+    Exit,
+
+    /// Reading an environment variable. The target is the name of the
+    /// variable.
+    ReadEnvironment,
+
+    /// Defining a class from bytes with `ClassLoader.defineClass`. The target
+    /// is the internal name of the class (e.g. `com/example/Plugin`).
+    DefineClass,
+}
+
+impl Operation {
+    // The permission that Java would check for this operation, as it's shown
+    // in the message of an `AccessControlException`.
+    fn describe(self, target: &str) -> String {
+        match self {
+            Operation::ReadFile => format!("\"java.io.FilePermission\" \"{}\" \"read\"", target),
+            Operation::WriteFile => format!("\"java.io.FilePermission\" \"{}\" \"write\"", target),
+            Operation::DeleteFile => {
+                format!("\"java.io.FilePermission\" \"{}\" \"delete\"", target)
+            }
+            Operation::Exit => format!("\"java.lang.RuntimePermission\" \"exitVM.{}\"", target),
+            Operation::ReadEnvironment => {
+                format!("\"java.lang.RuntimePermission\" \"getenv.{}\"", target)
+            }
+            Operation::DefineClass => format!(
+                "\"java.lang.RuntimePermission\" \"defineClass\" \"{}\"",
+                target.replace('/', ".")
+            ),
+        }
+    }
+}
+
+/// A set of rules deciding which [`Operation`]s the program running in the VM
+/// may perform. For example, a policy that only lets a plugin read and write
+/// the files in its own directory could be written as so:
+///
+/// ```ignore
+/// let mut policy = SecurityPolicy::deny_all();
+/// policy.allow(Operation::ReadFile, "/plugins/example/");
+/// policy.allow(Operation::WriteFile, "/plugins/example/");
+/// policy.deny(Operation::WriteFile, "/plugins/example/plugin.jar");
+///
+/// context.set_security_policy(policy);
+/// ```
+///
+/// An operation is decided by the rule for it with the longest prefix of its
+/// target; of rules with the same prefix, the one added last is used. If no
+/// rule matches, the default of the policy is used. Prefixes are compared as
+/// strings, so a prefix should end with `/` to only match the files in a
+/// directory. Paths are normalized before being checked, so `..` can't be
+/// used to escape a directory.
+///
+/// A denied operation throws a `java.security.AccessControlException`. Code
+/// loaded by the bootstrap loader can perform denied operations by calling
+/// `AccessController.doPrivileged`, as long as the privileged action doesn't
+/// call code loaded by another class loader.
+#[derive(Clone, Debug)]
+pub struct SecurityPolicy {
+    allow_by_default: bool,
+
+    rules: Vec<Rule>,
+}
+
+#[derive(Clone, Debug)]
+struct Rule {
+    operation: Operation,
+
+    prefix: String,
+
+    allow: bool,
+}
+
+impl SecurityPolicy {
+    /// Create a policy that allows every operation. This is the policy of a
+    /// `Context` until another is set.
+    pub fn allow_all() -> Self {
+        Self {
+            allow_by_default: true,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Create a policy that denies every operation.
+    pub fn deny_all() -> Self {
+        Self {
+            allow_by_default: false,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Allow `operation` on every target starting with `prefix`. An empty
+    /// prefix matches every target.
+    pub fn allow(&mut self, operation: Operation, prefix: &str) {
+        self.add_rule(operation, prefix, true);
+    }
+
+    /// Deny `operation` on every target starting with `prefix`. An empty
+    /// prefix matches every target.
+    pub fn deny(&mut self, operation: Operation, prefix: &str) {
+        self.add_rule(operation, prefix, false);
+    }
+
+    fn add_rule(&mut self, operation: Operation, prefix: &str, allow: bool) {
+        self.rules.push(Rule {
+            operation,
+            prefix: prefix.to_string(),
+            allow,
+        });
+    }
+
+    /// Whether this policy allows `operation` on `target`.
+    pub fn permits(&self, operation: Operation, target: &str) -> bool {
+        let mut decision: Option<&Rule> = None;
+        for rule in &self.rules {
+            if rule.operation != operation || !target.starts_with(&rule.prefix) {
+                continue;
+            }
+
+            // Later rules win over earlier ones with the same prefix
+            if decision.is_none_or(|d| rule.prefix.len() >= d.prefix.len()) {
+                decision = Some(rule);
+            }
+        }
+
+        decision.map_or(self.allow_by_default, |rule| rule.allow)
+    }
+}
+
+impl Context {
+    /// Set the policy that decides which operations the program may perform,
+    /// replacing the current one. See [`SecurityPolicy`].
+    pub fn set_security_policy(&self, policy: SecurityPolicy) {
+        *self.security_policy.borrow_mut() = policy;
+    }
+
+    /// Check that the current security policy allows `operation` on `target`,
+    /// returning an `AccessControlException` if it doesn't. Every operation is
+    /// allowed while running [`Context::run_privileged`], as long as only
+    /// code loaded by the bootstrap loader was called since it started.
+    pub fn check_permission(&self, operation: Operation, target: &str) -> Result<(), Error> {
+        if self.is_privileged() || self.security_policy.borrow().permits(operation, target) {
+            Ok(())
+        } else {
+            let message = format!("access denied ({})", operation.describe(target));
+
+            Err(self.throw_new("java/security/AccessControlException", &message))
+        }
+    }
+
+    /// Call `func`, allowing every operation until it returns regardless of
+    /// the security policy. This is used by `AccessController.doPrivileged`.
+    ///
+    /// Only the methods called after this one are privileged, and only while
+    /// every one of them was loaded by the bootstrap loader; untrusted code
+    /// called by `func` can't perform denied operations, even through trusted
+    /// code that it calls.
+    pub fn run_privileged<T>(&self, func: impl FnOnce() -> T) -> T {
+        let stack_size = self.call_stack_size();
+        self.privileged_frames.borrow_mut().push(stack_size);
+        let result = func();
+        self.privileged_frames.borrow_mut().pop();
+
+        result
+    }
+
+    // Whether the innermost call to `run_privileged` applies to the method
+    // currently being executed.
+    fn is_privileged(&self) -> bool {
+        let Some(stack_size) = self.privileged_frames.borrow().last().copied() else {
+            return false;
+        };
+
+        // The methods called since `run_privileged` started are the ones
+        // above the stack size it recorded
+        let called_methods = self.call_stack_size().saturating_sub(stack_size);
+
+        (0..called_methods).all(|depth| {
+            self.caller_method(depth)
+                .is_some_and(|method| method.class().loader() == Some(self.bootstrap_loader()))
+        })
+    }
+}
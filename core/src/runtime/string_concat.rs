@@ -0,0 +1,325 @@
+// Support for `invokedynamic` call sites bootstrapped by
+// `java.lang.invoke.StringConcatFactory`, which javac emits for every string
+// concatenation. Instead of calling the bootstrap method, the recipe of the
+// call site is parsed when the instruction is first executed, and the
+// interpreter concatenates the arguments itself.
+
+use super::class::Class;
+use super::constant;
+use super::context::Context;
+use super::descriptor::{Descriptor, MethodDescriptor};
+use super::error::Error;
+use super::method_handle::natives_method;
+use super::value::Value;
+
+use crate::classfile::attribute::BootstrapMethod;
+use crate::classfile::constant_pool::{ConstantPoolEntry, MethodHandle};
+use crate::string::JvmString;
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::cell::OnceCell;
+
+// The recipe characters that stand for an argument and a constant.
+const TAG_ARGUMENT: char = '\u{1}';
+const TAG_CONSTANT: char = '\u{2}';
+
+#[derive(Clone, Debug)]
+pub(crate) enum ConcatPart {
+    Literal(Box<[u16]>),
+    /// An argument of the call site, of the given type, found `depth` slots
+    /// below the top of the stack.
+    Argument(Descriptor, usize),
+}
+
+/// The `StringConcatFactory` method that bootstraps a call site.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ConcatBootstrap {
+    MakeConcat,
+    MakeConcatWithConstants,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct StringConcatInfo {
+    pub bootstrap: ConcatBootstrap,
+
+    /// The index of the bootstrap method in the `BootstrapMethods` attribute.
+    pub bootstrap_method_idx: u16,
+
+    pub descriptor: MethodDescriptor,
+
+    /// The parts of the resulting string, set when the call site is linked.
+    pub parts: OnceCell<Box<[ConcatPart]>>,
+}
+
+impl StringConcatInfo {
+    /// The parts of the resulting string. The call site is linked the first
+    /// time this is called, which can run Java code; failing to link it
+    /// throws a `BootstrapMethodError`.
+    pub(crate) fn parts(&self, context: &Context, class: Class) -> Result<&[ConcatPart], Error> {
+        if let Some(parts) = self.parts.get() {
+            return Ok(parts);
+        }
+
+        let bootstrap_method =
+            constant::bootstrap_method(context, class, self.bootstrap_method_idx)?;
+        let parts = link_string_concat(
+            context,
+            class,
+            self.bootstrap,
+            &bootstrap_method,
+            self.descriptor,
+        )?;
+
+        // Linking can run Java code, which could have executed this call site
+        // already, in which case the first parts are used
+        let _ = self.parts.set(parts);
+
+        Ok(self.parts.get().unwrap())
+    }
+}
+
+/// Returns which `StringConcatFactory` method `bootstrap_method` is, or `None`
+/// if it's any other bootstrap method.
+pub(crate) fn concat_bootstrap(
+    context: &Context,
+    class: Class,
+    bootstrap_method: &BootstrapMethod,
+) -> Result<Option<ConcatBootstrap>, Error> {
+    let class_file = class
+        .class_file()
+        .expect("Class with constant pool should have class file");
+    let constant_pool = class_file.constant_pool();
+
+    let entry = constant_pool
+        .entry(bootstrap_method.method_ref)
+        .map_err(|e| Error::from_class_file_error(context, e))?;
+
+    let ConstantPoolEntry::MethodHandle {
+        method_handle: MethodHandle::InvokeStatic(method_ref_idx),
+    } = entry
+    else {
+        return Ok(None);
+    };
+
+    let (class_name, method_name, _) = constant_pool
+        .get_any_method_ref(method_ref_idx)
+        .map_err(|e| Error::from_class_file_error(context, e))?;
+
+    if &*class_name != "java/lang/invoke/StringConcatFactory" {
+        return Ok(None);
+    }
+
+    Ok(match &*method_name {
+        "makeConcat" => Some(ConcatBootstrap::MakeConcat),
+        "makeConcatWithConstants" => Some(ConcatBootstrap::MakeConcatWithConstants),
+        _ => None,
+    })
+}
+
+// Parse the recipe of a call site bootstrapped by `bootstrap_method`, with the
+// given descriptor, into the parts of the resulting string.
+fn link_string_concat(
+    context: &Context,
+    class: Class,
+    bootstrap: ConcatBootstrap,
+    bootstrap_method: &BootstrapMethod,
+    descriptor: MethodDescriptor,
+) -> Result<Box<[ConcatPart]>, Error> {
+    let class_file = class
+        .class_file()
+        .expect("Class with constant pool should have class file");
+    let constant_pool = class_file.constant_pool();
+
+    let recipe = match bootstrap {
+        // Every argument is concatenated, without anything in between
+        ConcatBootstrap::MakeConcat => TAG_ARGUMENT.to_string().repeat(descriptor.args().len()),
+        ConcatBootstrap::MakeConcatWithConstants => {
+            let recipe_idx = bootstrap_method
+                .arguments
+                .first()
+                .copied()
+                .ok_or_else(|| context.bootstrap_method_error("Missing concat recipe"))?;
+
+            let ConstantPoolEntry::String { string_idx } = constant_pool
+                .entry(recipe_idx)
+                .map_err(|e| Error::from_class_file_error(context, e))?
+            else {
+                return Err(context.bootstrap_method_error("Concat recipe must be a String"));
+            };
+
+            constant_pool
+                .get_utf8(string_idx)
+                .map_err(|e| Error::from_class_file_error(context, e))?
+                .to_string()
+        }
+    };
+
+    let return_type = descriptor.return_type();
+    if !matches!(return_type, Descriptor::Class(name) if &*name == "java/lang/String") {
+        return Err(context.bootstrap_method_error(&format!(
+            "The return type should be compatible with String, but it is {}",
+            return_type
+        )));
+    }
+
+    // The constants used by the recipe follow the recipe itself
+    let constants = bootstrap_method.arguments.get(1..).unwrap_or(&[]);
+
+    let argument_count = recipe.chars().filter(|c| *c == TAG_ARGUMENT).count();
+    if argument_count != descriptor.args().len() {
+        return Err(context.bootstrap_method_error(&format!(
+            "Mismatched number of concat arguments: recipe wants {} arguments, but signature provides {}",
+            argument_count,
+            descriptor.args().len()
+        )));
+    }
+
+    let constant_count = recipe.chars().filter(|c| *c == TAG_CONSTANT).count();
+    if constant_count != constants.len() {
+        return Err(context.bootstrap_method_error(&format!(
+            "Mismatched number of concat constants: recipe wants {} constants, but only {} are passed",
+            constant_count,
+            constants.len()
+        )));
+    }
+
+    // The depth of each argument below the top of the stack. Wide values are
+    // stored in the first of their two slots.
+    let physical_arg_count = descriptor.physical_arg_count() as usize;
+    let mut depths = Vec::with_capacity(descriptor.args().len());
+    let mut position = 0;
+    for arg in descriptor.args() {
+        depths.push(physical_arg_count - position - 1);
+        position += if arg.is_wide() { 2 } else { 1 };
+    }
+
+    let mut parts = Vec::new();
+    let mut literal = Vec::new();
+    let mut args = descriptor.args().iter().zip(depths);
+    let mut constants = constants.iter();
+
+    for c in recipe.chars() {
+        match c {
+            TAG_ARGUMENT => {
+                if !literal.is_empty() {
+                    parts.push(ConcatPart::Literal(literal.into_boxed_slice()));
+                    literal = Vec::new();
+                }
+
+                let (arg, depth) = args.next().unwrap();
+                parts.push(ConcatPart::Argument(*arg, depth));
+            }
+            TAG_CONSTANT => {
+                let constant_idx = *constants.next().unwrap();
+
+                let value = constant::resolve_constant(context, class, constant_idx)?;
+                let value_type = constant::constant_type(context, constant_pool, constant_idx)?;
+
+                append_value(context, &mut literal, value, value_type)?;
+            }
+            other => {
+                let mut buffer = [0; 2];
+                literal.extend_from_slice(other.encode_utf16(&mut buffer));
+            }
+        }
+    }
+
+    if !literal.is_empty() {
+        parts.push(ConcatPart::Literal(literal.into_boxed_slice()));
+    }
+
+    Ok(parts.into_boxed_slice())
+}
+
+/// Append the string representation of `value`, of type `descriptor`, to
+/// `chars`, as `String.valueOf` would. This may run Java code, so any object
+/// in `value` must be kept alive by the caller.
+pub(crate) fn append_value(
+    context: &Context,
+    chars: &mut Vec<u16>,
+    value: Value,
+    descriptor: Descriptor,
+) -> Result<(), Error> {
+    match descriptor {
+        Descriptor::Boolean => {
+            let string = if value.int() != 0 { "true" } else { "false" };
+            chars.extend(string.encode_utf16());
+        }
+        Descriptor::Character => chars.push(value.int() as u16),
+        Descriptor::Byte | Descriptor::Short | Descriptor::Integer => {
+            chars.extend(value.int().to_string().encode_utf16());
+        }
+        Descriptor::Long => chars.extend(value.long().to_string().encode_utf16()),
+        Descriptor::Float => {
+            let string = to_string_with(
+                context,
+                "java/lang/Float",
+                "(F)Ljava/lang/String;",
+                &[value],
+            )?;
+            chars.extend_from_slice(&string);
+        }
+        Descriptor::Double => {
+            // Doubles take up two slots
+            let string = to_string_with(
+                context,
+                "java/lang/Double",
+                "(D)Ljava/lang/String;",
+                &[value, Value::Integer(0)],
+            )?;
+            chars.extend_from_slice(&string);
+        }
+        Descriptor::Class(_) | Descriptor::Array(_) => match value.object() {
+            None => chars.extend("null".encode_utf16()),
+            Some(object) if object.class() == context.builtins().java_lang_string => {
+                chars.extend_from_slice(&Context::unwrap_string(object));
+            }
+            Some(_) => {
+                let string = to_string_with(
+                    context,
+                    "java/lang/String",
+                    "(Ljava/lang/Object;)Ljava/lang/String;",
+                    &[value],
+                )?;
+                chars.extend_from_slice(&string);
+            }
+        },
+        Descriptor::Void => unreachable!("Values can't be void"),
+    }
+
+    Ok(())
+}
+
+// Convert `value` to a string by calling a static method of the given class:
+// `String.valueOf` for objects, or the `toString` method of the wrapper class
+// for floating-point numbers, as their formatting is implemented in Java.
+fn to_string_with(
+    context: &Context,
+    class_name: &str,
+    descriptor: &str,
+    args: &[Value],
+) -> Result<Box<[u16]>, Error> {
+    let class_name = JvmString::new(context.gc_ctx, class_name.to_string());
+    let class = context
+        .bootstrap_loader()
+        .lookup_class(context, class_name)?;
+    class.run_clinit(context)?;
+
+    let name = if &*class.name() == "java/lang/String" {
+        "valueOf"
+    } else {
+        "toString"
+    };
+
+    let method = natives_method(context, class, name, descriptor)?;
+
+    let result = context
+        .exec_method(method, args)?
+        .expect("Method returns a value");
+
+    // `String.valueOf` returns "null" when `toString` returns null
+    Ok(Context::unwrap_string(result.object().unwrap()))
+}
@@ -640,6 +640,28 @@ fn verify_block<'a>(
                 }
                 _ => unreachable!(),
             },
+            Op::LdcResolved(_, descriptor) => match **descriptor {
+                Descriptor::Class(_) | Descriptor::Array(_) => {
+                    push_stack!(ValueType::Reference);
+                }
+                Descriptor::Boolean
+                | Descriptor::Byte
+                | Descriptor::Character
+                | Descriptor::Short
+                | Descriptor::Integer => {
+                    push_stack!(ValueType::Integer);
+                }
+                Descriptor::Float => {
+                    push_stack!(ValueType::Float);
+                }
+                Descriptor::Double => {
+                    push_stack!(ValueType::Double);
+                }
+                Descriptor::Long => {
+                    push_stack!(ValueType::Long);
+                }
+                Descriptor::Void => unreachable!(),
+            },
             Op::LoadLong(_) => {
                 push_stack!(ValueType::Long);
             }
@@ -1428,6 +1450,142 @@ fn verify_block<'a>(
                     Descriptor::Void => {}
                 }
             }
+            Op::InvokePolymorphic(invoke_polymorphic) => {
+                let descriptor = invoke_polymorphic.descriptor;
+
+                for arg in descriptor.args().iter().rev() {
+                    match arg {
+                        Descriptor::Class(_) | Descriptor::Array(_) => {
+                            expect_pop_stack!(ValueType::Reference);
+                        }
+                        Descriptor::Boolean
+                        | Descriptor::Byte
+                        | Descriptor::Character
+                        | Descriptor::Short
+                        | Descriptor::Integer => {
+                            expect_pop_stack!(ValueType::Integer);
+                        }
+                        Descriptor::Float => {
+                            expect_pop_stack!(ValueType::Float);
+                        }
+                        Descriptor::Double => {
+                            expect_pop_stack!(ValueType::Double);
+                        }
+                        Descriptor::Long => {
+                            expect_pop_stack!(ValueType::Long);
+                        }
+                        Descriptor::Void => unreachable!(),
+                    }
+                }
+
+                // Method handle
+                expect_pop_stack!(ValueType::Reference);
+
+                match descriptor.return_type() {
+                    Descriptor::Class(_) | Descriptor::Array(_) => {
+                        push_stack!(ValueType::Reference);
+                    }
+                    Descriptor::Boolean
+                    | Descriptor::Byte
+                    | Descriptor::Character
+                    | Descriptor::Short
+                    | Descriptor::Integer => {
+                        push_stack!(ValueType::Integer);
+                    }
+                    Descriptor::Float => {
+                        push_stack!(ValueType::Float);
+                    }
+                    Descriptor::Double => {
+                        push_stack!(ValueType::Double);
+                    }
+                    Descriptor::Long => {
+                        push_stack!(ValueType::Long);
+                    }
+                    Descriptor::Void => {}
+                }
+            }
+            Op::InvokeDynamic(invoke_dynamic) => {
+                let descriptor = invoke_dynamic.descriptor;
+
+                for arg in descriptor.args().iter().rev() {
+                    match arg {
+                        Descriptor::Class(_) | Descriptor::Array(_) => {
+                            expect_pop_stack!(ValueType::Reference);
+                        }
+                        Descriptor::Boolean
+                        | Descriptor::Byte
+                        | Descriptor::Character
+                        | Descriptor::Short
+                        | Descriptor::Integer => {
+                            expect_pop_stack!(ValueType::Integer);
+                        }
+                        Descriptor::Float => {
+                            expect_pop_stack!(ValueType::Float);
+                        }
+                        Descriptor::Double => {
+                            expect_pop_stack!(ValueType::Double);
+                        }
+                        Descriptor::Long => {
+                            expect_pop_stack!(ValueType::Long);
+                        }
+                        Descriptor::Void => unreachable!(),
+                    }
+                }
+
+                match descriptor.return_type() {
+                    Descriptor::Class(_) | Descriptor::Array(_) => {
+                        push_stack!(ValueType::Reference);
+                    }
+                    Descriptor::Boolean
+                    | Descriptor::Byte
+                    | Descriptor::Character
+                    | Descriptor::Short
+                    | Descriptor::Integer => {
+                        push_stack!(ValueType::Integer);
+                    }
+                    Descriptor::Float => {
+                        push_stack!(ValueType::Float);
+                    }
+                    Descriptor::Double => {
+                        push_stack!(ValueType::Double);
+                    }
+                    Descriptor::Long => {
+                        push_stack!(ValueType::Long);
+                    }
+                    Descriptor::Void => {}
+                }
+            }
+            Op::StringConcat(string_concat) => {
+                let descriptor = string_concat.descriptor;
+
+                for arg in descriptor.args().iter().rev() {
+                    match arg {
+                        Descriptor::Class(_) | Descriptor::Array(_) => {
+                            expect_pop_stack!(ValueType::Reference);
+                        }
+                        Descriptor::Boolean
+                        | Descriptor::Byte
+                        | Descriptor::Character
+                        | Descriptor::Short
+                        | Descriptor::Integer => {
+                            expect_pop_stack!(ValueType::Integer);
+                        }
+                        Descriptor::Float => {
+                            expect_pop_stack!(ValueType::Float);
+                        }
+                        Descriptor::Double => {
+                            expect_pop_stack!(ValueType::Double);
+                        }
+                        Descriptor::Long => {
+                            expect_pop_stack!(ValueType::Long);
+                        }
+                        Descriptor::Void => unreachable!(),
+                    }
+                }
+
+                // The result is always a `String`
+                push_stack!(ValueType::Reference);
+            }
             Op::New(_) => {
                 push_stack!(ValueType::Reference);
             }
@@ -77,7 +77,7 @@ impl Trace for JvmString {
 pub fn hash_chars(length: usize, chars: impl core::iter::Iterator<Item = u32>) -> u64 {
     let mut hash = length as u64;
     for character in chars {
-        hash = hash * 11 + character as u64;
+        hash = hash.wrapping_mul(11).wrapping_add(character as u64);
     }
 
     hash
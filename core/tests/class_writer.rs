@@ -0,0 +1,226 @@
+use rjvm_core::classfile::attribute::AttributeInfo;
+use rjvm_core::classfile::opcodes::*;
+use rjvm_core::classfile::writer::{ClassWriter, Constant};
+use rjvm_core::{
+    ClassFile, ClassFileWriteError, ClassFlags, Context, FieldFlags, LoaderBackend, MethodFlags,
+};
+
+struct NoResources;
+
+impl LoaderBackend for NoResources {
+    fn load_filesystem_resource(&self, _resource_name: &str) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+// Parse the class file with a fresh `Context`, and pass it to `check`.
+fn with_parsed_class(bytes: &[u8], check: impl FnOnce(ClassFile)) {
+    Context::init(Box::new(NoResources));
+    Context::with(|context| {
+        let class_file = context
+            .parse_class_file(bytes)
+            .expect("Written class file should parse");
+        check(class_file);
+    });
+    unsafe { Context::clear() };
+}
+
+fn write_class() -> Result<Vec<u8>, ClassFileWriteError> {
+    let mut writer = ClassWriter::new(
+        ClassFlags::PUBLIC | ClassFlags::FINAL,
+        "Example",
+        Some("java/lang/Object"),
+    );
+    writer.add_interface("java/lang/Runnable")?;
+
+    let mut field = writer.add_field(
+        FieldFlags::PUBLIC | FieldFlags::STATIC | FieldFlags::FINAL,
+        "ANSWER",
+        "I",
+    );
+    field.set_constant_value(Constant::Integer(42))?;
+    field.finish()?;
+
+    writer
+        .add_field(FieldFlags::PRIVATE, "name", "Ljava/lang/String;")
+        .finish()?;
+
+    // static int pick(int n) {
+    //     switch (n) { case 0: return 10; case 1: return 20; default: return -1; }
+    // }
+    let mut method = writer.add_method(MethodFlags::STATIC, "pick", "(I)I");
+    let zero = method.new_label();
+    let one = method.new_label();
+    let default = method.new_label();
+    method.var_insn(I_LOAD, 0)?;
+    method.table_switch_insn(0, default, &[zero, one])?;
+    method.place_label(zero)?;
+    method.push_int(10)?;
+    method.insn(I_RETURN)?;
+    method.place_label(one)?;
+    method.push_int(20)?;
+    method.insn(I_RETURN)?;
+    method.place_label(default)?;
+    method.push_int(-1)?;
+    method.insn(I_RETURN)?;
+    method.finish()?;
+
+    // static Object guard(Runnable task) {
+    //     try { task.run(); return null; } catch (RuntimeException e) { return e; }
+    // }
+    let mut method = writer.add_method(
+        MethodFlags::STATIC,
+        "guard",
+        "(Ljava/lang/Runnable;)Ljava/lang/Object;",
+    );
+    let start = method.new_label();
+    let end = method.new_label();
+    let handler = method.new_label();
+    method.place_label(start)?;
+    method.var_insn(A_LOAD, 0)?;
+    method.method_insn(INVOKE_INTERFACE, "java/lang/Runnable", "run", "()V", true)?;
+    method.insn(A_CONST_NULL)?;
+    method.insn(A_RETURN)?;
+    method.place_label(end)?;
+    method.place_label(handler)?;
+    method.var_insn(A_STORE, 1)?;
+    method.var_insn(A_LOAD, 1)?;
+    method.insn(A_RETURN)?;
+    method.try_catch_block(start, end, handler, Some("java/lang/RuntimeException"))?;
+    method.add_exception("java/lang/Error")?;
+    method.finish()?;
+
+    writer
+        .add_method(MethodFlags::PUBLIC | MethodFlags::NATIVE, "run", "()V")
+        .finish()?;
+
+    writer.into_bytes()
+}
+
+#[test]
+fn written_class_round_trips() {
+    let bytes = write_class().expect("Class should be written");
+
+    with_parsed_class(&bytes, |class_file| {
+        let constant_pool = class_file.constant_pool();
+
+        assert_eq!(&*class_file.this_class(), "Example");
+        assert_eq!(
+            class_file.super_class().as_deref(),
+            Some("java/lang/Object")
+        );
+        assert_eq!(
+            class_file.flags().bits(),
+            (ClassFlags::PUBLIC | ClassFlags::FINAL).bits()
+        );
+        let interfaces = class_file.interfaces();
+        assert_eq!(interfaces.len(), 1);
+        assert_eq!(&*interfaces[0], "java/lang/Runnable");
+
+        let fields = class_file.fields();
+        assert_eq!(fields.len(), 2);
+        assert_eq!(&*fields[0].name(), "ANSWER");
+        assert_eq!(&*fields[0].descriptor(), "I");
+        let constant_value = fields[0]
+            .attributes()
+            .iter()
+            .find(|attribute| &*attribute.name() == "ConstantValue")
+            .expect("Constant field should have a ConstantValue attribute");
+        assert!(matches!(
+            constant_value.parse(constant_pool),
+            Ok(AttributeInfo::ConstantValue(_))
+        ));
+        assert_eq!(&*fields[1].name(), "name");
+        assert_eq!(fields[1].flags().bits(), FieldFlags::PRIVATE.bits());
+        assert!(fields[1].attributes().is_empty());
+
+        let methods = class_file.methods();
+        assert_eq!(methods.len(), 3);
+
+        let code_of = |index: usize| {
+            methods[index]
+                .attributes()
+                .iter()
+                .find_map(|attribute| match attribute.parse(constant_pool) {
+                    Ok(AttributeInfo::Code(code)) => Some(code),
+                    _ => None,
+                })
+                .expect("Method should have a Code attribute")
+        };
+        let has_stack_map = |attributes: &[rjvm_core::classfile::attribute::Attribute]| {
+            attributes
+                .iter()
+                .any(|attribute| &*attribute.name() == "StackMapTable")
+        };
+
+        assert_eq!(&*methods[0].name(), "pick");
+        assert_eq!(&*methods[0].descriptor(), "(I)I");
+        let pick = code_of(0);
+        assert_eq!(pick.max_stack, 1);
+        assert_eq!(pick.max_locals, 1);
+        assert_eq!(pick.code[1], TABLE_SWITCH);
+        assert!(pick.exception_table.is_empty());
+        assert!(has_stack_map(&pick.attributes));
+
+        assert_eq!(&*methods[1].name(), "guard");
+        let guard = code_of(1);
+        assert_eq!(guard.max_stack, 1);
+        assert_eq!(guard.max_locals, 2);
+        assert_eq!(guard.exception_table.len(), 1);
+        let entry = &guard.exception_table[0];
+        assert_eq!(entry.start_pc, 0);
+        assert_eq!(entry.end_pc, entry.handler_pc);
+        assert_eq!(
+            entry.catch_type.as_deref(),
+            Some("java/lang/RuntimeException")
+        );
+        assert!(has_stack_map(&guard.attributes));
+        assert!(methods[1].attributes().iter().any(|attribute| matches!(
+            attribute.parse(constant_pool),
+            Ok(AttributeInfo::Exceptions(classes)) if &*classes[0] == "java/lang/Error"
+        )));
+
+        assert_eq!(&*methods[2].name(), "run");
+        assert_eq!(
+            methods[2].flags().bits(),
+            (MethodFlags::PUBLIC | MethodFlags::NATIVE).bits()
+        );
+        assert!(methods[2].attributes().is_empty());
+    });
+}
+
+#[test]
+fn labels_are_specific_to_their_method() {
+    let mut writer = ClassWriter::new(ClassFlags::PUBLIC, "Labels", Some("java/lang/Object"));
+
+    let mut first = writer.add_method(MethodFlags::STATIC, "first", "()V");
+    let label = first.new_label();
+    first.place_label(label).unwrap();
+    first.insn(RETURN).unwrap();
+    first.finish().unwrap();
+
+    let mut second = writer.add_method(MethodFlags::STATIC, "second", "()V");
+    assert!(matches!(
+        second.place_label(label),
+        Err(ClassFileWriteError::ForeignLabel)
+    ));
+    assert!(matches!(second.jump_insn(GOTO, label), Ok(())));
+    second.insn(RETURN).unwrap();
+    assert!(matches!(
+        second.finish(),
+        Err(ClassFileWriteError::ForeignLabel)
+    ));
+}
+
+#[test]
+fn unplaced_labels_are_rejected() {
+    let mut writer = ClassWriter::new(ClassFlags::PUBLIC, "Labels", Some("java/lang/Object"));
+
+    let mut method = writer.add_method(MethodFlags::STATIC, "method", "()V");
+    let label = method.new_label();
+    method.jump_insn(GOTO, label).unwrap();
+    assert!(matches!(
+        method.finish(),
+        Err(ClassFileWriteError::LabelNotPlaced)
+    ));
+}
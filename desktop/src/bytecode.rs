@@ -0,0 +1,242 @@
+// Decoding of raw bytecode into instructions, for the commands that examine
+// class files without running them. Unlike the interpreter's decoder, this
+// doesn't resolve any constant pool entries.
+
+use rjvm_core::classfile::opcodes::{self, *};
+
+pub struct Insn {
+    /// The offset of this instruction from the start of the code.
+    pub offset: usize,
+    pub opcode: u8,
+    pub operands: Operands,
+}
+
+impl Insn {
+    /// The mnemonic of this instruction's opcode.
+    pub fn name(&self) -> &'static str {
+        // Only valid opcodes are decoded
+        opcodes::name(self.opcode).unwrap()
+    }
+
+    /// The constant pool index this instruction refers to, if any.
+    pub fn constant_index(&self) -> Option<u16> {
+        match self.operands {
+            Operands::Constant(index)
+            | Operands::InvokeInterface(index, _)
+            | Operands::InvokeDynamic(index)
+            | Operands::MultiANewArray(index, _) => Some(index),
+            _ => None,
+        }
+    }
+}
+
+pub enum Operands {
+    None,
+    /// The value pushed by `bipush` or `sipush`.
+    Int(i32),
+    /// A constant pool index.
+    Constant(u16),
+    /// The constant pool index of an interface method, and the argument count.
+    InvokeInterface(u16, u8),
+    /// The constant pool index of an `InvokeDynamic` entry.
+    InvokeDynamic(u16),
+    /// The constant pool index of an array class, and the dimension count.
+    MultiANewArray(u16, u8),
+    /// A local variable index.
+    Local(u16),
+    /// A local variable index, and the constant to add to it.
+    IInc(u16, i16),
+    /// The absolute offset of a branch target.
+    Branch(i64),
+    /// The `atype` operand of `newarray`.
+    ArrayType(u8),
+    /// The lowest and highest values of a `tableswitch`, the absolute offset
+    /// of the default target, and the absolute offsets of the targets for each
+    /// value in order.
+    TableSwitch {
+        low: i32,
+        high: i32,
+        default: i64,
+        targets: Vec<i64>,
+    },
+    /// The absolute offset of the default target of a `lookupswitch`, and
+    /// pairs of values and their absolute target offsets.
+    LookupSwitch {
+        default: i64,
+        pairs: Vec<(i32, i64)>,
+    },
+    /// The instruction modified by a `wide` instruction, its local variable
+    /// index, and for `iinc`, the constant to add.
+    Wide {
+        opcode: u8,
+        index: u16,
+        constant: Option<i16>,
+    },
+}
+
+pub enum DecodeError {
+    InvalidOpcode(u8),
+    Truncated,
+}
+
+// Reads big-endian operands from bytecode.
+struct CodeReader<'a> {
+    code: &'a [u8],
+    position: usize,
+}
+
+impl CodeReader<'_> {
+    fn read_u8(&mut self) -> Result<u8, DecodeError> {
+        let byte = *self.code.get(self.position).ok_or(DecodeError::Truncated)?;
+        self.position += 1;
+
+        Ok(byte)
+    }
+
+    fn read_u16(&mut self) -> Result<u16, DecodeError> {
+        Ok(u16::from_be_bytes([self.read_u8()?, self.read_u8()?]))
+    }
+
+    fn read_i32(&mut self) -> Result<i32, DecodeError> {
+        Ok(i32::from_be_bytes([
+            self.read_u8()?,
+            self.read_u8()?,
+            self.read_u8()?,
+            self.read_u8()?,
+        ]))
+    }
+}
+
+/// Decode `code` into instructions. If decoding fails partway through, this
+/// returns the instructions decoded before the failure, along with the offset
+/// of the instruction that couldn't be decoded and the error.
+pub fn decode(code: &[u8]) -> (Vec<Insn>, Option<(usize, DecodeError)>) {
+    let mut reader = CodeReader { code, position: 0 };
+    let mut insns = Vec::new();
+
+    while reader.position < code.len() {
+        let offset = reader.position;
+
+        match decode_insn(&mut reader) {
+            Ok(insn) => insns.push(insn),
+            Err(error) => return (insns, Some((offset, error))),
+        }
+    }
+
+    (insns, None)
+}
+
+fn decode_insn(reader: &mut CodeReader<'_>) -> Result<Insn, DecodeError> {
+    let offset = reader.position;
+    let opcode = reader.read_u8()?;
+    if opcodes::name(opcode).is_none() {
+        return Err(DecodeError::InvalidOpcode(opcode));
+    }
+
+    let branch_target = |relative: i32| offset as i64 + relative as i64;
+
+    let operands = match opcode {
+        B_I_PUSH => Operands::Int(reader.read_u8()? as i8 as i32),
+        S_I_PUSH => Operands::Int(reader.read_u16()? as i16 as i32),
+        LDC => Operands::Constant(reader.read_u8()? as u16),
+        LDC_W | LDC_2_W | GET_STATIC | PUT_STATIC | GET_FIELD | PUT_FIELD | INVOKE_VIRTUAL
+        | INVOKE_SPECIAL | INVOKE_STATIC | NEW | A_NEW_ARRAY | CHECK_CAST | INSTANCE_OF => {
+            Operands::Constant(reader.read_u16()?)
+        }
+        INVOKE_INTERFACE => {
+            let index = reader.read_u16()?;
+            let count = reader.read_u8()?;
+            reader.read_u8()?;
+
+            Operands::InvokeInterface(index, count)
+        }
+        INVOKE_DYNAMIC => {
+            let index = reader.read_u16()?;
+            reader.read_u16()?;
+
+            Operands::InvokeDynamic(index)
+        }
+        MULTI_A_NEW_ARRAY => {
+            let index = reader.read_u16()?;
+            let dimensions = reader.read_u8()?;
+
+            Operands::MultiANewArray(index, dimensions)
+        }
+        I_LOAD..=A_LOAD | I_STORE..=A_STORE | RET => Operands::Local(reader.read_u8()? as u16),
+        I_INC => {
+            let index = reader.read_u8()?;
+            let constant = reader.read_u8()? as i8;
+
+            Operands::IInc(index as u16, constant as i16)
+        }
+        IF_EQ..=JSR | IF_NULL | IF_NON_NULL => {
+            let relative = reader.read_u16()? as i16;
+
+            Operands::Branch(branch_target(relative as i32))
+        }
+        GOTO_W | JSR_W => Operands::Branch(branch_target(reader.read_i32()?)),
+        NEW_ARRAY => Operands::ArrayType(reader.read_u8()?),
+        TABLE_SWITCH | LOOKUP_SWITCH => {
+            // Operands are aligned to four bytes from the start of the code
+            while !reader.position.is_multiple_of(4) {
+                reader.read_u8()?;
+            }
+
+            let default = branch_target(reader.read_i32()?);
+
+            if opcode == TABLE_SWITCH {
+                let low = reader.read_i32()?;
+                let high = reader.read_i32()?;
+
+                let mut targets = Vec::new();
+                for _ in low..=high {
+                    targets.push(branch_target(reader.read_i32()?));
+                }
+
+                Operands::TableSwitch {
+                    low,
+                    high,
+                    default,
+                    targets,
+                }
+            } else {
+                let pair_count = reader.read_i32()?;
+
+                let mut pairs = Vec::new();
+                for _ in 0..pair_count {
+                    let value = reader.read_i32()?;
+                    pairs.push((value, branch_target(reader.read_i32()?)));
+                }
+
+                Operands::LookupSwitch { default, pairs }
+            }
+        }
+        WIDE => {
+            let wide_opcode = reader.read_u8()?;
+            if !matches!(wide_opcode, I_LOAD..=A_LOAD | I_STORE..=A_STORE | RET | I_INC) {
+                return Err(DecodeError::InvalidOpcode(wide_opcode));
+            }
+
+            let index = reader.read_u16()?;
+
+            let constant = if wide_opcode == I_INC {
+                Some(reader.read_u16()? as i16)
+            } else {
+                None
+            };
+
+            Operands::Wide {
+                opcode: wide_opcode,
+                index,
+                constant,
+            }
+        }
+        _ => Operands::None,
+    };
+
+    Ok(Insn {
+        offset,
+        opcode,
+        operands,
+    })
+}
@@ -0,0 +1,44 @@
+use rjvm_core::ConsoleBackend;
+
+use std::io::{self, ErrorKind, Read, Write};
+
+pub struct DesktopConsoleBackend {}
+
+impl DesktopConsoleBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+// Like `PrintStream`, errors while writing the output are ignored, so that a
+// closed pipe (as with `rjvm_desktop Main | head`) doesn't crash the JVM.
+fn ignore_error(_result: io::Result<()>) {}
+
+impl ConsoleBackend for DesktopConsoleBackend {
+    fn write_stdout(&self, data: &[u8]) {
+        ignore_error(io::stdout().write_all(data));
+    }
+
+    fn write_stderr(&self, data: &[u8]) {
+        ignore_error(io::stderr().write_all(data));
+    }
+
+    fn flush_stdout(&self) {
+        ignore_error(io::stdout().flush());
+    }
+
+    fn flush_stderr(&self) {
+        ignore_error(io::stderr().flush());
+    }
+
+    fn read_stdin(&self, buf: &mut [u8]) -> usize {
+        loop {
+            match io::stdin().read(buf) {
+                Ok(count) => return count,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                // Treat input that can't be read as having ended
+                Err(_) => return 0,
+            }
+        }
+    }
+}
@@ -0,0 +1,448 @@
+// The `coverage` command, which scans the classes of a JAR for references to
+// JDK classes and members that the builtin globals don't implement, either
+// because they're missing entirely or because they're stubbed out using
+// `rjvm.internal.Todo`.
+
+use crate::bytecode::{self, Operands};
+use crate::verify::{find_method, has_field, read_jar};
+
+use rjvm_core::classfile::attribute::{Attribute, AttributeInfo, BootstrapMethod};
+use rjvm_core::classfile::constant_pool::{ConstantPool, ConstantPoolEntry, MethodHandle};
+use rjvm_core::classfile::opcodes::INVOKE_STATIC;
+use rjvm_core::{ClassFile, Context, Descriptor, JvmString, Method, MethodDescriptor};
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+pub fn run(program_name: &str, args: &[String]) -> bool {
+    let [jar_name] = args else {
+        eprintln!("Run as {program_name} coverage app.jar");
+        return false;
+    };
+
+    let mut success = false;
+    crate::with_globals(|context| {
+        let Some(jar) = read_jar(context, jar_name) else {
+            return;
+        };
+
+        // The application's classes are loaded to find the methods they
+        // inherit from JDK classes
+        context.add_system_jar(jar);
+
+        let mut checker = Checker::new(context);
+        for class_name in crate::jar_class_names(jar) {
+            let data = jar
+                .read_file(format!("{}.class", class_name))
+                .expect("Listed file should exist");
+
+            match context.parse_class_file(&data) {
+                Ok(class_file) => checker.check_class(class_file),
+                Err(error) => eprintln!("Failed to parse {}: {:?}", class_name, error),
+            }
+        }
+
+        checker.print_report();
+
+        success = !checker.has_missing_apis();
+    });
+
+    success
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ProblemKind {
+    MissingClass,
+    MissingField,
+    MissingMethod,
+    NotImplemented,
+    PartiallyImplemented,
+}
+
+impl fmt::Display for ProblemKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            ProblemKind::MissingClass => "missing class",
+            ProblemKind::MissingField => "missing field",
+            ProblemKind::MissingMethod => "missing method",
+            ProblemKind::NotImplemented => "not implemented",
+            ProblemKind::PartiallyImplemented => "partially implemented",
+        };
+
+        write!(f, "{}", description)
+    }
+}
+
+// A class or member referenced by the scanned code.
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum Reference {
+    Class(JvmString),
+    Field(JvmString, JvmString, JvmString),
+    Method(JvmString, JvmString, JvmString),
+}
+
+impl Reference {
+    fn owner(&self) -> JvmString {
+        match self {
+            Reference::Class(class_name) => *class_name,
+            Reference::Field(owner, _, _) | Reference::Method(owner, _, _) => *owner,
+        }
+    }
+}
+
+struct Checker<'a> {
+    context: &'a Context,
+
+    // The result of checking each reference seen so far: the package of the
+    // unsupported API, the kind of problem, and the name of the API, if any
+    checked: HashMap<Reference, Option<(String, ProblemKind, String)>>,
+
+    // The number of call sites of each unsupported API, grouped by package
+    report: BTreeMap<String, BTreeMap<(ProblemKind, String), usize>>,
+}
+
+impl<'a> Checker<'a> {
+    fn new(context: &'a Context) -> Self {
+        Self {
+            context,
+            checked: HashMap::new(),
+            report: BTreeMap::new(),
+        }
+    }
+
+    fn check_class(&mut self, class_file: ClassFile) {
+        let constant_pool = class_file.constant_pool();
+
+        // The superclass and interfaces each count as a single use
+        if let Some(super_class) = class_file.super_class() {
+            self.record(Reference::Class(super_class));
+        }
+        for interface in class_file.interfaces() {
+            self.record(Reference::Class(*interface));
+        }
+
+        let bootstrap_methods =
+            match find_attribute(constant_pool, class_file.attributes(), "BootstrapMethods") {
+                Some(AttributeInfo::BootstrapMethods(bootstrap_methods)) => bootstrap_methods,
+                _ => Box::new([]),
+            };
+
+        for method in class_file.methods() {
+            let Some(AttributeInfo::Code(code)) =
+                find_attribute(constant_pool, method.attributes(), "Code")
+            else {
+                continue;
+            };
+
+            for entry in &code.exception_table {
+                if let Some(catch_type) = entry.catch_type {
+                    self.record(Reference::Class(catch_type));
+                }
+            }
+
+            // Code that can't be decoded is reported by the `verify` command;
+            // just check the instructions before the invalid one
+            let (insns, _) = bytecode::decode(&code.code);
+            for insn in insns {
+                let reference = insn.constant_index().and_then(|index| {
+                    entry_reference(constant_pool, index)
+                        .or_else(|| bootstrap_reference(constant_pool, &bootstrap_methods, index))
+                });
+
+                if let Some(reference) = reference {
+                    self.record(reference);
+                }
+            }
+        }
+    }
+
+    // Count a use of `reference`, if it's unsupported.
+    fn record(&mut self, reference: Reference) {
+        let problem = if let Some(problem) = self.checked.get(&reference) {
+            problem.clone()
+        } else {
+            let problem = self.check_reference(&reference);
+            self.checked.insert(reference, problem.clone());
+
+            problem
+        };
+
+        if let Some((package, kind, name)) = problem {
+            *self
+                .report
+                .entry(package)
+                .or_default()
+                .entry((kind, name))
+                .or_default() += 1;
+        }
+    }
+
+    fn check_reference(&self, reference: &Reference) -> Option<(String, ProblemKind, String)> {
+        let owner = reference.owner();
+
+        // Array classes are supported if their element class is
+        let element_name = owner.trim_start_matches('[');
+        let is_array = element_name.len() != owner.len();
+        let element_name = if is_array {
+            element_name.strip_prefix('L')?.strip_suffix(';')?
+        } else {
+            element_name
+        };
+
+        if !is_jdk_class(element_name) {
+            if is_array {
+                return None;
+            }
+
+            return self.check_inherited_method(reference);
+        }
+
+        let package = match element_name.rsplit_once('/') {
+            Some((package, _)) => package.replace('/', "."),
+            None => String::new(),
+        };
+
+        let element_name = JvmString::new(self.context.gc_ctx(), element_name.to_string());
+        let Ok(Some(class)) = self
+            .context
+            .bootstrap_loader()
+            .load_class(self.context, element_name)
+        else {
+            let class_name = element_name.to_string();
+
+            return Some((package, ProblemKind::MissingClass, class_name));
+        };
+
+        // Members of array classes are those of `Object`, which are present
+        if is_array {
+            return None;
+        }
+
+        let (kind, name) = match *reference {
+            Reference::Class(_) => return None,
+            Reference::Field(_, name, descriptor) => {
+                let found = Descriptor::try_from_string(self.context, descriptor)
+                    .is_some_and(|d| has_field(class, name, d));
+
+                if found {
+                    return None;
+                }
+
+                let field_name = format!("{}.{}:{}", owner, name, descriptor);
+
+                (ProblemKind::MissingField, field_name)
+            }
+            Reference::Method(_, name, descriptor) => {
+                let method_name = format!("{}.{}:{}", owner, name, descriptor);
+
+                let method = MethodDescriptor::from_string(self.context, descriptor)
+                    .ok()
+                    .and_then(|d| find_method(class, name, d));
+
+                match method {
+                    Some(method) => (stub_kind(method)?, method_name),
+                    None => (ProblemKind::MissingMethod, method_name),
+                }
+            }
+        };
+
+        Some((package, kind, name))
+    }
+
+    // Methods of the application's classes may be inherited from JDK classes,
+    // in which case the JDK method may be a stub.
+    fn check_inherited_method(
+        &self,
+        reference: &Reference,
+    ) -> Option<(String, ProblemKind, String)> {
+        let Reference::Method(owner, name, descriptor) = *reference else {
+            return None;
+        };
+
+        let class = self
+            .context
+            .system_loader()
+            .load_class(self.context, owner)
+            .ok()??;
+
+        let descriptor = MethodDescriptor::from_string(self.context, descriptor).ok()?;
+        let method = find_method(class, name, descriptor)?;
+
+        let declaring_class = method.class().name();
+        if !is_jdk_class(&declaring_class) {
+            return None;
+        }
+
+        let kind = stub_kind(method)?;
+
+        let package = match declaring_class.rsplit_once('/') {
+            Some((package, _)) => package.replace('/', "."),
+            None => String::new(),
+        };
+
+        let method_name = format!("{}.{}:{}", declaring_class, name, descriptor);
+
+        Some((package, kind, method_name))
+    }
+
+    fn has_missing_apis(&self) -> bool {
+        self.report.values().flatten().any(|((kind, _), _)| {
+            matches!(
+                kind,
+                ProblemKind::MissingClass | ProblemKind::MissingField | ProblemKind::MissingMethod
+            )
+        })
+    }
+
+    fn print_report(&self) {
+        let mut total_uses = 0;
+        let mut total_apis = 0;
+
+        for (package, problems) in &self.report {
+            let package_uses = problems.values().sum::<usize>();
+            println!("{} ({})", package, format_uses(package_uses));
+
+            for ((kind, name), uses) in problems {
+                println!("  {} {} ({})", kind, name, format_uses(*uses));
+            }
+
+            total_uses += package_uses;
+            total_apis += problems.len();
+        }
+
+        println!(
+            "Found {} of {} unsupported APIs",
+            format_uses(total_uses),
+            total_apis
+        );
+    }
+}
+
+fn format_uses(uses: usize) -> String {
+    if uses == 1 {
+        "1 use".to_string()
+    } else {
+        format!("{} uses", uses)
+    }
+}
+
+fn is_jdk_class(class_name: &str) -> bool {
+    class_name.starts_with("java/") || class_name.starts_with("javax/")
+}
+
+fn find_attribute(
+    constant_pool: &ConstantPool,
+    attributes: &[Attribute],
+    name: &str,
+) -> Option<AttributeInfo> {
+    attributes
+        .iter()
+        .find(|a| &*a.name() == name)
+        .and_then(|a| a.parse(constant_pool).ok())
+}
+
+// Returns the class or member referred to by the constant pool entry at
+// `index`, if it refers to one.
+fn entry_reference(constant_pool: &ConstantPool, index: u16) -> Option<Reference> {
+    match constant_pool.entry(index).ok()? {
+        ConstantPoolEntry::Class { .. } => {
+            let class_name = constant_pool.get_class(index).ok()?;
+
+            Some(Reference::Class(class_name))
+        }
+        ConstantPoolEntry::FieldRef { .. } => {
+            let (owner, name, descriptor) = constant_pool.get_field_ref(index).ok()?;
+
+            Some(Reference::Field(owner, name, descriptor))
+        }
+        ConstantPoolEntry::MethodRef { .. } | ConstantPoolEntry::InterfaceMethodRef { .. } => {
+            let (owner, name, descriptor) = constant_pool.get_any_method_ref(index).ok()?;
+
+            Some(Reference::Method(owner, name, descriptor))
+        }
+        ConstantPoolEntry::MethodHandle { method_handle } => {
+            let reference_index = match method_handle {
+                MethodHandle::GetField(index)
+                | MethodHandle::GetStatic(index)
+                | MethodHandle::PutField(index)
+                | MethodHandle::PutStatic(index)
+                | MethodHandle::InvokeVirtual(index)
+                | MethodHandle::InvokeStatic(index)
+                | MethodHandle::InvokeSpecial(index)
+                | MethodHandle::NewInvokeSpecial(index)
+                | MethodHandle::InvokeInterface(index) => index,
+            };
+
+            entry_reference(constant_pool, reference_index)
+        }
+        _ => None,
+    }
+}
+
+// Returns the bootstrap method of the `Dynamic` or `InvokeDynamic` entry at
+// `index`, which is what an `ldc` or `invokedynamic` instruction depends on.
+fn bootstrap_reference(
+    constant_pool: &ConstantPool,
+    bootstrap_methods: &[BootstrapMethod],
+    index: u16,
+) -> Option<Reference> {
+    let (ConstantPoolEntry::Dynamic {
+        bootstrap_method_idx,
+        ..
+    }
+    | ConstantPoolEntry::InvokeDynamic {
+        bootstrap_method_idx,
+        ..
+    }) = constant_pool.entry(index).ok()?
+    else {
+        return None;
+    };
+
+    let bootstrap_method = bootstrap_methods.get(bootstrap_method_idx as usize)?;
+
+    entry_reference(constant_pool, bootstrap_method.method_ref)
+}
+
+// Whether the body of `method` warns that it isn't (fully) implemented, by
+// calling `rjvm.internal.Todo`.
+fn stub_kind(method: Method) -> Option<ProblemKind> {
+    let class_file = (*method.class().class_file())?;
+    let constant_pool = class_file.constant_pool();
+
+    let descriptor = method.descriptor().to_string();
+    let method_data = class_file
+        .methods()
+        .iter()
+        .find(|m| m.name() == method.name() && *m.descriptor() == *descriptor)?;
+
+    let Some(AttributeInfo::Code(code)) =
+        find_attribute(constant_pool, method_data.attributes(), "Code")
+    else {
+        return None;
+    };
+
+    let mut stub_kind = None;
+    for insn in bytecode::decode(&code.code).0 {
+        let Operands::Constant(index) = insn.operands else {
+            continue;
+        };
+
+        if insn.opcode != INVOKE_STATIC {
+            continue;
+        }
+
+        let Ok((owner, name, _)) = constant_pool.get_any_method_ref(index) else {
+            continue;
+        };
+
+        if &*owner == "rjvm/internal/Todo" {
+            match &*name {
+                "warnNotImpl" => return Some(ProblemKind::NotImplemented),
+                "warnPartialImpl" => stub_kind = Some(ProblemKind::PartiallyImplemented),
+                _ => {}
+            }
+        }
+    }
+
+    stub_kind
+}
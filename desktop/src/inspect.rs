@@ -0,0 +1,734 @@
+// The `inspect` command, which prints the contents of class files in a format
+// similar to `javap -v`, along with the result of verifying each method on
+// this VM.
+
+use crate::bytecode::{self, DecodeError, Insn, Operands};
+use crate::verify::{error_message, verify_methods};
+
+use rjvm_core::classfile::attribute::{Attribute, AttributeInfo, CodeAttribute};
+use rjvm_core::classfile::constant_pool::{ConstantPool, ConstantPoolEntry, MethodHandle};
+use rjvm_core::classfile::opcodes::{self, *};
+use rjvm_core::{Class, Context, Error, Jar, JvmString, ResourceLoadSource};
+
+use std::fs;
+use std::io::{self, ErrorKind, Write};
+
+pub fn run(program_name: &str, args: &[String]) {
+    let (file_name, class_name) = match args {
+        [file_name] => (file_name, None),
+        [file_name, class_name] => (file_name, Some(class_name.replace('.', "/"))),
+        _ => {
+            eprintln!("Run as {program_name} inspect file.class|file.jar [com.example.MyClass]");
+            return;
+        }
+    };
+
+    let data = match fs::read(file_name) {
+        Ok(data) => data,
+        Err(error) => {
+            eprintln!("Failed to read {}: {}", file_name, error);
+            return;
+        }
+    };
+
+    crate::with_globals(|context| {
+        let mut out = io::stdout().lock();
+
+        let result = if file_name.ends_with(".jar") {
+            inspect_jar(&mut out, context, file_name, data, class_name)
+        } else {
+            inspect_class_file(&mut out, context, file_name, data)
+        };
+
+        // Stop quietly if the output was closed early, e.g. when piped into
+        // `head`
+        match result.and_then(|()| out.flush()) {
+            Ok(()) => {}
+            Err(error) if error.kind() == ErrorKind::BrokenPipe => {}
+            Err(error) => eprintln!("Failed to write output: {}", error),
+        }
+    });
+}
+
+fn inspect_jar(
+    out: &mut impl Write,
+    context: &Context,
+    file_name: &str,
+    data: Vec<u8>,
+    class_name: Option<String>,
+) -> io::Result<()> {
+    let Ok(jar) = Jar::from_bytes(context.gc_ctx(), data) else {
+        eprintln!("\"{}\" was not a valid JAR", file_name);
+        return Ok(());
+    };
+
+    // Make the other classes in the JAR available when verifying
+    context.add_system_jar(jar);
+
+    let class_names = if let Some(class_name) = class_name {
+        vec![class_name]
+    } else {
+        crate::jar_class_names(jar)
+    };
+
+    for (i, class_name) in class_names.into_iter().enumerate() {
+        if i != 0 {
+            writeln!(out)?;
+        }
+
+        let entry_name = format!("{}.class", class_name);
+        let Ok(data) = jar.read_file(entry_name.clone()) else {
+            eprintln!("Class {} not found in JAR", class_name);
+            continue;
+        };
+
+        let class_name = JvmString::new(context.gc_ctx(), class_name);
+        let loaded = context.system_loader().lookup_class(context, class_name);
+
+        inspect_class(out, context, &entry_name, &data, loaded)?;
+    }
+
+    Ok(())
+}
+
+fn inspect_class_file(
+    out: &mut impl Write,
+    context: &Context,
+    file_name: &str,
+    data: Vec<u8>,
+) -> io::Result<()> {
+    // Classes referenced by this one are looked up relative to the working
+    // directory, as when running a class file
+    let loader = context.system_loader();
+    loader.add_source(ResourceLoadSource::FileSystem);
+
+    let loaded = Class::from_data(context, loader, &data).and_then(|class| {
+        loader.define_class(context, class)?;
+
+        Ok(class)
+    });
+
+    inspect_class(out, context, file_name, &data, loaded)
+}
+
+fn inspect_class(
+    out: &mut impl Write,
+    context: &Context,
+    file_name: &str,
+    data: &[u8],
+    loaded: Result<Class, Error>,
+) -> io::Result<()> {
+    // If the class loaded, use its `ClassFile`; otherwise parse the data again
+    // so the class can still be disassembled
+    let (class_file, verify_results) = match loaded {
+        Ok(class) => {
+            let verify_results = verify_methods(context, class)
+                .into_iter()
+                .map(|result| result.map_err(|error| error_message(&error)))
+                .collect::<Vec<_>>();
+
+            (class.class_file().unwrap(), Ok(verify_results))
+        }
+        Err(error) => {
+            let message = error_message(&error);
+
+            match context.parse_class_file(data) {
+                Ok(class_file) => (class_file, Err(message)),
+                Err(_) => {
+                    writeln!(out, "Classfile {}", file_name)?;
+                    writeln!(out, "  Failed to load: {}", message)?;
+                    return Ok(());
+                }
+            }
+        }
+    };
+
+    let constant_pool = class_file.constant_pool();
+
+    writeln!(out, "Classfile {}", file_name)?;
+    writeln!(out, "  minor version: {}", class_file.minor_version())?;
+    writeln!(out, "  major version: {}", class_file.major_version())?;
+    writeln!(
+        out,
+        "  flags: {}",
+        format_flags(class_file.flags().bits(), class_file.flags().iter_names())
+    )?;
+    writeln!(out, "  this_class: {}", class_file.this_class())?;
+    if let Some(super_class) = class_file.super_class() {
+        writeln!(out, "  super_class: {}", super_class)?;
+    }
+    for interface in class_file.interfaces() {
+        writeln!(out, "  interface: {}", interface)?;
+    }
+    if let Err(message) = &verify_results {
+        writeln!(out, "  Failed to load: {}", message)?;
+    }
+
+    writeln!(out, "Constant pool:")?;
+    for index in 1..=constant_pool.entry_count() {
+        let entry = constant_pool.entry(index).unwrap();
+        if matches!(entry, ConstantPoolEntry::Placeholder) {
+            continue;
+        }
+
+        let (kind, value) = format_entry(entry);
+        let comment = describe_entry(constant_pool, entry);
+
+        let line = format!("{:>6} = {:<18} {:<14}", format!("#{}", index), kind, value);
+        if let Some(comment) = comment {
+            writeln!(out, "{} // {}", line, comment)?;
+        } else {
+            writeln!(out, "{}", line.trim_end())?;
+        }
+    }
+
+    writeln!(out, "Fields:")?;
+    for field in class_file.fields() {
+        writeln!(out, "  {}:{}", field.name(), field.descriptor())?;
+        writeln!(
+            out,
+            "    flags: {}",
+            format_flags(field.flags().bits(), field.flags().iter_names())
+        )?;
+        print_attributes(out, constant_pool, field.attributes(), 4)?;
+    }
+
+    writeln!(out, "Methods:")?;
+    for (i, method) in class_file.methods().iter().enumerate() {
+        writeln!(
+            out,
+            "  {}:{}",
+            quote_name(method.name()),
+            method.descriptor()
+        )?;
+        writeln!(
+            out,
+            "    flags: {}",
+            format_flags(method.flags().bits(), method.flags().iter_names())
+        )?;
+        print_attributes(out, constant_pool, method.attributes(), 4)?;
+
+        match &verify_results {
+            Ok(results) => match &results[i] {
+                Ok(()) => writeln!(out, "    Verification: OK")?,
+                Err(message) => writeln!(out, "    Verification: {}", message)?,
+            },
+            Err(_) => writeln!(out, "    Verification: skipped, class failed to load")?,
+        }
+    }
+
+    writeln!(out, "Attributes:")?;
+    print_attributes(out, constant_pool, class_file.attributes(), 2)
+}
+
+fn format_flags<'a, T>(bits: u16, names: impl Iterator<Item = (&'a str, T)>) -> String {
+    let names = names
+        .map(|(name, _)| format!("ACC_{}", name))
+        .collect::<Vec<_>>();
+
+    format!("(0x{:04x}) {}", bits, names.join(", "))
+}
+
+// Method names like `<init>` are quoted, as `javap` does.
+fn quote_name(name: JvmString) -> String {
+    if name.starts_with('<') {
+        format!("\"{}\"", name)
+    } else {
+        name.to_string()
+    }
+}
+
+// Returns the kind of a constant pool entry and its operands.
+fn format_entry(entry: ConstantPoolEntry) -> (&'static str, String) {
+    match entry {
+        ConstantPoolEntry::Placeholder => unreachable!(),
+        ConstantPoolEntry::Utf8 { string } => ("Utf8", string.to_string()),
+        ConstantPoolEntry::Integer { value } => ("Integer", value.to_string()),
+        ConstantPoolEntry::Float { value } => ("Float", format!("{:?}f", value)),
+        ConstantPoolEntry::Long { value } => ("Long", format!("{}l", value)),
+        ConstantPoolEntry::Double { value } => ("Double", format!("{:?}d", value)),
+        ConstantPoolEntry::Class { name_idx } => ("Class", format!("#{}", name_idx)),
+        ConstantPoolEntry::String { string_idx } => ("String", format!("#{}", string_idx)),
+        ConstantPoolEntry::FieldRef {
+            class_idx,
+            name_and_type_idx,
+        } => ("Fieldref", format!("#{}.#{}", class_idx, name_and_type_idx)),
+        ConstantPoolEntry::MethodRef {
+            class_idx,
+            name_and_type_idx,
+        } => (
+            "Methodref",
+            format!("#{}.#{}", class_idx, name_and_type_idx),
+        ),
+        ConstantPoolEntry::InterfaceMethodRef {
+            class_idx,
+            name_and_type_idx,
+        } => (
+            "InterfaceMethodref",
+            format!("#{}.#{}", class_idx, name_and_type_idx),
+        ),
+        ConstantPoolEntry::NameAndType {
+            name_idx,
+            descriptor_idx,
+        } => ("NameAndType", format!("#{}:#{}", name_idx, descriptor_idx)),
+        ConstantPoolEntry::MethodHandle { method_handle } => {
+            let (kind, reference_idx) = method_handle_kind(method_handle);
+
+            ("MethodHandle", format!("{}:#{}", kind, reference_idx))
+        }
+        ConstantPoolEntry::MethodType { descriptor_idx } => {
+            ("MethodType", format!("#{}", descriptor_idx))
+        }
+        ConstantPoolEntry::Dynamic {
+            bootstrap_method_idx,
+            name_and_type_idx,
+        } => (
+            "Dynamic",
+            format!("#{}:#{}", bootstrap_method_idx, name_and_type_idx),
+        ),
+        ConstantPoolEntry::InvokeDynamic {
+            bootstrap_method_idx,
+            name_and_type_idx,
+        } => (
+            "InvokeDynamic",
+            format!("#{}:#{}", bootstrap_method_idx, name_and_type_idx),
+        ),
+    }
+}
+
+fn method_handle_kind(method_handle: MethodHandle) -> (&'static str, u16) {
+    match method_handle {
+        MethodHandle::GetField(idx) => ("REF_getField", idx),
+        MethodHandle::GetStatic(idx) => ("REF_getStatic", idx),
+        MethodHandle::PutField(idx) => ("REF_putField", idx),
+        MethodHandle::PutStatic(idx) => ("REF_putStatic", idx),
+        MethodHandle::InvokeVirtual(idx) => ("REF_invokeVirtual", idx),
+        MethodHandle::InvokeStatic(idx) => ("REF_invokeStatic", idx),
+        MethodHandle::InvokeSpecial(idx) => ("REF_invokeSpecial", idx),
+        MethodHandle::NewInvokeSpecial(idx) => ("REF_newInvokeSpecial", idx),
+        MethodHandle::InvokeInterface(idx) => ("REF_invokeInterface", idx),
+    }
+}
+
+// Returns the resolved form of a constant pool entry, for entries that refer
+// to other entries.
+fn describe_entry(constant_pool: &ConstantPool, entry: ConstantPoolEntry) -> Option<String> {
+    // The constant pool was validated when it was parsed, so every index it
+    // contains refers to an entry of the right type
+    let utf8 = |idx| constant_pool.get_utf8(idx).unwrap();
+
+    let description = match entry {
+        ConstantPoolEntry::Placeholder
+        | ConstantPoolEntry::Utf8 { .. }
+        | ConstantPoolEntry::Integer { .. }
+        | ConstantPoolEntry::Float { .. }
+        | ConstantPoolEntry::Long { .. }
+        | ConstantPoolEntry::Double { .. } => return None,
+        ConstantPoolEntry::Class { name_idx } => utf8(name_idx).to_string(),
+        ConstantPoolEntry::String { string_idx } => utf8(string_idx).to_string(),
+        ConstantPoolEntry::FieldRef {
+            class_idx,
+            name_and_type_idx,
+        }
+        | ConstantPoolEntry::MethodRef {
+            class_idx,
+            name_and_type_idx,
+        }
+        | ConstantPoolEntry::InterfaceMethodRef {
+            class_idx,
+            name_and_type_idx,
+        } => {
+            let class_name = constant_pool.get_class(class_idx).unwrap();
+            let (name, descriptor) = constant_pool.get_name_and_type(name_and_type_idx).unwrap();
+
+            format!("{}.{}:{}", class_name, quote_name(name), descriptor)
+        }
+        ConstantPoolEntry::NameAndType {
+            name_idx,
+            descriptor_idx,
+        } => format!("{}:{}", quote_name(utf8(name_idx)), utf8(descriptor_idx)),
+        ConstantPoolEntry::MethodHandle { method_handle } => {
+            let (kind, reference_idx) = method_handle_kind(method_handle);
+            let reference = constant_pool.entry(reference_idx).unwrap();
+
+            format!(
+                "{} {}",
+                kind,
+                describe_entry(constant_pool, reference).unwrap()
+            )
+        }
+        ConstantPoolEntry::MethodType { descriptor_idx } => utf8(descriptor_idx).to_string(),
+        ConstantPoolEntry::Dynamic {
+            bootstrap_method_idx,
+            name_and_type_idx,
+        }
+        | ConstantPoolEntry::InvokeDynamic {
+            bootstrap_method_idx,
+            name_and_type_idx,
+        } => {
+            let (name, descriptor) = constant_pool.get_name_and_type(name_and_type_idx).unwrap();
+
+            format!("#{}:{}:{}", bootstrap_method_idx, name, descriptor)
+        }
+    };
+
+    Some(description)
+}
+
+// Describe the constant pool entry at `index` as an instruction operand,
+// including the kind of the entry.
+fn describe_operand(constant_pool: &ConstantPool, index: u16) -> String {
+    let Ok(entry) = constant_pool.entry(index) else {
+        return "<invalid constant pool index>".to_string();
+    };
+
+    let kind = match entry {
+        ConstantPoolEntry::Placeholder => return "<invalid constant pool index>".to_string(),
+        ConstantPoolEntry::Utf8 { string } => return format!("Utf8 {}", string),
+        ConstantPoolEntry::Integer { value } => return format!("int {}", value),
+        ConstantPoolEntry::Float { value } => return format!("float {:?}f", value),
+        ConstantPoolEntry::Long { value } => return format!("long {}l", value),
+        ConstantPoolEntry::Double { value } => return format!("double {:?}d", value),
+        ConstantPoolEntry::Class { .. } => "class",
+        ConstantPoolEntry::String { .. } => "String",
+        ConstantPoolEntry::FieldRef { .. } => "Field",
+        ConstantPoolEntry::MethodRef { .. } => "Method",
+        ConstantPoolEntry::InterfaceMethodRef { .. } => "InterfaceMethod",
+        ConstantPoolEntry::NameAndType { .. } => "NameAndType",
+        ConstantPoolEntry::MethodHandle { .. } => "MethodHandle",
+        ConstantPoolEntry::MethodType { .. } => "MethodType",
+        ConstantPoolEntry::Dynamic { .. } => "Dynamic",
+        ConstantPoolEntry::InvokeDynamic { .. } => "InvokeDynamic",
+    };
+
+    format!("{} {}", kind, describe_entry(constant_pool, entry).unwrap())
+}
+
+fn print_attributes(
+    out: &mut impl Write,
+    constant_pool: &ConstantPool,
+    attributes: &[Attribute],
+    indent: usize,
+) -> io::Result<()> {
+    let pad = " ".repeat(indent);
+
+    for attribute in attributes {
+        let info = match attribute.parse(constant_pool) {
+            Ok(info) => info,
+            Err(error) => {
+                writeln!(
+                    out,
+                    "{}{}: invalid attribute ({:?})",
+                    pad,
+                    attribute.name(),
+                    error
+                )?;
+                continue;
+            }
+        };
+
+        match info {
+            AttributeInfo::Code(code) => print_code(out, constant_pool, &code, indent)?,
+            AttributeInfo::ConstantValue(idx) => {
+                writeln!(
+                    out,
+                    "{}ConstantValue: {}",
+                    pad,
+                    describe_operand(constant_pool, idx)
+                )?;
+            }
+            AttributeInfo::Exceptions(classes) => {
+                let classes = classes.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+                writeln!(out, "{}Exceptions: throws {}", pad, classes.join(", "))?;
+            }
+            AttributeInfo::InnerClasses(inner_classes) => {
+                writeln!(out, "{}InnerClasses:", pad)?;
+                for inner_class in inner_classes {
+                    let outer_class = inner_class.outer_class.map(|c| c.to_string());
+                    let inner_name = inner_class.inner_name.map(|n| n.to_string());
+
+                    writeln!(
+                        out,
+                        "{}  {} (outer: {}, name: {}) flags: {}",
+                        pad,
+                        inner_class.inner_class,
+                        outer_class.as_deref().unwrap_or("none"),
+                        inner_name.as_deref().unwrap_or("anonymous"),
+                        format_flags(inner_class.flags.bits(), inner_class.flags.iter_names())
+                    )?;
+                }
+            }
+            AttributeInfo::EnclosingMethod { class, method } => {
+                if let Some((name, descriptor)) = method {
+                    writeln!(
+                        out,
+                        "{}EnclosingMethod: {}.{}:{}",
+                        pad,
+                        class,
+                        quote_name(name),
+                        descriptor
+                    )?;
+                } else {
+                    writeln!(out, "{}EnclosingMethod: {}", pad, class)?;
+                }
+            }
+            AttributeInfo::Synthetic => writeln!(out, "{}Synthetic: true", pad)?,
+            AttributeInfo::Deprecated => writeln!(out, "{}Deprecated: true", pad)?,
+            AttributeInfo::Signature(signature) => {
+                writeln!(out, "{}Signature: {}", pad, signature)?
+            }
+            AttributeInfo::SourceFile(source_file) => {
+                writeln!(out, "{}SourceFile: \"{}\"", pad, source_file)?
+            }
+            AttributeInfo::LineNumberTable(line_numbers) => {
+                writeln!(out, "{}LineNumberTable:", pad)?;
+                for line_number in line_numbers {
+                    writeln!(
+                        out,
+                        "{}  line {}: {}",
+                        pad, line_number.line_number, line_number.start_pc
+                    )?;
+                }
+            }
+            AttributeInfo::LocalVariableTable(variables)
+            | AttributeInfo::LocalVariableTypeTable(variables) => {
+                writeln!(out, "{}{}:", pad, attribute.name())?;
+                writeln!(out, "{}  Start  Length  Slot  Name   Signature", pad)?;
+                for variable in variables {
+                    writeln!(
+                        out,
+                        "{}  {:>5}  {:>6}  {:>4}  {:<6} {}",
+                        pad,
+                        variable.start_pc,
+                        variable.length,
+                        variable.index,
+                        variable.name,
+                        variable.descriptor
+                    )?;
+                }
+            }
+            AttributeInfo::BootstrapMethods(bootstrap_methods) => {
+                writeln!(out, "{}BootstrapMethods:", pad)?;
+                for (i, bootstrap_method) in bootstrap_methods.iter().enumerate() {
+                    writeln!(
+                        out,
+                        "{}  {}: #{} {}",
+                        pad,
+                        i,
+                        bootstrap_method.method_ref,
+                        describe_operand(constant_pool, bootstrap_method.method_ref)
+                    )?;
+                    writeln!(out, "{}    Method arguments:", pad)?;
+                    for argument in &bootstrap_method.arguments {
+                        writeln!(
+                            out,
+                            "{}      #{} {}",
+                            pad,
+                            argument,
+                            describe_operand(constant_pool, *argument)
+                        )?;
+                    }
+                }
+            }
+            AttributeInfo::NestHost(host) => writeln!(out, "{}NestHost: class {}", pad, host)?,
+            AttributeInfo::NestMembers(classes) | AttributeInfo::PermittedSubclasses(classes) => {
+                writeln!(out, "{}{}:", pad, attribute.name())?;
+                for class in classes {
+                    writeln!(out, "{}  {}", pad, class)?;
+                }
+            }
+            AttributeInfo::Record(components) => {
+                writeln!(out, "{}Record:", pad)?;
+                for component in components {
+                    writeln!(out, "{}  {}:{}", pad, component.name, component.descriptor)?;
+                    print_attributes(out, constant_pool, &component.attributes, indent + 4)?;
+                }
+            }
+            AttributeInfo::MethodParameters(parameters) => {
+                writeln!(out, "{}MethodParameters:", pad)?;
+                for parameter in parameters {
+                    let name = parameter.name.map(|n| n.to_string());
+
+                    writeln!(
+                        out,
+                        "{}  {} {}",
+                        pad,
+                        name.as_deref().unwrap_or("<no name>"),
+                        format_flags(parameter.flags.bits(), parameter.flags.iter_names())
+                    )?;
+                }
+            }
+            AttributeInfo::RuntimeVisibleAnnotations(annotations)
+            | AttributeInfo::RuntimeInvisibleAnnotations(annotations) => {
+                writeln!(out, "{}{}:", pad, attribute.name())?;
+                for annotation in annotations {
+                    writeln!(out, "{}  {}", pad, annotation.type_name)?;
+                }
+            }
+            AttributeInfo::RuntimeVisibleParameterAnnotations(parameters)
+            | AttributeInfo::RuntimeInvisibleParameterAnnotations(parameters) => {
+                writeln!(out, "{}{}:", pad, attribute.name())?;
+                for (i, annotations) in parameters.iter().enumerate() {
+                    for annotation in annotations {
+                        writeln!(out, "{}  parameter {}: {}", pad, i, annotation.type_name)?;
+                    }
+                }
+            }
+            AttributeInfo::AnnotationDefault(_) | AttributeInfo::Unknown => {
+                writeln!(
+                    out,
+                    "{}{}: length = {}",
+                    pad,
+                    attribute.name(),
+                    attribute.data().len()
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_code(
+    out: &mut impl Write,
+    constant_pool: &ConstantPool,
+    code: &CodeAttribute,
+    indent: usize,
+) -> io::Result<()> {
+    let pad = " ".repeat(indent);
+
+    writeln!(out, "{}Code:", pad)?;
+    writeln!(
+        out,
+        "{}  stack={}, locals={}, code_length={}",
+        pad,
+        code.max_stack,
+        code.max_locals,
+        code.code.len()
+    )?;
+
+    for line in disassemble(constant_pool, &code.code) {
+        writeln!(out, "{}  {}", pad, line)?;
+    }
+
+    if !code.exception_table.is_empty() {
+        writeln!(out, "{}  Exception table:", pad)?;
+        writeln!(out, "{}     from    to  target type", pad)?;
+        for entry in &code.exception_table {
+            let catch_type = match entry.catch_type {
+                Some(catch_type) => format!("Class {}", catch_type),
+                None => "any".to_string(),
+            };
+
+            writeln!(
+                out,
+                "{}    {:>5} {:>5} {:>5}   {}",
+                pad, entry.start_pc, entry.end_pc, entry.handler_pc, catch_type
+            )?;
+        }
+    }
+
+    print_attributes(out, constant_pool, &code.attributes, indent + 2)
+}
+
+// Disassemble `code` into one line per instruction (switch instructions take
+// up multiple lines), with branch targets written as absolute offsets.
+fn disassemble(constant_pool: &ConstantPool, code: &[u8]) -> Vec<String> {
+    let (insns, error) = bytecode::decode(code);
+
+    let mut lines = Vec::new();
+    for insn in insns {
+        let mut insn_lines = format_insn(constant_pool, &insn).into_iter();
+
+        lines.push(format!(
+            "{:>5}: {}",
+            insn.offset,
+            insn_lines.next().unwrap()
+        ));
+        lines.extend(insn_lines.map(|line| format!("       {}", line)));
+    }
+
+    match error {
+        Some((offset, DecodeError::InvalidOpcode(opcode))) => {
+            lines.push(format!("{:>5}: <invalid opcode 0x{:02x}>", offset, opcode));
+        }
+        Some((offset, DecodeError::Truncated)) => {
+            lines.push(format!("{:>5}: <truncated instruction>", offset));
+        }
+        None => {}
+    }
+
+    lines
+}
+
+fn format_insn(constant_pool: &ConstantPool, insn: &Insn) -> Vec<String> {
+    let name = insn.name();
+
+    let operands = match &insn.operands {
+        Operands::None => String::new(),
+        Operands::Int(value) => value.to_string(),
+        Operands::Constant(index) | Operands::InvokeDynamic(index) => format!("#{}", index),
+        Operands::InvokeInterface(index, count) | Operands::MultiANewArray(index, count) => {
+            format!("#{},  {}", index, count)
+        }
+        Operands::Local(index) => index.to_string(),
+        Operands::IInc(index, constant) => format!("{}, {}", index, constant),
+        Operands::Branch(target) => target.to_string(),
+        Operands::ArrayType(array_type) => match *array_type {
+            T_BOOLEAN => "boolean".to_string(),
+            T_CHAR => "char".to_string(),
+            T_FLOAT => "float".to_string(),
+            T_DOUBLE => "double".to_string(),
+            T_BYTE => "byte".to_string(),
+            T_SHORT => "short".to_string(),
+            T_INT => "int".to_string(),
+            T_LONG => "long".to_string(),
+            other => format!("<invalid array type {}>", other),
+        },
+        Operands::TableSwitch {
+            low,
+            high,
+            default,
+            targets,
+        } => {
+            let mut lines = vec![format!("{:<13} {{ // {} to {}", name, low, high)];
+            for (value, target) in (*low..=*high).zip(targets) {
+                lines.push(format!("{:>12}: {}", value, target));
+            }
+            lines.push(format!("{:>12}: {}", "default", default));
+            lines.push("    }".to_string());
+
+            return lines;
+        }
+        Operands::LookupSwitch { default, pairs } => {
+            let mut lines = vec![format!("{:<13} {{ // {}", name, pairs.len())];
+            for (value, target) in pairs {
+                lines.push(format!("{:>12}: {}", value, target));
+            }
+            lines.push(format!("{:>12}: {}", "default", default));
+            lines.push("    }".to_string());
+
+            return lines;
+        }
+        Operands::Wide {
+            opcode,
+            index,
+            constant,
+        } => {
+            let wide_name = opcodes::name(*opcode).unwrap();
+
+            match constant {
+                Some(constant) => format!("{} {}, {}", wide_name, index, constant),
+                None => format!("{} {}", wide_name, index),
+            }
+        }
+    };
+
+    let line = format!("{:<13} {}", name, operands);
+    let line = match insn.constant_index() {
+        Some(index) => format!("{:<32} // {}", line, describe_operand(constant_pool, index)),
+        None => line.trim_end().to_string(),
+    };
+
+    vec![line]
+}
@@ -1,13 +1,18 @@
-use rjvm_core::{
-    Class, Context, Jar, JvmString, MethodDescriptor, Object, ResourceLoadSource, Value,
-};
+use rjvm_core::{Class, Context, Jar, JvmString, ResourceLoadSource};
 use rjvm_globals::{GLOBALS_BASE_JAR, GLOBALS_DESKTOP_JAR, native_impl as base_native_impl};
 
+mod bytecode;
+mod console_backend;
+mod coverage;
+mod inspect;
 mod loader_backend;
-mod native_impl;
+mod platform;
+mod verify;
 
 use std::env;
 use std::fs;
+use std::process;
+use std::rc::Rc;
 
 enum FileType {
     Class,
@@ -224,22 +229,97 @@ fn init_main_class(context: &Context, options: &PassedOptions) -> Result<Class,
     Ok(main_class)
 }
 
+// Add the builtin global classes to the bootstrap loader and register their
+// native methods.
+fn load_globals(context: &Context) {
+    let globals_base_jar = Jar::from_bytes(context.gc_ctx(), GLOBALS_BASE_JAR.to_vec())
+        .expect("Builtin globals should be valid");
+    context.add_bootstrap_jar(globals_base_jar);
+    let globals_desktop_jar = Jar::from_bytes(context.gc_ctx(), GLOBALS_DESKTOP_JAR.to_vec())
+        .expect("Builtin globals should be valid");
+    context.add_bootstrap_jar(globals_desktop_jar);
+
+    let platform = Rc::new(platform::DesktopPlatform::new());
+    base_native_impl::register_native_mappings(context, platform);
+}
+
+// Run `func` on a new VM with the builtin globals loaded. This is used by the
+// commands that load classes without running them.
+fn with_globals(func: impl FnOnce(&Context)) {
+    let loader = loader_backend::DesktopLoaderBackend::new();
+    Context::init(Box::new(loader));
+
+    Context::with(|context| {
+        context.set_console_backend(Rc::new(console_backend::DesktopConsoleBackend::new()));
+        load_globals(context);
+        context.load_builtins();
+
+        func(context);
+    });
+
+    // SAFETY: The VM is not used after this point.
+    unsafe {
+        Context::clear();
+    }
+}
+
+// Returns the names of all classes in the JAR, sorted.
+fn jar_class_names(jar: Jar) -> Vec<String> {
+    let mut class_names = jar
+        .file_names()
+        .iter()
+        .filter_map(|name| name.strip_suffix(".class"))
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+    class_names.sort();
+
+    class_names
+}
+
 fn main() {
     let args: Vec<String> = env::args().collect();
 
     let program_name = args[0].clone();
 
+    match args.get(1).map(|arg| arg.as_str()) {
+        Some("inspect") => {
+            inspect::run(&program_name, &args[2..]);
+            return;
+        }
+        Some("coverage") => {
+            if !coverage::run(&program_name, &args[2..]) {
+                process::exit(1);
+            }
+            return;
+        }
+        Some("verify") => {
+            if !verify::run(&program_name, &args[2..]) {
+                process::exit(1);
+            }
+            return;
+        }
+        _ => {}
+    }
+
     let options = match PassedOptions::from_args(args) {
         Ok(Some(opts)) => opts,
         Ok(None) => {
             println!("Run as {program_name} [link options] [options] [args]
 or as {program_name} [link options] MyClass [args]
+or as {program_name} inspect file.class|file.jar [com.example.MyClass]
+or as {program_name} verify app.jar [--link library.jar]
+or as {program_name} coverage app.jar
 
 Options:
 --help: Show this message
 --jar file.jar: Run a JAR file instead of a class file
 --jar-with-main file.jar com.example.MainClass: Run a JAR file with an explicitly specified main class
 
+Commands:
+inspect: Disassemble a class file, or the classes of a JAR file, and verify their methods
+verify: Verify every class in a JAR file and check that everything it references exists
+coverage: List the JDK classes and members used by a JAR file that aren't implemented
+
 Link options:
 --link library.jar: Load a JAR file when loading this code (add it to classpath)
 --link-bootstrap rt.jar: Load this JAR file alongside the bootstrap classes
@@ -260,22 +340,15 @@ Advanced options:
     Context::init(Box::new(loader));
 
     Context::with(|context| {
+        context.set_console_backend(Rc::new(console_backend::DesktopConsoleBackend::new()));
+
         if let Some(gc_threshold) = options.gc_threshold {
             context.set_gc_threshold(gc_threshold);
         }
 
         // Load globals
         if options.load_globals {
-            let globals_base_jar = Jar::from_bytes(context.gc_ctx(), GLOBALS_BASE_JAR.to_vec())
-                .expect("Builtin globals should be valid");
-            context.add_bootstrap_jar(globals_base_jar);
-            let globals_desktop_jar =
-                Jar::from_bytes(context.gc_ctx(), GLOBALS_DESKTOP_JAR.to_vec())
-                    .expect("Builtin globals should be valid");
-            context.add_bootstrap_jar(globals_desktop_jar);
-
-            base_native_impl::register_native_mappings(&context);
-            native_impl::register_native_mappings(&context);
+            load_globals(&context);
         }
 
         // Load linked bootstrap JARs
@@ -334,47 +407,27 @@ Advanced options:
             }
         };
 
-        // Load program args
-        let mut program_args = Vec::new();
-        for arg in options.program_args {
-            let utf16_encoded = arg.encode_utf16().collect::<Box<_>>();
-
-            let string = context.create_string(&utf16_encoded);
-
-            program_args.push(Some(string));
-        }
-
-        let string_class = context.builtins().java_lang_string;
-        let args_array = Value::Object(Some(Object::obj_array(
-            &context,
-            string_class,
-            program_args.into_boxed_slice(),
-        )));
-
         // Call main method
-        let main_name = JvmString::new(context.gc_ctx(), "main".to_string());
-        let main_descriptor_name =
-            JvmString::new(context.gc_ctx(), "([Ljava/lang/String;)V".to_string());
-
-        let main_descriptor = MethodDescriptor::from_string(&context, main_descriptor_name)
-            .expect("Valid descriptor");
-
-        let method_idx = main_class
-            .static_method_vtable()
-            .lookup((main_name, main_descriptor));
+        let result = main_class.call_static::<()>(
+            &context,
+            "main",
+            "([Ljava/lang/String;)V",
+            (options.program_args,),
+        );
 
-        if let Some(method_idx) = method_idx {
-            let method = main_class.get_static_method(method_idx);
-            let result = context.exec_method(method, &[args_array]);
+        if let Err(error) = result {
+            let info = error.info(context);
 
-            if let Err(error) = result {
+            // `call_static` throws a `NoSuchMethodError` without running any
+            // Java code if the method doesn't exist
+            if info.class_name == "java.lang.NoSuchMethodError" && info.frames.is_empty() {
+                eprintln!(
+                    "Class {} has no `void main(String[] args)` method",
+                    main_class.dot_name()
+                );
+            } else {
                 eprint!("Error while running main: {}", error.display(&context));
             }
-        } else {
-            eprintln!(
-                "Class {} has no `void main(String[] args)` method",
-                main_class.dot_name()
-            );
         }
     });
 
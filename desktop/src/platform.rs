@@ -0,0 +1,194 @@
+use rjvm_globals::platform::{FileMetadata, OpenMode, Platform, PlatformError, SeekFrom};
+
+use std::cell::RefCell;
+use std::env;
+use std::fs;
+use std::io::{self, Read, Seek, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process;
+use std::time::{Instant, SystemTime};
+
+pub struct DesktopPlatform {
+    // The open files, indexed by handle. Closed files are `None`.
+    files: RefCell<Vec<Option<fs::File>>>,
+
+    // The time the VM started, which `nano_time` is relative to
+    start_time: Instant,
+}
+
+impl DesktopPlatform {
+    pub fn new() -> Self {
+        Self {
+            files: RefCell::new(Vec::new()),
+            start_time: Instant::now(),
+        }
+    }
+
+    fn with_file<T>(
+        &self,
+        handle: u32,
+        func: impl FnOnce(&mut fs::File) -> io::Result<T>,
+    ) -> Result<T, PlatformError> {
+        let mut files = self.files.borrow_mut();
+
+        match files.get_mut(handle as usize) {
+            Some(Some(file)) => func(file).map_err(platform_error),
+            _ => Err(PlatformError::new("Bad file descriptor")),
+        }
+    }
+}
+
+impl Platform for DesktopPlatform {
+    fn open(&self, path: &str, mode: OpenMode) -> Result<u32, PlatformError> {
+        // FIXME this sometimes returns Err when the file has a different owner
+        // even if it's actually writeable for us
+        let file = match mode {
+            OpenMode::Read => fs::File::open(path),
+            OpenMode::Write => fs::File::create(path),
+        };
+        let file = file.map_err(platform_error)?;
+
+        let mut files = self.files.borrow_mut();
+        files.push(Some(file));
+
+        Ok(files.len() as u32 - 1)
+    }
+
+    fn read(&self, handle: u32, buf: &mut [u8]) -> Result<usize, PlatformError> {
+        self.with_file(handle, |file| file.read(buf))
+    }
+
+    fn write(&self, handle: u32, data: &[u8]) -> Result<(), PlatformError> {
+        self.with_file(handle, |file| file.write_all(data))
+    }
+
+    fn flush(&self, handle: u32) -> Result<(), PlatformError> {
+        self.with_file(handle, |file| file.flush())
+    }
+
+    fn seek(&self, handle: u32, position: SeekFrom) -> Result<u64, PlatformError> {
+        let position = match position {
+            SeekFrom::Start(offset) => io::SeekFrom::Start(offset),
+            SeekFrom::End(offset) => io::SeekFrom::End(offset),
+            SeekFrom::Current(offset) => io::SeekFrom::Current(offset),
+        };
+
+        self.with_file(handle, |file| file.seek(position))
+    }
+
+    fn close(&self, handle: u32) -> Result<(), PlatformError> {
+        let mut files = self.files.borrow_mut();
+
+        match files.get_mut(handle as usize) {
+            Some(file @ Some(_)) => {
+                *file = None;
+
+                Ok(())
+            }
+            _ => Err(PlatformError::new("Bad file descriptor")),
+        }
+    }
+
+    fn stat(&self, path: &str) -> Option<FileMetadata> {
+        let metadata = fs::metadata(path).ok()?;
+
+        let last_modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_millis() as i64);
+
+        Some(FileMetadata {
+            is_directory: metadata.is_dir(),
+            length: metadata.len(),
+            last_modified,
+        })
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<String>, PlatformError> {
+        let entries = fs::read_dir(path).map_err(platform_error)?;
+
+        let mut names = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(platform_error)?;
+            names.push(entry.file_name().to_string_lossy().to_string());
+        }
+
+        Ok(names)
+    }
+
+    fn delete(&self, path: &str) -> Result<(), PlatformError> {
+        let metadata = fs::symlink_metadata(path).map_err(platform_error)?;
+
+        let result = if metadata.is_dir() {
+            fs::remove_dir(path)
+        } else {
+            fs::remove_file(path)
+        };
+
+        result.map_err(platform_error)
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), PlatformError> {
+        fs::rename(from, to).map_err(platform_error)
+    }
+
+    fn current_time_millis(&self) -> i64 {
+        let millisecs = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("User didn't set their clock to 1969")
+            .as_millis();
+
+        millisecs as i64
+    }
+
+    fn nano_time(&self) -> i64 {
+        self.start_time.elapsed().as_nanos() as i64
+    }
+
+    fn exit(&self, code: i32) -> ! {
+        process::exit(code)
+    }
+
+    fn env_var(&self, name: &str) -> Option<String> {
+        env::var(name).ok()
+    }
+
+    fn current_dir(&self) -> String {
+        let current_dir = env::current_dir().unwrap_or_default();
+
+        current_dir.to_string_lossy().to_string()
+    }
+
+    fn canonicalize(&self, path: &str) -> String {
+        // Resolve one component at a time, so that the links in the path are
+        // followed before the `..` components after them are applied. Files
+        // that don't exist yet can't be canonicalized, so their names are
+        // kept as they are.
+        let mut resolved = PathBuf::from("/");
+        for component in Path::new(path).components() {
+            match component {
+                Component::Normal(name) => {
+                    resolved.push(name);
+                    if let Ok(canonical) = fs::canonicalize(&resolved) {
+                        resolved = canonical;
+                    }
+                }
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                Component::RootDir | Component::CurDir | Component::Prefix(_) => {}
+            }
+        }
+
+        resolved.to_string_lossy().to_string()
+    }
+}
+
+fn platform_error(error: io::Error) -> PlatformError {
+    // Strip the " (os error N)" that Rust appends to the OS's description
+    let description = error.to_string();
+    let reason = description.split(" (os error").next().unwrap_or_default();
+
+    PlatformError::new(reason)
+}
@@ -0,0 +1,325 @@
+// The `verify` command, which loads every class in a JAR, verifies all of
+// their methods, and checks that every class, field, and method they reference
+// exists, without running any code.
+
+use rjvm_core::classfile::constant_pool::ConstantPoolEntry;
+use rjvm_core::{
+    Class, Context, Descriptor, Error, Jar, JvmString, Method, MethodDescriptor, MethodFlags,
+};
+
+use std::collections::HashSet;
+use std::fs;
+
+pub fn run(program_name: &str, args: &[String]) -> bool {
+    let mut jar_name = None;
+    let mut linked_jar_names = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "--link" {
+            let Some(linked_jar_name) = args.get(i + 1) else {
+                eprintln!("--link flag requires a file");
+                return false;
+            };
+
+            linked_jar_names.push(linked_jar_name.clone());
+
+            i += 1;
+        } else if args[i].starts_with("--") {
+            eprintln!("Unknown flag {}", args[i]);
+            return false;
+        } else if jar_name.is_none() {
+            jar_name = Some(args[i].clone());
+        } else {
+            eprintln!("Only one JAR file can be verified at a time");
+            return false;
+        }
+
+        i += 1;
+    }
+
+    let Some(jar_name) = jar_name else {
+        eprintln!("Run as {program_name} verify app.jar [--link library.jar]");
+        return false;
+    };
+
+    let mut success = false;
+    crate::with_globals(|context| {
+        let Some(jar) = read_jar(context, &jar_name) else {
+            return;
+        };
+        context.add_system_jar(jar);
+
+        for linked_jar_name in &linked_jar_names {
+            let Some(linked_jar) = read_jar(context, linked_jar_name) else {
+                return;
+            };
+            context.add_system_jar(linked_jar);
+        }
+
+        let class_names = crate::jar_class_names(jar);
+
+        let mut problem_count = 0;
+        for class_name in &class_names {
+            // `module-info` isn't a class and can't be loaded
+            if class_name.ends_with("module-info") {
+                continue;
+            }
+
+            for problem in check_class(context, class_name) {
+                println!("{}: {}", class_name, problem);
+                problem_count += 1;
+            }
+        }
+
+        println!(
+            "Checked {} classes, found {} problems",
+            class_names.len(),
+            problem_count
+        );
+
+        success = problem_count == 0;
+    });
+
+    success
+}
+
+pub(crate) fn read_jar(context: &Context, file_name: &str) -> Option<Jar> {
+    let data = match fs::read(file_name) {
+        Ok(data) => data,
+        Err(error) => {
+            eprintln!("Failed to read {}: {}", file_name, error);
+            return None;
+        }
+    };
+
+    match Jar::from_bytes(context.gc_ctx(), data) {
+        Ok(jar) => Some(jar),
+        Err(_) => {
+            eprintln!("\"{}\" was not a valid JAR", file_name);
+            None
+        }
+    }
+}
+
+// Load the class with the given name and check it, returning a description of
+// each problem found.
+fn check_class(context: &Context, class_name: &str) -> Vec<String> {
+    let class_name = JvmString::new(context.gc_ctx(), class_name.to_string());
+
+    let class = match context.system_loader().lookup_class(context, class_name) {
+        Ok(class) => class,
+        Err(error) => return vec![format!("failed to load: {}", error_message(&error))],
+    };
+
+    let mut problems = check_references(context, class);
+
+    // Verifying a method fails at the first reference that can't be resolved,
+    // which was already reported above
+    let missing_references = !problems.is_empty();
+
+    let class_file = class.class_file().unwrap();
+    let results = verify_methods(context, class);
+    for (method, result) in class_file.methods().iter().zip(results) {
+        if let Err(error) = result {
+            let error_class = error.0.class().dot_name();
+            let is_missing_reference = matches!(
+                &*error_class,
+                "java.lang.NoClassDefFoundError"
+                    | "java.lang.NoSuchFieldError"
+                    | "java.lang.NoSuchMethodError"
+            );
+
+            if !(missing_references && is_missing_reference) {
+                problems.push(format!(
+                    "method {}:{}: {}",
+                    method.name(),
+                    method.descriptor(),
+                    error_message(&error)
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+// Resolve every class, field, and method referenced by the constant pool of
+// `class`, returning a description of each one that couldn't be resolved.
+fn check_references(context: &Context, class: Class) -> Vec<String> {
+    let class_file = class.class_file().unwrap();
+    let constant_pool = class_file.constant_pool();
+    let loader = class.loader().unwrap();
+
+    let mut problems = Vec::new();
+    let mut missing_classes = HashSet::new();
+
+    // Class entries come first, so that references to members of missing
+    // classes can be skipped
+    for index in 1..=constant_pool.entry_count() {
+        if let Ok(class_name) = constant_pool.get_class(index)
+            && let Err(error) = loader.lookup_class(context, class_name)
+            && missing_classes.insert(class_name)
+        {
+            problems.push(format!(
+                "missing class {}: {}",
+                class_name,
+                error_message(&error)
+            ));
+        }
+    }
+
+    for index in 1..=constant_pool.entry_count() {
+        let (class_name, name, descriptor, is_field) = match constant_pool.entry(index) {
+            Ok(ConstantPoolEntry::FieldRef { .. }) => {
+                let (class_name, name, descriptor) = constant_pool.get_field_ref(index).unwrap();
+                (class_name, name, descriptor, true)
+            }
+            Ok(
+                ConstantPoolEntry::MethodRef { .. } | ConstantPoolEntry::InterfaceMethodRef { .. },
+            ) => {
+                let (class_name, name, descriptor) =
+                    constant_pool.get_any_method_ref(index).unwrap();
+                (class_name, name, descriptor, false)
+            }
+            _ => continue,
+        };
+
+        if missing_classes.contains(&class_name) {
+            continue;
+        }
+
+        let owner = loader.lookup_class(context, class_name).unwrap();
+
+        let found = if is_field {
+            Descriptor::try_from_string(context, descriptor)
+                .is_some_and(|d| has_field(owner, name, d))
+        } else {
+            MethodDescriptor::from_string(context, descriptor)
+                .is_ok_and(|d| find_method(owner, name, d).is_some())
+        };
+
+        if !found {
+            let kind = if is_field { "field" } else { "method" };
+
+            problems.push(format!(
+                "missing {} {}.{}:{}",
+                kind, class_name, name, descriptor
+            ));
+        }
+    }
+
+    problems
+}
+
+// Whether a field reference to `class` can be resolved, looking through its
+// superclasses and then its superinterfaces.
+pub(crate) fn has_field(class: Class, name: JvmString, descriptor: Descriptor) -> bool {
+    let key = (name, descriptor);
+
+    if class.instance_field_vtable().lookup(key).is_some()
+        || class.static_field_vtable().lookup(key).is_some()
+    {
+        return true;
+    }
+
+    let mut current_class = Some(class);
+    while let Some(cls) = current_class {
+        if find_in_interfaces(cls, &|i| i.static_field_vtable().lookup(key)).is_some() {
+            return true;
+        }
+
+        current_class = cls.super_class();
+    }
+
+    false
+}
+
+// Find the method a method reference to `class` resolves to, looking through
+// its superclasses and then its superinterfaces.
+pub(crate) fn find_method(
+    class: Class,
+    name: JvmString,
+    descriptor: MethodDescriptor,
+) -> Option<Method> {
+    let key = (name, descriptor);
+
+    let instance_vtable = class.instance_method_vtable();
+    if let Some(index) = instance_vtable.lookup(key) {
+        return Some(instance_vtable.get_element(index));
+    }
+
+    if let Some(index) = class.static_method_vtable().lookup(key) {
+        return Some(class.get_static_method(index));
+    }
+
+    let mut current_class = Some(class);
+    while let Some(cls) = current_class {
+        let method = find_in_interfaces(cls, &|i| {
+            let vtable = i.instance_method_vtable();
+            vtable.lookup(key).map(|index| vtable.get_element(index))
+        });
+
+        if method.is_some() {
+            return method;
+        }
+
+        current_class = cls.super_class();
+    }
+
+    None
+}
+
+// Returns the first result of `find` that isn't `None`, trying each interface
+// `class` declares, followed by its superinterfaces.
+fn find_in_interfaces<T>(class: Class, find: &impl Fn(Class) -> Option<T>) -> Option<T> {
+    class
+        .own_interfaces()
+        .iter()
+        .find_map(|interface| find(*interface).or_else(|| find_in_interfaces(*interface, find)))
+}
+
+// Verify each method of the class file of `class`, returning the results in
+// the same order as the methods of the class file.
+pub(crate) fn verify_methods(context: &Context, class: Class) -> Vec<Result<(), Error>> {
+    let class_file = class.class_file().unwrap();
+
+    let mut results = Vec::with_capacity(class_file.methods().len());
+    for method in class_file.methods() {
+        let descriptor = match MethodDescriptor::from_string(context, method.descriptor()) {
+            Ok(descriptor) => descriptor,
+            Err(error) => {
+                results.push(Err(error));
+                continue;
+            }
+        };
+
+        let key = (method.name(), descriptor);
+        let loaded_method = if method.flags().contains(MethodFlags::STATIC) {
+            let vtable = class.static_method_vtable();
+            vtable.lookup(key).map(|i| class.get_static_method(i))
+        } else {
+            let vtable = class.instance_method_vtable();
+            vtable.lookup(key).map(|i| vtable.get_element(i))
+        };
+
+        // Every method in the class file was loaded into the class
+        let loaded_method = loaded_method
+            .filter(|m| m.class() == class)
+            .expect("Method should have been loaded");
+
+        results.push(loaded_method.verify(context));
+    }
+
+    results
+}
+
+// Formats an error like `Throwable.toString` does, without running Java code.
+pub(crate) fn error_message(error: &Error) -> String {
+    let class_name = error.0.class().dot_name();
+
+    match error.message() {
+        Some(message) => format!("{}: {}", class_name, message),
+        None => class_name,
+    }
+}
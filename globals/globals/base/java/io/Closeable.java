@@ -2,6 +2,6 @@ package java.io;
 
 import java.io.IOException;
 
-public interface Closeable {
+public interface Closeable extends AutoCloseable {
     public void close() throws IOException;
 }
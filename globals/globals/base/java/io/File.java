@@ -14,12 +14,8 @@ public class File {
     private static int tempFileCounter;
 
     private String normalizedPath;
-    private boolean exists;
-    private boolean isDirectory;
 
     public File(String name) {
-        // We can initialize all properties in an `internalInitFromName`
-        // because File is immutable
         this.internalInitFileData(Charset.stringToUtf8(name));
     }
 
@@ -68,17 +64,17 @@ public class File {
         return this.normalizedPath.charAt(0) == '/';
     }
 
-    public boolean isDirectory() {
-        return this.isDirectory;
-    }
+    public native boolean isDirectory();
 
     public boolean isFile() {
-        return this.exists && !this.isDirectory;
+        return this.exists() && !this.isDirectory();
     }
 
-    public boolean exists() {
-        return this.exists;
-    }
+    public native boolean exists();
+
+    public native long length();
+
+    public native long lastModified();
 
     public native String getAbsolutePath();
 
@@ -118,11 +114,23 @@ public class File {
     }
 
     public String[] list() {
-        // TODO implement
-        Todo.warnNotImpl("java.io.File.list");
-        return null;
+        return this.listNative();
     }
 
+    private native String[] listNative();
+
+    public native boolean delete();
+
+    public boolean renameTo(File dest) {
+        if (dest == null) {
+            throw new NullPointerException();
+        }
+
+        return this.renameToNative(dest);
+    }
+
+    private native boolean renameToNative(File dest);
+
     public boolean mkdirs() {
         // TODO implement
         Todo.warnNotImpl("java.io.File.mkdirs: " + this.getPath());
@@ -22,11 +22,8 @@ public final class FileDescriptor {
     }
 
     static FileDescriptor writeableFromFile(File file) throws FileNotFoundException {
+        // This throws a `FileNotFoundException` if the file can't be opened
         int registeredDescriptor = FileDescriptor.internalWriteableDescriptorFromPath(file.getPath());
-        // -1 signals an error
-        if (registeredDescriptor == -1) {
-            throw new FileNotFoundException(file.getPath());
-        }
 
         FileDescriptor fd = new FileDescriptor();
         fd.descriptor = registeredDescriptor;
@@ -34,18 +31,15 @@ public final class FileDescriptor {
     }
 
     static FileDescriptor readableFromFile(File file) throws FileNotFoundException {
+        // This throws a `FileNotFoundException` if the file can't be opened
         int registeredDescriptor = FileDescriptor.internalReadableDescriptorFromPath(file.getPath());
-        // -1 signals an error
-        if (registeredDescriptor == -1) {
-            throw new FileNotFoundException(file.getPath());
-        }
 
         FileDescriptor fd = new FileDescriptor();
         fd.descriptor = registeredDescriptor;
         return fd;
     }
 
-    private static native int internalWriteableDescriptorFromPath(String filePath);
+    private static native int internalWriteableDescriptorFromPath(String filePath) throws FileNotFoundException;
 
-    private static native int internalReadableDescriptorFromPath(String filePath);
+    private static native int internalReadableDescriptorFromPath(String filePath) throws FileNotFoundException;
 }
@@ -57,6 +57,13 @@ public class FileInputStream extends InputStream {
         return this.readMultipleNative(b, offset, length);
     }
 
+    public void close() throws IOException {
+        if (this.isOpen) {
+            this.isOpen = false;
+            this.closeNative();
+        }
+    }
+
     public final FileDescriptor getFD() throws IOException {
         return this.fd;
     }
@@ -68,4 +75,6 @@ public class FileInputStream extends InputStream {
     private native int readMultipleNative(byte[] b, int offset, int length);
 
     private native int availableNative();
+
+    private native void closeNative();
 }
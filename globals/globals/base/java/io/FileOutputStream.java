@@ -54,6 +54,13 @@ public class FileOutputStream extends OutputStream {
         this.flushNative();
     }
 
+    public void close() throws IOException {
+        if (this.isOpen) {
+            this.isOpen = false;
+            this.closeNative();
+        }
+    }
+
     public final FileDescriptor getFD() throws IOException {
         return this.fd;
     }
@@ -65,4 +72,6 @@ public class FileOutputStream extends OutputStream {
     private native void writeMultipleNative(byte[] buffer, int ofs, int len);
 
     private native void flushNative();
+
+    private native void closeNative();
 }
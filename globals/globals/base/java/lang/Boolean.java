@@ -49,6 +49,23 @@ public final class Boolean implements Comparable<Boolean> {
         }
     }
 
+    public boolean equals(Object obj) {
+        if (obj instanceof Boolean) {
+            Boolean other = (Boolean) obj;
+            return this.value == other.value;
+        } else {
+            return false;
+        }
+    }
+
+    public int hashCode() {
+        if (this.value) {
+            return 1231;
+        } else {
+            return 1237;
+        }
+    }
+
     public boolean booleanValue() {
         return this.value;
     }
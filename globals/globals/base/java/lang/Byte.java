@@ -18,6 +18,14 @@ public final class Byte extends Number implements Comparable<Byte> {
         }
     }
 
+    public int hashCode() {
+        return (int) this.value;
+    }
+
+    public String toString() {
+        return Integer.toString(this.value);
+    }
+
     public byte byteValue() {
         return this.value;
     }
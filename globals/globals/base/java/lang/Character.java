@@ -24,6 +24,18 @@ public final class Character extends Number implements Comparable<Character> {
         }
     }
 
+    public int hashCode() {
+        return (int) this.value;
+    }
+
+    public String toString() {
+        return String.valueOf(this.value);
+    }
+
+    public static Character valueOf(char c) {
+        return new Character(c);
+    }
+
     public char charValue() {
         return this.value;
     }
@@ -6,13 +6,19 @@ import java.lang.reflect.Field;
 import java.lang.reflect.GenericDeclaration;
 import java.lang.reflect.InvocationTargetException;
 import java.lang.reflect.Method;
+import java.lang.reflect.RecordComponent;
 import java.lang.reflect.Type;
 import java.lang.reflect.TypeVariable;
 
 import java.io.InputStream;
 
+import java.lang.annotation.Annotation;
+import java.lang.annotation.Inherited;
+
 import java.security.ProtectionDomain;
 
+import rjvm.internal.SignatureParser;
+
 // NOTE: The native `Class` corresponding to this `Class<T>` is stored in the
 // native `Context` and can be retrieved with `Context::class_for_java_class`
 public final class Class<T> implements AnnotatedElement, GenericDeclaration, Type {
@@ -81,6 +87,56 @@ public final class Class<T> implements AnnotatedElement, GenericDeclaration, Typ
 
     public native int getModifiers();
 
+    public String getPackageName() {
+        Class<?> cls = this;
+        while (cls.isArray()) {
+            cls = cls.getComponentType();
+        }
+
+        if (cls.isPrimitive()) {
+            return "java.lang";
+        }
+
+        String name = cls.getName();
+        int lastDot = name.lastIndexOf('.');
+        if (lastDot == -1) {
+            return "";
+        } else {
+            return name.substring(0, lastDot);
+        }
+    }
+
+    public native Class<?> getNestHost();
+
+    public boolean isNestmateOf(Class<?> cls) {
+        return this.getNestHost() == cls.getNestHost();
+    }
+
+    public boolean isRecord() {
+        // Record class must extend `Record` *and* have the `Record` attribute
+        return this.getSuperclass() == Record.class && this.getRecordComponentsNative() != null;
+    }
+
+    public RecordComponent[] getRecordComponents() {
+        if (!this.isRecord()) {
+            return null;
+        }
+
+        return this.getRecordComponentsNative();
+    }
+
+    private native RecordComponent[] getRecordComponentsNative();
+
+    public boolean isSealed() {
+        return this.getPermittedSubclassesNative() != null;
+    }
+
+    public Class<?>[] getPermittedSubclasses() {
+        return this.getPermittedSubclassesNative();
+    }
+
+    private native Class<?>[] getPermittedSubclassesNative();
+
     public T[] getEnumConstants() {
         if (!this.isEnum()) {
             return null;
@@ -88,6 +144,8 @@ public final class Class<T> implements AnnotatedElement, GenericDeclaration, Typ
 
         try {
             Method valuesMethod = this.getMethod("values");
+            // The enum class itself may not be accessible
+            valuesMethod.setAccessible(true);
             T[] values = (T[]) valuesMethod.invoke(null);
 
             if (values == null) {
@@ -104,9 +162,97 @@ public final class Class<T> implements AnnotatedElement, GenericDeclaration, Typ
         }
     }
 
+    // Generic type information
+
+    private native String getGenericSignatureNative();
+
+    private TypeVariable<Class<T>>[] cachedTypeParameters;
     public TypeVariable<Class<T>>[] getTypeParameters() {
-        // TODO implement
-        return null;
+        if (this.cachedTypeParameters == null) {
+            String signature = this.getGenericSignatureNative();
+            if (signature == null) {
+                this.cachedTypeParameters = (TypeVariable<Class<T>>[]) new TypeVariable<?>[0];
+            } else {
+                this.cachedTypeParameters = (TypeVariable<Class<T>>[]) SignatureParser.parseTypeParameters(signature, this);
+            }
+        }
+        return this.cachedTypeParameters.clone();
+    }
+
+    public Type getGenericSuperclass() {
+        Class<? super T> superClass = this.getSuperclass();
+        if (superClass == null) {
+            return null;
+        }
+
+        String signature = this.getGenericSignatureNative();
+        if (signature == null) {
+            return superClass;
+        } else {
+            return SignatureParser.parseSuperclass(signature, this);
+        }
+    }
+
+    public Type[] getGenericInterfaces() {
+        String signature = this.getGenericSignatureNative();
+        if (signature == null) {
+            return this.getInterfaces();
+        } else {
+            return SignatureParser.parseSuperinterfaces(signature, this);
+        }
+    }
+
+    public String getTypeName() {
+        if (this.isArray()) {
+            Class<?> cls = this;
+            int dimensions = 0;
+            while (cls.isArray()) {
+                cls = cls.getComponentType();
+                dimensions += 1;
+            }
+
+            StringBuilder result = new StringBuilder();
+            result.append(cls.getName());
+            for (int i = 0; i < dimensions; i ++) {
+                result.append("[]");
+            }
+
+            return result.toString();
+        } else {
+            return this.getName();
+        }
+    }
+
+    public String descriptorString() {
+        if (this == boolean.class) {
+            return "Z";
+        } else if (this == byte.class) {
+            return "B";
+        } else if (this == char.class) {
+            return "C";
+        } else if (this == short.class) {
+            return "S";
+        } else if (this == int.class) {
+            return "I";
+        } else if (this == long.class) {
+            return "J";
+        } else if (this == float.class) {
+            return "F";
+        } else if (this == double.class) {
+            return "D";
+        } else if (this == void.class) {
+            return "V";
+        } else if (this.isArray()) {
+            // Array class names are already in descriptor form
+            return this.getName().replace('.', '/');
+        } else {
+            StringBuilder result = new StringBuilder();
+            result.append('L');
+            result.append(this.getName().replace('.', '/'));
+            result.append(';');
+
+            return result.toString();
+        }
     }
 
     public InputStream getResourceAsStream(String resourceName) {
@@ -133,10 +279,19 @@ public final class Class<T> implements AnnotatedElement, GenericDeclaration, Typ
 
     public String getSimpleName() {
         if (this.isArray()) {
-            return getComponentType().getSimpleName() + "[]";
+            StringBuilder result = new StringBuilder(getComponentType().getSimpleName());
+            result.append("[]");
+            return result.toString();
         }
 
         String name = this.getName();
+
+        // Member classes are named after their declaring class
+        Class<?> declaringClass = this.getDeclaringClass();
+        if (declaringClass != null) {
+            return name.substring(declaringClass.getName().length() + 1);
+        }
+
         if (name.indexOf(".") == -1) {
             // Top-level class, return as-is
             return name;
@@ -261,6 +416,88 @@ public final class Class<T> implements AnnotatedElement, GenericDeclaration, Typ
     }
     private native Field[] getDeclaredFieldsNative();
 
+    // Get annotations
+
+    private Annotation[] cachedDeclaredAnnotations;
+    public Annotation[] getDeclaredAnnotations() {
+        if (this.cachedDeclaredAnnotations == null) {
+            this.cachedDeclaredAnnotations = this.getDeclaredAnnotationsNative();
+        }
+        return this.cachedDeclaredAnnotations.clone();
+    }
+    private native Annotation[] getDeclaredAnnotationsNative();
+
+    public Annotation[] getAnnotations() {
+        Annotation[] declared = this.getDeclaredAnnotations();
+
+        Class<? super T> superClass = this.getSuperclass();
+        if (superClass == null) {
+            return declared;
+        }
+
+        // Annotations whose type is marked `@Inherited` are inherited from the
+        // superclass, unless this class declares an annotation of the same
+        // type. Inherited annotations come first, matching the JDK's ordering.
+        Annotation[] superAnnotations = superClass.getAnnotations();
+        Annotation[] result = new Annotation[superAnnotations.length + declared.length];
+        int resultCount = 0;
+        for (int i = 0; i < superAnnotations.length; i ++) {
+            Class<? extends Annotation> type = superAnnotations[i].annotationType();
+            if (type.isAnnotationPresent(Inherited.class)) {
+                Annotation override = this.getDeclaredAnnotation(type);
+                if (override != null) {
+                    result[resultCount] = override;
+                } else {
+                    result[resultCount] = superAnnotations[i];
+                }
+                resultCount += 1;
+            }
+        }
+
+        int inheritedCount = resultCount;
+        for (int i = 0; i < declared.length; i ++) {
+            boolean alreadyAdded = false;
+            for (int j = 0; j < inheritedCount; j ++) {
+                if (result[j] == declared[i]) {
+                    alreadyAdded = true;
+                    break;
+                }
+            }
+
+            if (!alreadyAdded) {
+                result[resultCount] = declared[i];
+                resultCount += 1;
+            }
+        }
+
+        if (resultCount == result.length) {
+            return result;
+        }
+
+        Annotation[] trimmed = new Annotation[resultCount];
+        System.arraycopy(result, 0, trimmed, 0, resultCount);
+        return trimmed;
+    }
+
+    public <A extends Annotation> A getAnnotation(Class<A> annotationClass) {
+        if (annotationClass == null) {
+            throw new NullPointerException();
+        }
+
+        Annotation[] annotations = this.getAnnotations();
+        for (int i = 0; i < annotations.length; i ++) {
+            if (annotations[i].annotationType() == annotationClass) {
+                return (A) annotations[i];
+            }
+        }
+
+        return null;
+    }
+
+    public boolean isAnnotation() {
+        return (this.getModifiers() & 0x2000) != 0;
+    }
+
     public ProtectionDomain getProtectionDomain() {
         // TODO implement
         return null;
@@ -4,4 +4,8 @@ public class ClassCastException extends RuntimeException {
     public ClassCastException() {
         super();
     }
+
+    public ClassCastException(String message) {
+        super(message);
+    }
 }
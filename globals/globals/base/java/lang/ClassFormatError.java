@@ -4,4 +4,8 @@ public class ClassFormatError extends LinkageError {
     public ClassFormatError() {
         super();
     }
+
+    public ClassFormatError(String message) {
+        super(message);
+    }
 }
@@ -26,7 +26,21 @@ public final class Double extends Number {
         return Double.parseDouble(string);
     }
 
-    // TODO: Implement equals and compareTo with NaN comparison rules
+    // TODO: Implement compareTo with NaN comparison rules
+
+    public boolean equals(Object obj) {
+        if (obj instanceof Double) {
+            Double other = (Double) obj;
+            return Double.doubleToLongBits(this.value) == Double.doubleToLongBits(other.value);
+        } else {
+            return false;
+        }
+    }
+
+    public int hashCode() {
+        long bits = Double.doubleToLongBits(this.value);
+        return (int) (bits ^ (bits >>> 32));
+    }
 
     public int intValue() {
         return (int) this.value;
@@ -8,4 +8,12 @@ public class Error extends Throwable {
     public Error(String message) {
         super(message);
     }
+
+    public Error(String message, Throwable cause) {
+        super(message, cause);
+    }
+
+    public Error(Throwable cause) {
+        super(cause);
+    }
 }
@@ -25,7 +25,20 @@ public final class Float extends Number {
         return new Float(f);
     }
 
-    // TODO: Implement equals and compareTo with NaN comparison rules
+    // TODO: Implement compareTo with NaN comparison rules
+
+    public boolean equals(Object obj) {
+        if (obj instanceof Float) {
+            Float other = (Float) obj;
+            return Float.floatToIntBits(this.value) == Float.floatToIntBits(other.value);
+        } else {
+            return false;
+        }
+    }
+
+    public int hashCode() {
+        return Float.floatToIntBits(this.value);
+    }
 
     public int intValue() {
         return (int) this.value;
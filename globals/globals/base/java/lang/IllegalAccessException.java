@@ -4,4 +4,8 @@ public class IllegalAccessException extends ReflectiveOperationException {
     public IllegalAccessException() {
         super();
     }
+
+    public IllegalAccessException(String message) {
+        super(message);
+    }
 }
@@ -8,4 +8,12 @@ public class IllegalArgumentException extends RuntimeException {
     public IllegalArgumentException(String message) {
         super(message);
     }
+
+    public IllegalArgumentException(String message, Throwable cause) {
+        super(message, cause);
+    }
+
+    public IllegalArgumentException(Throwable cause) {
+        super(cause);
+    }
 }
@@ -4,4 +4,12 @@ public class LinkageError extends Error {
     public LinkageError() {
         super();
     }
+
+    public LinkageError(String message) {
+        super(message);
+    }
+
+    public LinkageError(String message, Throwable cause) {
+        super(message, cause);
+    }
 }
@@ -36,8 +36,7 @@ public final class Long extends Number implements Comparable<Long> {
     }
 
     public int hashCode() {
-        // TODO
-        return ((int) this.value);
+        return (int) (this.value ^ (this.value >>> 32));
     }
 
     public String toString() {
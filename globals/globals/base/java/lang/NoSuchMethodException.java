@@ -4,4 +4,8 @@ public class NoSuchMethodException extends ReflectiveOperationException {
     public NoSuchMethodException() {
         super();
     }
+
+    public NoSuchMethodException(String message) {
+        super(message);
+    }
 }
@@ -21,6 +21,14 @@ public final class Short extends Number implements Comparable<Short> {
         }
     }
 
+    public int hashCode() {
+        return (int) this.value;
+    }
+
+    public String toString() {
+        return Integer.toString(this.value);
+    }
+
     public short shortValue() {
         return this.value;
     }
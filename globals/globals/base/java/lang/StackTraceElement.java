@@ -2,6 +2,7 @@ package java.lang;
 
 import java.lang.reflect.Constructor;
 import java.lang.reflect.Executable;
+import java.util.Objects;
 
 public final class StackTraceElement {
     // NOTE These fields are set from native code, field ordering matters!
@@ -45,6 +46,27 @@ public final class StackTraceElement {
         return this.isNativeMethod;
     }
 
+    public boolean equals(Object obj) {
+        if (obj == this) {
+            return true;
+        } else if (!(obj instanceof StackTraceElement)) {
+            return false;
+        }
+
+        StackTraceElement other = (StackTraceElement) obj;
+        return this.declaringClass.equals(other.declaringClass) &&
+            this.methodName.equals(other.methodName) &&
+            Objects.equals(this.fileName, other.fileName) &&
+            this.lineNumber == other.lineNumber;
+    }
+
+    public int hashCode() {
+        int result = 31 * this.declaringClass.hashCode() + this.methodName.hashCode();
+        result = 31 * result + Objects.hashCode(this.fileName);
+        result = 31 * result + this.lineNumber;
+        return result;
+    }
+
     public String toString() {
         return this.declaringClass + '.' + this.methodName + "()";
     }
@@ -100,8 +100,11 @@ public final class String implements CharSequence, Comparable<String> {
     }
 
     public int hashCode() {
-        // TODO implement better
-        return this.length();
+        int result = 0;
+        for (int i = 0; i < this.data.length; i ++) {
+            result = 31 * result + this.data[i];
+        }
+        return result;
     }
 
     public String toString() {
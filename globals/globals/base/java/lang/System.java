@@ -29,6 +29,7 @@ public final class System {
         properties.setProperty("file.separator", "/");
         properties.setProperty("path.separator", ":");
         properties.setProperty("line.separator", "\n");
+        properties.setProperty("user.dir", System.currentDirectory());
 
         // Pretend we're Java 8
         properties.setProperty("java.class.version", "52.0");
@@ -89,10 +90,11 @@ public final class System {
     // Misc native functions
     public static native long currentTimeMillis();
 
-    public static long nanoTime() {
-        // :p
-        return System.currentTimeMillis() * 1000000;
-    }
+    public static native long nanoTime();
+
+    public static native String getenv(String name);
+
+    private static native String currentDirectory();
 
     public static native int identityHashCode(Object x);
 
@@ -1,6 +1,7 @@
 package java.lang;
 
 import java.io.PrintStream;
+import java.util.ArrayList;
 
 public class Throwable {
     // NOTE field ordering is important
@@ -11,6 +12,9 @@ public class Throwable {
     // If cause is equal to self, then it's not-yet-set
     private Throwable cause;
 
+    // Null if no exceptions were suppressed
+    private Throwable[] suppressedExceptions;
+
     // Constructors
     public Throwable() {
         this.fillInStackTrace();
@@ -76,17 +80,49 @@ public class Throwable {
         return this.message;
     }
 
+    public String getLocalizedMessage() {
+        return this.getMessage();
+    }
+
     public StackTraceElement[] getStackTrace() {
         return this.stackTrace;
     }
 
+    public final synchronized void addSuppressed(Throwable exception) {
+        if (exception == this) {
+            throw new IllegalArgumentException("Self-suppression not permitted", exception);
+        } else if (exception == null) {
+            throw new NullPointerException("Cannot suppress a null exception.");
+        }
+
+        if (this.suppressedExceptions == null) {
+            this.suppressedExceptions = new Throwable[] { exception };
+        } else {
+            int length = this.suppressedExceptions.length;
+            Throwable[] newSuppressed = new Throwable[length + 1];
+            System.arraycopy(this.suppressedExceptions, 0, newSuppressed, 0, length);
+            newSuppressed[length] = exception;
+
+            this.suppressedExceptions = newSuppressed;
+        }
+    }
+
+    public final synchronized Throwable[] getSuppressed() {
+        if (this.suppressedExceptions == null) {
+            return new Throwable[0];
+        } else {
+            return this.suppressedExceptions.clone();
+        }
+    }
+
     public String toString() {
         String className = this.getClass().getName();
+        String message = this.getLocalizedMessage();
 
-        if (this.message == null) {
+        if (message == null) {
             return className;
         } else {
-            return className + ": " + this.message;
+            return className + ": " + message;
         }
     }
 
@@ -94,21 +130,84 @@ public class Throwable {
         this.printStackTrace(System.err);
     }
 
+    // This is the only place where throwables are formatted: native code calls
+    // this method to display uncaught exceptions
     public void printStackTrace(PrintStream s) {
+        ArrayList<Throwable> seen = new ArrayList<Throwable>();
+        seen.add(this);
+
         s.println(this);
 
-        for (int i = 0; i < this.stackTrace.length; i ++) {
-            StringBuilder builder = new StringBuilder();
-            builder.append("\tat ");
-            builder.append(this.stackTrace[i]);
+        StackTraceElement[] trace = this.getOurStackTrace();
+        for (int i = 0; i < trace.length; i ++) {
+            s.println("\tat " + trace[i]);
+        }
 
-            s.println(builder);
+        Throwable[] suppressed = this.getSuppressed();
+        for (int i = 0; i < suppressed.length; i ++) {
+            suppressed[i].printEnclosedStackTrace(s, trace, "Suppressed: ", "\t", seen);
         }
 
-        // TODO guard against recursion
-        if (this.cause != this && this.cause != null) {
-            s.print("Caused by: ");
-            this.cause.printStackTrace(s);
+        Throwable cause = this.getCause();
+        if (cause != null) {
+            cause.printEnclosedStackTrace(s, trace, "Caused by: ", "", seen);
+        }
+    }
+
+    // Print the stack trace of a cause or suppressed exception, omitting the
+    // frames it has in common with the enclosing trace
+    private void printEnclosedStackTrace(
+        PrintStream s,
+        StackTraceElement[] enclosingTrace,
+        String caption,
+        String prefix,
+        ArrayList<Throwable> seen
+    ) {
+        // `seen` is compared by identity, rather than with `equals`
+        for (int i = 0; i < seen.size(); i ++) {
+            if (seen.get(i) == this) {
+                s.println(prefix + caption + "[CIRCULAR REFERENCE: " + this + "]");
+                return;
+            }
+        }
+
+        seen.add(this);
+
+        StackTraceElement[] trace = this.getOurStackTrace();
+        int m = trace.length - 1;
+        int n = enclosingTrace.length - 1;
+        while (m >= 0 && n >= 0 && trace[m].equals(enclosingTrace[n])) {
+            m --;
+            n --;
+        }
+
+        int framesInCommon = trace.length - 1 - m;
+
+        s.println(prefix + caption + this);
+        for (int i = 0; i <= m; i ++) {
+            s.println(prefix + "\tat " + trace[i]);
+        }
+
+        if (framesInCommon != 0) {
+            s.println(prefix + "\t... " + framesInCommon + " more");
+        }
+
+        Throwable[] suppressed = this.getSuppressed();
+        for (int i = 0; i < suppressed.length; i ++) {
+            suppressed[i].printEnclosedStackTrace(s, trace, "Suppressed: ", prefix + "\t", seen);
+        }
+
+        Throwable cause = this.getCause();
+        if (cause != null) {
+            cause.printEnclosedStackTrace(s, trace, "Caused by: ", prefix, seen);
+        }
+    }
+
+    private StackTraceElement[] getOurStackTrace() {
+        if (this.stackTrace == null) {
+            return new StackTraceElement[0];
+        } else {
+            return this.stackTrace;
         }
     }
 }
@@ -0,0 +1,24 @@
+package java.lang;
+
+public class TypeNotPresentException extends RuntimeException {
+    private String typeName;
+
+    public TypeNotPresentException(String typeName, Throwable cause) {
+        super(TypeNotPresentException.createMessage(typeName), cause);
+
+        this.typeName = typeName;
+    }
+
+    private static String createMessage(String typeName) {
+        StringBuilder result = new StringBuilder();
+        result.append("Type ");
+        result.append(typeName);
+        result.append(" not present");
+
+        return result.toString();
+    }
+
+    public String typeName() {
+        return this.typeName;
+    }
+}
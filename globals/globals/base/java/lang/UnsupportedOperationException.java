@@ -1,3 +1,11 @@
 package java.lang;
 
-public class UnsupportedOperationException extends RuntimeException { }
+public class UnsupportedOperationException extends RuntimeException {
+    public UnsupportedOperationException() {
+        super();
+    }
+
+    public UnsupportedOperationException(String message) {
+        super(message);
+    }
+}
@@ -0,0 +1,30 @@
+package java.lang.annotation;
+
+public class IncompleteAnnotationException extends RuntimeException {
+    private Class<? extends Annotation> annotationType;
+    private String elementName;
+
+    public IncompleteAnnotationException(Class<? extends Annotation> annotationType, String elementName) {
+        super(IncompleteAnnotationException.createMessage(annotationType, elementName));
+
+        this.annotationType = annotationType;
+        this.elementName = elementName;
+    }
+
+    private static String createMessage(Class<? extends Annotation> annotationType, String elementName) {
+        StringBuilder result = new StringBuilder();
+        result.append(annotationType.getName());
+        result.append(" missing element ");
+        result.append(elementName);
+
+        return result.toString();
+    }
+
+    public Class<? extends Annotation> annotationType() {
+        return this.annotationType;
+    }
+
+    public String elementName() {
+        return this.elementName;
+    }
+}
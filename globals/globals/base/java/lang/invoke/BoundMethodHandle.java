@@ -0,0 +1,36 @@
+package java.lang.invoke;
+
+// A method handle that inserts fixed values among its arguments before calling
+// another handle. This implements `MethodHandles.insertArguments`.
+final class BoundMethodHandle extends MethodHandle {
+    private final MethodHandle target;
+    private final int position;
+
+    // The values, converted to the types of the parameters they're bound to
+    private final Object[] values;
+
+    BoundMethodHandle(MethodHandle target, int position, Object[] values) {
+        super(target.type().dropParameterTypes(position, position + values.length));
+
+        MethodType targetType = target.type();
+
+        Object[] converted = new Object[values.length];
+        for (int i = 0; i < values.length; i ++) {
+            Class<?> parameterType = targetType.parameterType(position + i);
+            converted[i] = ConvertingMethodHandle.convert(values[i], Object.class, parameterType);
+        }
+
+        this.target = target;
+        this.position = position;
+        this.values = converted;
+    }
+
+    Object invokeImpl(Object[] arguments) throws Throwable {
+        Object[] targetArguments = new Object[arguments.length + this.values.length];
+        System.arraycopy(arguments, 0, targetArguments, 0, this.position);
+        System.arraycopy(this.values, 0, targetArguments, this.position, this.values.length);
+        System.arraycopy(arguments, this.position, targetArguments, this.position + this.values.length, arguments.length - this.position);
+
+        return this.target.invokeImpl(targetArguments);
+    }
+}
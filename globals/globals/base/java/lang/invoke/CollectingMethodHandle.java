@@ -0,0 +1,45 @@
+package java.lang.invoke;
+
+import java.lang.reflect.Array;
+
+// A method handle that collects a number of trailing arguments into an array,
+// which is passed to another handle as its last argument. This is how variable
+// arity handles are called with a different arity.
+final class CollectingMethodHandle extends MethodHandle {
+    private final MethodHandle target;
+
+    CollectingMethodHandle(MethodHandle target, int collectCount) {
+        super(CollectingMethodHandle.typeOf(target.type(), collectCount));
+
+        this.target = target;
+    }
+
+    private static MethodType typeOf(MethodType targetType, int collectCount) {
+        int collectPosition = targetType.parameterCount() - 1;
+        Class<?> componentType = targetType.parameterType(collectPosition).getComponentType();
+
+        Class<?>[] collectedTypes = new Class<?>[collectCount];
+        for (int i = 0; i < collectCount; i ++) {
+            collectedTypes[i] = componentType;
+        }
+
+        return targetType.dropParameterTypes(collectPosition, collectPosition + 1).appendParameterTypes(collectedTypes);
+    }
+
+    Object invokeImpl(Object[] arguments) throws Throwable {
+        MethodType targetType = this.target.type();
+        int collectPosition = targetType.parameterCount() - 1;
+
+        int collectCount = arguments.length - collectPosition;
+        Object collected = Array.newInstance(targetType.parameterType(collectPosition).getComponentType(), collectCount);
+        for (int i = 0; i < collectCount; i ++) {
+            Array.set(collected, i, arguments[collectPosition + i]);
+        }
+
+        Object[] targetArguments = new Object[collectPosition + 1];
+        System.arraycopy(arguments, 0, targetArguments, 0, collectPosition);
+        targetArguments[collectPosition] = collected;
+
+        return this.target.invokeImpl(targetArguments);
+    }
+}
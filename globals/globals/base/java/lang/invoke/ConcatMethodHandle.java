@@ -0,0 +1,36 @@
+package java.lang.invoke;
+
+// A method handle that concatenates its arguments with the constants of a
+// recipe. This implements the call sites created by `StringConcatFactory`;
+// call sites linked by the VM concatenate their arguments natively instead.
+final class ConcatMethodHandle extends MethodHandle {
+    private final String recipe;
+    private final Object[] constants;
+
+    ConcatMethodHandle(MethodType type, String recipe, Object[] constants) {
+        super(type);
+
+        this.recipe = recipe;
+        this.constants = constants;
+    }
+
+    Object invokeImpl(Object[] arguments) throws Throwable {
+        StringBuilder result = new StringBuilder();
+
+        int argumentIndex = 0;
+        int constantIndex = 0;
+        for (int i = 0; i < this.recipe.length(); i ++) {
+            char c = this.recipe.charAt(i);
+
+            if (c == StringConcatFactory.TAG_ARGUMENT) {
+                result.append(String.valueOf(arguments[argumentIndex ++]));
+            } else if (c == StringConcatFactory.TAG_CONSTANT) {
+                result.append(String.valueOf(this.constants[constantIndex ++]));
+            } else {
+                result.append(c);
+            }
+        }
+
+        return result.toString();
+    }
+}
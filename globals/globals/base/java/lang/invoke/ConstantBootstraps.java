@@ -0,0 +1,56 @@
+package java.lang.invoke;
+
+public final class ConstantBootstraps {
+    private ConstantBootstraps() { }
+
+    public static Object nullConstant(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        if (type.isPrimitive()) {
+            throw new IllegalArgumentException(ConstantBootstraps.describe("not reference: ", type.getName()));
+        }
+
+        return null;
+    }
+
+    public static Class<?> primitiveClass(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        if (type != Class.class) {
+            throw new ClassCastException();
+        } else if (name.length() != 1) {
+            throw new IllegalArgumentException(ConstantBootstraps.describe("not primitive: ", name));
+        }
+
+        switch (name.charAt(0)) {
+            case 'Z':
+                return boolean.class;
+            case 'B':
+                return byte.class;
+            case 'C':
+                return char.class;
+            case 'S':
+                return short.class;
+            case 'I':
+                return int.class;
+            case 'J':
+                return long.class;
+            case 'F':
+                return float.class;
+            case 'D':
+                return double.class;
+            case 'V':
+                return void.class;
+            default:
+                throw new IllegalArgumentException(ConstantBootstraps.describe("not primitive: ", name));
+        }
+    }
+
+    public static Object invoke(MethodHandles.Lookup lookup, String name, Class<?> type, MethodHandle handle, Object... args) throws Throwable {
+        return handle.invokeWithArguments(args);
+    }
+
+    private static String describe(String reason, String value) {
+        StringBuilder result = new StringBuilder();
+        result.append(reason);
+        result.append(value);
+
+        return result.toString();
+    }
+}
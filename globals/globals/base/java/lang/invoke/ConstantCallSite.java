@@ -0,0 +1,21 @@
+package java.lang.invoke;
+
+public class ConstantCallSite extends CallSite {
+    private final MethodHandle target;
+
+    public ConstantCallSite(MethodHandle target) {
+        if (target == null) {
+            throw new NullPointerException();
+        }
+
+        this.target = target;
+    }
+
+    public final MethodHandle getTarget() {
+        return this.target;
+    }
+
+    public final void setTarget(MethodHandle newTarget) {
+        throw new UnsupportedOperationException();
+    }
+}
@@ -0,0 +1,232 @@
+package java.lang.invoke;
+
+// A method handle that converts its arguments to the parameter types of
+// another handle, and the result of that handle to its own return type. This
+// implements `MethodHandle.asType`.
+final class ConvertingMethodHandle extends MethodHandle {
+    private final MethodHandle target;
+
+    ConvertingMethodHandle(MethodHandle target, MethodType newType) {
+        super(newType);
+
+        this.target = target;
+    }
+
+    Object invokeImpl(Object[] arguments) throws Throwable {
+        MethodType type = this.type();
+        MethodType targetType = this.target.type();
+
+        Object[] converted = new Object[arguments.length];
+        for (int i = 0; i < arguments.length; i ++) {
+            converted[i] = ConvertingMethodHandle.convert(arguments[i], type.parameterType(i), targetType.parameterType(i));
+        }
+
+        Object result = this.target.invokeImpl(converted);
+
+        return ConvertingMethodHandle.convert(result, targetType.returnType(), type.returnType());
+    }
+
+    // Whether a handle of type `oldType` can be adapted to `newType`.
+    static boolean canConvert(MethodType oldType, MethodType newType) {
+        if (oldType.parameterCount() != newType.parameterCount()) {
+            return false;
+        }
+
+        for (int i = 0; i < oldType.parameterCount(); i ++) {
+            if (!ConvertingMethodHandle.canConvert(newType.parameterType(i), oldType.parameterType(i))) {
+                return false;
+            }
+        }
+
+        return ConvertingMethodHandle.canConvert(oldType.returnType(), newType.returnType());
+    }
+
+    // Whether a value of type `from` can be converted to type `to`. Conversions
+    // between reference types, and from references to primitives, may still
+    // fail when a value is actually converted.
+    static boolean canConvert(Class<?> from, Class<?> to) {
+        if (from == to || from == void.class || to == void.class) {
+            return true;
+        } else if (from.isPrimitive() && to.isPrimitive()) {
+            return ConvertingMethodHandle.isWidening(from, to);
+        } else if (from.isPrimitive()) {
+            // Box, then widen the reference
+            return to.isAssignableFrom(ConvertingMethodHandle.wrapperFor(from));
+        } else if (to.isPrimitive()) {
+            // Unbox a value whose wrapper type could be `from`, or unbox a
+            // wrapper type and widen the primitive
+            if (from.isAssignableFrom(ConvertingMethodHandle.wrapperFor(to))) {
+                return true;
+            }
+
+            Class<?> unboxed = ConvertingMethodHandle.primitiveFor(from);
+
+            return unboxed != null && ConvertingMethodHandle.isWidening(unboxed, to);
+        } else {
+            return true;
+        }
+    }
+
+    // Convert `value`, the boxed form of a value of type `from`, to the boxed
+    // form of type `to`.
+    static Object convert(Object value, Class<?> from, Class<?> to) {
+        if (to == void.class) {
+            return null;
+        } else if (from == void.class) {
+            return to.isPrimitive() ? ConvertingMethodHandle.zeroValue(to) : null;
+        }
+
+        if (!to.isPrimitive()) {
+            if (value != null && !to.isInstance(value)) {
+                throw ConvertingMethodHandle.castFailure(value, to);
+            }
+
+            return value;
+        }
+
+        if (value == null) {
+            throw new NullPointerException();
+        }
+
+        Class<?> unboxed = ConvertingMethodHandle.primitiveFor(value.getClass());
+        if (unboxed == null || !ConvertingMethodHandle.isWidening(unboxed, to)) {
+            throw ConvertingMethodHandle.castFailure(value, ConvertingMethodHandle.wrapperFor(to));
+        }
+
+        return ConvertingMethodHandle.widen(value, to);
+    }
+
+    private static ClassCastException castFailure(Object value, Class<?> type) {
+        StringBuilder message = new StringBuilder();
+        message.append("Cannot cast ");
+        message.append(value.getClass().getName());
+        message.append(" to ");
+        message.append(type.getName());
+
+        return new ClassCastException(message.toString());
+    }
+
+    // Whether `from` is the same primitive type as `to`, or can be converted
+    // to it by a widening primitive conversion.
+    private static boolean isWidening(Class<?> from, Class<?> to) {
+        if (from == to) {
+            return true;
+        } else if (from == boolean.class || to == boolean.class || to == char.class) {
+            return false;
+        } else if (from == char.class) {
+            return ConvertingMethodHandle.rank(to) >= ConvertingMethodHandle.rank(int.class);
+        } else {
+            return ConvertingMethodHandle.rank(to) > ConvertingMethodHandle.rank(from);
+        }
+    }
+
+    // The order of the numeric types in widening conversions.
+    private static int rank(Class<?> type) {
+        if (type == byte.class) {
+            return 0;
+        } else if (type == short.class) {
+            return 1;
+        } else if (type == int.class) {
+            return 2;
+        } else if (type == long.class) {
+            return 3;
+        } else if (type == float.class) {
+            return 4;
+        } else {
+            return 5;
+        }
+    }
+
+    // Apply a widening primitive conversion to a boxed value.
+    private static Object widen(Object value, Class<?> to) {
+        if (ConvertingMethodHandle.wrapperFor(to) == value.getClass()) {
+            return value;
+        }
+
+        long longValue;
+        double doubleValue;
+        if (value instanceof Character) {
+            longValue = ((Character) value).charValue();
+            doubleValue = longValue;
+        } else {
+            longValue = ((Number) value).longValue();
+            doubleValue = ((Number) value).doubleValue();
+        }
+
+        if (to == short.class) {
+            return Short.valueOf((short) longValue);
+        } else if (to == int.class) {
+            return Integer.valueOf((int) longValue);
+        } else if (to == long.class) {
+            return Long.valueOf(longValue);
+        } else if (to == float.class) {
+            return Float.valueOf((float) longValue);
+        } else {
+            return Double.valueOf(doubleValue);
+        }
+    }
+
+    private static Object zeroValue(Class<?> type) {
+        if (type == boolean.class) {
+            return Boolean.FALSE;
+        } else if (type == byte.class) {
+            return Byte.valueOf((byte) 0);
+        } else if (type == char.class) {
+            return Character.valueOf((char) 0);
+        } else if (type == short.class) {
+            return Short.valueOf((short) 0);
+        } else if (type == int.class) {
+            return Integer.valueOf(0);
+        } else if (type == long.class) {
+            return Long.valueOf(0);
+        } else if (type == float.class) {
+            return Float.valueOf(0);
+        } else {
+            return Double.valueOf(0);
+        }
+    }
+
+    static Class<?> wrapperFor(Class<?> type) {
+        if (type == boolean.class) {
+            return Boolean.class;
+        } else if (type == byte.class) {
+            return Byte.class;
+        } else if (type == char.class) {
+            return Character.class;
+        } else if (type == short.class) {
+            return Short.class;
+        } else if (type == int.class) {
+            return Integer.class;
+        } else if (type == long.class) {
+            return Long.class;
+        } else if (type == float.class) {
+            return Float.class;
+        } else {
+            return Double.class;
+        }
+    }
+
+    // The primitive type that `type` is the wrapper of, or `null` if it isn't
+    // a wrapper type.
+    private static Class<?> primitiveFor(Class<?> type) {
+        if (type == Boolean.class) {
+            return boolean.class;
+        } else if (type == Byte.class) {
+            return byte.class;
+        } else if (type == Character.class) {
+            return char.class;
+        } else if (type == Short.class) {
+            return short.class;
+        } else if (type == Integer.class) {
+            return int.class;
+        } else if (type == Long.class) {
+            return long.class;
+        } else if (type == Float.class) {
+            return float.class;
+        } else if (type == Double.class) {
+            return double.class;
+        } else {
+            return null;
+        }
+    }
+}
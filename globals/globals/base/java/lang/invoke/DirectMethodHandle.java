@@ -0,0 +1,140 @@
+package java.lang.invoke;
+
+import java.lang.reflect.AccessibleObject;
+import java.lang.reflect.Constructor;
+import java.lang.reflect.Executable;
+import java.lang.reflect.Field;
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Method;
+
+// A method handle that accesses a field or calls a method directly, with the
+// behavior of the bytecode instruction its reference kind corresponds to.
+final class DirectMethodHandle extends MethodHandle {
+    static final int REF_getField = 1;
+    static final int REF_getStatic = 2;
+    static final int REF_putField = 3;
+    static final int REF_putStatic = 4;
+    static final int REF_invokeVirtual = 5;
+    static final int REF_invokeStatic = 6;
+    static final int REF_invokeSpecial = 7;
+    static final int REF_newInvokeSpecial = 8;
+    static final int REF_invokeInterface = 9;
+
+    private final int kind;
+
+    // A `Field`, `Method`, or `Constructor` that isn't shared with user code,
+    // with access checks suppressed
+    private final AccessibleObject member;
+
+    // Whether this handle collects trailing arguments into an array when it's
+    // called with a different arity, as variable arity methods are
+    private final boolean varargs;
+
+    DirectMethodHandle(int kind, Class<?> refc, AccessibleObject member) {
+        super(DirectMethodHandle.typeOf(kind, refc, member));
+
+        member.setAccessible(true);
+
+        this.kind = kind;
+        this.member = member;
+        this.varargs = member instanceof Executable && ((Executable) member).isVarArgs();
+    }
+
+    private DirectMethodHandle(DirectMethodHandle handle, boolean varargs) {
+        super(handle.type());
+
+        this.kind = handle.kind;
+        this.member = handle.member;
+        this.varargs = varargs;
+    }
+
+    // The type of a handle of the given kind for `member`, referenced through
+    // the class `refc`.
+    private static MethodType typeOf(int kind, Class<?> refc, AccessibleObject member) {
+        switch (kind) {
+            case REF_getField:
+                return MethodType.methodType(((Field) member).getType(), refc);
+            case REF_getStatic:
+                return MethodType.methodType(((Field) member).getType());
+            case REF_putField:
+                return MethodType.methodType(void.class, refc, ((Field) member).getType());
+            case REF_putStatic:
+                return MethodType.methodType(void.class, ((Field) member).getType());
+            case REF_newInvokeSpecial:
+                return MethodType.methodType(refc, ((Constructor<?>) member).getParameterTypes());
+            case REF_invokeStatic: {
+                Method method = (Method) member;
+                return MethodType.methodType(method.getReturnType(), method.getParameterTypes());
+            }
+            default: {
+                // Instance methods take the receiver as their first parameter
+                Method method = (Method) member;
+                Class<?>[] parameterTypes = method.getParameterTypes();
+
+                Class<?>[] handleTypes = new Class<?>[parameterTypes.length + 1];
+                handleTypes[0] = refc;
+                System.arraycopy(parameterTypes, 0, handleTypes, 1, parameterTypes.length);
+
+                return MethodType.methodType(method.getReturnType(), handleTypes);
+            }
+        }
+    }
+
+    public boolean isVarargsCollector() {
+        return this.varargs;
+    }
+
+    public MethodHandle asFixedArity() {
+        if (this.varargs) {
+            return new DirectMethodHandle(this, false);
+        } else {
+            return this;
+        }
+    }
+
+    Object invokeImpl(Object[] arguments) throws Throwable {
+        try {
+            switch (this.kind) {
+                case REF_getField:
+                    return ((Field) this.member).get(DirectMethodHandle.receiver(arguments));
+                case REF_getStatic:
+                    return ((Field) this.member).get(null);
+                case REF_putField:
+                    ((Field) this.member).set(DirectMethodHandle.receiver(arguments), arguments[1]);
+                    return null;
+                case REF_putStatic:
+                    ((Field) this.member).set(null, arguments[0]);
+                    return null;
+                case REF_newInvokeSpecial:
+                    return ((Constructor<?>) this.member).newInstance(arguments);
+                case REF_invokeStatic:
+                    return ((Method) this.member).invoke(null, arguments);
+                case REF_invokeSpecial: {
+                    Object receiver = DirectMethodHandle.receiver(arguments);
+                    Object[] methodArguments = new Object[arguments.length - 1];
+                    System.arraycopy(arguments, 1, methodArguments, 0, methodArguments.length);
+
+                    return MethodHandleNatives.invokeSpecial((Method) this.member, receiver, methodArguments);
+                }
+                default: {
+                    Object[] methodArguments = new Object[arguments.length - 1];
+                    System.arraycopy(arguments, 1, methodArguments, 0, methodArguments.length);
+
+                    Object receiver = DirectMethodHandle.receiver(arguments);
+                    return ((Method) this.member).invoke(receiver, methodArguments);
+                }
+            }
+        } catch (InvocationTargetException e) {
+            // Exceptions are thrown directly from method handles
+            throw e.getCause();
+        }
+    }
+
+    private static Object receiver(Object[] arguments) {
+        if (arguments[0] == null) {
+            throw new NullPointerException();
+        }
+
+        return arguments[0];
+    }
+}
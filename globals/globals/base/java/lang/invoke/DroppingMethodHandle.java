@@ -0,0 +1,25 @@
+package java.lang.invoke;
+
+// A method handle that ignores some of its arguments before calling another
+// handle. This implements `MethodHandles.dropArguments`.
+final class DroppingMethodHandle extends MethodHandle {
+    private final MethodHandle target;
+    private final int position;
+    private final int dropCount;
+
+    DroppingMethodHandle(MethodHandle target, int position, Class<?>[] valueTypes) {
+        super(target.type().insertParameterTypes(position, valueTypes));
+
+        this.target = target;
+        this.position = position;
+        this.dropCount = valueTypes.length;
+    }
+
+    Object invokeImpl(Object[] arguments) throws Throwable {
+        Object[] targetArguments = new Object[arguments.length - this.dropCount];
+        System.arraycopy(arguments, 0, targetArguments, 0, this.position);
+        System.arraycopy(arguments, this.position + this.dropCount, targetArguments, this.position, targetArguments.length - this.position);
+
+        return this.target.invokeImpl(targetArguments);
+    }
+}
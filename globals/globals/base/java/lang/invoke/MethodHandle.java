@@ -1,3 +1,114 @@
 package java.lang.invoke;
 
-public abstract class MethodHandle { }
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.lang.annotation.Target;
+import java.util.List;
+
+public abstract class MethodHandle {
+    // Marks the methods that can be called with any descriptor. The VM
+    // recognizes these methods by their flags and parameter types instead.
+    @Target({ElementType.METHOD})
+    @Retention(RetentionPolicy.RUNTIME)
+    @interface PolymorphicSignature { }
+
+    private final MethodType type;
+
+    MethodHandle(MethodType type) {
+        this.type = type;
+    }
+
+    public MethodType type() {
+        return this.type;
+    }
+
+    @PolymorphicSignature
+    public final native Object invokeExact(Object... args) throws Throwable;
+
+    @PolymorphicSignature
+    public final native Object invoke(Object... args) throws Throwable;
+
+    public boolean isVarargsCollector() {
+        return false;
+    }
+
+    public MethodHandle asFixedArity() {
+        return this;
+    }
+
+    public MethodHandle asType(MethodType newType) {
+        if (newType.equals(this.type)) {
+            return this;
+        }
+
+        if (this.isVarargsCollector()) {
+            int collectPosition = this.type.parameterCount() - 1;
+            Class<?> arrayType = this.type.parameterType(collectPosition);
+
+            // A trailing argument that can be passed as the array itself
+            // calls the handle with fixed arity
+            if (newType.parameterCount() == collectPosition + 1 && arrayType.isAssignableFrom(newType.parameterType(collectPosition))) {
+                return this.asFixedArity().asType(newType);
+            }
+
+            int collectCount = newType.parameterCount() - collectPosition;
+            if (collectCount < 0) {
+                throw this.cannotConvert(newType);
+            }
+
+            return new CollectingMethodHandle(this.asFixedArity(), collectCount).asType(newType);
+        }
+
+        if (!ConvertingMethodHandle.canConvert(this.type, newType)) {
+            throw this.cannotConvert(newType);
+        }
+
+        return new ConvertingMethodHandle(this, newType);
+    }
+
+    private WrongMethodTypeException cannotConvert(MethodType newType) {
+        StringBuilder message = new StringBuilder();
+        message.append("cannot convert ");
+        message.append(this.toString());
+        message.append(" to ");
+        message.append(newType.toString());
+
+        return new WrongMethodTypeException(message.toString());
+    }
+
+    public MethodHandle bindTo(Object x) {
+        if (this.type.parameterCount() == 0 || this.type.parameterType(0).isPrimitive()) {
+            throw new IllegalArgumentException("no leading reference parameter");
+        }
+
+        return MethodHandles.insertArguments(this, 0, x);
+    }
+
+    public Object invokeWithArguments(Object... arguments) throws Throwable {
+        if (arguments == null) {
+            arguments = new Object[0];
+        }
+
+        MethodType genericType = MethodType.genericMethodType(arguments.length);
+
+        return this.asType(genericType).invokeImpl(arguments);
+    }
+
+    public Object invokeWithArguments(List<?> arguments) throws Throwable {
+        return this.invokeWithArguments(arguments.toArray());
+    }
+
+    // Invoke this handle with arguments matching its type. The arguments are
+    // boxed, and the result is boxed to the return type, or `null` if the
+    // return type is `void`.
+    abstract Object invokeImpl(Object[] arguments) throws Throwable;
+
+    public String toString() {
+        StringBuilder result = new StringBuilder();
+        result.append("MethodHandle");
+        result.append(this.type.toString());
+
+        return result.toString();
+    }
+}
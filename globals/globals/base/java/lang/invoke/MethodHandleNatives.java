@@ -0,0 +1,136 @@
+package java.lang.invoke;
+
+import java.lang.reflect.AccessibleObject;
+import java.lang.reflect.Method;
+
+// Methods called by the VM to resolve `MethodHandle` and dynamic constants and
+// to invoke method handles, and natives used to look up the members of handles.
+final class MethodHandleNatives {
+    private MethodHandleNatives() { }
+
+    // Returns a new `Field`, `Method`, or `Constructor` for the member named
+    // `name` with the given descriptor, found through the class `refc`, or
+    // `null` if it doesn't exist. Methods and fields that aren't static are
+    // returned if no static member exists, and the reverse.
+    static native AccessibleObject resolveMember(int kind, Class<?> refc, String name, String descriptor);
+
+    // Returns the return type followed by the parameter types of a method
+    // descriptor, with classes loaded by `loader`, or `null` if the
+    // descriptor isn't valid.
+    static native Class<?>[] resolveMethodDescriptor(String descriptor, ClassLoader loader);
+
+    // Call the instance method `method` on `receiver` without dispatching on
+    // the class of the receiver, as `invokespecial` does. Primitive arguments
+    // and results are boxed, and exceptions are thrown directly.
+    static native Object invokeSpecial(Method method, Object receiver, Object[] arguments) throws Throwable;
+
+    // Call `handle` from a call site of `invokeExact` or `invoke` with the given
+    // types. The arguments are boxed, and the result is boxed to the return
+    // type of the call site.
+    static Object invokeFromCallSite(MethodHandle handle, boolean exact, Class<?> returnType, Class<?>[] parameterTypes, Object[] arguments) throws Throwable {
+        MethodType type = MethodType.methodType(returnType, parameterTypes);
+
+        if (exact) {
+            if (!handle.type().equals(type)) {
+                StringBuilder message = new StringBuilder();
+                message.append("expected ");
+                message.append(handle.type().toString());
+                message.append(" but found ");
+                message.append(type.toString());
+
+                throw new WrongMethodTypeException(message.toString());
+            }
+
+            return handle.invokeImpl(arguments);
+        } else {
+            return handle.asType(type).invokeImpl(arguments);
+        }
+    }
+
+    // Create the handle for a `CONSTANT_MethodHandle` of the given kind, whose
+    // reference resolved to `member` through the class `refc`. For
+    // `invokeSpecial` handles, `refc` is the class containing the constant.
+    static MethodHandle linkMethodHandleConstant(int kind, Class<?> refc, AccessibleObject member) {
+        return new DirectMethodHandle(kind, refc, member);
+    }
+
+    // Compute the value of a `CONSTANT_Dynamic` of type `type` in the class
+    // `caller`, by calling its bootstrap method.
+    static Object linkDynamicConstant(Class<?> caller, MethodHandle bootstrapMethod, String name, Class<?> type, Object[] staticArguments) {
+        Object[] arguments = new Object[staticArguments.length + 3];
+        arguments[0] = new MethodHandles.Lookup(caller);
+        arguments[1] = name;
+        arguments[2] = type;
+        System.arraycopy(staticArguments, 0, arguments, 3, staticArguments.length);
+
+        Object result;
+        try {
+            result = bootstrapMethod.invokeWithArguments(arguments);
+        } catch (Error e) {
+            throw e;
+        } catch (Throwable t) {
+            throw new BootstrapMethodError("bootstrap method initialization exception", t);
+        }
+
+        if (!type.isPrimitive()) {
+            if (result != null && !type.isInstance(result)) {
+                throw MethodHandleNatives.resultMismatch(result, type);
+            }
+        } else if (result == null) {
+            throw new BootstrapMethodError("bootstrap method initialization exception", new NullPointerException());
+        } else if (result.getClass() != ConvertingMethodHandle.wrapperFor(type)) {
+            throw MethodHandleNatives.resultMismatch(result, type);
+        }
+
+        return result;
+    }
+
+    // Link an `invokedynamic` call site with the given type in the class
+    // `caller`, by calling its bootstrap method. Returns the target of the
+    // resulting `CallSite`.
+    static MethodHandle linkCallSite(Class<?> caller, MethodHandle bootstrapMethod, String name, MethodType type, Object[] staticArguments) {
+        Object[] arguments = new Object[staticArguments.length + 3];
+        arguments[0] = new MethodHandles.Lookup(caller);
+        arguments[1] = name;
+        arguments[2] = type;
+        System.arraycopy(staticArguments, 0, arguments, 3, staticArguments.length);
+
+        Object result;
+        try {
+            result = bootstrapMethod.invokeWithArguments(arguments);
+        } catch (Error e) {
+            throw e;
+        } catch (Throwable t) {
+            throw new BootstrapMethodError("CallSite bootstrap method initialization exception", t);
+        }
+
+        if (result == null) {
+            throw new BootstrapMethodError("CallSite bootstrap method initialization exception", new NullPointerException());
+        } else if (!(result instanceof CallSite)) {
+            throw new BootstrapMethodError("CallSite bootstrap method initialization exception", new ClassCastException());
+        }
+
+        MethodHandle target = ((CallSite) result).getTarget();
+        if (!target.type().equals(type)) {
+            StringBuilder message = new StringBuilder();
+            message.append("CallSite bootstrap method initialization exception: expected ");
+            message.append(type.toString());
+            message.append(" but found ");
+            message.append(target.type().toString());
+
+            throw new BootstrapMethodError(message.toString(), new WrongMethodTypeException());
+        }
+
+        return target;
+    }
+
+    private static BootstrapMethodError resultMismatch(Object result, Class<?> type) {
+        StringBuilder message = new StringBuilder();
+        message.append("Cannot cast ");
+        message.append(result.getClass().getName());
+        message.append(" to ");
+        message.append(type.getName());
+
+        return new BootstrapMethodError("bootstrap method initialization exception", new ClassCastException(message.toString()));
+    }
+}
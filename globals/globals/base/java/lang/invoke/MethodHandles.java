@@ -1,5 +1,255 @@
 package java.lang.invoke;
 
+import java.lang.reflect.AccessibleObject;
+import java.lang.reflect.Constructor;
+import java.lang.reflect.Executable;
+import java.lang.reflect.Field;
+import java.lang.reflect.Member;
+import java.lang.reflect.Method;
+import java.lang.reflect.Modifier;
+import java.util.List;
+
+import rjvm.internal.Reflection;
+
 public class MethodHandles {
-    public static final class Lookup { }
+    private MethodHandles() { }
+
+    public static Lookup lookup() {
+        return new Lookup(Reflection.getCallerClass());
+    }
+
+    public static MethodHandle insertArguments(MethodHandle target, int pos, Object... values) {
+        if (pos < 0 || pos + values.length > target.type().parameterCount()) {
+            throw new IllegalArgumentException("too many values to insert");
+        } else if (values.length == 0) {
+            return target;
+        }
+
+        return new BoundMethodHandle(target, pos, values);
+    }
+
+    public static MethodHandle dropArguments(MethodHandle target, int pos, Class<?>... valueTypes) {
+        if (pos < 0 || pos > target.type().parameterCount()) {
+            throw new IllegalArgumentException("bad argument position");
+        } else if (valueTypes.length == 0) {
+            return target;
+        }
+
+        return new DroppingMethodHandle(target, pos, valueTypes.clone());
+    }
+
+    public static MethodHandle dropArguments(MethodHandle target, int pos, List<Class<?>> valueTypes) {
+        return MethodHandles.dropArguments(target, pos, valueTypes.toArray(new Class<?>[0]));
+    }
+
+    public static final class Lookup {
+        private final Class<?> lookupClass;
+
+        Lookup(Class<?> lookupClass) {
+            this.lookupClass = lookupClass;
+        }
+
+        public Class<?> lookupClass() {
+            return this.lookupClass;
+        }
+
+        public MethodHandle findStatic(Class<?> refc, String name, MethodType type) throws NoSuchMethodException, IllegalAccessException {
+            return this.findMethod(DirectMethodHandle.REF_invokeStatic, refc, name, type);
+        }
+
+        public MethodHandle findVirtual(Class<?> refc, String name, MethodType type) throws NoSuchMethodException, IllegalAccessException {
+            int kind = refc.isInterface() ? DirectMethodHandle.REF_invokeInterface : DirectMethodHandle.REF_invokeVirtual;
+
+            return this.findMethod(kind, refc, name, type);
+        }
+
+        public MethodHandle findConstructor(Class<?> refc, MethodType type) throws NoSuchMethodException, IllegalAccessException {
+            return this.findMethod(DirectMethodHandle.REF_newInvokeSpecial, refc, "<init>", type);
+        }
+
+        public MethodHandle findGetter(Class<?> refc, String name, Class<?> type) throws NoSuchFieldException, IllegalAccessException {
+            return this.findField(DirectMethodHandle.REF_getField, refc, name, type);
+        }
+
+        public MethodHandle findSetter(Class<?> refc, String name, Class<?> type) throws NoSuchFieldException, IllegalAccessException {
+            return this.findField(DirectMethodHandle.REF_putField, refc, name, type);
+        }
+
+        public MethodHandle findStaticGetter(Class<?> refc, String name, Class<?> type) throws NoSuchFieldException, IllegalAccessException {
+            return this.findField(DirectMethodHandle.REF_getStatic, refc, name, type);
+        }
+
+        public MethodHandle findStaticSetter(Class<?> refc, String name, Class<?> type) throws NoSuchFieldException, IllegalAccessException {
+            return this.findField(DirectMethodHandle.REF_putStatic, refc, name, type);
+        }
+
+        public MethodHandle unreflect(Method method) throws IllegalAccessException {
+            int kind;
+            if (Modifier.isStatic(method.getModifiers())) {
+                kind = DirectMethodHandle.REF_invokeStatic;
+            } else if (method.getDeclaringClass().isInterface()) {
+                kind = DirectMethodHandle.REF_invokeInterface;
+            } else {
+                kind = DirectMethodHandle.REF_invokeVirtual;
+            }
+
+            MethodType type = MethodType.methodType(method.getReturnType(), method.getParameterTypes());
+
+            return this.unreflectMember(kind, method, type, method.isAccessible());
+        }
+
+        public MethodHandle unreflectConstructor(Constructor<?> constructor) throws IllegalAccessException {
+            MethodType type = MethodType.methodType(void.class, constructor.getParameterTypes());
+
+            return this.unreflectMember(DirectMethodHandle.REF_newInvokeSpecial, constructor, type, constructor.isAccessible());
+        }
+
+        public MethodHandle unreflectGetter(Field field) throws IllegalAccessException {
+            int kind = Modifier.isStatic(field.getModifiers()) ? DirectMethodHandle.REF_getStatic : DirectMethodHandle.REF_getField;
+
+            return this.unreflectMember(kind, field, field.getType(), field.isAccessible());
+        }
+
+        public MethodHandle unreflectSetter(Field field) throws IllegalAccessException {
+            int kind = Modifier.isStatic(field.getModifiers()) ? DirectMethodHandle.REF_putStatic : DirectMethodHandle.REF_putField;
+
+            return this.unreflectMember(kind, field, field.getType(), field.isAccessible());
+        }
+
+        private MethodHandle findMethod(int kind, Class<?> refc, String name, MethodType type) throws NoSuchMethodException, IllegalAccessException {
+            AccessibleObject member = MethodHandleNatives.resolveMember(kind, refc, name, type.toMethodDescriptorString());
+            if (member == null) {
+                throw new NoSuchMethodException(Lookup.describeMember("no such method", kind, refc, name, type));
+            }
+
+            return this.linkMember(kind, refc, name, type, member, false);
+        }
+
+        private MethodHandle findField(int kind, Class<?> refc, String name, Class<?> type) throws NoSuchFieldException, IllegalAccessException {
+            AccessibleObject member = MethodHandleNatives.resolveMember(kind, refc, name, type.descriptorString());
+            if (member == null) {
+                throw new NoSuchFieldException(Lookup.describeMember("no such field", kind, refc, name, type));
+            }
+
+            return this.linkMember(kind, refc, name, type, member, false);
+        }
+
+        // Create a handle for a member that was obtained through reflection.
+        // A new member object is used, so that its accessibility flag can be
+        // set without affecting the one passed to the handle.
+        private MethodHandle unreflectMember(int kind, Member reflected, Object type, boolean accessible) throws IllegalAccessException {
+            Class<?> refc = reflected.getDeclaringClass();
+            String name = reflected instanceof Constructor ? "<init>" : reflected.getName();
+
+            String descriptor;
+            if (type instanceof MethodType) {
+                descriptor = ((MethodType) type).toMethodDescriptorString();
+            } else {
+                descriptor = ((Class<?>) type).descriptorString();
+            }
+
+            AccessibleObject member = MethodHandleNatives.resolveMember(kind, refc, name, descriptor);
+
+            return this.linkMember(kind, refc, name, type, member, accessible);
+        }
+
+        // Check that `member` can be accessed by a handle of the given kind
+        // from the lookup class, and create that handle.
+        private MethodHandle linkMember(int kind, Class<?> refc, String name, Object type, AccessibleObject member, boolean accessible) throws IllegalAccessException {
+            int modifiers = ((Member) member).getModifiers();
+            Class<?> declaringClass = ((Member) member).getDeclaringClass();
+
+            boolean isField = !(member instanceof Executable);
+            boolean expectStatic = kind == DirectMethodHandle.REF_getStatic || kind == DirectMethodHandle.REF_putStatic || kind == DirectMethodHandle.REF_invokeStatic;
+
+            if (Modifier.isStatic(modifiers) != expectStatic) {
+                String reason = isField ? "no such field" : "no such method";
+                throw new IllegalAccessException(Lookup.describeMember(reason, kind, refc, name, type));
+            }
+
+            boolean isSetter = kind == DirectMethodHandle.REF_putField || kind == DirectMethodHandle.REF_putStatic;
+
+            // Final instance fields can only be set through fields that had
+            // access checks suppressed
+            if (isSetter && Modifier.isFinal(modifiers) && (expectStatic || !accessible)) {
+                throw this.accessFailure("unexpected set of a final field", kind, refc, name, type);
+            }
+
+            if (!accessible && !Reflection.verifyMemberAccess(this.lookupClass, declaringClass, null, modifiers)) {
+                String reason;
+                if (Modifier.isPrivate(modifiers)) {
+                    reason = "member is private";
+                } else if (Modifier.isProtected(modifiers)) {
+                    reason = "member is protected";
+                } else if (Modifier.isPublic(modifiers)) {
+                    reason = "class is not public";
+                } else {
+                    reason = "member is private to package";
+                }
+
+                throw this.accessFailure(reason, kind, refc, name, type);
+            }
+
+            return new DirectMethodHandle(kind, refc, member);
+        }
+
+        private IllegalAccessException accessFailure(String reason, int kind, Class<?> refc, String name, Object type) {
+            StringBuilder message = new StringBuilder();
+            message.append(Lookup.describeMember(reason, kind, refc, name, type));
+            message.append(", from class ");
+            message.append(this.lookupClass.getName());
+
+            return new IllegalAccessException(message.toString());
+        }
+
+        // Describe a member in the format used by exception messages, such as
+        // `no such method: Example.run(int)void/invokeStatic`.
+        private static String describeMember(String reason, int kind, Class<?> refc, String name, Object type) {
+            StringBuilder result = new StringBuilder();
+            result.append(reason);
+            result.append(": ");
+            result.append(refc.getName());
+            result.append('.');
+            result.append(name);
+
+            if (type instanceof MethodType) {
+                result.append(type.toString());
+            } else {
+                result.append('/');
+                result.append(((Class<?>) type).getName());
+            }
+
+            result.append('/');
+            result.append(Lookup.kindName(kind));
+
+            return result.toString();
+        }
+
+        private static String kindName(int kind) {
+            switch (kind) {
+                case DirectMethodHandle.REF_getField:
+                    return "getField";
+                case DirectMethodHandle.REF_getStatic:
+                    return "getStatic";
+                case DirectMethodHandle.REF_putField:
+                    return "putField";
+                case DirectMethodHandle.REF_putStatic:
+                    return "putStatic";
+                case DirectMethodHandle.REF_invokeVirtual:
+                    return "invokeVirtual";
+                case DirectMethodHandle.REF_invokeStatic:
+                    return "invokeStatic";
+                case DirectMethodHandle.REF_invokeSpecial:
+                    return "invokeSpecial";
+                case DirectMethodHandle.REF_newInvokeSpecial:
+                    return "newInvokeSpecial";
+                default:
+                    return "invokeInterface";
+            }
+        }
+
+        public String toString() {
+            return this.lookupClass.getName();
+        }
+    }
 }
@@ -1,3 +1,218 @@
 package java.lang.invoke;
 
-public final class MethodType { }
+import java.util.Arrays;
+import java.util.List;
+
+public final class MethodType implements TypeDescriptor {
+    private final Class<?> rtype;
+    private final Class<?>[] ptypes;
+
+    private MethodType(Class<?> rtype, Class<?>[] ptypes) {
+        if (rtype == null) {
+            throw new NullPointerException();
+        }
+
+        for (int i = 0; i < ptypes.length; i ++) {
+            if (ptypes[i] == null) {
+                throw new NullPointerException();
+            } else if (ptypes[i] == void.class) {
+                throw new IllegalArgumentException("parameter type cannot be void");
+            }
+        }
+
+        this.rtype = rtype;
+        this.ptypes = ptypes;
+    }
+
+    public static MethodType methodType(Class<?> rtype) {
+        return new MethodType(rtype, new Class<?>[0]);
+    }
+
+    public static MethodType methodType(Class<?> rtype, Class<?> ptype0) {
+        return new MethodType(rtype, new Class<?>[] { ptype0 });
+    }
+
+    public static MethodType methodType(Class<?> rtype, Class<?> ptype0, Class<?>... ptypes) {
+        Class<?>[] allTypes = new Class<?>[ptypes.length + 1];
+        allTypes[0] = ptype0;
+        System.arraycopy(ptypes, 0, allTypes, 1, ptypes.length);
+
+        return new MethodType(rtype, allTypes);
+    }
+
+    public static MethodType methodType(Class<?> rtype, Class<?>[] ptypes) {
+        return new MethodType(rtype, ptypes.clone());
+    }
+
+    public static MethodType methodType(Class<?> rtype, List<Class<?>> ptypes) {
+        return new MethodType(rtype, ptypes.toArray(new Class<?>[0]));
+    }
+
+    public static MethodType methodType(Class<?> rtype, MethodType ptypes) {
+        return new MethodType(rtype, ptypes.ptypes);
+    }
+
+    public static MethodType genericMethodType(int objectArgCount) {
+        if (objectArgCount < 0 || objectArgCount > 255) {
+            throw new IllegalArgumentException("bad parameter count");
+        }
+
+        Class<?>[] ptypes = new Class<?>[objectArgCount];
+        for (int i = 0; i < objectArgCount; i ++) {
+            ptypes[i] = Object.class;
+        }
+
+        return new MethodType(Object.class, ptypes);
+    }
+
+    public static MethodType fromMethodDescriptorString(String descriptor, ClassLoader loader) throws IllegalArgumentException, TypeNotPresentException {
+        if (loader == null) {
+            loader = ClassLoader.getSystemClassLoader();
+        }
+
+        Class<?>[] types;
+        try {
+            types = MethodHandleNatives.resolveMethodDescriptor(descriptor, loader);
+        } catch (NoClassDefFoundError e) {
+            throw new TypeNotPresentException(e.getMessage().replace('/', '.'), e);
+        }
+
+        if (types == null) {
+            StringBuilder message = new StringBuilder();
+            message.append("not a method descriptor: ");
+            message.append(descriptor);
+
+            throw new IllegalArgumentException(message.toString());
+        }
+
+        // The return type comes first
+        return new MethodType(types[0], Arrays.copyOfRange(types, 1, types.length));
+    }
+
+    public Class<?> returnType() {
+        return this.rtype;
+    }
+
+    public Class<?> parameterType(int num) {
+        return this.ptypes[num];
+    }
+
+    public int parameterCount() {
+        return this.ptypes.length;
+    }
+
+    public List<Class<?>> parameterList() {
+        return Arrays.asList(this.ptypes.clone());
+    }
+
+    public Class<?>[] parameterArray() {
+        return this.ptypes.clone();
+    }
+
+    public Class<?> lastParameterType() {
+        if (this.ptypes.length == 0) {
+            return void.class;
+        } else {
+            return this.ptypes[this.ptypes.length - 1];
+        }
+    }
+
+    public MethodType changeReturnType(Class<?> nrtype) {
+        return new MethodType(nrtype, this.ptypes);
+    }
+
+    public MethodType changeParameterType(int num, Class<?> nptype) {
+        Class<?>[] newTypes = this.ptypes.clone();
+        newTypes[num] = nptype;
+
+        return new MethodType(this.rtype, newTypes);
+    }
+
+    public MethodType insertParameterTypes(int num, Class<?>... ptypesToInsert) {
+        if (num < 0 || num > this.ptypes.length) {
+            throw new IndexOutOfBoundsException();
+        }
+
+        Class<?>[] newTypes = new Class<?>[this.ptypes.length + ptypesToInsert.length];
+        System.arraycopy(this.ptypes, 0, newTypes, 0, num);
+        System.arraycopy(ptypesToInsert, 0, newTypes, num, ptypesToInsert.length);
+        System.arraycopy(this.ptypes, num, newTypes, num + ptypesToInsert.length, this.ptypes.length - num);
+
+        return new MethodType(this.rtype, newTypes);
+    }
+
+    public MethodType insertParameterTypes(int num, List<Class<?>> ptypesToInsert) {
+        return this.insertParameterTypes(num, ptypesToInsert.toArray(new Class<?>[0]));
+    }
+
+    public MethodType appendParameterTypes(Class<?>... ptypesToInsert) {
+        return this.insertParameterTypes(this.ptypes.length, ptypesToInsert);
+    }
+
+    public MethodType appendParameterTypes(List<Class<?>> ptypesToInsert) {
+        return this.insertParameterTypes(this.ptypes.length, ptypesToInsert);
+    }
+
+    public MethodType dropParameterTypes(int start, int end) {
+        if (start < 0 || start > end || end > this.ptypes.length) {
+            throw new IndexOutOfBoundsException();
+        }
+
+        Class<?>[] newTypes = new Class<?>[this.ptypes.length - (end - start)];
+        System.arraycopy(this.ptypes, 0, newTypes, 0, start);
+        System.arraycopy(this.ptypes, end, newTypes, start, this.ptypes.length - end);
+
+        return new MethodType(this.rtype, newTypes);
+    }
+
+    public String toMethodDescriptorString() {
+        StringBuilder result = new StringBuilder();
+        result.append('(');
+        for (int i = 0; i < this.ptypes.length; i ++) {
+            result.append(this.ptypes[i].descriptorString());
+        }
+        result.append(')');
+        result.append(this.rtype.descriptorString());
+
+        return result.toString();
+    }
+
+    public String descriptorString() {
+        return this.toMethodDescriptorString();
+    }
+
+    public boolean equals(Object other) {
+        if (!(other instanceof MethodType)) {
+            return false;
+        }
+
+        MethodType otherType = (MethodType) other;
+
+        return this.rtype == otherType.rtype && Arrays.equals(this.ptypes, otherType.ptypes);
+    }
+
+    public int hashCode() {
+        int hashCode = 31 + this.rtype.hashCode();
+        for (int i = 0; i < this.ptypes.length; i ++) {
+            hashCode = 31 * hashCode + this.ptypes[i].hashCode();
+        }
+
+        return hashCode;
+    }
+
+    public String toString() {
+        StringBuilder result = new StringBuilder();
+        result.append('(');
+        for (int i = 0; i < this.ptypes.length; i ++) {
+            if (i != 0) {
+                result.append(',');
+            }
+
+            result.append(this.ptypes[i].getSimpleName());
+        }
+        result.append(')');
+        result.append(this.rtype.getSimpleName());
+
+        return result.toString();
+    }
+}
@@ -0,0 +1,69 @@
+package java.lang.invoke;
+
+public final class StringConcatFactory {
+    static final char TAG_ARGUMENT = '\u0001';
+    static final char TAG_CONSTANT = '\u0002';
+
+    private StringConcatFactory() { }
+
+    public static CallSite makeConcat(MethodHandles.Lookup lookup, String name, MethodType concatType) throws StringConcatException {
+        StringBuilder recipe = new StringBuilder();
+        for (int i = 0; i < concatType.parameterCount(); i ++) {
+            recipe.append(TAG_ARGUMENT);
+        }
+
+        return StringConcatFactory.makeConcatWithConstants(lookup, name, concatType, recipe.toString());
+    }
+
+    public static CallSite makeConcatWithConstants(MethodHandles.Lookup lookup, String name, MethodType concatType, String recipe, Object... constants) throws StringConcatException {
+        if (lookup == null || name == null || concatType == null || recipe == null || constants == null) {
+            throw new NullPointerException();
+        }
+
+        if (!concatType.returnType().isAssignableFrom(String.class)) {
+            StringBuilder message = new StringBuilder();
+            message.append("The return type should be compatible with String, but it is ");
+            message.append(concatType.returnType().getName());
+
+            throw new StringConcatException(message.toString());
+        }
+
+        int argumentCount = 0;
+        int constantCount = 0;
+        for (int i = 0; i < recipe.length(); i ++) {
+            char c = recipe.charAt(i);
+            if (c == TAG_ARGUMENT) {
+                argumentCount ++;
+            } else if (c == TAG_CONSTANT) {
+                constantCount ++;
+            }
+        }
+
+        if (argumentCount != concatType.parameterCount()) {
+            StringBuilder message = new StringBuilder();
+            message.append("Mismatched number of concat arguments: recipe wants ");
+            message.append(argumentCount);
+            message.append(" arguments, but signature provides ");
+            message.append(concatType.parameterCount());
+
+            throw new StringConcatException(message.toString());
+        }
+
+        if (constantCount != constants.length) {
+            StringBuilder message = new StringBuilder();
+            message.append("Mismatched number of concat constants: recipe wants ");
+            message.append(constantCount);
+            message.append(" constants, but only ");
+            message.append(constants.length);
+            message.append(" are passed");
+
+            throw new StringConcatException(message.toString());
+        }
+
+        // The call site can declare a supertype of `String` as its return type
+        MethodType genericType = concatType.changeReturnType(String.class);
+        MethodHandle handle = new ConcatMethodHandle(genericType, recipe, constants.clone());
+
+        return new ConstantCallSite(handle.asType(concatType));
+    }
+}
@@ -1,5 +1,70 @@
 package java.lang.reflect;
 
+import java.lang.annotation.Annotation;
+
+import rjvm.internal.Reflection;
+
 public class AccessibleObject implements AnnotatedElement {
     protected AccessibleObject() { }
+
+    public static void setAccessible(AccessibleObject[] array, boolean flag) {
+        for (int i = 0; i < array.length; i ++) {
+            array[i].setAccessible(flag);
+        }
+    }
+
+    public void setAccessible(boolean flag) {
+        this.setOverride(flag);
+    }
+
+    public final boolean trySetAccessible() {
+        this.setOverride(true);
+        return true;
+    }
+
+    @Deprecated
+    public boolean isAccessible() {
+        return this.getOverride();
+    }
+
+    // The accessibility flag is stored by subclasses, as adding a field here
+    // would change the field ordering that native code relies on
+    boolean getOverride() {
+        throw new Error("All subclasses should override this method");
+    }
+
+    void setOverride(boolean override) {
+        throw new Error("All subclasses should override this method");
+    }
+
+    // Check that `caller` can access a member of `declaringClass` with the
+    // given modifiers, ignoring the accessibility flag. `receiver` is the
+    // object the member is accessed on, or `null` for static members.
+    static void checkMemberAccess(Class<?> caller, Class<?> declaringClass, Object receiver, int modifiers) throws IllegalAccessException {
+        if (!Reflection.verifyMemberAccess(caller, declaringClass, receiver, modifiers)) {
+            StringBuilder message = new StringBuilder();
+            message.append("class ");
+            message.append(caller.getName());
+            message.append(" cannot access a member of class ");
+            message.append(declaringClass.getName());
+            message.append(" with modifiers \"");
+            message.append(Modifier.toString(modifiers & (Modifier.PUBLIC | Modifier.PROTECTED | Modifier.PRIVATE | Modifier.STATIC | Modifier.FINAL)));
+            message.append('"');
+
+            throw new IllegalAccessException(message.toString());
+        }
+    }
+
+    public <T extends Annotation> T getAnnotation(Class<T> annotationClass) {
+        // Annotations on fields, methods, and constructors are never inherited
+        return this.getDeclaredAnnotation(annotationClass);
+    }
+
+    public Annotation[] getAnnotations() {
+        return this.getDeclaredAnnotations();
+    }
+
+    public Annotation[] getDeclaredAnnotations() {
+        throw new Error("All subclasses should override this method");
+    }
 }
@@ -1,3 +1,30 @@
 package java.lang.reflect;
 
-public interface AnnotatedElement { }
+import java.lang.annotation.Annotation;
+
+public interface AnnotatedElement {
+    default boolean isAnnotationPresent(Class<? extends Annotation> annotationClass) {
+        return this.getAnnotation(annotationClass) != null;
+    }
+
+    <T extends Annotation> T getAnnotation(Class<T> annotationClass);
+
+    Annotation[] getAnnotations();
+
+    default <T extends Annotation> T getDeclaredAnnotation(Class<T> annotationClass) {
+        if (annotationClass == null) {
+            throw new NullPointerException();
+        }
+
+        Annotation[] annotations = this.getDeclaredAnnotations();
+        for (int i = 0; i < annotations.length; i ++) {
+            if (annotations[i].annotationType() == annotationClass) {
+                return (T) annotations[i];
+            }
+        }
+
+        return null;
+    }
+
+    Annotation[] getDeclaredAnnotations();
+}
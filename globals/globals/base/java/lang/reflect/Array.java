@@ -14,5 +14,47 @@ public final class Array {
         return Array.newInstanceNative(componentType, length);
     }
 
+    public static void set(Object array, int index, Object value) throws IllegalArgumentException, ArrayIndexOutOfBoundsException {
+        if (array == null) {
+            throw new NullPointerException();
+        }
+
+        Class<?> componentType = array.getClass().getComponentType();
+        if (componentType == null) {
+            throw new IllegalArgumentException("Argument is not an array");
+        }
+
+        if (!componentType.isPrimitive()) {
+            if (value != null && !componentType.isInstance(value)) {
+                throw new IllegalArgumentException("array element type mismatch");
+            }
+
+            ((Object[]) array)[index] = value;
+            return;
+        }
+
+        // Primitive elements are stored with the same conversions as
+        // reflective call arguments
+        Object converted = Executable.convertArgument(componentType, value);
+
+        if (componentType == boolean.class) {
+            ((boolean[]) array)[index] = ((Boolean) converted).booleanValue();
+        } else if (componentType == byte.class) {
+            ((byte[]) array)[index] = ((Byte) converted).byteValue();
+        } else if (componentType == char.class) {
+            ((char[]) array)[index] = ((Character) converted).charValue();
+        } else if (componentType == short.class) {
+            ((short[]) array)[index] = ((Short) converted).shortValue();
+        } else if (componentType == int.class) {
+            ((int[]) array)[index] = ((Integer) converted).intValue();
+        } else if (componentType == long.class) {
+            ((long[]) array)[index] = ((Long) converted).longValue();
+        } else if (componentType == float.class) {
+            ((float[]) array)[index] = ((Float) converted).floatValue();
+        } else {
+            ((double[]) array)[index] = ((Double) converted).doubleValue();
+        }
+    }
+
     private static native Object newInstanceNative(Class<?> componentType, int length);
 }
@@ -1,5 +1,7 @@
 package java.lang.reflect;
 
+import rjvm.internal.Reflection;
+
 public final class Constructor<T> extends Executable {
     // `Constructor` is only ever natively constructed (`Object::constructor_object`)
     private Constructor() { }
@@ -10,10 +12,7 @@ public final class Constructor<T> extends Executable {
         return this.getDeclaringClass().getName();
     }
 
-    public Class<?>[] getExceptionTypes() {
-        // TODO implement
-        return new Class<?>[0];
-    }
+    public native Class<?>[] getExceptionTypes();
 
     public int getParameterCount() {
         return this.getParameterTypes().length;
@@ -24,11 +23,25 @@ public final class Constructor<T> extends Executable {
     public native int getModifiers();
 
     public T newInstance(Object... args) throws InstantiationException, IllegalAccessException, IllegalArgumentException, InvocationTargetException {
-        if (args == null) {
-            args = new Object[0];
+        Class<?> caller = Reflection.getCallerClass();
+
+        Class<?> declaringClass = this.getDeclaringClass();
+
+        if (!this.getOverride()) {
+            AccessibleObject.checkMemberAccess(caller, declaringClass, null, this.getModifiers());
         }
 
-        return this.newInstanceNative(args);
+        if (Modifier.isAbstract(declaringClass.getModifiers())) {
+            throw new InstantiationException();
+        }
+
+        Object[] convertedArgs = this.convertArguments(args);
+
+        try {
+            return this.newInstanceNative(convertedArgs);
+        } catch (Throwable t) {
+            throw new InvocationTargetException(t);
+        }
     }
 
     private native T newInstanceNative(Object[] args) throws InstantiationException, IllegalAccessException, IllegalArgumentException, InvocationTargetException;
@@ -1,8 +1,13 @@
 package java.lang.reflect;
 
+import java.lang.annotation.Annotation;
+
+import rjvm.internal.SignatureParser;
+
 public abstract class Executable extends AccessibleObject implements GenericDeclaration, Member {
     // NOTE: THIS FIELD IS ACCESSED FROM NATIVE CODE! FIELD ORDERING MATTERS!
     private int internalId;
+    private boolean override;
 
     public abstract Class<?> getDeclaringClass();
 
@@ -15,4 +20,158 @@ public abstract class Executable extends AccessibleObject implements GenericDecl
     public abstract Class<?>[] getParameterTypes();
 
     public abstract int getModifiers();
+
+    public boolean isVarArgs() {
+        // `ACC_VARARGS` has the same value as `Modifier.TRANSIENT`
+        return (this.getModifiers() & Modifier.TRANSIENT) != 0;
+    }
+
+    boolean getOverride() {
+        return this.override;
+    }
+
+    void setOverride(boolean override) {
+        this.override = override;
+    }
+
+    // Check the args of a reflective call, and convert them to the exact boxed
+    // types of the parameters by applying unboxing and widening conversions.
+    Object[] convertArguments(Object[] args) {
+        // Like an empty array, a `null` array passes no arguments
+        if (args == null) {
+            args = new Object[0];
+        }
+
+        Class<?>[] parameterTypes = this.getParameterTypes();
+        if (args.length != parameterTypes.length) {
+            StringBuilder message = new StringBuilder();
+            message.append("wrong number of arguments: ");
+            message.append(args.length);
+            message.append(" expected: ");
+            message.append(parameterTypes.length);
+
+            throw new IllegalArgumentException(message.toString());
+        }
+
+        Object[] result = new Object[args.length];
+        for (int i = 0; i < args.length; i ++) {
+            result[i] = Executable.convertArgument(parameterTypes[i], args[i]);
+        }
+
+        return result;
+    }
+
+    static Object convertArgument(Class<?> type, Object value) {
+        if (!type.isPrimitive()) {
+            if (value == null || type.isInstance(value)) {
+                return value;
+            }
+        } else if (type == boolean.class) {
+            if (value instanceof Boolean) {
+                return value;
+            }
+        } else if (type == char.class) {
+            if (value instanceof Character) {
+                return value;
+            }
+        } else if (type == byte.class) {
+            if (value instanceof Byte) {
+                return value;
+            }
+        } else if (type == short.class) {
+            if (value instanceof Short) {
+                return value;
+            } else if (value instanceof Byte) {
+                return Short.valueOf(((Byte) value).byteValue());
+            }
+        } else if (value instanceof Byte || value instanceof Short || value instanceof Integer || value instanceof Character) {
+            // Widening from a subword type or `int`
+            long l;
+            if (value instanceof Character) {
+                l = ((Character) value).charValue();
+            } else {
+                l = ((Number) value).intValue();
+            }
+
+            if (type == int.class) {
+                return Integer.valueOf((int) l);
+            } else if (type == long.class) {
+                return Long.valueOf(l);
+            } else if (type == float.class) {
+                return Float.valueOf((float) l);
+            } else {
+                return Double.valueOf((double) l);
+            }
+        } else if (value instanceof Long) {
+            long l = ((Long) value).longValue();
+            if (type == long.class) {
+                return value;
+            } else if (type == float.class) {
+                return Float.valueOf((float) l);
+            } else if (type == double.class) {
+                return Double.valueOf((double) l);
+            }
+        } else if (value instanceof Float) {
+            if (type == float.class) {
+                return value;
+            } else if (type == double.class) {
+                return Double.valueOf((double) ((Float) value).floatValue());
+            }
+        } else if (value instanceof Double) {
+            if (type == double.class) {
+                return value;
+            }
+        }
+
+        throw new IllegalArgumentException("argument type mismatch");
+    }
+
+    // Generic type information
+
+    native String getGenericSignatureNative();
+
+    private TypeVariable<?>[] cachedTypeParameters;
+    public TypeVariable<?>[] getTypeParameters() {
+        if (this.cachedTypeParameters == null) {
+            String signature = this.getGenericSignatureNative();
+            if (signature == null) {
+                this.cachedTypeParameters = new TypeVariable<?>[0];
+            } else {
+                this.cachedTypeParameters = SignatureParser.parseTypeParameters(signature, this);
+            }
+        }
+        return this.cachedTypeParameters.clone();
+    }
+
+    public Type[] getGenericParameterTypes() {
+        String signature = this.getGenericSignatureNative();
+        if (signature == null) {
+            return this.getParameterTypes();
+        } else {
+            return SignatureParser.parseParameterTypes(signature, this);
+        }
+    }
+
+    public Type[] getGenericExceptionTypes() {
+        String signature = this.getGenericSignatureNative();
+        if (signature == null || signature.indexOf('^') == -1) {
+            return this.getExceptionTypes();
+        } else {
+            return SignatureParser.parseExceptionTypes(signature, this);
+        }
+    }
+
+    private Annotation[] cachedDeclaredAnnotations;
+    public Annotation[] getDeclaredAnnotations() {
+        if (this.cachedDeclaredAnnotations == null) {
+            this.cachedDeclaredAnnotations = this.getDeclaredAnnotationsNative();
+        }
+        return this.cachedDeclaredAnnotations.clone();
+    }
+    private native Annotation[] getDeclaredAnnotationsNative();
+
+    public Annotation[][] getParameterAnnotations() {
+        return this.getParameterAnnotationsNative();
+    }
+    private native Annotation[][] getParameterAnnotationsNative();
 }
@@ -1,8 +1,14 @@
 package java.lang.reflect;
 
+import java.lang.annotation.Annotation;
+
+import rjvm.internal.Reflection;
+import rjvm.internal.SignatureParser;
+
 public final class Field extends AccessibleObject implements Member {
     // NOTE: THIS FIELD IS ACCESSED FROM NATIVE CODE! FIELD ORDERING MATTERS!
     private int internalId;
+    private boolean override;
 
     private Field() { }
 
@@ -12,21 +18,53 @@ public final class Field extends AccessibleObject implements Member {
 
     public native Class<?> getType();
 
+    private native String getGenericSignatureNative();
+
+    public Type getGenericType() {
+        String signature = this.getGenericSignatureNative();
+        if (signature == null) {
+            return this.getType();
+        } else {
+            return SignatureParser.parseFieldType(signature, this.getDeclaringClass());
+        }
+    }
+
     public native int getModifiers();
 
+    private Annotation[] cachedDeclaredAnnotations;
+    public Annotation[] getDeclaredAnnotations() {
+        if (this.cachedDeclaredAnnotations == null) {
+            this.cachedDeclaredAnnotations = this.getDeclaredAnnotationsNative();
+        }
+        return this.cachedDeclaredAnnotations.clone();
+    }
+    private native Annotation[] getDeclaredAnnotationsNative();
+
+    boolean getOverride() {
+        return this.override;
+    }
+
+    void setOverride(boolean override) {
+        this.override = override;
+    }
+
+    // Reading the field
+
     public Object get(Object receiver) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, false);
+
         boolean isStatic = Modifier.isStatic(this.getModifiers());
 
-        Class<?> fieldCls = this.getDeclaringClass();
         Class<?> typeCls = this.getType();
 
         if (isStatic) {
             if (typeCls == boolean.class) {
-                throw new Error("TODO - static boolean field get");
+                return Boolean.valueOf(FieldAccess.getIntStaticNative(this) != 0);
             } else if (typeCls == byte.class) {
-                throw new Error("TODO - static byte field get");
+                return Byte.valueOf((byte) FieldAccess.getIntStaticNative(this));
             } else if (typeCls == char.class) {
-                throw new Error("TODO - static char field get");
+                return Character.valueOf((char) FieldAccess.getIntStaticNative(this));
             } else if (typeCls == double.class) {
                 return FieldAccess.getDoubleStaticNative(this);
             } else if (typeCls == float.class) {
@@ -36,24 +74,17 @@ public final class Field extends AccessibleObject implements Member {
             } else if (typeCls == long.class) {
                 return FieldAccess.getLongStaticNative(this);
             } else if (typeCls == short.class) {
-                throw new Error("TODO - static short field get");
+                return Short.valueOf((short) FieldAccess.getIntStaticNative(this));
             } else {
                 return FieldAccess.getObjectStaticNative(this);
             }
         } else {
-            if (receiver == null) {
-                throw new NullPointerException();
-            } else if (!fieldCls.isInstance(receiver)) {
-                // Not the right type of receiver
-                throw new IllegalArgumentException();
-            }
-
             if (typeCls == boolean.class) {
-                throw new Error("TODO - instance boolean field get");
+                return Boolean.valueOf(FieldAccess.getIntInstanceNative(this, receiver) != 0);
             } else if (typeCls == byte.class) {
-                throw new Error("TODO - instance byte field get");
+                return Byte.valueOf((byte) FieldAccess.getIntInstanceNative(this, receiver));
             } else if (typeCls == char.class) {
-                throw new Error("TODO - instance char field get");
+                return Character.valueOf((char) FieldAccess.getIntInstanceNative(this, receiver));
             } else if (typeCls == double.class) {
                 return FieldAccess.getDoubleInstanceNative(this, receiver);
             } else if (typeCls == float.class) {
@@ -63,12 +94,287 @@ public final class Field extends AccessibleObject implements Member {
             } else if (typeCls == long.class) {
                 return FieldAccess.getLongInstanceNative(this, receiver);
             } else if (typeCls == short.class) {
-                throw new Error("TODO - instance short field get");
+                return Short.valueOf((short) FieldAccess.getIntInstanceNative(this, receiver));
             } else {
                 return FieldAccess.getObjectInstanceNative(this, receiver);
             }
         }
     }
+
+    // Writing the field
+
+    public void set(Object receiver, Object value) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, true);
+
+        Class<?> typeCls = this.getType();
+
+        if (!typeCls.isPrimitive()) {
+            if (value != null && !typeCls.isInstance(value)) {
+                throw this.setError(value.getClass().getName());
+            }
+
+            if (Modifier.isStatic(this.getModifiers())) {
+                FieldAccess.setObjectStaticNative(this, value);
+            } else {
+                FieldAccess.setObjectInstanceNative(this, receiver, value);
+            }
+        } else if (value instanceof Boolean) {
+            this.storeBoolean(receiver, ((Boolean) value).booleanValue());
+        } else if (value instanceof Byte) {
+            this.storeByte(receiver, ((Byte) value).byteValue());
+        } else if (value instanceof Character) {
+            this.storeChar(receiver, ((Character) value).charValue());
+        } else if (value instanceof Short) {
+            this.storeShort(receiver, ((Short) value).shortValue());
+        } else if (value instanceof Integer) {
+            this.storeInt(receiver, ((Integer) value).intValue());
+        } else if (value instanceof Long) {
+            this.storeLong(receiver, ((Long) value).longValue());
+        } else if (value instanceof Float) {
+            this.storeFloat(receiver, ((Float) value).floatValue());
+        } else if (value instanceof Double) {
+            this.storeDouble(receiver, ((Double) value).doubleValue());
+        } else if (value == null) {
+            // Primitive fields can't be set to `null`
+            throw this.setError("null value");
+        } else {
+            throw this.setError(value.getClass().getName());
+        }
+    }
+
+    public void setBoolean(Object receiver, boolean z) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, true);
+        this.storeBoolean(receiver, z);
+    }
+
+    public void setByte(Object receiver, byte b) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, true);
+        this.storeByte(receiver, b);
+    }
+
+    public void setChar(Object receiver, char c) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, true);
+        this.storeChar(receiver, c);
+    }
+
+    public void setShort(Object receiver, short s) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, true);
+        this.storeShort(receiver, s);
+    }
+
+    public void setInt(Object receiver, int i) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, true);
+        this.storeInt(receiver, i);
+    }
+
+    public void setLong(Object receiver, long l) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, true);
+        this.storeLong(receiver, l);
+    }
+
+    public void setFloat(Object receiver, float f) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, true);
+        this.storeFloat(receiver, f);
+    }
+
+    public void setDouble(Object receiver, double d) throws IllegalArgumentException, IllegalAccessException {
+        Class<?> caller = Reflection.getCallerClass();
+        this.checkAccess(caller, receiver, true);
+        this.storeDouble(receiver, d);
+    }
+
+    // Store a primitive value into the field, applying a widening primitive
+    // conversion if the field's type is wider than the value's type
+
+    private void storeBoolean(Object receiver, boolean z) {
+        if (this.getType() == boolean.class) {
+            this.putInt(receiver, z ? 1 : 0);
+        } else {
+            throw this.setError("boolean");
+        }
+    }
+
+    private void storeByte(Object receiver, byte b) {
+        Class<?> typeCls = this.getType();
+        if (typeCls == byte.class || typeCls == short.class || typeCls == int.class) {
+            this.putInt(receiver, b);
+        } else {
+            this.storeWidened(receiver, b, "byte");
+        }
+    }
+
+    private void storeChar(Object receiver, char c) {
+        Class<?> typeCls = this.getType();
+        if (typeCls == char.class || typeCls == int.class) {
+            this.putInt(receiver, c);
+        } else {
+            this.storeWidened(receiver, c, "char");
+        }
+    }
+
+    private void storeShort(Object receiver, short s) {
+        Class<?> typeCls = this.getType();
+        if (typeCls == short.class || typeCls == int.class) {
+            this.putInt(receiver, s);
+        } else {
+            this.storeWidened(receiver, s, "short");
+        }
+    }
+
+    private void storeInt(Object receiver, int i) {
+        if (this.getType() == int.class) {
+            this.putInt(receiver, i);
+        } else {
+            this.storeWidened(receiver, i, "int");
+        }
+    }
+
+    private void storeLong(Object receiver, long l) {
+        if (this.getType() == long.class) {
+            this.putLong(receiver, l);
+        } else {
+            this.storeWidened(receiver, l, "long");
+        }
+    }
+
+    // Store an integral value into a `long`, `float`, or `double` field
+    private void storeWidened(Object receiver, long l, String valueType) {
+        Class<?> typeCls = this.getType();
+        if (typeCls == long.class) {
+            this.putLong(receiver, l);
+        } else if (typeCls == float.class) {
+            this.putFloat(receiver, (float) l);
+        } else if (typeCls == double.class) {
+            this.putDouble(receiver, (double) l);
+        } else {
+            throw this.setError(valueType);
+        }
+    }
+
+    private void storeFloat(Object receiver, float f) {
+        Class<?> typeCls = this.getType();
+        if (typeCls == float.class) {
+            this.putFloat(receiver, f);
+        } else if (typeCls == double.class) {
+            this.putDouble(receiver, (double) f);
+        } else {
+            throw this.setError("float");
+        }
+    }
+
+    private void storeDouble(Object receiver, double d) {
+        if (this.getType() == double.class) {
+            this.putDouble(receiver, d);
+        } else {
+            throw this.setError("double");
+        }
+    }
+
+    private void putInt(Object receiver, int value) {
+        if (Modifier.isStatic(this.getModifiers())) {
+            FieldAccess.setIntStaticNative(this, value);
+        } else {
+            FieldAccess.setIntInstanceNative(this, receiver, value);
+        }
+    }
+
+    private void putLong(Object receiver, long value) {
+        if (Modifier.isStatic(this.getModifiers())) {
+            FieldAccess.setLongStaticNative(this, value);
+        } else {
+            FieldAccess.setLongInstanceNative(this, receiver, value);
+        }
+    }
+
+    private void putFloat(Object receiver, float value) {
+        if (Modifier.isStatic(this.getModifiers())) {
+            FieldAccess.setFloatStaticNative(this, value);
+        } else {
+            FieldAccess.setFloatInstanceNative(this, receiver, value);
+        }
+    }
+
+    private void putDouble(Object receiver, double value) {
+        if (Modifier.isStatic(this.getModifiers())) {
+            FieldAccess.setDoubleStaticNative(this, value);
+        } else {
+            FieldAccess.setDoubleInstanceNative(this, receiver, value);
+        }
+    }
+
+    // Access checks
+
+    private void checkAccess(Class<?> caller, Object receiver, boolean isWrite) throws IllegalAccessException {
+        int modifiers = this.getModifiers();
+        boolean isStatic = Modifier.isStatic(modifiers);
+
+        Class<?> fieldCls = this.getDeclaringClass();
+
+        if (!isStatic) {
+            if (receiver == null) {
+                throw new NullPointerException();
+            } else if (!fieldCls.isInstance(receiver)) {
+                // Not the right type of receiver
+                StringBuilder message = new StringBuilder();
+                message.append("Can not ");
+                message.append(isWrite ? "set " : "get ");
+                this.appendDescription(message);
+                message.append(isWrite ? " to " : " on ");
+                message.append(receiver.getClass().getName());
+
+                throw new IllegalArgumentException(message.toString());
+            }
+        }
+
+        if (!this.override) {
+            AccessibleObject.checkMemberAccess(caller, fieldCls, isStatic ? null : receiver, modifiers);
+        }
+
+        // Final instance fields can be written if the field was made
+        // accessible, but final static fields can never be written
+        if (isWrite && Modifier.isFinal(modifiers) && (isStatic || !this.override)) {
+            StringBuilder message = new StringBuilder();
+            message.append("Can not set ");
+            this.appendDescription(message);
+
+            throw new IllegalAccessException(message.toString());
+        }
+    }
+
+    private IllegalArgumentException setError(String valueType) {
+        StringBuilder message = new StringBuilder();
+        message.append("Can not set ");
+        this.appendDescription(message);
+        message.append(" to ");
+        message.append(valueType);
+
+        return new IllegalArgumentException(message.toString());
+    }
+
+    // Append a description of this field, such as `static final int Foo.bar`
+    private void appendDescription(StringBuilder result) {
+        int modifiers = this.getModifiers();
+        if (Modifier.isStatic(modifiers)) {
+            result.append("static ");
+        }
+        if (Modifier.isFinal(modifiers)) {
+            result.append("final ");
+        }
+
+        result.append(this.getType().getName());
+        result.append(" field ");
+        result.append(this.getDeclaringClass().getName());
+        result.append('.');
+        result.append(this.getName());
+    }
 }
 
 // A class containing simple native implementations for field access.
@@ -87,4 +393,19 @@ class FieldAccess {
 
     static native Object getObjectStaticNative(Field field);
     static native Object getObjectInstanceNative(Field field, Object receiver);
+
+    static native void setDoubleStaticNative(Field field, double value);
+    static native void setDoubleInstanceNative(Field field, Object receiver, double value);
+
+    static native void setFloatStaticNative(Field field, float value);
+    static native void setFloatInstanceNative(Field field, Object receiver, float value);
+
+    static native void setIntStaticNative(Field field, int value);
+    static native void setIntInstanceNative(Field field, Object receiver, int value);
+
+    static native void setLongStaticNative(Field field, long value);
+    static native void setLongInstanceNative(Field field, Object receiver, long value);
+
+    static native void setObjectStaticNative(Field field, Object value);
+    static native void setObjectInstanceNative(Field field, Object receiver, Object value);
 }
@@ -0,0 +1,5 @@
+package java.lang.reflect;
+
+public interface InvocationHandler {
+    Object invoke(Object proxy, Method method, Object[] args) throws Throwable;
+}
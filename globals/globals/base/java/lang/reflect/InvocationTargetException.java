@@ -1,7 +1,27 @@
 package java.lang.reflect;
 
 public class InvocationTargetException extends ReflectiveOperationException {
+    private Throwable target;
+
     protected InvocationTargetException() {
-        super();
+        super((Throwable) null);
+    }
+
+    public InvocationTargetException(Throwable target) {
+        super((Throwable) null);
+        this.target = target;
+    }
+
+    public InvocationTargetException(Throwable target, String message) {
+        super(message, null);
+        this.target = target;
+    }
+
+    public Throwable getTargetException() {
+        return this.target;
+    }
+
+    public Throwable getCause() {
+        return this.target;
     }
 }
@@ -1,5 +1,10 @@
 package java.lang.reflect;
 
+import java.lang.invoke.MethodHandle;
+
+import rjvm.internal.Reflection;
+import rjvm.internal.SignatureParser;
+
 public final class Method extends Executable {
     private Method() { }
 
@@ -7,10 +12,7 @@ public final class Method extends Executable {
 
     public native String getName();
 
-    public Class<?>[] getExceptionTypes() {
-        // TODO implement
-        return new Class<?>[0];
-    }
+    public native Class<?>[] getExceptionTypes();
 
     public int getParameterCount() {
         return this.getParameterTypes().length;
@@ -22,12 +24,47 @@ public final class Method extends Executable {
 
     public native Class<?> getReturnType();
 
+    public Type getGenericReturnType() {
+        String signature = this.getGenericSignatureNative();
+        if (signature == null) {
+            return this.getReturnType();
+        } else {
+            return SignatureParser.parseReturnType(signature, this);
+        }
+    }
+
+    public native Object getDefaultValue();
+
     public Object invoke(Object obj, Object... args) throws IllegalAccessException, IllegalArgumentException, InvocationTargetException {
-        if (args == null) {
-            args = new Object[0];
+        Class<?> caller = Reflection.getCallerClass();
+
+        int modifiers = this.getModifiers();
+        Class<?> declaringClass = this.getDeclaringClass();
+
+        if (Modifier.isStatic(modifiers)) {
+            obj = null;
+        } else if (obj == null) {
+            throw new NullPointerException();
+        } else if (!declaringClass.isInstance(obj)) {
+            throw new IllegalArgumentException("object is not an instance of declaring class");
+        }
+
+        if (!this.getOverride()) {
+            AccessibleObject.checkMemberAccess(caller, declaringClass, obj, modifiers);
         }
 
-        return this.invokeNative(obj, args);
+        Object[] convertedArgs = this.convertArguments(args);
+
+        // Signature polymorphic methods can only be called from bytecode
+        if (declaringClass == MethodHandle.class && Modifier.isNative(modifiers) && this.isVarArgs()) {
+            throw new InvocationTargetException(new UnsupportedOperationException("cannot reflectively invoke MethodHandle"));
+        }
+
+        try {
+            return this.invokeNative(obj, convertedArgs);
+        } catch (Throwable t) {
+            throw new InvocationTargetException(t);
+        }
     }
 
     private native Object invokeNative(Object obj, Object[] args) throws IllegalAccessException, IllegalArgumentException, InvocationTargetException;
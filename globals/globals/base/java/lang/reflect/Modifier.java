@@ -56,4 +56,31 @@ public class Modifier {
     public static boolean isAbstract(int mod) {
         return (mod & Modifier.ABSTRACT) != 0;
     }
+
+    public static String toString(int mod) {
+        StringBuilder result = new StringBuilder();
+
+        Modifier.appendIfSet(result, mod, Modifier.PUBLIC, "public");
+        Modifier.appendIfSet(result, mod, Modifier.PROTECTED, "protected");
+        Modifier.appendIfSet(result, mod, Modifier.PRIVATE, "private");
+        Modifier.appendIfSet(result, mod, Modifier.ABSTRACT, "abstract");
+        Modifier.appendIfSet(result, mod, Modifier.STATIC, "static");
+        Modifier.appendIfSet(result, mod, Modifier.FINAL, "final");
+        Modifier.appendIfSet(result, mod, Modifier.TRANSIENT, "transient");
+        Modifier.appendIfSet(result, mod, Modifier.VOLATILE, "volatile");
+        Modifier.appendIfSet(result, mod, Modifier.SYNCHRONIZED, "synchronized");
+        Modifier.appendIfSet(result, mod, Modifier.NATIVE, "native");
+        Modifier.appendIfSet(result, mod, Modifier.INTERFACE, "interface");
+
+        return result.toString();
+    }
+
+    private static void appendIfSet(StringBuilder result, int mod, int flag, String name) {
+        if ((mod & flag) != 0) {
+            if (result.length() != 0) {
+                result.append(' ');
+            }
+            result.append(name);
+        }
+    }
 }
@@ -0,0 +1,207 @@
+package java.lang.reflect;
+
+import java.io.Serializable;
+import java.util.Arrays;
+import java.util.HashMap;
+
+public class Proxy implements Serializable {
+    // NOTE: THIS FIELD IS ACCESSED FROM NATIVE CODE! FIELD ORDERING MATTERS!
+    protected InvocationHandler h;
+
+    private static HashMap<ProxyClassKey, Class<?>> proxyClasses = new HashMap<ProxyClassKey, Class<?>>();
+    private static int nextProxyIndex = 0;
+
+    protected Proxy(InvocationHandler h) {
+        if (h == null) {
+            throw new NullPointerException();
+        }
+
+        this.h = h;
+    }
+
+    @Deprecated
+    public static Class<?> getProxyClass(ClassLoader loader, Class<?>... interfaces) throws IllegalArgumentException {
+        return Proxy.getOrCreateProxyClass(loader, interfaces);
+    }
+
+    public static Object newProxyInstance(ClassLoader loader, Class<?>[] interfaces, InvocationHandler h) throws IllegalArgumentException {
+        if (h == null) {
+            throw new NullPointerException();
+        }
+
+        Class<?> proxyClass = Proxy.getOrCreateProxyClass(loader, interfaces);
+        return Proxy.newProxyInstanceNative(proxyClass, h);
+    }
+
+    public static boolean isProxyClass(Class<?> cls) {
+        return Proxy.class.isAssignableFrom(cls) && Proxy.proxyClasses.values().contains(cls);
+    }
+
+    public static InvocationHandler getInvocationHandler(Object proxy) throws IllegalArgumentException {
+        if (!Proxy.isProxyClass(proxy.getClass())) {
+            throw new IllegalArgumentException("not a proxy instance");
+        }
+
+        return ((Proxy) proxy).h;
+    }
+
+    private static Class<?> getOrCreateProxyClass(ClassLoader loader, Class<?>[] interfaces) {
+        interfaces = interfaces.clone();
+
+        if (interfaces.length > 65535) {
+            throw new IllegalArgumentException("interface limit exceeded");
+        }
+
+        // Proxy classes implementing non-public interfaces must be in the same
+        // package as those interfaces
+        String packageName = null;
+
+        for (int i = 0; i < interfaces.length; i ++) {
+            Class<?> intf = interfaces[i];
+
+            if (!intf.isInterface()) {
+                throw Proxy.interfaceError(intf, " is not an interface");
+            }
+
+            for (int j = 0; j < i; j ++) {
+                if (interfaces[j] == intf) {
+                    throw Proxy.interfaceError(intf, " is repeated");
+                }
+            }
+
+            Class<?> visibleClass;
+            try {
+                visibleClass = Class.forName(intf.getName(), false, loader);
+            } catch (ClassNotFoundException e) {
+                visibleClass = null;
+            }
+
+            if (visibleClass != intf) {
+                throw Proxy.interfaceError(intf, " is not visible from class loader");
+            }
+
+            if (!Modifier.isPublic(intf.getModifiers())) {
+                String interfacePackage = intf.getPackageName();
+                if (packageName == null) {
+                    packageName = interfacePackage;
+                } else if (!packageName.equals(interfacePackage)) {
+                    throw new IllegalArgumentException("non-public interfaces from different packages");
+                }
+            }
+        }
+
+        ProxyClassKey key = new ProxyClassKey(loader, interfaces);
+        Class<?> proxyClass = Proxy.proxyClasses.get(key);
+
+        if (proxyClass == null) {
+            StringBuilder name = new StringBuilder();
+            if (packageName == null) {
+                name.append("rjvm.proxy.");
+            } else if (packageName.length() != 0) {
+                name.append(packageName);
+                name.append('.');
+            }
+            name.append("$Proxy");
+            name.append(Proxy.nextProxyIndex ++);
+
+            proxyClass = Proxy.defineProxyClass(loader, name.toString(), interfaces);
+            Proxy.proxyClasses.put(key, proxyClass);
+        }
+
+        return proxyClass;
+    }
+
+    private static IllegalArgumentException interfaceError(Class<?> intf, String problem) {
+        StringBuilder message = new StringBuilder();
+        message.append(intf.getName());
+        message.append(problem);
+
+        return new IllegalArgumentException(message.toString());
+    }
+
+    private static native Class<?> defineProxyClass(ClassLoader loader, String name, Class<?>[] interfaces);
+
+    private static native Object newProxyInstanceNative(Class<?> proxyClass, InvocationHandler h);
+
+    // Called from native code by the methods of proxy classes
+    private static Object invokeHandler(Proxy proxy, Method method, Object[] args) throws Throwable {
+        Object result;
+        try {
+            result = proxy.h.invoke(proxy, method, args);
+        } catch (RuntimeException e) {
+            throw e;
+        } catch (Error e) {
+            throw e;
+        } catch (Throwable t) {
+            // Checked exceptions that the method can't throw are wrapped
+            Class<?>[] exceptionTypes = method.getExceptionTypes();
+            for (int i = 0; i < exceptionTypes.length; i ++) {
+                if (exceptionTypes[i].isInstance(t)) {
+                    throw t;
+                }
+            }
+
+            throw new UndeclaredThrowableException(t);
+        }
+
+        Class<?> returnType = method.getReturnType();
+        if (returnType == void.class) {
+            return null;
+        } else if (returnType.isPrimitive()) {
+            if (result == null) {
+                throw new NullPointerException();
+            } else if (!Proxy.boxClassFor(returnType).isInstance(result)) {
+                throw new ClassCastException();
+            }
+        } else if (result != null && !returnType.isInstance(result)) {
+            throw new ClassCastException();
+        }
+
+        return result;
+    }
+
+    private static Class<?> boxClassFor(Class<?> primitiveType) {
+        if (primitiveType == boolean.class) {
+            return Boolean.class;
+        } else if (primitiveType == byte.class) {
+            return Byte.class;
+        } else if (primitiveType == char.class) {
+            return Character.class;
+        } else if (primitiveType == short.class) {
+            return Short.class;
+        } else if (primitiveType == int.class) {
+            return Integer.class;
+        } else if (primitiveType == long.class) {
+            return Long.class;
+        } else if (primitiveType == float.class) {
+            return Float.class;
+        } else {
+            return Double.class;
+        }
+    }
+}
+
+// The key used to cache proxy classes: proxy classes are shared between calls
+// with the same loader and the same interfaces in the same order.
+class ProxyClassKey {
+    private ClassLoader loader;
+    private Class<?>[] interfaces;
+
+    ProxyClassKey(ClassLoader loader, Class<?>[] interfaces) {
+        this.loader = loader;
+        this.interfaces = interfaces;
+    }
+
+    public boolean equals(Object obj) {
+        if (obj instanceof ProxyClassKey) {
+            ProxyClassKey other = (ProxyClassKey) obj;
+            return this.loader == other.loader && Arrays.equals(this.interfaces, other.interfaces);
+        } else {
+            return false;
+        }
+    }
+
+    public int hashCode() {
+        return System.identityHashCode(this.loader) * 31 + Arrays.hashCode(this.interfaces);
+    }
+}
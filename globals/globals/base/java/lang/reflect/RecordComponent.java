@@ -0,0 +1,40 @@
+package java.lang.reflect;
+
+public final class RecordComponent {
+    private final Class<?> declaringRecord;
+    private final String name;
+    private final Class<?> type;
+    private final Method accessor;
+
+    RecordComponent(Class<?> declaringRecord, String name, Class<?> type, Method accessor) {
+        this.declaringRecord = declaringRecord;
+        this.name = name;
+        this.type = type;
+        this.accessor = accessor;
+    }
+
+    public Class<?> getDeclaringRecord() {
+        return this.declaringRecord;
+    }
+
+    public String getName() {
+        return this.name;
+    }
+
+    public Class<?> getType() {
+        return this.type;
+    }
+
+    public Method getAccessor() {
+        return this.accessor;
+    }
+
+    public String toString() {
+        StringBuilder result = new StringBuilder();
+        result.append(this.type.getTypeName());
+        result.append(' ');
+        result.append(this.name);
+
+        return result.toString();
+    }
+}
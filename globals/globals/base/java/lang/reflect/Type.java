@@ -1,3 +1,7 @@
 package java.lang.reflect;
 
-public interface Type { }
+public interface Type {
+    default String getTypeName() {
+        return this.toString();
+    }
+}
@@ -1,5 +1,9 @@
 package java.lang.reflect;
 
 public interface TypeVariable<D extends GenericDeclaration> extends Type, AnnotatedElement {
+    Type[] getBounds();
+
+    D getGenericDeclaration();
+
     String getName();
 }
@@ -0,0 +1,15 @@
+package java.lang.reflect;
+
+public class UndeclaredThrowableException extends RuntimeException {
+    public UndeclaredThrowableException(Throwable undeclaredThrowable) {
+        super(null, undeclaredThrowable);
+    }
+
+    public UndeclaredThrowableException(Throwable undeclaredThrowable, String message) {
+        super(message, undeclaredThrowable);
+    }
+
+    public Throwable getUndeclaredThrowable() {
+        return this.getCause();
+    }
+}
@@ -0,0 +1,109 @@
+package java.lang.runtime;
+
+import java.lang.invoke.CallSite;
+import java.lang.invoke.ConstantCallSite;
+import java.lang.invoke.MethodHandle;
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.MethodType;
+import java.lang.invoke.TypeDescriptor;
+import java.util.Objects;
+
+public final class ObjectMethods {
+    private ObjectMethods() { }
+
+    public static Object bootstrap(MethodHandles.Lookup lookup, String methodName, TypeDescriptor type, Class<?> recordClass, String names, MethodHandle... getters) throws Throwable {
+        if (methodName == null || recordClass == null || names == null || getters == null) {
+            throw new NullPointerException();
+        }
+
+        // Components are separated by semicolons; an empty string means the
+        // record has no components
+        String[] splitNames;
+        if (names.isEmpty()) {
+            splitNames = new String[0];
+        } else {
+            splitNames = names.split(";");
+        }
+
+        if (splitNames.length != getters.length) {
+            throw new IllegalArgumentException("Number of names does not match the number of getters");
+        }
+
+        MethodHandles.Lookup ownLookup = MethodHandles.lookup();
+        MethodHandle handle;
+        MethodType handleType;
+        if (methodName.equals("equals")) {
+            handle = ownLookup.findStatic(ObjectMethods.class, "recordEquals", MethodType.methodType(boolean.class, Class.class, MethodHandle[].class, Object.class, Object.class));
+            handle = MethodHandles.insertArguments(handle, 0, recordClass, getters);
+            handleType = MethodType.methodType(boolean.class, recordClass, Object.class);
+        } else if (methodName.equals("hashCode")) {
+            handle = ownLookup.findStatic(ObjectMethods.class, "recordHashCode", MethodType.methodType(int.class, MethodHandle[].class, Object.class));
+            handle = MethodHandles.insertArguments(handle, 0, (Object) getters);
+            handleType = MethodType.methodType(int.class, recordClass);
+        } else if (methodName.equals("toString")) {
+            handle = ownLookup.findStatic(ObjectMethods.class, "recordToString", MethodType.methodType(String.class, Class.class, String[].class, MethodHandle[].class, Object.class));
+            handle = MethodHandles.insertArguments(handle, 0, recordClass, splitNames, getters);
+            handleType = MethodType.methodType(String.class, recordClass);
+        } else {
+            throw new IllegalArgumentException(methodName);
+        }
+
+        handle = handle.asType(handleType);
+
+        // Called from `invokedynamic` with a `MethodType`, or as the bootstrap
+        // method of a dynamic constant with a `Class`
+        if (type instanceof MethodType) {
+            return new ConstantCallSite(handle.asType((MethodType) type));
+        } else {
+            return handle;
+        }
+    }
+
+    private static boolean recordEquals(Class<?> recordClass, MethodHandle[] getters, Object record, Object other) throws Throwable {
+        if (!recordClass.isInstance(other)) {
+            return false;
+        }
+
+        for (int i = 0; i < getters.length; i ++) {
+            Object value = getters[i].invokeWithArguments(new Object[] { record });
+            Object otherValue = getters[i].invokeWithArguments(new Object[] { other });
+
+            if (!Objects.equals(value, otherValue)) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    private static int recordHashCode(MethodHandle[] getters, Object record) throws Throwable {
+        int result = 0;
+        for (int i = 0; i < getters.length; i ++) {
+            Object value = getters[i].invokeWithArguments(new Object[] { record });
+
+            result = result * 31 + Objects.hashCode(value);
+        }
+
+        return result;
+    }
+
+    private static String recordToString(Class<?> recordClass, String[] names, MethodHandle[] getters, Object record) throws Throwable {
+        StringBuilder result = new StringBuilder();
+        result.append(recordClass.getSimpleName());
+        result.append('[');
+
+        for (int i = 0; i < getters.length; i ++) {
+            if (i != 0) {
+                result.append(", ");
+            }
+
+            result.append(names[i]);
+            result.append('=');
+            result.append(String.valueOf(getters[i].invokeWithArguments(new Object[] { record })));
+        }
+
+        result.append(']');
+
+        return result.toString();
+    }
+}
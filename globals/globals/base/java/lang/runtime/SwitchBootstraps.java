@@ -0,0 +1,152 @@
+package java.lang.runtime;
+
+import java.lang.invoke.CallSite;
+import java.lang.invoke.ConstantCallSite;
+import java.lang.invoke.MethodHandle;
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.MethodType;
+
+public class SwitchBootstraps {
+    private SwitchBootstraps() { }
+
+    // Link a pattern `switch`. The call site takes the value being switched on
+    // and the index of the label to start matching from, and returns the index
+    // of the first matching label, `-1` for `null`, or the number of labels if
+    // none match.
+    public static CallSite typeSwitch(MethodHandles.Lookup lookup, String invocationName, MethodType invocationType, Object... labels) throws Throwable {
+        SwitchBootstraps.checkInvocationType(invocationType);
+
+        if (labels == null) {
+            throw new NullPointerException();
+        }
+
+        labels = labels.clone();
+        for (int i = 0; i < labels.length; i ++) {
+            Object label = labels[i];
+            if (label == null) {
+                throw new IllegalArgumentException("null label found");
+            } else if (!(label instanceof Class) && !(label instanceof String) && !(label instanceof Integer)) {
+                throw SwitchBootstraps.illegalLabel(label);
+            }
+        }
+
+        MethodHandle handle = MethodHandles.lookup().findStatic(SwitchBootstraps.class, "doTypeSwitch", MethodType.methodType(int.class, Object.class, int.class, Object[].class));
+        handle = MethodHandles.insertArguments(handle, 2, (Object) labels);
+
+        return new ConstantCallSite(handle.asType(invocationType));
+    }
+
+    // Link a `switch` over an enum with pattern labels. Labels are either the
+    // names of constants of the enum or classes.
+    public static CallSite enumSwitch(MethodHandles.Lookup lookup, String invocationName, MethodType invocationType, Object... labels) throws Throwable {
+        SwitchBootstraps.checkInvocationType(invocationType);
+
+        Class<?> enumClass = invocationType.parameterType(0);
+        if (!enumClass.isEnum()) {
+            throw SwitchBootstraps.illegalInvocationType(invocationType);
+        }
+
+        if (labels == null) {
+            throw new NullPointerException();
+        }
+
+        labels = labels.clone();
+        for (int i = 0; i < labels.length; i ++) {
+            Object label = labels[i];
+            if (label == null) {
+                throw new IllegalArgumentException("null label found");
+            } else if (!(label instanceof Class) && !(label instanceof String)) {
+                throw SwitchBootstraps.illegalLabel(label);
+            }
+        }
+
+        MethodHandle handle = MethodHandles.lookup().findStatic(SwitchBootstraps.class, "doEnumSwitch", MethodType.methodType(int.class, Enum.class, int.class, Object[].class));
+        handle = MethodHandles.insertArguments(handle, 2, (Object) labels);
+
+        return new ConstantCallSite(handle.asType(invocationType));
+    }
+
+    private static int doTypeSwitch(Object target, int restartIndex, Object[] labels) {
+        SwitchBootstraps.checkRestartIndex(restartIndex, labels);
+
+        if (target == null) {
+            return -1;
+        }
+
+        for (int i = restartIndex; i < labels.length; i ++) {
+            Object label = labels[i];
+
+            if (label instanceof Class) {
+                if (((Class<?>) label).isInstance(target)) {
+                    return i;
+                }
+            } else if (label instanceof Integer) {
+                int constant = ((Integer) label).intValue();
+
+                // Integer labels also match the other types that widen to int
+                if (target instanceof Number && ((Number) target).intValue() == constant) {
+                    return i;
+                } else if (target instanceof Character && ((Character) target).charValue() == constant) {
+                    return i;
+                }
+            } else if (label.equals(target)) {
+                return i;
+            }
+        }
+
+        return labels.length;
+    }
+
+    private static int doEnumSwitch(Enum<?> target, int restartIndex, Object[] labels) {
+        SwitchBootstraps.checkRestartIndex(restartIndex, labels);
+
+        if (target == null) {
+            return -1;
+        }
+
+        for (int i = restartIndex; i < labels.length; i ++) {
+            Object label = labels[i];
+
+            if (label instanceof Class) {
+                if (((Class<?>) label).isInstance(target)) {
+                    return i;
+                }
+            } else if (label.equals(target.name())) {
+                return i;
+            }
+        }
+
+        return labels.length;
+    }
+
+    private static void checkInvocationType(MethodType invocationType) {
+        if (invocationType.parameterCount() != 2
+                || invocationType.returnType() != int.class
+                || invocationType.parameterType(0).isPrimitive()
+                || invocationType.parameterType(1) != int.class) {
+            throw SwitchBootstraps.illegalInvocationType(invocationType);
+        }
+    }
+
+    private static void checkRestartIndex(int restartIndex, Object[] labels) {
+        if (restartIndex < 0 || restartIndex > labels.length) {
+            throw new IndexOutOfBoundsException();
+        }
+    }
+
+    private static IllegalArgumentException illegalInvocationType(MethodType invocationType) {
+        StringBuilder message = new StringBuilder();
+        message.append("Illegal invocation type ");
+        message.append(invocationType.toString());
+
+        return new IllegalArgumentException(message.toString());
+    }
+
+    private static IllegalArgumentException illegalLabel(Object label) {
+        StringBuilder message = new StringBuilder();
+        message.append("label with illegal type found: ");
+        message.append(label.getClass().toString());
+
+        return new IllegalArgumentException(message.toString());
+    }
+}
@@ -1,3 +1,7 @@
 package java.security;
 
-public class AccessControlException extends SecurityException { }
+public class AccessControlException extends SecurityException {
+    public AccessControlException(String message) {
+        super(message);
+    }
+}
@@ -120,6 +120,160 @@ public class Arrays {
         return true;
     }
 
+    public static boolean equals(boolean[] array1, boolean[] array2) {
+        if (array1 == array2) {
+            return true;
+        }
+
+        if (array1 == null || array2 == null) {
+            return false;
+        }
+
+        if (array1.length != array2.length) {
+            return false;
+        }
+
+        for (int i = 0; i < array1.length; i ++) {
+            if (array1[i] != array2[i]) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    public static boolean equals(byte[] array1, byte[] array2) {
+        if (array1 == array2) {
+            return true;
+        }
+
+        if (array1 == null || array2 == null) {
+            return false;
+        }
+
+        if (array1.length != array2.length) {
+            return false;
+        }
+
+        for (int i = 0; i < array1.length; i ++) {
+            if (array1[i] != array2[i]) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    public static boolean equals(char[] array1, char[] array2) {
+        if (array1 == array2) {
+            return true;
+        }
+
+        if (array1 == null || array2 == null) {
+            return false;
+        }
+
+        if (array1.length != array2.length) {
+            return false;
+        }
+
+        for (int i = 0; i < array1.length; i ++) {
+            if (array1[i] != array2[i]) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    public static boolean equals(short[] array1, short[] array2) {
+        if (array1 == array2) {
+            return true;
+        }
+
+        if (array1 == null || array2 == null) {
+            return false;
+        }
+
+        if (array1.length != array2.length) {
+            return false;
+        }
+
+        for (int i = 0; i < array1.length; i ++) {
+            if (array1[i] != array2[i]) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    public static boolean equals(long[] array1, long[] array2) {
+        if (array1 == array2) {
+            return true;
+        }
+
+        if (array1 == null || array2 == null) {
+            return false;
+        }
+
+        if (array1.length != array2.length) {
+            return false;
+        }
+
+        for (int i = 0; i < array1.length; i ++) {
+            if (array1[i] != array2[i]) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    public static boolean equals(float[] array1, float[] array2) {
+        if (array1 == array2) {
+            return true;
+        }
+
+        if (array1 == null || array2 == null) {
+            return false;
+        }
+
+        if (array1.length != array2.length) {
+            return false;
+        }
+
+        for (int i = 0; i < array1.length; i ++) {
+            if (Float.floatToIntBits(array1[i]) != Float.floatToIntBits(array2[i])) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    public static boolean equals(double[] array1, double[] array2) {
+        if (array1 == array2) {
+            return true;
+        }
+
+        if (array1 == null || array2 == null) {
+            return false;
+        }
+
+        if (array1.length != array2.length) {
+            return false;
+        }
+
+        for (int i = 0; i < array1.length; i ++) {
+            if (Double.doubleToLongBits(array1[i]) != Double.doubleToLongBits(array2[i])) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
     public static void fill(byte[] arr, int fromIndex, int toIndex, byte val) {
         if (fromIndex > toIndex) {
             throw new IllegalArgumentException();
@@ -140,11 +294,110 @@ public class Arrays {
         }
     }
 
+    public static int hashCode(Object[] arr) {
+        if (arr == null) {
+            return 0;
+        }
+
+        int result = 1;
+        for (int i = 0; i < arr.length; i ++) {
+            result = 31 * result + (arr[i] == null ? 0 : arr[i].hashCode());
+        }
+        return result;
+    }
+
+    public static int hashCode(boolean[] arr) {
+        if (arr == null) {
+            return 0;
+        }
+
+        int result = 1;
+        for (int i = 0; i < arr.length; i ++) {
+            result = 31 * result + (arr[i] ? 1231 : 1237);
+        }
+        return result;
+    }
+
+    public static int hashCode(byte[] arr) {
+        if (arr == null) {
+            return 0;
+        }
+
+        int result = 1;
+        for (int i = 0; i < arr.length; i ++) {
+            result = 31 * result + arr[i];
+        }
+        return result;
+    }
+
+    public static int hashCode(char[] arr) {
+        if (arr == null) {
+            return 0;
+        }
+
+        int result = 1;
+        for (int i = 0; i < arr.length; i ++) {
+            result = 31 * result + arr[i];
+        }
+        return result;
+    }
+
+    public static int hashCode(short[] arr) {
+        if (arr == null) {
+            return 0;
+        }
+
+        int result = 1;
+        for (int i = 0; i < arr.length; i ++) {
+            result = 31 * result + arr[i];
+        }
+        return result;
+    }
+
     public static int hashCode(int[] arr) {
-        // TODO make better
-        int result = 0;
+        if (arr == null) {
+            return 0;
+        }
+
+        int result = 1;
+        for (int i = 0; i < arr.length; i ++) {
+            result = 31 * result + arr[i];
+        }
+        return result;
+    }
+
+    public static int hashCode(long[] arr) {
+        if (arr == null) {
+            return 0;
+        }
+
+        int result = 1;
+        for (int i = 0; i < arr.length; i ++) {
+            result = 31 * result + (int) (arr[i] ^ (arr[i] >>> 32));
+        }
+        return result;
+    }
+
+    public static int hashCode(float[] arr) {
+        if (arr == null) {
+            return 0;
+        }
+
+        int result = 1;
+        for (int i = 0; i < arr.length; i ++) {
+            result = 31 * result + Float.floatToIntBits(arr[i]);
+        }
+        return result;
+    }
+
+    public static int hashCode(double[] arr) {
+        if (arr == null) {
+            return 0;
+        }
+
+        int result = 1;
         for (int i = 0; i < arr.length; i ++) {
-            result += i;
+            result = 31 * result + (int) (Double.doubleToLongBits(arr[i]) ^ (Double.doubleToLongBits(arr[i]) >>> 32));
         }
         return result;
     }
@@ -0,0 +1,313 @@
+package rjvm.internal;
+
+import java.lang.annotation.Annotation;
+import java.lang.annotation.IncompleteAnnotationException;
+import java.lang.reflect.Method;
+import java.util.Arrays;
+
+// Superclass of the classes the VM synthesizes to implement annotation
+// interfaces. The member methods of the annotation interface are implemented
+// natively and call `getMemberValue`.
+public abstract class AnnotationImpl implements Annotation {
+    // NOTE: THESE FIELDS ARE ACCESSED FROM NATIVE CODE! FIELD ORDERING MATTERS!
+    private Class<? extends Annotation> type;
+    private String[] names;
+    private Object[] values;
+
+    private AnnotationImpl() { }
+
+    // Called from native code
+    Object getMemberValue(String name) {
+        for (int i = 0; i < this.names.length; i ++) {
+            if (this.names[i].equals(name)) {
+                if (this.values[i] == null) {
+                    break;
+                }
+
+                return this.values[i];
+            }
+        }
+
+        throw new IncompleteAnnotationException(this.type, name);
+    }
+
+    public Class<? extends Annotation> annotationType() {
+        return this.type;
+    }
+
+    public boolean equals(Object obj) {
+        if (obj == this) {
+            return true;
+        }
+
+        if (!this.type.isInstance(obj)) {
+            return false;
+        }
+
+        for (int i = 0; i < this.names.length; i ++) {
+            Object otherValue;
+            if (obj instanceof AnnotationImpl) {
+                otherValue = ((AnnotationImpl) obj).getMemberValue(this.names[i]);
+            } else {
+                try {
+                    Method member = this.type.getMethod(this.names[i]);
+                    member.setAccessible(true);
+                    otherValue = member.invoke(obj);
+                } catch (Exception e) {
+                    return false;
+                }
+            }
+
+            if (!AnnotationImpl.memberValueEquals(this.getMemberValue(this.names[i]), otherValue)) {
+                return false;
+            }
+        }
+
+        return true;
+    }
+
+    private static boolean memberValueEquals(Object value1, Object value2) {
+        if (value1 instanceof Object[] && value2 instanceof Object[]) {
+            return Arrays.equals((Object[]) value1, (Object[]) value2);
+        } else if (value1 instanceof boolean[] && value2 instanceof boolean[]) {
+            return Arrays.equals((boolean[]) value1, (boolean[]) value2);
+        } else if (value1 instanceof byte[] && value2 instanceof byte[]) {
+            return Arrays.equals((byte[]) value1, (byte[]) value2);
+        } else if (value1 instanceof char[] && value2 instanceof char[]) {
+            return Arrays.equals((char[]) value1, (char[]) value2);
+        } else if (value1 instanceof short[] && value2 instanceof short[]) {
+            return Arrays.equals((short[]) value1, (short[]) value2);
+        } else if (value1 instanceof int[] && value2 instanceof int[]) {
+            return Arrays.equals((int[]) value1, (int[]) value2);
+        } else if (value1 instanceof long[] && value2 instanceof long[]) {
+            return Arrays.equals((long[]) value1, (long[]) value2);
+        } else if (value1 instanceof float[] && value2 instanceof float[]) {
+            return Arrays.equals((float[]) value1, (float[]) value2);
+        } else if (value1 instanceof double[] && value2 instanceof double[]) {
+            return Arrays.equals((double[]) value1, (double[]) value2);
+        } else {
+            return value1.equals(value2);
+        }
+    }
+
+    public int hashCode() {
+        int result = 0;
+        for (int i = 0; i < this.names.length; i ++) {
+            Object value = this.getMemberValue(this.names[i]);
+            result += (127 * this.names[i].hashCode()) ^ AnnotationImpl.memberValueHashCode(value);
+        }
+
+        return result;
+    }
+
+    private static int memberValueHashCode(Object value) {
+        if (value instanceof Object[]) {
+            return Arrays.hashCode((Object[]) value);
+        } else if (value instanceof boolean[]) {
+            return Arrays.hashCode((boolean[]) value);
+        } else if (value instanceof byte[]) {
+            return Arrays.hashCode((byte[]) value);
+        } else if (value instanceof char[]) {
+            return Arrays.hashCode((char[]) value);
+        } else if (value instanceof short[]) {
+            return Arrays.hashCode((short[]) value);
+        } else if (value instanceof int[]) {
+            return Arrays.hashCode((int[]) value);
+        } else if (value instanceof long[]) {
+            return Arrays.hashCode((long[]) value);
+        } else if (value instanceof float[]) {
+            return Arrays.hashCode((float[]) value);
+        } else if (value instanceof double[]) {
+            return Arrays.hashCode((double[]) value);
+        } else {
+            return value.hashCode();
+        }
+    }
+
+    public String toString() {
+        StringBuilder result = new StringBuilder();
+        result.append('@');
+        result.append(this.type.getName());
+        result.append('(');
+
+        for (int i = 0; i < this.names.length; i ++) {
+            if (i != 0) {
+                result.append(", ");
+            }
+
+            // The name of a lone `value` member is omitted
+            if (this.names.length != 1 || !this.names[i].equals("value")) {
+                result.append(this.names[i]);
+                result.append('=');
+            }
+
+            AnnotationImpl.appendMemberValue(result, this.getMemberValue(this.names[i]), false);
+        }
+
+        result.append(')');
+        return result.toString();
+    }
+
+    // Append a member value in the format of a Java source code literal
+    private static void appendMemberValue(StringBuilder result, Object value, boolean inArray) {
+        if (value instanceof Object[]) {
+            Object[] array = (Object[]) value;
+
+            result.append('{');
+            for (int i = 0; i < array.length; i ++) {
+                if (i != 0) {
+                    result.append(", ");
+                }
+                AnnotationImpl.appendMemberValue(result, array[i], true);
+            }
+            result.append('}');
+        } else if (value.getClass().isArray()) {
+            AnnotationImpl.appendMemberValue(result, AnnotationImpl.boxPrimitiveArray(value), true);
+        } else if (value instanceof Class) {
+            Class<?> cls = (Class<?>) value;
+            int dimensions = 0;
+            while (cls.isArray()) {
+                cls = cls.getComponentType();
+                dimensions += 1;
+            }
+
+            result.append(cls.getName().replace('$', '.'));
+            for (int i = 0; i < dimensions; i ++) {
+                result.append("[]");
+            }
+            result.append(".class");
+        } else if (value instanceof String) {
+            String string = (String) value;
+
+            result.append('"');
+            for (int i = 0; i < string.length(); i ++) {
+                AnnotationImpl.appendEscapedChar(result, string.charAt(i), '"');
+            }
+            result.append('"');
+        } else if (value instanceof Character) {
+            result.append('\'');
+            AnnotationImpl.appendEscapedChar(result, ((Character) value).charValue(), '\'');
+            result.append('\'');
+        } else if (value instanceof Float) {
+            float f = ((Float) value).floatValue();
+            if (Float.isNaN(f)) {
+                result.append("0.0f/0.0f");
+            } else if (Float.isInfinite(f)) {
+                result.append(f < 0 ? "-1.0f/0.0f" : "1.0f/0.0f");
+            } else {
+                result.append(f);
+                result.append('f');
+            }
+        } else if (value instanceof Double) {
+            double d = ((Double) value).doubleValue();
+            if (Double.isNaN(d)) {
+                result.append("0.0/0.0");
+            } else if (Double.isInfinite(d)) {
+                result.append(d < 0 ? "-1.0/0.0" : "1.0/0.0");
+            } else {
+                result.append(d);
+            }
+        } else if (value instanceof Long) {
+            result.append(((Long) value).longValue());
+            result.append('L');
+        } else if (value instanceof Byte) {
+            int b = ((Byte) value).byteValue() & 0xFF;
+
+            result.append("(byte)0x");
+            if (b < 0x10) {
+                result.append('0');
+            }
+            result.append(Integer.toHexString(b));
+        } else if (value instanceof Short) {
+            result.append("(short)");
+            result.append((int) ((Short) value).shortValue());
+        } else if (inArray && value instanceof Enum) {
+            result.append(((Enum<?>) value).name());
+        } else {
+            result.append(value.toString());
+        }
+    }
+
+    private static Object[] boxPrimitiveArray(Object value) {
+        Object[] result;
+        if (value instanceof boolean[]) {
+            boolean[] array = (boolean[]) value;
+            result = new Object[array.length];
+            for (int i = 0; i < array.length; i ++) {
+                result[i] = Boolean.valueOf(array[i]);
+            }
+        } else if (value instanceof byte[]) {
+            byte[] array = (byte[]) value;
+            result = new Object[array.length];
+            for (int i = 0; i < array.length; i ++) {
+                result[i] = Byte.valueOf(array[i]);
+            }
+        } else if (value instanceof char[]) {
+            char[] array = (char[]) value;
+            result = new Object[array.length];
+            for (int i = 0; i < array.length; i ++) {
+                result[i] = Character.valueOf(array[i]);
+            }
+        } else if (value instanceof short[]) {
+            short[] array = (short[]) value;
+            result = new Object[array.length];
+            for (int i = 0; i < array.length; i ++) {
+                result[i] = Short.valueOf(array[i]);
+            }
+        } else if (value instanceof int[]) {
+            int[] array = (int[]) value;
+            result = new Object[array.length];
+            for (int i = 0; i < array.length; i ++) {
+                result[i] = Integer.valueOf(array[i]);
+            }
+        } else if (value instanceof long[]) {
+            long[] array = (long[]) value;
+            result = new Object[array.length];
+            for (int i = 0; i < array.length; i ++) {
+                result[i] = Long.valueOf(array[i]);
+            }
+        } else if (value instanceof float[]) {
+            float[] array = (float[]) value;
+            result = new Object[array.length];
+            for (int i = 0; i < array.length; i ++) {
+                result[i] = Float.valueOf(array[i]);
+            }
+        } else {
+            double[] array = (double[]) value;
+            result = new Object[array.length];
+            for (int i = 0; i < array.length; i ++) {
+                result[i] = Double.valueOf(array[i]);
+            }
+        }
+
+        return result;
+    }
+
+    private static void appendEscapedChar(StringBuilder result, char c, char quote) {
+        switch (c) {
+            case '\b':
+                result.append("\\b");
+                break;
+            case '\f':
+                result.append("\\f");
+                break;
+            case '\n':
+                result.append("\\n");
+                break;
+            case '\r':
+                result.append("\\r");
+                break;
+            case '\t':
+                result.append("\\t");
+                break;
+            case '\\':
+                result.append("\\\\");
+                break;
+            default:
+                if (c == quote) {
+                    result.append('\\');
+                }
+                result.append(c);
+        }
+    }
+}
@@ -0,0 +1,38 @@
+package rjvm.internal;
+
+import java.lang.reflect.GenericArrayType;
+import java.lang.reflect.Type;
+
+public final class GenericArrayTypeImpl implements GenericArrayType {
+    private Type genericComponentType;
+
+    GenericArrayTypeImpl(Type genericComponentType) {
+        this.genericComponentType = genericComponentType;
+    }
+
+    public Type getGenericComponentType() {
+        return this.genericComponentType;
+    }
+
+    public boolean equals(Object obj) {
+        if (obj instanceof GenericArrayType) {
+            GenericArrayType other = (GenericArrayType) obj;
+
+            return this.genericComponentType.equals(other.getGenericComponentType());
+        } else {
+            return false;
+        }
+    }
+
+    public int hashCode() {
+        return this.genericComponentType.hashCode();
+    }
+
+    public String toString() {
+        StringBuilder result = new StringBuilder();
+        result.append(this.genericComponentType.getTypeName());
+        result.append("[]");
+
+        return result.toString();
+    }
+}
@@ -0,0 +1,76 @@
+package rjvm.internal;
+
+import java.lang.reflect.ParameterizedType;
+import java.lang.reflect.Type;
+import java.util.Arrays;
+import java.util.Objects;
+
+public final class ParameterizedTypeImpl implements ParameterizedType {
+    private Class<?> rawType;
+    private Type[] actualTypeArguments;
+    private Type ownerType;
+
+    ParameterizedTypeImpl(Class<?> rawType, Type[] actualTypeArguments, Type ownerType) {
+        this.rawType = rawType;
+        this.actualTypeArguments = actualTypeArguments;
+        this.ownerType = ownerType;
+    }
+
+    public Type[] getActualTypeArguments() {
+        return this.actualTypeArguments.clone();
+    }
+
+    public Type getRawType() {
+        return this.rawType;
+    }
+
+    public Type getOwnerType() {
+        return this.ownerType;
+    }
+
+    public boolean equals(Object obj) {
+        if (obj instanceof ParameterizedType) {
+            ParameterizedType other = (ParameterizedType) obj;
+
+            return Objects.equals(this.ownerType, other.getOwnerType()) &&
+                Objects.equals(this.rawType, other.getRawType()) &&
+                Arrays.equals(this.actualTypeArguments, other.getActualTypeArguments());
+        } else {
+            return false;
+        }
+    }
+
+    public int hashCode() {
+        return Arrays.hashCode(this.actualTypeArguments) ^
+            Objects.hashCode(this.ownerType) ^
+            Objects.hashCode(this.rawType);
+    }
+
+    public String toString() {
+        StringBuilder result = new StringBuilder();
+
+        if (this.ownerType != null) {
+            result.append(this.ownerType.getTypeName());
+            result.append('$');
+
+            // Only append the part of the name after the owner's name
+            String name = this.rawType.getName();
+            result.append(name.substring(name.lastIndexOf('$') + 1));
+        } else {
+            result.append(this.rawType.getName());
+        }
+
+        if (this.actualTypeArguments.length != 0) {
+            result.append('<');
+            for (int i = 0; i < this.actualTypeArguments.length; i ++) {
+                if (i != 0) {
+                    result.append(", ");
+                }
+                result.append(this.actualTypeArguments[i].getTypeName());
+            }
+            result.append('>');
+        }
+
+        return result.toString();
+    }
+}
@@ -0,0 +1,41 @@
+package rjvm.internal;
+
+import java.lang.reflect.Modifier;
+
+public final class Reflection {
+    private Reflection() { }
+
+    // Returns the class of the method that called the method calling
+    // `getCallerClass`, or `null` if that method wasn't called from Java code.
+    public static native Class<?> getCallerClass();
+
+    // Returns whether `caller` can access a member of `declaringClass` with
+    // the given modifiers. `receiver` is the object the member is accessed on,
+    // or `null` for static members.
+    public static boolean verifyMemberAccess(Class<?> caller, Class<?> declaringClass, Object receiver, int modifiers) {
+        // Calls that didn't come from Java code are always allowed
+        if (caller == null || caller == declaringClass) {
+            return true;
+        }
+
+        boolean samePackage = Reflection.isSamePackage(caller, declaringClass);
+
+        if (Modifier.isPublic(modifiers)) {
+            return Modifier.isPublic(declaringClass.getModifiers()) || samePackage;
+        } else if (Modifier.isPrivate(modifiers)) {
+            return caller.isNestmateOf(declaringClass);
+        } else if (samePackage) {
+            return true;
+        } else if (Modifier.isProtected(modifiers) && declaringClass.isAssignableFrom(caller)) {
+            // Protected instance members can only be accessed through the
+            // caller's own class or its subclasses
+            return receiver == null || caller.isInstance(receiver);
+        } else {
+            return false;
+        }
+    }
+
+    private static boolean isSamePackage(Class<?> class1, Class<?> class2) {
+        return class1.getClassLoader() == class2.getClassLoader() && class1.getPackageName().equals(class2.getPackageName());
+    }
+}
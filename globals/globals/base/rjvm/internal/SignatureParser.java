@@ -0,0 +1,437 @@
+package rjvm.internal;
+
+import java.lang.reflect.Array;
+import java.lang.reflect.Executable;
+import java.lang.reflect.GenericDeclaration;
+import java.lang.reflect.GenericSignatureFormatError;
+import java.lang.reflect.Type;
+import java.lang.reflect.TypeVariable;
+import java.util.ArrayList;
+
+// Parser for the generic signatures stored in `Signature` attributes (JVMS
+// 4.7.9.1). Classes and type variables referenced by a signature are resolved
+// relative to the `GenericDeclaration` (or, for fields, the class) it belongs to.
+public final class SignatureParser {
+    private String signature;
+    private GenericDeclaration scope;
+    private int position;
+
+    private SignatureParser(String signature, GenericDeclaration scope, int position) {
+        this.signature = signature;
+        this.scope = scope;
+        this.position = position;
+    }
+
+    // Class signatures
+
+    public static TypeVariable<?>[] parseTypeParameters(String signature, GenericDeclaration scope) {
+        SignatureParser parser = new SignatureParser(signature, scope, 0);
+        return parser.typeParameters();
+    }
+
+    public static Type parseSuperclass(String signature, Class<?> scope) {
+        SignatureParser parser = new SignatureParser(signature, scope, 0);
+        parser.skipTypeParameters();
+
+        return parser.classTypeSignature();
+    }
+
+    public static Type[] parseSuperinterfaces(String signature, Class<?> scope) {
+        SignatureParser parser = new SignatureParser(signature, scope, 0);
+        parser.skipTypeParameters();
+        parser.skipReferenceType();
+
+        ArrayList<Type> result = new ArrayList<Type>();
+        while (parser.position < signature.length()) {
+            result.add(parser.classTypeSignature());
+        }
+
+        return result.toArray(new Type[0]);
+    }
+
+    // Method signatures
+
+    public static Type[] parseParameterTypes(String signature, GenericDeclaration scope) {
+        SignatureParser parser = new SignatureParser(signature, scope, 0);
+        parser.skipTypeParameters();
+
+        ArrayList<Type> result = new ArrayList<Type>();
+        parser.expect('(');
+        while (parser.peek() != ')') {
+            result.add(parser.javaTypeSignature());
+        }
+
+        return result.toArray(new Type[0]);
+    }
+
+    public static Type parseReturnType(String signature, GenericDeclaration scope) {
+        SignatureParser parser = new SignatureParser(signature, scope, 0);
+        parser.skipToReturnType();
+
+        if (parser.peek() == 'V') {
+            return void.class;
+        } else {
+            return parser.javaTypeSignature();
+        }
+    }
+
+    public static Type[] parseExceptionTypes(String signature, GenericDeclaration scope) {
+        SignatureParser parser = new SignatureParser(signature, scope, 0);
+        parser.skipToReturnType();
+
+        if (parser.peek() == 'V') {
+            parser.position += 1;
+        } else {
+            parser.skipJavaType();
+        }
+
+        ArrayList<Type> result = new ArrayList<Type>();
+        while (parser.position < signature.length()) {
+            parser.expect('^');
+            result.add(parser.referenceTypeSignature());
+        }
+
+        return result.toArray(new Type[0]);
+    }
+
+    // Field signatures
+
+    public static Type parseFieldType(String signature, Class<?> scope) {
+        SignatureParser parser = new SignatureParser(signature, scope, 0);
+        return parser.referenceTypeSignature();
+    }
+
+    // Called by `TypeVariableImpl` to parse the bounds starting at `boundsStart`
+    static Type[] parseBounds(String signature, int boundsStart, GenericDeclaration scope) {
+        SignatureParser parser = new SignatureParser(signature, scope, boundsStart);
+
+        ArrayList<Type> result = new ArrayList<Type>();
+
+        // The class bound may be empty if there are interface bounds
+        parser.expect(':');
+        if (parser.peek() != ':') {
+            result.add(parser.referenceTypeSignature());
+        }
+
+        while (parser.peek() == ':') {
+            parser.position += 1;
+            result.add(parser.referenceTypeSignature());
+        }
+
+        if (result.size() == 0) {
+            result.add(Object.class);
+        }
+
+        return result.toArray(new Type[0]);
+    }
+
+    // Grammar productions
+
+    private TypeVariable<?>[] typeParameters() {
+        ArrayList<TypeVariable<?>> result = new ArrayList<TypeVariable<?>>();
+
+        if (this.peek() != '<') {
+            return new TypeVariable<?>[0];
+        }
+
+        this.position += 1;
+        while (this.peek() != '>') {
+            String name = this.identifier();
+            int boundsStart = this.position;
+
+            // Skip over the bounds; they're parsed lazily by `TypeVariableImpl`
+            this.expect(':');
+            if (this.peek() != ':') {
+                this.skipReferenceType();
+            }
+
+            while (this.peek() == ':') {
+                this.position += 1;
+                this.skipReferenceType();
+            }
+
+            result.add(new TypeVariableImpl<GenericDeclaration>(name, this.scope, this.signature, boundsStart));
+        }
+        this.position += 1;
+
+        return result.toArray(new TypeVariable<?>[0]);
+    }
+
+    private Type javaTypeSignature() {
+        char c = this.peek();
+        switch (c) {
+            case 'B':
+                this.position += 1;
+                return byte.class;
+            case 'C':
+                this.position += 1;
+                return char.class;
+            case 'D':
+                this.position += 1;
+                return double.class;
+            case 'F':
+                this.position += 1;
+                return float.class;
+            case 'I':
+                this.position += 1;
+                return int.class;
+            case 'J':
+                this.position += 1;
+                return long.class;
+            case 'S':
+                this.position += 1;
+                return short.class;
+            case 'Z':
+                this.position += 1;
+                return boolean.class;
+            default:
+                return this.referenceTypeSignature();
+        }
+    }
+
+    private Type referenceTypeSignature() {
+        char c = this.peek();
+        if (c == 'L') {
+            return this.classTypeSignature();
+        } else if (c == 'T') {
+            this.position += 1;
+            String name = this.identifier();
+            this.expect(';');
+
+            return SignatureParser.findTypeVariable(this.scope, name);
+        } else if (c == '[') {
+            this.position += 1;
+            Type componentType = this.javaTypeSignature();
+
+            if (componentType instanceof Class) {
+                return Array.newInstance((Class<?>) componentType, 0).getClass();
+            } else {
+                return new GenericArrayTypeImpl(componentType);
+            }
+        } else {
+            throw this.error();
+        }
+    }
+
+    private Type classTypeSignature() {
+        this.expect('L');
+
+        // The package name and the outermost class name
+        String name = this.identifier();
+        Class<?> rawType = this.loadClass(name);
+
+        Type[] typeArguments = this.typeArguments();
+        Type result;
+        if (typeArguments != null) {
+            result = new ParameterizedTypeImpl(rawType, typeArguments, rawType.getDeclaringClass());
+        } else {
+            result = rawType;
+        }
+
+        // Inner classes of a parameterized class
+        while (this.peek() == '.') {
+            this.position += 1;
+
+            StringBuilder innerName = new StringBuilder();
+            innerName.append(name);
+            innerName.append('$');
+            innerName.append(this.identifier());
+            name = innerName.toString();
+
+            rawType = this.loadClass(name);
+
+            typeArguments = this.typeArguments();
+            if (typeArguments != null || result instanceof ParameterizedTypeImpl) {
+                if (typeArguments == null) {
+                    typeArguments = new Type[0];
+                }
+
+                result = new ParameterizedTypeImpl(rawType, typeArguments, result);
+            } else {
+                result = rawType;
+            }
+        }
+
+        this.expect(';');
+
+        return result;
+    }
+
+    // Returns `null` if there are no type arguments
+    private Type[] typeArguments() {
+        if (this.peek() != '<') {
+            return null;
+        }
+
+        ArrayList<Type> result = new ArrayList<Type>();
+
+        this.position += 1;
+        while (this.peek() != '>') {
+            char c = this.peek();
+            if (c == '*') {
+                this.position += 1;
+                result.add(new WildcardTypeImpl(new Type[] { Object.class }, new Type[0]));
+            } else if (c == '+') {
+                this.position += 1;
+                Type bound = this.referenceTypeSignature();
+                result.add(new WildcardTypeImpl(new Type[] { bound }, new Type[0]));
+            } else if (c == '-') {
+                this.position += 1;
+                Type bound = this.referenceTypeSignature();
+                result.add(new WildcardTypeImpl(new Type[] { Object.class }, new Type[] { bound }));
+            } else {
+                result.add(this.referenceTypeSignature());
+            }
+        }
+        this.position += 1;
+
+        return result.toArray(new Type[0]);
+    }
+
+    // Skipping, used to find the parts of the signature that are needed
+    // without resolving the parts before them
+
+    private void skipToReturnType() {
+        this.skipTypeParameters();
+
+        this.expect('(');
+        while (this.peek() != ')') {
+            this.skipJavaType();
+        }
+        this.position += 1;
+    }
+
+    private void skipTypeParameters() {
+        if (this.peek() == '<') {
+            this.skipTypeArguments();
+        }
+    }
+
+    private void skipJavaType() {
+        char c = this.peek();
+        if (c == 'L' || c == 'T' || c == '[') {
+            this.skipReferenceType();
+        } else {
+            this.position += 1;
+        }
+    }
+
+    private void skipReferenceType() {
+        char c = this.peek();
+        if (c == '[') {
+            this.position += 1;
+            this.skipJavaType();
+        } else if (c == 'L' || c == 'T') {
+            while (this.peek() != ';') {
+                if (this.peek() == '<') {
+                    this.skipTypeArguments();
+                } else {
+                    this.position += 1;
+                }
+            }
+            this.position += 1;
+        } else {
+            throw this.error();
+        }
+    }
+
+    // Skip a balanced `<...>` section
+    private void skipTypeArguments() {
+        int depth = 0;
+        do {
+            char c = this.peek();
+            if (c == '<') {
+                depth += 1;
+            } else if (c == '>') {
+                depth -= 1;
+            }
+            this.position += 1;
+        } while (depth != 0);
+    }
+
+    // Helpers
+
+    private String identifier() {
+        int start = this.position;
+        while (this.position < this.signature.length()) {
+            char c = this.signature.charAt(this.position);
+            if (c == ';' || c == '<' || c == '>' || c == '.' || c == ':') {
+                break;
+            }
+            this.position += 1;
+        }
+
+        if (start == this.position) {
+            throw this.error();
+        }
+
+        return this.signature.substring(start, this.position);
+    }
+
+    private char peek() {
+        if (this.position >= this.signature.length()) {
+            throw this.error();
+        }
+
+        return this.signature.charAt(this.position);
+    }
+
+    private void expect(char c) {
+        if (this.peek() != c) {
+            throw this.error();
+        }
+
+        this.position += 1;
+    }
+
+    private GenericSignatureFormatError error() {
+        StringBuilder message = new StringBuilder();
+        message.append("Signature Parse error: ");
+        message.append(this.signature);
+        message.append(" at position ");
+        message.append(this.position);
+
+        return new GenericSignatureFormatError(message.toString());
+    }
+
+    private Class<?> loadClass(String internalName) {
+        String name = internalName.replace('/', '.');
+
+        try {
+            return Class.forName(name, false, SignatureParser.declaringClassOf(this.scope).getClassLoader());
+        } catch (ClassNotFoundException e) {
+            throw new TypeNotPresentException(name, e);
+        }
+    }
+
+    private static Class<?> declaringClassOf(GenericDeclaration declaration) {
+        if (declaration instanceof Class) {
+            return (Class<?>) declaration;
+        } else {
+            return ((Executable) declaration).getDeclaringClass();
+        }
+    }
+
+    // Find the type variable named `name` that is visible from `scope`
+    private static TypeVariable<?> findTypeVariable(GenericDeclaration scope, String name) {
+        GenericDeclaration declaration = scope;
+        while (declaration != null) {
+            TypeVariable<?>[] typeParameters = declaration.getTypeParameters();
+            for (int i = 0; i < typeParameters.length; i ++) {
+                if (typeParameters[i].getName().equals(name)) {
+                    return typeParameters[i];
+                }
+            }
+
+            if (declaration instanceof Executable) {
+                declaration = ((Executable) declaration).getDeclaringClass();
+            } else {
+                declaration = ((Class<?>) declaration).getDeclaringClass();
+            }
+        }
+
+        // The type variable is declared by something we can't find (such as
+        // the method enclosing a local class), so it has no known bounds
+        return new TypeVariableImpl<GenericDeclaration>(name, scope, null, 0);
+    }
+}
@@ -0,0 +1,79 @@
+package rjvm.internal;
+
+import java.lang.annotation.Annotation;
+import java.lang.reflect.GenericDeclaration;
+import java.lang.reflect.Type;
+import java.lang.reflect.TypeVariable;
+
+public final class TypeVariableImpl<D extends GenericDeclaration> implements TypeVariable<D> {
+    private String name;
+    private D genericDeclaration;
+
+    // The bounds are parsed lazily, since they can refer to this type variable
+    // or to type variables declared after it
+    private String signature;
+    private int boundsStart;
+    private Type[] cachedBounds;
+
+    TypeVariableImpl(String name, D genericDeclaration, String signature, int boundsStart) {
+        this.name = name;
+        this.genericDeclaration = genericDeclaration;
+        this.signature = signature;
+        this.boundsStart = boundsStart;
+    }
+
+    public Type[] getBounds() {
+        if (this.cachedBounds == null) {
+            if (this.signature == null) {
+                this.cachedBounds = new Type[] { Object.class };
+            } else {
+                this.cachedBounds = SignatureParser.parseBounds(this.signature, this.boundsStart, this.genericDeclaration);
+            }
+        }
+        return this.cachedBounds.clone();
+    }
+
+    public D getGenericDeclaration() {
+        return this.genericDeclaration;
+    }
+
+    public String getName() {
+        return this.name;
+    }
+
+    public <T extends Annotation> T getAnnotation(Class<T> annotationClass) {
+        if (annotationClass == null) {
+            throw new NullPointerException();
+        }
+
+        // TODO: Implement type annotations
+        return null;
+    }
+
+    public Annotation[] getAnnotations() {
+        return new Annotation[0];
+    }
+
+    public Annotation[] getDeclaredAnnotations() {
+        return new Annotation[0];
+    }
+
+    public boolean equals(Object obj) {
+        if (obj instanceof TypeVariable) {
+            TypeVariable<?> other = (TypeVariable<?>) obj;
+
+            return this.genericDeclaration.equals(other.getGenericDeclaration()) &&
+                this.name.equals(other.getName());
+        } else {
+            return false;
+        }
+    }
+
+    public int hashCode() {
+        return this.genericDeclaration.hashCode() ^ this.name.hashCode();
+    }
+
+    public String toString() {
+        return this.name;
+    }
+}
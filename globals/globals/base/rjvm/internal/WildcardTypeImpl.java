@@ -0,0 +1,62 @@
+package rjvm.internal;
+
+import java.lang.reflect.Type;
+import java.lang.reflect.WildcardType;
+import java.util.Arrays;
+
+public final class WildcardTypeImpl implements WildcardType {
+    private Type[] upperBounds;
+    private Type[] lowerBounds;
+
+    WildcardTypeImpl(Type[] upperBounds, Type[] lowerBounds) {
+        this.upperBounds = upperBounds;
+        this.lowerBounds = lowerBounds;
+    }
+
+    public Type[] getUpperBounds() {
+        return this.upperBounds.clone();
+    }
+
+    public Type[] getLowerBounds() {
+        return this.lowerBounds.clone();
+    }
+
+    public boolean equals(Object obj) {
+        if (obj instanceof WildcardType) {
+            WildcardType other = (WildcardType) obj;
+
+            return Arrays.equals(this.lowerBounds, other.getLowerBounds()) &&
+                Arrays.equals(this.upperBounds, other.getUpperBounds());
+        } else {
+            return false;
+        }
+    }
+
+    public int hashCode() {
+        return Arrays.hashCode(this.lowerBounds) ^ Arrays.hashCode(this.upperBounds);
+    }
+
+    public String toString() {
+        Type[] bounds;
+        StringBuilder result = new StringBuilder();
+
+        if (this.lowerBounds.length != 0) {
+            bounds = this.lowerBounds;
+            result.append("? super ");
+        } else if (this.upperBounds.length != 0 && this.upperBounds[0] != Object.class) {
+            bounds = this.upperBounds;
+            result.append("? extends ");
+        } else {
+            return "?";
+        }
+
+        for (int i = 0; i < bounds.length; i ++) {
+            if (i != 0) {
+                result.append(" & ");
+            }
+            result.append(bounds[i].getTypeName());
+        }
+
+        return result.toString();
+    }
+}
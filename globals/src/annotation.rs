@@ -0,0 +1,474 @@
+// Creation of `java.lang.annotation.Annotation` instances from the annotation
+// attributes of a class file.
+//
+// Annotation instances are objects of a class that the VM synthesizes for each
+// annotation interface. The class extends `rjvm.internal.AnnotationImpl`, which
+// stores the member values, and implements the member methods natively.
+//
+// Creating an annotation can require loading classes and running class
+// initializers (for enum constants), both of which can run Java code and so
+// trigger a garbage collection. To keep the objects created here from being
+// collected, annotations are created in two passes: `prepare_annotations` does
+// everything that can run Java code, and `create_annotation_array` then creates
+// the objects without running any Java code.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use rjvm_core::classfile::attribute::{Annotation, AttributeInfo, ElementValue};
+use rjvm_core::{
+    Class, ClassFlags, ClassLoader, Context, Descriptor, Error, JvmString, MethodDescriptor,
+    MethodFlags, Object, PrimitiveType, ResolvedDescriptor, Value,
+};
+
+// A member of an annotation interface, along with the value it has in a
+// particular annotation.
+struct Member {
+    name: JvmString,
+    return_type: Descriptor,
+    value: Option<ElementValue>,
+}
+
+/// Load every class and run every class initializer needed to create the
+/// given annotations. This returns the annotation interface of each
+/// annotation, or `None` for annotations that should be ignored because their
+/// interface could not be found.
+pub(crate) fn prepare_annotations(
+    context: &Context,
+    loader: ClassLoader,
+    annotations: &[Annotation],
+) -> Result<Box<[Option<Class>]>, Error> {
+    let mut types = Vec::with_capacity(annotations.len());
+
+    for annotation in annotations {
+        // Annotations whose interface can't be loaded are silently ignored
+        let annotation_type = match lookup_descriptor_class(context, loader, annotation.type_name) {
+            Ok(class) if class.flags().contains(ClassFlags::ANNOTATION) => class,
+            _ => {
+                types.push(None);
+                continue;
+            }
+        };
+
+        prepare_annotation(context, loader, annotation_type, annotation)?;
+
+        types.push(Some(annotation_type));
+    }
+
+    Ok(types.into_boxed_slice())
+}
+
+/// Create an `Annotation[]` holding the given annotations. `types` must be the
+/// result of calling `prepare_annotations` with the same annotations.
+pub(crate) fn create_annotation_array(
+    context: &Context,
+    loader: ClassLoader,
+    annotations: &[Annotation],
+    types: &[Option<Class>],
+) -> Result<Object, Error> {
+    let mut objects = Vec::with_capacity(annotations.len());
+
+    for (annotation, annotation_type) in annotations.iter().zip(types) {
+        if let Some(annotation_type) = annotation_type {
+            let object = create_annotation(context, loader, *annotation_type, annotation)?;
+            objects.push(Some(object));
+        }
+    }
+
+    let annotation_class = annotation_class(context)?;
+
+    Ok(Object::obj_array(
+        context,
+        annotation_class,
+        objects.into_boxed_slice(),
+    ))
+}
+
+/// Returns the `java/lang/annotation/Annotation` class.
+pub(crate) fn annotation_class(context: &Context) -> Result<Class, Error> {
+    let name = JvmString::new(context.gc_ctx(), "java/lang/annotation/Annotation".into());
+
+    context.bootstrap_loader().lookup_class(context, name)
+}
+
+fn prepare_annotation(
+    context: &Context,
+    loader: ClassLoader,
+    annotation_type: Class,
+    annotation: &Annotation,
+) -> Result<(), Error> {
+    impl_class_for(context, annotation_type)?;
+
+    for member in annotation_members(context, annotation_type, annotation)? {
+        if let Some(value) = &member.value {
+            prepare_element_value(context, loader, value, member.return_type)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Load every class and run every class initializer needed to create a member
+/// value of type `return_type`, such as the default value of an annotation
+/// interface method.
+pub(crate) fn prepare_element_value(
+    context: &Context,
+    loader: ClassLoader,
+    value: &ElementValue,
+    return_type: Descriptor,
+) -> Result<(), Error> {
+    match (value, return_type) {
+        (ElementValue::Enum(type_name, _), _) => {
+            let enum_class = lookup_descriptor_class(context, loader, *type_name)?;
+
+            enum_class.run_clinit(context)?;
+        }
+        (ElementValue::Class(descriptor), _) => {
+            resolve_class_literal(context, loader, *descriptor)?;
+        }
+        (ElementValue::Annotation(annotation), _) => {
+            let annotation_type = lookup_descriptor_class(context, loader, annotation.type_name)?;
+
+            prepare_annotation(context, loader, annotation_type, annotation)?;
+        }
+        (ElementValue::Array(values), Descriptor::Array(inner_type)) => {
+            if let Descriptor::Class(class_name) = *inner_type {
+                loader.lookup_class(context, class_name)?;
+            }
+
+            for value in values.iter() {
+                prepare_element_value(context, loader, value, *inner_type)?;
+            }
+        }
+        (ElementValue::String(_), _) | (ElementValue::Array(_), _) => {}
+        (_, return_type) => {
+            if return_type.is_primitive() {
+                return_type.box_class(context)?.run_clinit(context)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn create_annotation(
+    context: &Context,
+    loader: ClassLoader,
+    annotation_type: Class,
+    annotation: &Annotation,
+) -> Result<Object, Error> {
+    let impl_class = impl_class_for(context, annotation_type)?;
+    let members = annotation_members(context, annotation_type, annotation)?;
+
+    let mut names = Vec::with_capacity(members.len());
+    let mut values = Vec::with_capacity(members.len());
+    for member in members {
+        names.push(Some(context.str_to_string(&member.name)));
+
+        let value = if let Some(value) = &member.value {
+            create_element_value(context, loader, value, member.return_type)?
+        } else {
+            None
+        };
+        values.push(value);
+    }
+
+    let names = Object::obj_array(
+        context,
+        context.builtins().java_lang_string,
+        names.into_boxed_slice(),
+    );
+    let values = Object::obj_array(context, context.object_class(), values.into_boxed_slice());
+
+    let type_object = annotation_type.get_or_init_object(context);
+
+    let object = Object::from_class(context.gc_ctx(), impl_class);
+    object.set_field(0, Value::Object(Some(type_object)));
+    object.set_field(1, Value::Object(Some(names)));
+    object.set_field(2, Value::Object(Some(values)));
+
+    Ok(object)
+}
+
+/// Create the object representing a member value of type `return_type`.
+/// Primitive values are boxed. This returns `None` if the value doesn't match
+/// `return_type`.
+pub(crate) fn create_element_value(
+    context: &Context,
+    loader: ClassLoader,
+    value: &ElementValue,
+    return_type: Descriptor,
+) -> Result<Option<Object>, Error> {
+    Ok(match (value, return_type) {
+        (ElementValue::String(string), Descriptor::Class(_)) => Some(context.str_to_string(string)),
+        (ElementValue::Enum(type_name, const_name), Descriptor::Class(_)) => {
+            let enum_class = lookup_descriptor_class(context, loader, *type_name)?;
+
+            let field_key = (*const_name, Descriptor::Class(enum_class.name()));
+            let field_index = enum_class.static_field_vtable().lookup(field_key);
+
+            field_index.and_then(|i| enum_class.get_static_field(i).value().object())
+        }
+        (ElementValue::Class(descriptor), Descriptor::Class(_)) => {
+            let class = resolve_class_literal(context, loader, *descriptor)?;
+
+            Some(class.get_or_init_object(context))
+        }
+        (ElementValue::Annotation(annotation), Descriptor::Class(_)) => {
+            let annotation_type = lookup_descriptor_class(context, loader, annotation.type_name)?;
+
+            Some(create_annotation(
+                context,
+                loader,
+                annotation_type,
+                annotation,
+            )?)
+        }
+        (ElementValue::Array(values), Descriptor::Array(inner_type)) => {
+            create_array(context, loader, values, *inner_type)?
+        }
+        (value, return_type) => {
+            if let Some(primitive) = primitive_value(value, return_type) {
+                let object = Object::from_class(context.gc_ctx(), return_type.box_class(context)?);
+                object.set_field(0, primitive);
+
+                Some(object)
+            } else {
+                None
+            }
+        }
+    })
+}
+
+fn create_array(
+    context: &Context,
+    loader: ClassLoader,
+    values: &[ElementValue],
+    inner_type: Descriptor,
+) -> Result<Option<Object>, Error> {
+    if let Descriptor::Class(class_name) = inner_type {
+        let elem_class = loader.lookup_class(context, class_name)?;
+
+        let mut elements = Vec::with_capacity(values.len());
+        for value in values {
+            let Some(element) = create_element_value(context, loader, value, inner_type)? else {
+                return Ok(None);
+            };
+
+            elements.push(Some(element));
+        }
+
+        return Ok(Some(Object::obj_array(
+            context,
+            elem_class,
+            elements.into_boxed_slice(),
+        )));
+    }
+
+    let mut primitives = Vec::with_capacity(values.len());
+    for value in values {
+        let Some(primitive) = primitive_value(value, inner_type) else {
+            return Ok(None);
+        };
+
+        primitives.push(primitive);
+    }
+
+    let primitives = primitives.iter();
+
+    Ok(Some(match inner_type {
+        Descriptor::Boolean => {
+            Object::bool_array(context, primitives.map(|v| v.int() as i8).collect())
+        }
+        Descriptor::Byte => {
+            Object::byte_array(context, primitives.map(|v| v.int() as i8).collect())
+        }
+        Descriptor::Character => {
+            Object::char_array(context, primitives.map(|v| v.int() as u16).collect())
+        }
+        Descriptor::Short => {
+            Object::short_array(context, primitives.map(|v| v.int() as i16).collect())
+        }
+        Descriptor::Integer => Object::int_array(context, primitives.map(|v| v.int()).collect()),
+        Descriptor::Long => Object::long_array(context, primitives.map(|v| v.long()).collect()),
+        Descriptor::Float => Object::float_array(context, primitives.map(|v| v.float()).collect()),
+        Descriptor::Double => {
+            Object::double_array(context, primitives.map(|v| v.double()).collect())
+        }
+        // Annotation members can't return nested arrays
+        _ => return Ok(None),
+    }))
+}
+
+// Returns the value of a primitive element value, or `None` if it isn't a
+// primitive of type `descriptor`.
+fn primitive_value(value: &ElementValue, descriptor: Descriptor) -> Option<Value> {
+    Some(match (value, descriptor) {
+        (ElementValue::Boolean(value), Descriptor::Boolean) => Value::Integer(*value as i32),
+        (ElementValue::Byte(value), Descriptor::Byte) => Value::Integer(*value as i32),
+        (ElementValue::Char(value), Descriptor::Character) => Value::Integer(*value as i32),
+        (ElementValue::Short(value), Descriptor::Short) => Value::Integer(*value as i32),
+        (ElementValue::Int(value), Descriptor::Integer) => Value::Integer(*value),
+        (ElementValue::Long(value), Descriptor::Long) => Value::Long(*value),
+        (ElementValue::Float(value), Descriptor::Float) => Value::Float(*value),
+        (ElementValue::Double(value), Descriptor::Double) => Value::Double(*value),
+        _ => return None,
+    })
+}
+
+// Look up the class named by a field descriptor of the form `Lpkg/Name;`.
+fn lookup_descriptor_class(
+    context: &Context,
+    loader: ClassLoader,
+    descriptor: JvmString,
+) -> Result<Class, Error> {
+    match Descriptor::try_from_string(context, descriptor) {
+        Some(Descriptor::Class(class_name)) => loader.lookup_class(context, class_name),
+        _ => Err(context.class_format_error("Invalid annotation descriptor")),
+    }
+}
+
+// Resolve the class referenced by a class literal element value, which is
+// stored as a return descriptor.
+fn resolve_class_literal(
+    context: &Context,
+    loader: ClassLoader,
+    descriptor: JvmString,
+) -> Result<Class, Error> {
+    if &*descriptor == "V" {
+        return Ok(context.primitive_class_for(PrimitiveType::Void));
+    }
+
+    let Some(descriptor) = Descriptor::try_from_string(context, descriptor) else {
+        return Err(context.class_format_error("Invalid class literal descriptor"));
+    };
+
+    let resolved = ResolvedDescriptor::from_descriptor(context, loader, descriptor)?;
+
+    Ok(resolved.reflection_class(context))
+}
+
+// Returns the members of an annotation interface in declaration order, with
+// the values they have in `annotation`. Members that `annotation` doesn't
+// specify get their default value, if they have one.
+fn annotation_members(
+    context: &Context,
+    annotation_type: Class,
+    annotation: &Annotation,
+) -> Result<Vec<Member>, Error> {
+    let Some(class_file) = *annotation_type.class_file() else {
+        return Ok(Vec::new());
+    };
+
+    let mut members = Vec::with_capacity(class_file.methods().len());
+    for method in class_file.methods() {
+        // Only the abstract methods of an annotation interface are members
+        if !method.flags().contains(MethodFlags::ABSTRACT) {
+            continue;
+        }
+
+        let descriptor = MethodDescriptor::from_string(context, method.descriptor())?;
+
+        let value = annotation
+            .elements
+            .iter()
+            .find(|(name, _)| *name == method.name())
+            .map(|(_, value)| value.clone());
+
+        let value = if value.is_some() {
+            value
+        } else {
+            let default = method
+                .attributes()
+                .iter()
+                .find(|a| &*a.name() == "AnnotationDefault");
+
+            if let Some(default) = default {
+                match default.parse(class_file.constant_pool()) {
+                    Ok(AttributeInfo::AnnotationDefault(value)) => Some(value),
+                    Ok(_) => None,
+                    Err(e) => return Err(Error::from_class_file_error(context, e)),
+                }
+            } else {
+                None
+            }
+        };
+
+        members.push(Member {
+            name: method.name(),
+            return_type: descriptor.return_type(),
+            value,
+        });
+    }
+
+    Ok(members)
+}
+
+// Returns the class used to implement the given annotation interface, creating
+// it if it doesn't exist yet.
+fn impl_class_for(context: &Context, annotation_type: Class) -> Result<Class, Error> {
+    let loader = annotation_type
+        .loader()
+        .expect("Annotation interface should have loader");
+
+    let impl_name = format!("{}$$AnnotationImpl", annotation_type.name());
+    let impl_name = JvmString::new(context.gc_ctx(), impl_name);
+
+    if let Some(impl_class) = loader.find_loaded_class(impl_name) {
+        return Ok(impl_class);
+    }
+
+    let super_name = JvmString::new(context.gc_ctx(), "rjvm/internal/AnnotationImpl".into());
+    let super_class = context
+        .bootstrap_loader()
+        .lookup_class(context, super_name)?;
+
+    let impl_class = Class::for_interface_impl(
+        context,
+        loader,
+        impl_name,
+        super_class,
+        &[annotation_type],
+        annotation_member,
+    )?;
+    loader.define_class(context, impl_class)?;
+
+    Ok(impl_class)
+}
+
+// The native implementation of every member method of an annotation class.
+fn annotation_member(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let receiver = args[0].object().unwrap();
+
+    let method = context.current_method();
+
+    let get_member_value = JvmString::new(context.gc_ctx(), "getMemberValue".into());
+    let get_member_value = receiver
+        .class()
+        .instance_method_vtable()
+        .elements_for_name(get_member_value)[0];
+
+    let name = context.str_to_string(&method.name());
+
+    let value = context
+        .exec_method(
+            get_member_value,
+            &[Value::Object(Some(receiver)), Value::Object(Some(name))],
+        )?
+        .expect("getMemberValue returns a value")
+        .object()
+        .expect("getMemberValue never returns null");
+
+    let return_type = method.descriptor().return_type();
+    if matches!(return_type, Descriptor::Array(_)) {
+        // Arrays are mutable, so return a copy each time
+        Ok(Some(Value::Object(Some(
+            value.create_clone(context.gc_ctx()),
+        ))))
+    } else if return_type.is_primitive() {
+        // Unbox primitive values
+        Ok(Some(value.get_field(0)))
+    } else {
+        Ok(Some(Value::Object(Some(value))))
+    }
+}
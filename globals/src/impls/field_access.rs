@@ -17,6 +17,21 @@ pub fn register_native_mappings(context: &Context) {
 
         ("java/lang/reflect/FieldAccess.getObjectStaticNative.(Ljava/lang/reflect/Field;)Ljava/lang/Object;", get_static_field),
         ("java/lang/reflect/FieldAccess.getObjectInstanceNative.(Ljava/lang/reflect/Field;Ljava/lang/Object;)Ljava/lang/Object;", get_instance_field),
+
+        ("java/lang/reflect/FieldAccess.setDoubleStaticNative.(Ljava/lang/reflect/Field;D)V", set_static_field),
+        ("java/lang/reflect/FieldAccess.setDoubleInstanceNative.(Ljava/lang/reflect/Field;Ljava/lang/Object;D)V", set_instance_field),
+
+        ("java/lang/reflect/FieldAccess.setFloatStaticNative.(Ljava/lang/reflect/Field;F)V", set_static_field),
+        ("java/lang/reflect/FieldAccess.setFloatInstanceNative.(Ljava/lang/reflect/Field;Ljava/lang/Object;F)V", set_instance_field),
+
+        ("java/lang/reflect/FieldAccess.setIntStaticNative.(Ljava/lang/reflect/Field;I)V", set_static_field),
+        ("java/lang/reflect/FieldAccess.setIntInstanceNative.(Ljava/lang/reflect/Field;Ljava/lang/Object;I)V", set_instance_field),
+
+        ("java/lang/reflect/FieldAccess.setLongStaticNative.(Ljava/lang/reflect/Field;J)V", set_static_field),
+        ("java/lang/reflect/FieldAccess.setLongInstanceNative.(Ljava/lang/reflect/Field;Ljava/lang/Object;J)V", set_instance_field),
+
+        ("java/lang/reflect/FieldAccess.setObjectStaticNative.(Ljava/lang/reflect/Field;Ljava/lang/Object;)V", set_static_field),
+        ("java/lang/reflect/FieldAccess.setObjectInstanceNative.(Ljava/lang/reflect/Field;Ljava/lang/Object;Ljava/lang/Object;)V", set_instance_field),
     ];
 
     context.register_native_mappings(mappings);
@@ -31,6 +46,9 @@ fn get_static_field(context: &Context, args: &[Value]) -> Result<Option<Value>,
     let cls = field.defining_class();
     let id = field.id();
 
+    // Accessing a static field initializes its class
+    cls.run_clinit(context)?;
+
     let value = cls.get_static_field(id).value();
 
     Ok(Some(value))
@@ -51,3 +69,38 @@ fn get_instance_field(context: &Context, args: &[Value]) -> Result<Option<Value>
 
     Ok(Some(value))
 }
+
+fn set_static_field(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Field should never be null
+    let field_obj = args[0].object().unwrap();
+    let field_id = field_obj.get_field(0).int();
+    let field = context.field_object_by_id(field_id);
+
+    let cls = field.defining_class();
+    let id = field.id();
+
+    // Accessing a static field initializes its class
+    cls.run_clinit(context)?;
+
+    // Value was type-checked and converted by Java code
+    cls.get_static_field(id).set_value(args[1]);
+
+    Ok(None)
+}
+
+fn set_instance_field(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Field should never be null
+    let field_obj = args[0].object().unwrap();
+    let field_id = field_obj.get_field(0).int();
+    let field = context.field_object_by_id(field_id);
+
+    // Object null-checked by Java code
+    let object = args[1].object().unwrap();
+
+    let id = field.id();
+
+    // Value was type-checked and converted by Java code
+    object.set_field(id, args[2]);
+
+    Ok(None)
+}
@@ -0,0 +1,185 @@
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::vec::Vec;
+use rjvm_core::{
+    Context, Descriptor, Error, FieldTemplate, JvmString, MethodDescriptor, MethodFlags,
+    NativeMethod, Object, ResolvedMethodDescriptor, Value,
+};
+
+pub fn register_native_mappings(context: &Context) {
+    #[rustfmt::skip]
+    let mappings: &[(&str, NativeMethod)] = &[
+        ("java/lang/invoke/MethodHandleNatives.resolveMember.(ILjava/lang/Class;Ljava/lang/String;Ljava/lang/String;)Ljava/lang/reflect/AccessibleObject;", resolve_member),
+        ("java/lang/invoke/MethodHandleNatives.resolveMethodDescriptor.(Ljava/lang/String;Ljava/lang/ClassLoader;)[Ljava/lang/Class;", resolve_method_descriptor),
+        ("java/lang/invoke/MethodHandleNatives.invokeSpecial.(Ljava/lang/reflect/Method;Ljava/lang/Object;[Ljava/lang/Object;)Ljava/lang/Object;", invoke_special),
+    ];
+
+    context.register_native_mappings(mappings);
+}
+
+// The reference kinds used by `DirectMethodHandle`
+const REF_GET_FIELD: i32 = 1;
+const REF_GET_STATIC: i32 = 2;
+const REF_PUT_FIELD: i32 = 3;
+const REF_PUT_STATIC: i32 = 4;
+const REF_INVOKE_STATIC: i32 = 6;
+const REF_NEW_INVOKE_SPECIAL: i32 = 8;
+
+// java/lang/invoke/MethodHandleNatives : AccessibleObject resolveMember(int, Class, String, String)
+fn resolve_member(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    let kind = args[0].int();
+
+    // Class, name, and descriptor should never be null
+    let class_obj = args[1].object().unwrap();
+    let class_id = class_obj.get_field(0).int();
+    let class = context.class_object_by_id(class_id);
+
+    let name = Context::string_object_to_string(args[2].object().unwrap());
+    let name = JvmString::new(context.gc_ctx(), name);
+
+    let descriptor = Context::string_object_to_string(args[3].object().unwrap());
+    let descriptor = JvmString::new(context.gc_ctx(), descriptor);
+
+    // The member is looked up with both the kind it was requested as and the
+    // opposite kind, so that Java code can report a member that exists but
+    // isn't static (or is) differently from one that doesn't exist. Every
+    // member gets a new object, as handles suppress access checks on them.
+    let member = match kind {
+        REF_GET_FIELD | REF_GET_STATIC | REF_PUT_FIELD | REF_PUT_STATIC => {
+            let Some(descriptor) = Descriptor::try_from_string(context, descriptor) else {
+                return Ok(Some(Value::Object(None)));
+            };
+
+            let key = (name, descriptor);
+
+            let instance_field = class
+                .instance_field_vtable()
+                .lookup(key)
+                .map(|slot| FieldTemplate::for_instance_field(context, class, slot as usize));
+
+            let static_field = class
+                .static_field_vtable()
+                .lookup(key)
+                .map(|slot| FieldTemplate::for_static_field(context, class, slot as usize));
+
+            if kind == REF_GET_STATIC || kind == REF_PUT_STATIC {
+                static_field.or(instance_field)
+            } else {
+                instance_field.or(static_field)
+            }
+            .map(|field| field.object())
+        }
+        _ => {
+            let Ok(descriptor) = MethodDescriptor::from_string(context, descriptor) else {
+                return Ok(Some(Value::Object(None)));
+            };
+
+            let key = (name, descriptor);
+
+            let instance_vtable = class.instance_method_vtable();
+            let instance_method = instance_vtable
+                .lookup(key)
+                .map(|slot| instance_vtable.get_element(slot));
+
+            let static_method = class
+                .static_method_vtable()
+                .lookup(key)
+                .map(|slot| class.get_static_method(slot));
+
+            if kind == REF_NEW_INVOKE_SPECIAL {
+                // Constructors aren't inherited
+                instance_method.filter(|m| m.class() == class)
+            } else if kind == REF_INVOKE_STATIC {
+                static_method.or(instance_method)
+            } else {
+                instance_method.or(static_method)
+            }
+            .map(|method| method.create_object(context))
+        }
+    };
+
+    Ok(Some(Value::Object(member)))
+}
+
+// java/lang/invoke/MethodHandleNatives : Class[] resolveMethodDescriptor(String, ClassLoader)
+fn resolve_method_descriptor(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Descriptor should never be null
+    let descriptor = Context::string_object_to_string(args[0].object().unwrap());
+    let descriptor = JvmString::new(context.gc_ctx(), descriptor);
+
+    // Loader should never be null
+    let class_loader_obj = args[1].object().unwrap();
+    let class_loader_id = class_loader_obj.get_field(0).int();
+    let class_loader = context.class_loader_object_by_id(class_loader_id);
+
+    // Malformed descriptors return `null`; Java code will throw an
+    // `IllegalArgumentException`
+    let Ok(descriptor) = MethodDescriptor::from_string(context, descriptor) else {
+        return Ok(Some(Value::Object(None)));
+    };
+
+    let resolved =
+        ResolvedMethodDescriptor::from_method_descriptor(context, class_loader, descriptor)?;
+
+    // The return type comes first, followed by the parameter types
+    let return_type = resolved.return_type().reflection_class(context);
+    let types = [return_type]
+        .into_iter()
+        .chain(
+            resolved
+                .args()
+                .iter()
+                .map(|arg| arg.reflection_class(context)),
+        )
+        .map(|class| Some(class.get_or_init_object(context)))
+        .collect::<Box<_>>();
+
+    let types = Object::obj_array(context, context.builtins().java_lang_class, types);
+
+    Ok(Some(Value::Object(Some(types))))
+}
+
+// java/lang/invoke/MethodHandleNatives : Object invokeSpecial(Method, Object, Object[])
+fn invoke_special(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Method should never be null
+    let method_obj = args[0].object().unwrap();
+    let method_id = method_obj.get_field(0).int();
+    let method = context.executable_object_by_id(method_id);
+
+    // Receiver and args were checked and converted by Java code
+    let receiver = args[1].object().unwrap();
+
+    let raw_args = args[2].object().unwrap();
+    let raw_args = raw_args.array_data().as_object_array();
+    let args_array = raw_args
+        .iter()
+        .map(|arg| Value::Object(arg.get()))
+        .collect::<Vec<_>>();
+
+    // The method is called as declared, so there may be no implementation
+    if method.flags().contains(MethodFlags::ABSTRACT) {
+        let message = format!(
+            "{}.{}{}",
+            method.class().dot_name(),
+            method.name(),
+            method.descriptor()
+        );
+
+        return Err(context.throw_new("java/lang/AbstractMethodError", &message));
+    }
+
+    let real_args = crate::reflect::args_for_instance_call(method, Some(receiver), &args_array);
+
+    let result = context.exec_method(method, &real_args)?;
+
+    let return_type = method.descriptor().return_type();
+    match (result, return_type) {
+        (None, _) => Ok(Some(Value::Object(None))),
+        (Some(value), Descriptor::Class(_) | Descriptor::Array(_)) => Ok(Some(value)),
+        (Some(value), _) => {
+            let boxed = crate::reflect::box_primitive(context, value, return_type)?;
+
+            Ok(Some(Value::Object(Some(boxed))))
+        }
+    }
+}
@@ -0,0 +1,510 @@
+use crate::platform::{OpenMode, Platform, PlatformError, SeekFrom};
+
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use rjvm_core::{Context, Error, Object, Operation, Value};
+
+pub fn register_native_mappings(context: &Context, platform: Rc<dyn Platform>) {
+    #[rustfmt::skip]
+    register_platform_natives!(context, platform, [
+        instance "java/io/File.internalInitFileData.([B)V" => file_init_data(this: Object, name: Object),
+        instance "java/io/File.exists.()Z" => file_exists(this: Object),
+        instance "java/io/File.isDirectory.()Z" => file_is_directory(this: Object),
+        instance "java/io/File.length.()J" => file_length(this: Object),
+        instance "java/io/File.lastModified.()J" => file_last_modified(this: Object),
+        instance "java/io/File.getAbsolutePath.()Ljava/lang/String;" => file_get_absolute_path(this: Object),
+        instance "java/io/File.getCanonicalPath.()Ljava/lang/String;" => file_get_canonical_path(this: Object),
+        instance "java/io/File.listNative.()[Ljava/lang/String;" => file_list(this: Object),
+        instance "java/io/File.delete.()Z" => file_delete(this: Object),
+        instance "java/io/File.renameToNative.(Ljava/io/File;)Z" => file_rename_to(this: Object, dest: Object),
+
+        instance "java/io/FileOutputStream.writeNative.(I)V" => file_stream_write(this: Object, byte: i32),
+        instance "java/io/FileOutputStream.writeMultipleNative.([BII)V" => file_stream_write_multiple(this: Object, buffer: Object, offset: i32, length: i32),
+        instance "java/io/FileOutputStream.flushNative.()V" => file_stream_flush(this: Object),
+        instance "java/io/FileOutputStream.closeNative.()V" => file_stream_close(this: Object),
+        instance "java/io/FileInputStream.readNative.()I" => file_stream_read(this: Object),
+        instance "java/io/FileInputStream.readMultipleNative.([BII)I" => file_stream_read_multiple(this: Object, buffer: Object, offset: i32, length: i32),
+        instance "java/io/FileInputStream.availableNative.()I" => file_stream_available(this: Object),
+        instance "java/io/FileInputStream.closeNative.()V" => file_stream_close(this: Object),
+
+        static "java/io/FileDescriptor.internalWriteableDescriptorFromPath.(Ljava/lang/String;)I" => writeable_descriptor_from_path(path: Object),
+        static "java/io/FileDescriptor.internalReadableDescriptorFromPath.(Ljava/lang/String;)I" => readable_descriptor_from_path(path: Object),
+    ]);
+}
+
+// The descriptors below this one are the standard streams, which are provided
+// by the `ConsoleBackend` rather than the `Platform`
+const FIRST_FILE_DESCRIPTOR: i32 = 3;
+
+// java/io/File : private void internalInitFileData(byte[])
+fn file_init_data(
+    context: &Context,
+    _platform: &dyn Platform,
+    this: Object,
+    name: Object,
+) -> Result<(), Error> {
+    let name_bytes = name.array_data().as_byte_array();
+
+    let mut file_name = Vec::with_capacity(name_bytes.len());
+    for value in name_bytes {
+        let byte = value.get() as u8;
+        file_name.push(byte);
+    }
+
+    file_name.dedup_by(|a, b| *a == b'/' && *b == b'/');
+
+    let file_name = String::from_utf8_lossy(&file_name);
+
+    let file_name = if file_name == "/" {
+        &file_name
+    } else if let Some(stripped) = file_name.strip_suffix('/') {
+        stripped
+    } else {
+        &file_name
+    };
+
+    this.set_field(0, Value::Object(Some(create_string(context, file_name))));
+
+    Ok(())
+}
+
+// java/io/File : public boolean exists()
+fn file_exists(context: &Context, platform: &dyn Platform, this: Object) -> Result<bool, Error> {
+    let path = check_file(context, platform, Operation::ReadFile, &file_path(this))?;
+
+    Ok(platform.stat(&path).is_some())
+}
+
+// java/io/File : public boolean isDirectory()
+fn file_is_directory(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+) -> Result<bool, Error> {
+    let path = check_file(context, platform, Operation::ReadFile, &file_path(this))?;
+
+    let metadata = platform.stat(&path);
+
+    Ok(metadata.is_some_and(|m| m.is_directory))
+}
+
+// java/io/File : public long length()
+fn file_length(context: &Context, platform: &dyn Platform, this: Object) -> Result<i64, Error> {
+    let path = check_file(context, platform, Operation::ReadFile, &file_path(this))?;
+
+    let metadata = platform.stat(&path);
+
+    Ok(metadata.map_or(0, |m| m.length as i64))
+}
+
+// java/io/File : public long lastModified()
+fn file_last_modified(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+) -> Result<i64, Error> {
+    let path = check_file(context, platform, Operation::ReadFile, &file_path(this))?;
+
+    let metadata = platform.stat(&path);
+
+    Ok(metadata.map_or(0, |m| m.last_modified))
+}
+
+// java/io/File : public String getAbsolutePath()
+fn file_get_absolute_path(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+) -> Result<Object, Error> {
+    let absolute_path = absolute_path(platform, &file_path(this));
+
+    Ok(create_string(context, &absolute_path))
+}
+
+// java/io/File : public String getCanonicalPath()
+fn file_get_canonical_path(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+) -> Result<Object, Error> {
+    let canonical_path = canonical_path(platform, &file_path(this));
+
+    Ok(create_string(context, &canonical_path))
+}
+
+// java/io/File : private String[] listNative()
+fn file_list(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+) -> Result<Option<Object>, Error> {
+    let path = check_file(context, platform, Operation::ReadFile, &file_path(this))?;
+
+    let Ok(names) = platform.list(&path) else {
+        return Ok(None);
+    };
+
+    let names = names
+        .iter()
+        .map(|name| Some(create_string(context, name)))
+        .collect::<Vec<_>>();
+
+    let string_class = context.builtins().java_lang_string;
+    let array = Object::obj_array(context, string_class, names.into_boxed_slice());
+
+    Ok(Some(array))
+}
+
+// java/io/File : public boolean delete()
+fn file_delete(context: &Context, platform: &dyn Platform, this: Object) -> Result<bool, Error> {
+    let path = check_link(context, platform, Operation::DeleteFile, &file_path(this))?;
+
+    Ok(platform.delete(&path).is_ok())
+}
+
+// java/io/File : private boolean renameToNative(File)
+fn file_rename_to(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+    dest: Object,
+) -> Result<bool, Error> {
+    // Moving a file deletes it from its old path
+    let from = check_link(context, platform, Operation::DeleteFile, &file_path(this))?;
+    let to = check_link(context, platform, Operation::WriteFile, &file_path(dest))?;
+
+    Ok(platform.rename(&from, &to).is_ok())
+}
+
+// java/io/FileOutputStream : private void writeNative(int)
+fn file_stream_write(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+    byte: i32,
+) -> Result<(), Error> {
+    write_to_descriptor(context, platform, stream_descriptor(this), &[byte as u8])
+}
+
+// java/io/FileOutputStream : private void writeMultipleNative(byte[], int, int)
+fn file_stream_write_multiple(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+    buffer: Object,
+    offset: i32,
+    length: i32,
+) -> Result<(), Error> {
+    // Java code has already checked these values for us
+    let offset = offset as usize;
+    let length = length as usize;
+
+    let array_data = buffer.array_data().as_byte_array();
+    let write_data = array_data[offset..offset + length]
+        .iter()
+        .map(|b| b.get() as u8)
+        .collect::<Vec<_>>();
+
+    write_to_descriptor(context, platform, stream_descriptor(this), &write_data)
+}
+
+// java/io/FileOutputStream : private void flushNative()
+fn file_stream_flush(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+) -> Result<(), Error> {
+    match stream_descriptor(this) {
+        0 => {
+            // Flushing stdin is a noop
+        }
+        1 => context.console_backend().flush_stdout(),
+        2 => context.console_backend().flush_stderr(),
+        descriptor => platform
+            .flush(file_handle(context, descriptor)?)
+            .map_err(|e| io_exception(context, e))?,
+    }
+
+    Ok(())
+}
+
+// java/io/FileOutputStream : private void closeNative()
+// java/io/FileInputStream : private void closeNative()
+fn file_stream_close(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+) -> Result<(), Error> {
+    let descriptor = stream_descriptor(this);
+
+    // The standard streams stay open for the host
+    if descriptor >= FIRST_FILE_DESCRIPTOR {
+        platform
+            .close(file_handle(context, descriptor)?)
+            .map_err(|e| io_exception(context, e))?;
+    }
+
+    Ok(())
+}
+
+// java/io/FileInputStream : private int readNative()
+fn file_stream_read(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+) -> Result<i32, Error> {
+    let mut read_buf = [0; 1];
+
+    let bytes_read =
+        read_from_descriptor(context, platform, stream_descriptor(this), &mut read_buf)?;
+    if bytes_read == 0 {
+        Ok(-1)
+    } else {
+        Ok(read_buf[0] as i32)
+    }
+}
+
+// java/io/FileInputStream : private int readMultipleNative(byte[], int, int)
+fn file_stream_read_multiple(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+    buffer: Object,
+    offset: i32,
+    length: i32,
+) -> Result<i32, Error> {
+    let offset = offset as usize;
+    let length = length as usize;
+
+    let mut read_buf = vec![0; length];
+
+    let bytes_read =
+        read_from_descriptor(context, platform, stream_descriptor(this), &mut read_buf)?;
+
+    let array_data = buffer.array_data().as_byte_array();
+    for (dest, src) in array_data[offset..].iter().zip(&read_buf[..bytes_read]) {
+        dest.set(*src as i8);
+    }
+
+    if bytes_read == 0 && length != 0 {
+        // End of stream
+        Ok(-1)
+    } else {
+        Ok(bytes_read as i32)
+    }
+}
+
+// java/io/FileInputStream : private int availableNative()
+fn file_stream_available(
+    context: &Context,
+    platform: &dyn Platform,
+    this: Object,
+) -> Result<i32, Error> {
+    let result = match stream_descriptor(this) {
+        0 => context.console_backend().stdin_available() as u64,
+        1 | 2 => 0,
+        descriptor => {
+            let handle = file_handle(context, descriptor)?;
+
+            let seek = |position| {
+                platform
+                    .seek(handle, position)
+                    .map_err(|e| io_exception(context, e))
+            };
+
+            let position = seek(SeekFrom::Current(0))?;
+            let length = seek(SeekFrom::End(0))?;
+            seek(SeekFrom::Start(position))?;
+
+            length.saturating_sub(position)
+        }
+    };
+
+    Ok(result.min(i32::MAX as u64) as i32)
+}
+
+// java/io/FileDescriptor : private static int internalWriteableDescriptorFromPath(String)
+fn writeable_descriptor_from_path(
+    context: &Context,
+    platform: &dyn Platform,
+    path: Object,
+) -> Result<i32, Error> {
+    open_descriptor(context, platform, path, OpenMode::Write)
+}
+
+// java/io/FileDescriptor : private static int internalReadableDescriptorFromPath(String)
+fn readable_descriptor_from_path(
+    context: &Context,
+    platform: &dyn Platform,
+    path: Object,
+) -> Result<i32, Error> {
+    open_descriptor(context, platform, path, OpenMode::Read)
+}
+
+fn open_descriptor(
+    context: &Context,
+    platform: &dyn Platform,
+    path: Object,
+    mode: OpenMode,
+) -> Result<i32, Error> {
+    let path = Context::string_object_to_string(path);
+
+    let operation = match mode {
+        OpenMode::Read => Operation::ReadFile,
+        OpenMode::Write => Operation::WriteFile,
+    };
+    let canonical_path = check_file(context, platform, operation, &path)?;
+
+    if platform
+        .stat(&canonical_path)
+        .is_some_and(|m| m.is_directory)
+    {
+        return Err(file_not_found(context, &path, "Is a directory"));
+    }
+
+    let handle = platform
+        .open(&canonical_path, mode)
+        .map_err(|e| file_not_found(context, &path, e.reason()))?;
+
+    Ok(handle as i32 + FIRST_FILE_DESCRIPTOR)
+}
+
+fn write_to_descriptor(
+    context: &Context,
+    platform: &dyn Platform,
+    descriptor: i32,
+    data: &[u8],
+) -> Result<(), Error> {
+    match descriptor {
+        0 => {
+            // Writing to stdin is a noop
+        }
+        1 => context.console_backend().write_stdout(data),
+        2 => context.console_backend().write_stderr(data),
+        descriptor => platform
+            .write(file_handle(context, descriptor)?, data)
+            .map_err(|e| io_exception(context, e))?,
+    }
+
+    Ok(())
+}
+
+fn read_from_descriptor(
+    context: &Context,
+    platform: &dyn Platform,
+    descriptor: i32,
+    buf: &mut [u8],
+) -> Result<usize, Error> {
+    match descriptor {
+        0 => Ok(context.console_backend().read_stdin(buf)),
+        1 | 2 => Err(context.throw_new(
+            "java/io/IOException",
+            "Standard output streams can't be read",
+        )),
+        descriptor => platform
+            .read(file_handle(context, descriptor)?, buf)
+            .map_err(|e| io_exception(context, e)),
+    }
+}
+
+// The descriptor of the `FileDescriptor` of a `FileInputStream` or
+// `FileOutputStream`.
+fn stream_descriptor(stream: Object) -> i32 {
+    let stream_fd = stream.get_field(0).object().unwrap();
+
+    stream_fd.get_field(0).int()
+}
+
+// The `Platform` handle of a descriptor that isn't a standard stream.
+fn file_handle(context: &Context, descriptor: i32) -> Result<u32, Error> {
+    descriptor
+        .checked_sub(FIRST_FILE_DESCRIPTOR)
+        .and_then(|handle| u32::try_from(handle).ok())
+        .ok_or_else(|| context.throw_new("java/io/IOException", "Invalid file descriptor"))
+}
+
+// The normalized path of a `File`.
+fn file_path(file: Object) -> String {
+    let path_object = file.get_field(0).object().unwrap();
+
+    Context::string_object_to_string(path_object)
+}
+
+fn absolute_path(platform: &dyn Platform, path: &str) -> String {
+    // TODO use correct file separator instead of assuming it must be '/'
+    if path.starts_with('/') {
+        path.to_string()
+    } else if path.is_empty() {
+        platform.current_dir()
+    } else {
+        let current_dir = platform.current_dir();
+
+        format!("{}/{}", current_dir.trim_end_matches('/'), path)
+    }
+}
+
+// The absolute path of `path`, with `.` and `..` components and symbolic
+// links resolved by the platform.
+fn canonical_path(platform: &dyn Platform, path: &str) -> String {
+    platform.canonicalize(&absolute_path(platform, path))
+}
+
+// Check that the security policy allows `operation` on the file at `path`,
+// returning the canonical path that it was checked against. The file must be
+// accessed with the returned path, so that neither `..` nor symbolic links can
+// make the platform access a different file than the one that was checked.
+fn check_file(
+    context: &Context,
+    platform: &dyn Platform,
+    operation: Operation,
+    path: &str,
+) -> Result<String, Error> {
+    let canonical_path = canonical_path(platform, path);
+    context.check_permission(operation, &canonical_path)?;
+
+    Ok(canonical_path)
+}
+
+// Like `check_file`, for operations that act on a symbolic link itself rather
+// than on the file it points to (deleting and renaming). Only the parent
+// directory of the file is resolved.
+fn check_link(
+    context: &Context,
+    platform: &dyn Platform,
+    operation: Operation,
+    path: &str,
+) -> Result<String, Error> {
+    let absolute_path = absolute_path(platform, path);
+
+    let resolved_path = match absolute_path.rsplit_once('/') {
+        Some((parent, name)) if !matches!(name, "" | "." | "..") => {
+            let parent = platform.canonicalize(if parent.is_empty() { "/" } else { parent });
+
+            format!("{}/{}", parent.trim_end_matches('/'), name)
+        }
+        _ => platform.canonicalize(&absolute_path),
+    };
+    context.check_permission(operation, &resolved_path)?;
+
+    Ok(resolved_path)
+}
+
+fn create_string(context: &Context, string: &str) -> Object {
+    let chars = string.encode_utf16().collect::<Vec<_>>();
+
+    context.create_string(&chars)
+}
+
+// Create a `FileNotFoundException` for the file at `path`. Like Java, the
+// message includes the reason the file couldn't be opened.
+fn file_not_found(context: &Context, path: &str, reason: &str) -> Error {
+    context.throw_new(
+        "java/io/FileNotFoundException",
+        &format!("{} ({})", path, reason),
+    )
+}
+
+fn io_exception(context: &Context, error: PlatformError) -> Error {
+    context.throw_new("java/io/IOException", error.reason())
+}
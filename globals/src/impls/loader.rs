@@ -1,6 +1,8 @@
 use alloc::boxed::Box;
 use alloc::vec::Vec;
-use rjvm_core::{Class, ClassLoader, Context, Error, JvmString, NativeMethod, Object, Value};
+use rjvm_core::{
+    Class, ClassLoader, Context, Error, JvmString, NativeMethod, Object, Operation, Value,
+};
 
 pub fn register_native_mappings(context: &Context) {
     #[rustfmt::skip]
@@ -153,6 +155,7 @@ fn define_class_nameless(context: &Context, args: &[Value]) -> Result<Option<Val
 
     let class = Class::from_data(&context, class_loader, &data)?;
 
+    context.check_permission(Operation::DefineClass, &class.name())?;
     class_loader.define_class(context, class)?;
 
     class.run_clinit(context)?;
@@ -187,6 +190,7 @@ fn define_class_named(context: &Context, args: &[Value]) -> Result<Option<Value>
     let class = Class::from_data(&context, class_loader, &data)?;
 
     if *class.name() == class_name {
+        context.check_permission(Operation::DefineClass, &class.name())?;
         class_loader.define_class(context, class)?;
 
         class.run_clinit(context)?;
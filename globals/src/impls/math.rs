@@ -1,48 +1,41 @@
-use rjvm_core::{Context, Error, NativeMethod, Value};
+use rjvm_core::{Context, Error};
 
 pub fn register_native_mappings(context: &Context) {
-    #[rustfmt::skip]
-    let mappings: &[(&str, NativeMethod)] = &[
-        ("java/lang/Math.atan2.(DD)D", math_atan2),
-        ("java/lang/Math.floor.(D)D", math_floor),
-        ("java/lang/Math.log.(D)D", math_log),
-        ("java/lang/Math.pow.(DD)D", math_pow),
-        ("java/lang/Math.sqrt.(D)D", math_sqrt),
-    ];
-
-    context.register_native_mappings(mappings);
+    context
+        .register_static_native("java/lang/Math.atan2.(DD)D", math_atan2)
+        .expect("Valid native");
+    context
+        .register_static_native("java/lang/Math.floor.(D)D", math_floor)
+        .expect("Valid native");
+    context
+        .register_static_native("java/lang/Math.log.(D)D", math_log)
+        .expect("Valid native");
+    context
+        .register_static_native("java/lang/Math.pow.(DD)D", math_pow)
+        .expect("Valid native");
+    context
+        .register_static_native("java/lang/Math.sqrt.(D)D", math_sqrt)
+        .expect("Valid native");
 }
 
-fn math_atan2(_context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
-    let y = args[0].double();
-    let x = args[2].double();
-
+fn math_atan2(_context: &Context, y: f64, x: f64) -> Result<f64, Error> {
     // TODO docs say this has some special-cases
 
-    Ok(Some(Value::Double(libm::atan2(y, x))))
+    Ok(libm::atan2(y, x))
 }
 
-fn math_floor(_context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
-    let value = args[0].double();
-
-    Ok(Some(Value::Double(libm::floor(value))))
+fn math_floor(_context: &Context, value: f64) -> Result<f64, Error> {
+    Ok(libm::floor(value))
 }
 
-fn math_log(_context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
-    let value = args[0].double();
-
-    Ok(Some(Value::Double(libm::log(value))))
+fn math_log(_context: &Context, value: f64) -> Result<f64, Error> {
+    Ok(libm::log(value))
 }
 
-fn math_pow(_context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
-    let base = args[0].double();
-    let exp = args[2].double();
-
-    Ok(Some(Value::Double(libm::pow(base, exp))))
+fn math_pow(_context: &Context, base: f64, exp: f64) -> Result<f64, Error> {
+    Ok(libm::pow(base, exp))
 }
 
-fn math_sqrt(_context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
-    let value = args[0].double();
-
-    Ok(Some(Value::Double(libm::sqrt(value))))
+fn math_sqrt(_context: &Context, value: f64) -> Result<f64, Error> {
+    Ok(libm::sqrt(value))
 }
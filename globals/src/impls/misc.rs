@@ -16,6 +16,8 @@ pub fn register_native_mappings(context: &Context) {
         ("java/nio/charset/Charset.stringToUtf8.(Ljava/lang/String;)[B", string_to_utf8),
         ("java/lang/Double.toString.(D)Ljava/lang/String;", double_to_string),
         ("java/lang/Float.toString.(F)Ljava/lang/String;", float_to_string),
+
+        ("java/security/AccessController.doPrivileged.(Ljava/security/PrivilegedAction;)Ljava/lang/Object;", do_privileged),
     ];
 
     context.register_native_mappings(mappings);
@@ -142,3 +144,26 @@ fn float_to_string(context: &Context, args: &[Value]) -> Result<Option<Value>, E
 
     Ok(Some(Value::Object(Some(context.create_string(&chars)))))
 }
+
+// java/security/AccessController : static Object doPrivileged(PrivilegedAction)
+fn do_privileged(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    let Some(action) = args[0].object() else {
+        return Err(context.null_pointer_exception());
+    };
+
+    // Only code loaded by the bootstrap loader may ignore the security policy.
+    // Depth 0 is this method and depth 1 is the method that called it.
+    let is_trusted = context
+        .caller_method(1)
+        .is_some_and(|caller| caller.class().loader() == Some(context.bootstrap_loader()));
+
+    let run = || action.call_virtual::<Option<Object>>(context, "run", "()Ljava/lang/Object;", ());
+
+    let result = if is_trusted {
+        context.run_privileged(run)?
+    } else {
+        run()?
+    };
+
+    Ok(Some(Value::Object(result)))
+}
@@ -1,4 +1,46 @@
+use crate::platform::Platform;
+
+use alloc::rc::Rc;
+use rjvm_core::Trace;
+
+// The platform, passed to the natives registered by `register_platform_natives`
+pub(crate) struct PlatformRef(pub(crate) Rc<dyn Platform>);
+
+// Platforms live outside of the JVM, so they can't hold any GC pointers
+impl Trace for PlatformRef {
+    fn trace(&self) {}
+}
+
+// Register a table of native methods, each implemented by a function that
+// takes the `Platform` after the `Context`, followed by the arguments of the
+// method (starting with the receiver, for `instance` methods).
+macro_rules! register_platform_natives {
+    ($context:expr, $platform:expr, [$($kind:ident $name:literal => $function:ident($($arg:ident: $ty:ty),*)),* $(,)?]) => {
+        $(register_platform_natives!(@$kind $context, $platform, $name, $function($($arg: $ty),*));)*
+    };
+    (@static $context:expr, $platform:expr, $name:literal, $function:ident($($arg:ident: $ty:ty),*)) => {
+        register_platform_natives!(@register register_static_native_with, $context, $platform, $name, $function($($arg: $ty),*))
+    };
+    (@instance $context:expr, $platform:expr, $name:literal, $function:ident($($arg:ident: $ty:ty),*)) => {
+        register_platform_natives!(@register register_instance_native_with, $context, $platform, $name, $function($($arg: $ty),*))
+    };
+    (@register $register:ident, $context:expr, $platform:expr, $name:literal, $function:ident($($arg:ident: $ty:ty),*)) => {{
+        let platform = $crate::impls::PlatformRef($platform.clone());
+        $context
+            .$register(
+                $name,
+                platform,
+                |context: &Context, platform: &$crate::impls::PlatformRef, $($arg: $ty),*| {
+                    $function(context, &*platform.0, $($arg),*)
+                },
+            )
+            .expect("Valid native");
+    }};
+}
+
 pub(crate) mod field_access;
+pub(crate) mod invoke;
+pub(crate) mod io;
 pub(crate) mod loader;
 pub(crate) mod math;
 pub(crate) mod misc;
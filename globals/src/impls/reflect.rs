@@ -1,8 +1,10 @@
 use alloc::boxed::Box;
+use alloc::format;
 use alloc::vec::Vec;
+use rjvm_core::classfile::attribute::AttributeInfo;
 use rjvm_core::{
-    Context, Descriptor, Error, FieldTemplate, JvmString, MethodFlags, NativeMethod, Object,
-    PrimitiveType, ResolvedDescriptor, Value,
+    ClassLoader, Context, Descriptor, Error, FieldTemplate, JvmString, MethodDescriptor,
+    MethodFlags, NativeMethod, Object, PrimitiveType, ResolvedDescriptor, Value,
 };
 
 pub fn register_native_mappings(context: &Context) {
@@ -39,6 +41,22 @@ pub fn register_native_mappings(context: &Context) {
         ("java/lang/reflect/Field.getName.()Ljava/lang/String;", field_get_name),
         ("java/lang/reflect/Field.getType.()Ljava/lang/Class;", field_get_type),
         ("java/lang/reflect/Field.getModifiers.()I", field_get_modifiers),
+        ("java/lang/Class.getDeclaredAnnotationsNative.()[Ljava/lang/annotation/Annotation;", class_get_declared_annotations),
+        ("java/lang/reflect/Executable.getDeclaredAnnotationsNative.()[Ljava/lang/annotation/Annotation;", exec_get_declared_annotations),
+        ("java/lang/reflect/Executable.getParameterAnnotationsNative.()[[Ljava/lang/annotation/Annotation;", exec_get_parameter_annotations),
+        ("java/lang/reflect/Field.getDeclaredAnnotationsNative.()[Ljava/lang/annotation/Annotation;", field_get_declared_annotations),
+        ("java/lang/reflect/Method.getDefaultValue.()Ljava/lang/Object;", method_get_default_value),
+        ("java/lang/Class.getGenericSignatureNative.()Ljava/lang/String;", class_get_generic_signature),
+        ("java/lang/reflect/Executable.getGenericSignatureNative.()Ljava/lang/String;", exec_get_generic_signature),
+        ("java/lang/reflect/Field.getGenericSignatureNative.()Ljava/lang/String;", field_get_generic_signature),
+        ("java/lang/Class.getNestHost.()Ljava/lang/Class;", class_get_nest_host),
+        ("java/lang/Class.getRecordComponentsNative.()[Ljava/lang/reflect/RecordComponent;", class_get_record_components),
+        ("java/lang/Class.getPermittedSubclassesNative.()[Ljava/lang/Class;", class_get_permitted_subclasses),
+        ("java/lang/reflect/Method.getExceptionTypes.()[Ljava/lang/Class;", exec_get_exception_types),
+        ("java/lang/reflect/Constructor.getExceptionTypes.()[Ljava/lang/Class;", exec_get_exception_types),
+        ("java/lang/reflect/Proxy.defineProxyClass.(Ljava/lang/ClassLoader;Ljava/lang/String;[Ljava/lang/Class;)Ljava/lang/Class;", proxy_define_proxy_class),
+        ("java/lang/reflect/Proxy.newProxyInstanceNative.(Ljava/lang/Class;Ljava/lang/reflect/InvocationHandler;)Ljava/lang/Object;", proxy_new_proxy_instance),
+        ("rjvm/internal/Reflection.getCallerClass.()Ljava/lang/Class;", get_caller_class),
     ];
 
     context.register_native_mappings(mappings);
@@ -147,6 +165,7 @@ fn new_instance_native(context: &Context, args: &[Value]) -> Result<Option<Value
     let ctor_id = ctor_obj.get_field(0).int();
     let ctor_method = context.executable_object_by_id(ctor_id);
 
+    // Args were checked and converted by Java code
     let raw_args = args[1].object().unwrap();
     let raw_args = raw_args.array_data().as_object_array();
 
@@ -156,21 +175,15 @@ fn new_instance_native(context: &Context, args: &[Value]) -> Result<Option<Value
         args_array.push(Value::Object(raw_args[i].get()));
     }
 
-    if ctor_method.class().cant_instantiate() {
-        return Err(context.instantiation_exception());
-    }
-
     let instance = Object::from_class(context.gc_ctx(), ctor_method.class());
 
     let real_args =
-        crate::reflect::args_for_instance_call(context, ctor_method, Some(instance), &args_array)?;
+        crate::reflect::args_for_instance_call(ctor_method, Some(instance), &args_array);
 
-    if let Err(e) = context.exec_method(ctor_method, &real_args) {
-        // FIXME this should throw `InvocationTargetException`
-        Err(e)
-    } else {
-        Ok(Some(Value::Object(Some(instance))))
-    }
+    // Exceptions thrown by the constructor are wrapped by Java code
+    context.exec_method(ctor_method, &real_args)?;
+
+    Ok(Some(Value::Object(Some(instance))))
 }
 
 fn exec_get_parameter_types(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
@@ -197,6 +210,37 @@ fn exec_get_parameter_types(context: &Context, args: &[Value]) -> Result<Option<
     Ok(Some(Value::Object(Some(created_array))))
 }
 
+fn exec_get_exception_types(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let exec_obj = args[0].object().unwrap();
+    let exec_id = exec_obj.get_field(0).int();
+    let method = context.executable_object_by_id(exec_id);
+
+    let attribute = crate::reflect::method_attribute(context, method, "Exceptions")?;
+
+    let mut exception_classes = Vec::new();
+    if let (Some(loader), Some(AttributeInfo::Exceptions(class_names))) =
+        (method.class().loader(), attribute)
+    {
+        for class_name in class_names {
+            exception_classes.push(loader.lookup_class(context, class_name)?);
+        }
+    }
+
+    let resulting_classes = exception_classes
+        .iter()
+        .map(|c| Some(c.get_or_init_object(context)))
+        .collect::<Box<[_]>>();
+
+    let created_array = Object::obj_array(
+        context,
+        context.builtins().java_lang_class,
+        resulting_classes,
+    );
+
+    Ok(Some(Value::Object(Some(created_array))))
+}
+
 fn method_get_return_type(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
     // Receiver should never be null
     let exec_obj = args[0].object().unwrap();
@@ -397,6 +441,7 @@ fn invoke_native(context: &Context, args: &[Value]) -> Result<Option<Value>, Err
     let method_id = method_obj.get_field(0).int();
     let method = context.executable_object_by_id(method_id);
 
+    // Receiver and args were checked and converted by Java code
     let receiver_arg = args[1].object();
 
     let raw_args = args[2].object().unwrap();
@@ -408,45 +453,39 @@ fn invoke_native(context: &Context, args: &[Value]) -> Result<Option<Value>, Err
         args_array.push(Value::Object(raw_args[i].get()));
     }
 
-    let receiver_arg = if !method.is_static() {
-        if let Some(receiver_arg) = receiver_arg {
-            // TODO verify that receiver matches class
-            Some(receiver_arg)
-        } else {
-            // Receiver cannot be null
-            return Err(context.null_pointer_exception());
-        }
+    let (method, receiver_arg) = if method.is_static() {
+        (method, None)
     } else {
-        None
+        let receiver_arg = receiver_arg.unwrap();
+
+        // Non-private instance methods are dispatched on the receiver's class
+        let method = if method.flags().contains(MethodFlags::PRIVATE) {
+            method
+        } else {
+            let vtable = receiver_arg.class().instance_method_vtable();
+            let index = vtable.lookup((method.name(), method.descriptor()));
+
+            index.map_or(method, |i| vtable.get_element(i))
+        };
+
+        (method, Some(receiver_arg))
     };
 
-    let real_args =
-        crate::reflect::args_for_instance_call(context, method, receiver_arg, &args_array)?;
-
-    match context.exec_method(method, &real_args) {
-        Ok(Some(value)) => {
-            let return_type = method.descriptor().return_type();
-            match return_type {
-                Descriptor::Class(_) | Descriptor::Array(_) => {
-                    // This method returns pointer type, just return the value
-                    Ok(Some(value))
-                }
-                Descriptor::Void => {
-                    unreachable!("Condition handled below")
-                }
-                _ => {
-                    // TODO implement boxing
-                    unimplemented!("Boxing method return value");
-                }
-            }
-        }
-        Ok(None) => {
-            // Return `null` if this is a void method
-            Ok(Some(Value::Object(None)))
-        }
-        Err(e) => {
-            // FIXME this should throw `InvocationTargetException`
-            Err(e)
+    let real_args = crate::reflect::args_for_instance_call(method, receiver_arg, &args_array);
+
+    // Exceptions thrown by the method are wrapped by Java code
+    let result = context.exec_method(method, &real_args)?;
+
+    let return_type = method.descriptor().return_type();
+    match (result, return_type) {
+        // Return `null` if this is a void method
+        (None, _) => Ok(Some(Value::Object(None))),
+        (Some(value), Descriptor::Class(_) | Descriptor::Array(_)) => Ok(Some(value)),
+        (Some(value), _) => {
+            // Primitive return values are boxed
+            let boxed = crate::reflect::box_primitive(context, value, return_type)?;
+
+            Ok(Some(Value::Object(Some(boxed))))
         }
     }
 }
@@ -676,3 +715,384 @@ fn field_get_modifiers(context: &Context, args: &[Value]) -> Result<Option<Value
 
     Ok(Some(Value::Integer(field.modifiers() as i32)))
 }
+
+// Create an `Annotation[]` from the contents of a `RuntimeVisibleAnnotations`
+// attribute, or an empty array if the attribute wasn't present.
+fn annotations_from_attribute(
+    context: &Context,
+    loader: Option<ClassLoader>,
+    attribute: Option<AttributeInfo>,
+) -> Result<Object, Error> {
+    let annotations = match (loader, attribute) {
+        (Some(loader), Some(AttributeInfo::RuntimeVisibleAnnotations(annotations))) => {
+            Some((loader, annotations))
+        }
+        _ => None,
+    };
+
+    if let Some((loader, annotations)) = annotations {
+        let types = crate::annotation::prepare_annotations(context, loader, &annotations)?;
+
+        crate::annotation::create_annotation_array(context, loader, &annotations, &types)
+    } else {
+        let annotation_class = crate::annotation::annotation_class(context)?;
+
+        Ok(Object::obj_array(context, annotation_class, Box::new([])))
+    }
+}
+
+fn class_get_declared_annotations(
+    context: &Context,
+    args: &[Value],
+) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let class_obj = args[0].object().unwrap();
+    let class_id = class_obj.get_field(0).int();
+    let class = context.class_object_by_id(class_id);
+
+    let attribute = crate::reflect::class_attribute(context, class, "RuntimeVisibleAnnotations")?;
+    let array = annotations_from_attribute(context, class.loader(), attribute)?;
+
+    Ok(Some(Value::Object(Some(array))))
+}
+
+fn exec_get_declared_annotations(
+    context: &Context,
+    args: &[Value],
+) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let exec_obj = args[0].object().unwrap();
+    let exec_id = exec_obj.get_field(0).int();
+    let method = context.executable_object_by_id(exec_id);
+
+    let attribute = crate::reflect::method_attribute(context, method, "RuntimeVisibleAnnotations")?;
+    let array = annotations_from_attribute(context, method.class().loader(), attribute)?;
+
+    Ok(Some(Value::Object(Some(array))))
+}
+
+fn exec_get_parameter_annotations(
+    context: &Context,
+    args: &[Value],
+) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let exec_obj = args[0].object().unwrap();
+    let exec_id = exec_obj.get_field(0).int();
+    let method = context.executable_object_by_id(exec_id);
+
+    let param_count = method.descriptor().args().len();
+
+    let attribute =
+        crate::reflect::method_attribute(context, method, "RuntimeVisibleParameterAnnotations")?;
+    let parameter_annotations = match (method.class().loader(), attribute) {
+        (Some(loader), Some(AttributeInfo::RuntimeVisibleParameterAnnotations(annotations))) => {
+            Some((loader, annotations))
+        }
+        _ => None,
+    };
+
+    let annotation_class = crate::annotation::annotation_class(context)?;
+    let annotation_array_class =
+        ClassLoader::array_class_for(context, ResolvedDescriptor::Class(annotation_class));
+
+    let mut result = Vec::with_capacity(param_count);
+    if let Some((loader, parameter_annotations)) = parameter_annotations {
+        // Load everything needed for all parameters before creating any
+        // objects, so that no Java code runs in between
+        let mut all_types = Vec::with_capacity(parameter_annotations.len());
+        for annotations in parameter_annotations.iter() {
+            all_types.push(crate::annotation::prepare_annotations(
+                context,
+                loader,
+                annotations,
+            )?);
+        }
+
+        // Some compilers leave out synthetic parameters (such as the outer
+        // instance of an inner class) from the attribute; they have no
+        // annotations
+        let missing = param_count.saturating_sub(parameter_annotations.len());
+        for _ in 0..missing {
+            result.push(Some(Object::obj_array(
+                context,
+                annotation_class,
+                Box::new([]),
+            )));
+        }
+
+        for (annotations, types) in parameter_annotations.iter().zip(all_types) {
+            let array =
+                crate::annotation::create_annotation_array(context, loader, annotations, &types)?;
+            result.push(Some(array));
+        }
+    } else {
+        for _ in 0..param_count {
+            result.push(Some(Object::obj_array(
+                context,
+                annotation_class,
+                Box::new([]),
+            )));
+        }
+    }
+
+    let array = Object::obj_array(context, annotation_array_class, result.into_boxed_slice());
+
+    Ok(Some(Value::Object(Some(array))))
+}
+
+fn field_get_declared_annotations(
+    context: &Context,
+    args: &[Value],
+) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let field_obj = args[0].object().unwrap();
+    let field_id = field_obj.get_field(0).int();
+    let field = context.field_object_by_id(field_id);
+
+    let attribute = crate::reflect::field_attribute(context, field, "RuntimeVisibleAnnotations")?;
+    let array = annotations_from_attribute(context, field.defining_class().loader(), attribute)?;
+
+    Ok(Some(Value::Object(Some(array))))
+}
+
+fn method_get_default_value(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let exec_obj = args[0].object().unwrap();
+    let exec_id = exec_obj.get_field(0).int();
+    let method = context.executable_object_by_id(exec_id);
+
+    let attribute = crate::reflect::method_attribute(context, method, "AnnotationDefault")?;
+
+    let default_value = match (method.class().loader(), attribute) {
+        (Some(loader), Some(AttributeInfo::AnnotationDefault(value))) => Some((loader, value)),
+        _ => None,
+    };
+
+    let Some((loader, value)) = default_value else {
+        return Ok(Some(Value::Object(None)));
+    };
+
+    let return_type = method.descriptor().return_type();
+
+    crate::annotation::prepare_element_value(context, loader, &value, return_type)?;
+    let object = crate::annotation::create_element_value(context, loader, &value, return_type)?;
+
+    Ok(Some(Value::Object(object)))
+}
+
+// Create a `String` from the contents of a `Signature` attribute, or `null` if
+// the attribute wasn't present.
+fn signature_from_attribute(context: &Context, attribute: Option<AttributeInfo>) -> Value {
+    if let Some(AttributeInfo::Signature(signature)) = attribute {
+        Value::Object(Some(context.str_to_string(&signature)))
+    } else {
+        Value::Object(None)
+    }
+}
+
+fn class_get_generic_signature(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let class_obj = args[0].object().unwrap();
+    let class_id = class_obj.get_field(0).int();
+    let class = context.class_object_by_id(class_id);
+
+    let attribute = crate::reflect::class_attribute(context, class, "Signature")?;
+
+    Ok(Some(signature_from_attribute(context, attribute)))
+}
+
+fn exec_get_generic_signature(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let exec_obj = args[0].object().unwrap();
+    let exec_id = exec_obj.get_field(0).int();
+    let method = context.executable_object_by_id(exec_id);
+
+    let attribute = crate::reflect::method_attribute(context, method, "Signature")?;
+
+    Ok(Some(signature_from_attribute(context, attribute)))
+}
+
+fn field_get_generic_signature(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let field_obj = args[0].object().unwrap();
+    let field_id = field_obj.get_field(0).int();
+    let field = context.field_object_by_id(field_id);
+
+    let attribute = crate::reflect::field_attribute(context, field, "Signature")?;
+
+    Ok(Some(signature_from_attribute(context, attribute)))
+}
+
+fn class_get_nest_host(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let class_obj = args[0].object().unwrap();
+    let class_id = class_obj.get_field(0).int();
+    let class = context.class_object_by_id(class_id);
+
+    let nest_host = class.nest_host(context);
+
+    Ok(Some(Value::Object(Some(
+        nest_host.get_or_init_object(context),
+    ))))
+}
+
+// Returns the components of a record class, or `null` if the class has no
+// `Record` attribute.
+fn class_get_record_components(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let class_obj = args[0].object().unwrap();
+    let class_id = class_obj.get_field(0).int();
+    let class = context.class_object_by_id(class_id);
+
+    let attribute = crate::reflect::class_attribute(context, class, "Record")?;
+    let Some(AttributeInfo::Record(components)) = attribute else {
+        return Ok(Some(Value::Object(None)));
+    };
+
+    // Classes with a class file always have a loader
+    let loader = class.loader().unwrap();
+
+    let component_class_name =
+        JvmString::new(context.gc_ctx(), "java/lang/reflect/RecordComponent".into());
+    let component_class = context
+        .bootstrap_loader()
+        .lookup_class(context, component_class_name)?;
+
+    let mut result = Vec::with_capacity(components.len());
+    for component in &*components {
+        let Some(descriptor) = Descriptor::try_from_string(context, component.descriptor) else {
+            return Err(context.class_format_error("Invalid record component descriptor"));
+        };
+
+        let component_type = ResolvedDescriptor::from_descriptor(context, loader, descriptor)?
+            .reflection_class(context);
+
+        // The accessor is the method with the name of the component that
+        // takes no arguments and returns its type
+        let accessor_descriptor =
+            JvmString::new(context.gc_ctx(), format!("(){}", &*component.descriptor));
+        let accessor_descriptor = MethodDescriptor::from_string(context, accessor_descriptor)?;
+
+        let vtable = class.instance_method_vtable();
+        let accessor = vtable
+            .lookup((component.name, accessor_descriptor))
+            .map(|slot| vtable.get_element(slot))
+            .filter(|method| method.class() == class)
+            .map(|method| method.get_or_init_object(context));
+
+        let object = Object::from_class(context.gc_ctx(), component_class);
+        object.set_field(0, Value::Object(Some(class_obj)));
+        object.set_field(
+            1,
+            Value::Object(Some(context.str_to_string(&component.name))),
+        );
+        object.set_field(
+            2,
+            Value::Object(Some(component_type.get_or_init_object(context))),
+        );
+        object.set_field(3, Value::Object(accessor));
+
+        result.push(Some(object));
+    }
+
+    let created_array = Object::obj_array(context, component_class, result.into_boxed_slice());
+
+    Ok(Some(Value::Object(Some(created_array))))
+}
+
+// Returns the classes listed by the `PermittedSubclasses` attribute of a
+// class, or `null` if the class isn't sealed.
+fn class_get_permitted_subclasses(
+    context: &Context,
+    args: &[Value],
+) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let class_obj = args[0].object().unwrap();
+    let class_id = class_obj.get_field(0).int();
+    let class = context.class_object_by_id(class_id);
+
+    let attribute = crate::reflect::class_attribute(context, class, "PermittedSubclasses")?;
+    let Some(AttributeInfo::PermittedSubclasses(names)) = attribute else {
+        return Ok(Some(Value::Object(None)));
+    };
+
+    // Classes with a class file always have a loader
+    let loader = class.loader().unwrap();
+
+    // Subclasses that can't be loaded are left out
+    let subclasses = names
+        .iter()
+        .filter_map(|name| loader.lookup_class(context, *name).ok())
+        .map(|subclass| Some(subclass.get_or_init_object(context)))
+        .collect::<Box<_>>();
+
+    let created_array = Object::obj_array(context, context.builtins().java_lang_class, subclasses);
+
+    Ok(Some(Value::Object(Some(created_array))))
+}
+
+// rjvm/internal/Reflection : Class getCallerClass()
+fn get_caller_class(context: &Context, _args: &[Value]) -> Result<Option<Value>, Error> {
+    // Depth 0 is this method and depth 1 is the method that called it
+    let caller = context.caller_method(2);
+
+    if let Some(caller) = caller {
+        let object = caller.class().get_or_init_object(context);
+
+        Ok(Some(Value::Object(Some(object))))
+    } else {
+        Ok(Some(Value::Object(None)))
+    }
+}
+
+fn proxy_define_proxy_class(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // A null loader means the bootstrap loader
+    let loader = args[0]
+        .object()
+        .map(|o| {
+            let loader_id = o.get_field(0).int();
+
+            context.class_loader_object_by_id(loader_id)
+        })
+        .unwrap_or(context.bootstrap_loader());
+
+    // Name was created by Java code and is never null
+    let name = args[1].object().unwrap();
+    let name = Context::string_object_to_string(name).replace('.', "/");
+    let name = JvmString::new(context.gc_ctx(), name);
+
+    // Interfaces were checked by Java code
+    let interfaces = args[2].object().unwrap();
+    let interfaces = interfaces
+        .array_data()
+        .as_object_array()
+        .iter()
+        .map(|o| {
+            let class_id = o.get().unwrap().get_field(0).int();
+
+            context.class_object_by_id(class_id)
+        })
+        .collect::<Vec<_>>();
+
+    let proxy_class = crate::proxy::define_proxy_class(context, loader, name, &interfaces)?;
+
+    Ok(Some(Value::Object(Some(
+        proxy_class.get_or_init_object(context),
+    ))))
+}
+
+fn proxy_new_proxy_instance(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Class was created by `defineProxyClass` and is never null
+    let class_obj = args[0].object().unwrap();
+    let class_id = class_obj.get_field(0).int();
+    let proxy_class = context.class_object_by_id(class_id);
+
+    proxy_class.run_clinit(context)?;
+
+    let instance = Object::from_class(context.gc_ctx(), proxy_class);
+
+    // Set the `h` field; the handler was null-checked by Java code
+    instance.set_field(0, args[1]);
+
+    Ok(Some(Value::Object(Some(instance))))
+}
@@ -1,9 +1,13 @@
+use crate::platform::Platform;
+
 use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::ToString;
 use alloc::vec::Vec;
 use core::cell::Cell;
-use rjvm_core::{Array, Context, Error, NativeMethod, Value};
+use rjvm_core::{Array, Context, Error, NativeMethod, Object, Operation, Value};
 
-pub fn register_native_mappings(context: &Context) {
+pub fn register_native_mappings(context: &Context, platform: Rc<dyn Platform>) {
     #[rustfmt::skip]
     let mappings: &[(&str, NativeMethod)] = &[
         ("java/lang/System.arraycopy.(Ljava/lang/Object;ILjava/lang/Object;II)V", array_copy),
@@ -11,6 +15,62 @@ pub fn register_native_mappings(context: &Context) {
     ];
 
     context.register_native_mappings(mappings);
+
+    #[rustfmt::skip]
+    register_platform_natives!(context, platform, [
+        static "java/lang/System.currentTimeMillis.()J" => current_time_millis(),
+        static "java/lang/System.nanoTime.()J" => nano_time(),
+        static "java/lang/System.getenv.(Ljava/lang/String;)Ljava/lang/String;" => getenv(name: Object),
+        static "java/lang/System.currentDirectory.()Ljava/lang/String;" => current_directory(),
+        instance "java/lang/Runtime.exit.(I)V" => runtime_exit(this: Object, status: i32),
+    ]);
+}
+
+// java/lang/System : static long currentTimeMillis()
+fn current_time_millis(_context: &Context, platform: &dyn Platform) -> Result<i64, Error> {
+    Ok(platform.current_time_millis())
+}
+
+// java/lang/System : static long nanoTime()
+fn nano_time(_context: &Context, platform: &dyn Platform) -> Result<i64, Error> {
+    Ok(platform.nano_time())
+}
+
+// java/lang/System : static String getenv(String)
+fn getenv(
+    context: &Context,
+    platform: &dyn Platform,
+    name: Object,
+) -> Result<Option<Object>, Error> {
+    let name = Context::string_object_to_string(name);
+    context.check_permission(Operation::ReadEnvironment, &name)?;
+
+    let value = platform.env_var(&name).map(|value| {
+        let chars = value.encode_utf16().collect::<Vec<_>>();
+
+        context.create_string(&chars)
+    });
+
+    Ok(value)
+}
+
+// java/lang/System : private static String currentDirectory()
+fn current_directory(context: &Context, platform: &dyn Platform) -> Result<Object, Error> {
+    let chars = platform.current_dir().encode_utf16().collect::<Vec<_>>();
+
+    Ok(context.create_string(&chars))
+}
+
+// java/lang/Runtime : void exit(int)
+fn runtime_exit(
+    context: &Context,
+    platform: &dyn Platform,
+    _this: Object,
+    status: i32,
+) -> Result<(), Error> {
+    context.check_permission(Operation::Exit, &status.to_string())?;
+
+    platform.exit(status)
 }
 
 // java/lang/System: static void arraycopy(Object, int, Object, int, int)
@@ -6,10 +6,14 @@
 #[macro_use]
 extern crate alloc;
 
+pub(crate) mod annotation;
 pub(crate) mod hash_code;
 pub(crate) mod impls;
 pub mod native_impl;
+pub mod platform;
+pub(crate) mod proxy;
 pub(crate) mod reflect;
+pub mod vfs;
 
 pub const GLOBALS_BASE_JAR: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/classes-base.jar"));
 pub const GLOBALS_DESKTOP_JAR: &[u8] =
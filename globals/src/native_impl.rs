@@ -1,12 +1,19 @@
+use crate::platform::Platform;
+
+use alloc::rc::Rc;
 use rjvm_core::Context;
 
 // Native implementations of functions declared in globals
 
-pub fn register_native_mappings(context: &Context) {
+/// Register the native methods of the classes in globals. Natives that depend
+/// on the environment, such as those for files and clocks, call `platform`.
+pub fn register_native_mappings(context: &Context, platform: Rc<dyn Platform>) {
     crate::impls::field_access::register_native_mappings(context);
+    crate::impls::invoke::register_native_mappings(context);
+    crate::impls::io::register_native_mappings(context, platform.clone());
     crate::impls::loader::register_native_mappings(context);
     crate::impls::math::register_native_mappings(context);
     crate::impls::misc::register_native_mappings(context);
     crate::impls::reflect::register_native_mappings(context);
-    crate::impls::system::register_native_mappings(context);
+    crate::impls::system::register_native_mappings(context, platform);
 }
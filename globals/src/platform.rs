@@ -0,0 +1,173 @@
+// The interface between the natives in this crate and the environment the VM
+// runs in. Each frontend implements `Platform` once, and passes it to
+// `native_impl::register_native_mappings`.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// An error returned by a [`Platform`] operation.
+///
+/// The reason is used in the message of the exception thrown to Java, like
+/// the OS's description of an error (e.g. `No such file or directory`).
+#[derive(Clone, Debug)]
+pub struct PlatformError {
+    reason: String,
+}
+
+impl PlatformError {
+    pub fn new(reason: &str) -> Self {
+        Self {
+            reason: reason.to_string(),
+        }
+    }
+
+    /// The error for a file that doesn't exist.
+    pub fn not_found() -> Self {
+        Self::new("No such file or directory")
+    }
+
+    /// The error for an operation that the platform doesn't support.
+    pub fn unsupported() -> Self {
+        Self::new("Operation not supported")
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+/// How a file is opened by [`Platform::open`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpenMode {
+    /// Open an existing file for reading.
+    Read,
+
+    /// Open a file for writing, creating it if it doesn't exist and
+    /// truncating it if it does.
+    Write,
+}
+
+/// A position in a file, as passed to [`Platform::seek`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SeekFrom {
+    Start(u64),
+    End(i64),
+    Current(i64),
+}
+
+/// Information about a file, as returned by [`Platform::stat`].
+#[derive(Clone, Copy, Debug)]
+pub struct FileMetadata {
+    pub is_directory: bool,
+
+    /// The size of the file in bytes.
+    pub length: u64,
+
+    /// The time the file was last modified, in milliseconds since the epoch.
+    pub last_modified: i64,
+}
+
+/// The environment the VM runs in: its files, clocks, environment variables,
+/// and process.
+///
+/// Files are referred to by the handles returned by [`Platform::open`]. The
+/// standard streams aren't files, and are provided by the `ConsoleBackend` of
+/// the `Context` instead.
+///
+/// Only the clock and [`Platform::exit`] must be implemented. By default, the
+/// platform has no files (opening any file fails with
+/// [`PlatformError::not_found`]), no environment variables, and its working
+/// directory is `/`.
+pub trait Platform {
+    /// Open the file at `path`, returning a handle to it.
+    fn open(&self, _path: &str, _mode: OpenMode) -> Result<u32, PlatformError> {
+        Err(PlatformError::not_found())
+    }
+
+    /// Read bytes from the file into `buf`, returning the number of bytes
+    /// read, or 0 at the end of the file.
+    fn read(&self, _handle: u32, _buf: &mut [u8]) -> Result<usize, PlatformError> {
+        Err(PlatformError::unsupported())
+    }
+
+    /// Write all of `data` to the file.
+    fn write(&self, _handle: u32, _data: &[u8]) -> Result<(), PlatformError> {
+        Err(PlatformError::unsupported())
+    }
+
+    fn flush(&self, _handle: u32) -> Result<(), PlatformError> {
+        Ok(())
+    }
+
+    /// Move the position of the file, returning the new position from the
+    /// start of the file.
+    fn seek(&self, _handle: u32, _position: SeekFrom) -> Result<u64, PlatformError> {
+        Err(PlatformError::unsupported())
+    }
+
+    /// Close the file. The handle isn't used again afterward.
+    fn close(&self, _handle: u32) -> Result<(), PlatformError> {
+        Ok(())
+    }
+
+    /// Get information about the file at `path`, or `None` if it doesn't
+    /// exist.
+    fn stat(&self, _path: &str) -> Option<FileMetadata> {
+        None
+    }
+
+    /// List the names of the files in the directory at `path`.
+    fn list(&self, _path: &str) -> Result<Vec<String>, PlatformError> {
+        Err(PlatformError::not_found())
+    }
+
+    /// Delete the file or empty directory at `path`.
+    fn delete(&self, _path: &str) -> Result<(), PlatformError> {
+        Err(PlatformError::not_found())
+    }
+
+    fn rename(&self, _from: &str, _to: &str) -> Result<(), PlatformError> {
+        Err(PlatformError::not_found())
+    }
+
+    /// The current time, in milliseconds since the epoch.
+    fn current_time_millis(&self) -> i64;
+
+    /// The value of a monotonic clock in nanoseconds, which is only meaningful
+    /// compared to other values returned by this method.
+    fn nano_time(&self) -> i64 {
+        self.current_time_millis() * 1_000_000
+    }
+
+    /// Exit the process with the given status code.
+    fn exit(&self, code: i32) -> !;
+
+    fn env_var(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// The absolute path of the working directory.
+    fn current_dir(&self) -> String {
+        "/".to_string()
+    }
+
+    /// The path of the file that the absolute path `path` refers to, with `.`
+    /// and `..` components and symbolic links resolved. Like `realpath`, a
+    /// link is followed before the `..` components after it are applied. The
+    /// file at `path` may not exist yet. By default, the platform has no
+    /// links, so `path` is resolved as text.
+    fn canonicalize(&self, path: &str) -> String {
+        let mut components = Vec::new();
+        for component in path.split('/') {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    components.pop();
+                }
+                component => components.push(component),
+            }
+        }
+
+        format!("/{}", components.join("/"))
+    }
+}
@@ -0,0 +1,135 @@
+// Support for `java.lang.reflect.Proxy`. Proxy classes are synthesized classes
+// that extend `Proxy`; all of their interface methods are implemented by the
+// native `proxy_method`, which boxes the arguments and passes them to the
+// `InvocationHandler` stored in the proxy instance.
+
+use alloc::vec::Vec;
+use rjvm_core::{Class, ClassLoader, Context, Descriptor, Error, JvmString, Method, Object, Value};
+
+// Create a proxy class named `name` implementing `interfaces`, and register
+// it in `loader`.
+pub(crate) fn define_proxy_class(
+    context: &Context,
+    loader: ClassLoader,
+    name: JvmString,
+    interfaces: &[Class],
+) -> Result<Class, Error> {
+    let proxy_class = proxy_class(context)?;
+
+    let class = Class::for_proxy(context, loader, name, proxy_class, interfaces, proxy_method)?;
+    loader.define_class(context, class)?;
+
+    Ok(class)
+}
+
+fn proxy_class(context: &Context) -> Result<Class, Error> {
+    let proxy_name = JvmString::new(context.gc_ctx(), "java/lang/reflect/Proxy".into());
+
+    context.bootstrap_loader().lookup_class(context, proxy_name)
+}
+
+// Find the method that is passed to the `InvocationHandler` when `method` is
+// called on an instance of `proxy_class`. This is the method of
+// `java.lang.Object` for `equals`, `hashCode`, and `toString`, and otherwise
+// the method of the first interface (in the order the interfaces were passed
+// to `Proxy`) that has the method.
+fn interface_method(context: &Context, proxy_class: Class, method: Method) -> Method {
+    let key = (method.name(), method.descriptor());
+
+    if matches!(&*method.name(), "equals" | "hashCode" | "toString") {
+        let object_vtable = context.object_class().instance_method_vtable();
+
+        if let Some(index) = object_vtable.lookup(key) {
+            return object_vtable.get_element(index);
+        }
+    }
+
+    for interface in proxy_class.own_interfaces() {
+        let interface_vtable = interface.instance_method_vtable();
+
+        if let Some(index) = interface_vtable.lookup(key) {
+            return interface_vtable.get_element(index);
+        }
+    }
+
+    unreachable!("Proxy methods are always declared by an interface")
+}
+
+// The native implementation of every proxied method of a proxy class.
+fn proxy_method(context: &Context, args: &[Value]) -> Result<Option<Value>, Error> {
+    // Receiver should never be null
+    let proxy = args[0].object().unwrap();
+
+    let method = context.current_method();
+    let descriptor = method.descriptor();
+
+    // Initialize the classes used to box the arguments first, as that may run
+    // Java code
+    for arg_type in descriptor.args() {
+        if arg_type.is_primitive() {
+            arg_type.box_class(context)?.run_clinit(context)?;
+        }
+    }
+
+    let method_object =
+        interface_method(context, proxy.class(), method).get_or_init_object(context);
+
+    // Box the arguments. Long and double arguments take up two slots.
+    let mut boxed_args = Vec::with_capacity(descriptor.args().len());
+    let mut slot = 1;
+    for arg_type in descriptor.args() {
+        let value = args[slot];
+
+        if arg_type.is_primitive() {
+            let box_class = arg_type.box_class(context)?;
+
+            let boxed = Object::from_class(context.gc_ctx(), box_class);
+            boxed.set_field(0, value);
+            boxed_args.push(Some(boxed));
+        } else {
+            boxed_args.push(value.object());
+        }
+
+        slot += if arg_type.is_wide() { 2 } else { 1 };
+    }
+
+    // Methods without arguments are passed a `null` array
+    let args_array = if boxed_args.is_empty() {
+        None
+    } else {
+        Some(Object::obj_array(
+            context,
+            context.object_class(),
+            boxed_args.into_boxed_slice(),
+        ))
+    };
+
+    let invoke_handler = JvmString::new(context.gc_ctx(), "invokeHandler".into());
+    let proxy_class = proxy_class(context)?;
+    let invoke_handler_slot = proxy_class
+        .static_method_vtable()
+        .slots_for_name(invoke_handler)[0];
+    let invoke_handler = proxy_class.get_static_method(invoke_handler_slot);
+
+    let result = context
+        .exec_method(
+            invoke_handler,
+            &[
+                Value::Object(Some(proxy)),
+                Value::Object(Some(method_object)),
+                Value::Object(args_array),
+            ],
+        )?
+        .expect("invokeHandler returns a value");
+
+    // The result was checked against the return type by Java code
+    match descriptor.return_type() {
+        Descriptor::Void => Ok(None),
+        Descriptor::Class(_) | Descriptor::Array(_) => Ok(Some(result)),
+        _ => {
+            let boxed = result.object().unwrap();
+
+            Ok(Some(boxed.get_field(0)))
+        }
+    }
+}
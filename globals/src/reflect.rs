@@ -1,7 +1,12 @@
 // Helper functions for reflection methods
 
+use alloc::string::ToString;
 use alloc::vec::Vec;
-use rjvm_core::{Class, Context, Descriptor, Error, JvmString, Method, MethodFlags, Object, Value};
+use rjvm_core::classfile::attribute::{Attribute, AttributeInfo};
+use rjvm_core::{
+    Class, ClassFile, Context, Descriptor, Error, FieldTemplate, JvmString, Method, MethodFlags,
+    Object, Value,
+};
 
 pub(crate) fn get_class_method(
     class: Class,
@@ -45,40 +50,125 @@ pub(crate) fn get_class_method(
 }
 
 // Change the provided args into a form suitable for calling the given method.
+// Java code has already checked the args and converted them to the exact boxed
+// types of the method's parameters, so primitive args only need to be unboxed.
 pub(crate) fn args_for_instance_call(
-    _context: &Context,
     method: Method,
     receiver: Option<Object>,
     args: &[Value],
-) -> Result<Vec<Value>, Error> {
-    let mut unboxed_args = Vec::with_capacity(args.len() + 1);
+) -> Vec<Value> {
+    let mut real_args = Vec::with_capacity(method.physical_arg_count() as usize);
 
     if let Some(receiver) = receiver {
-        unboxed_args.push(Value::Object(Some(receiver)));
+        real_args.push(Value::Object(Some(receiver)));
     }
 
-    for (i, arg) in args.iter().enumerate() {
-        // All args are passed as objects
-        let arg = arg.object();
-
-        // TODO implement more argument type verification
-
-        // TODO implement more unboxing
-        // NOTE remember to account for the physical vs virtual difference in
-        // args when implementing unboxing for Long and Double
-        if matches!(method.descriptor().args()[i], Descriptor::Integer) {
-            if let Some(arg) = arg {
-                // FIXME this is really hacky
-                if &*arg.class().name() == "java/lang/Integer" {
-                    unboxed_args.push(arg.get_field(0));
-                    continue;
-                }
-            } else {
-                todo!("Throw an IllegalArgumentException");
+    for (arg, descriptor) in args.iter().zip(method.descriptor().args()) {
+        if descriptor.is_primitive() {
+            // Boxed args were null-checked by Java code
+            let boxed = arg.object().unwrap();
+            real_args.push(boxed.get_field(0));
+
+            // Long and double args take up two slots
+            if descriptor.is_wide() {
+                real_args.push(Value::Integer(0));
             }
+        } else {
+            real_args.push(*arg);
         }
-        unboxed_args.push(Value::Object(arg));
     }
 
-    Ok(unboxed_args)
+    real_args
+}
+
+// Box a primitive value of type `descriptor`.
+pub(crate) fn box_primitive(
+    context: &Context,
+    value: Value,
+    descriptor: Descriptor,
+) -> Result<Object, Error> {
+    let box_class = descriptor.box_class(context)?;
+    box_class.run_clinit(context)?;
+
+    let object = Object::from_class(context.gc_ctx(), box_class);
+    object.set_field(0, value);
+
+    Ok(object)
+}
+
+// Find the attribute named `name` in `attributes` and parse it.
+fn parse_attribute(
+    context: &Context,
+    class_file: ClassFile,
+    attributes: &[Attribute],
+    name: &str,
+) -> Result<Option<AttributeInfo>, Error> {
+    let Some(attribute) = attributes.iter().find(|a| &*a.name() == name) else {
+        return Ok(None);
+    };
+
+    attribute
+        .parse(class_file.constant_pool())
+        .map(Some)
+        .map_err(|e| Error::from_class_file_error(context, e))
+}
+
+// Find and parse the attribute named `name` on the given class. Classes that
+// weren't loaded from a class file (such as array classes) have no attributes.
+pub(crate) fn class_attribute(
+    context: &Context,
+    class: Class,
+    name: &str,
+) -> Result<Option<AttributeInfo>, Error> {
+    let Some(class_file) = *class.class_file() else {
+        return Ok(None);
+    };
+
+    parse_attribute(context, class_file, class_file.attributes(), name)
+}
+
+// Find and parse the attribute named `name` on the given method.
+pub(crate) fn method_attribute(
+    context: &Context,
+    method: Method,
+    name: &str,
+) -> Result<Option<AttributeInfo>, Error> {
+    let Some(class_file) = *method.class().class_file() else {
+        return Ok(None);
+    };
+
+    let descriptor = method.descriptor().to_string();
+    let method_data = class_file
+        .methods()
+        .iter()
+        .find(|m| m.name() == method.name() && *m.descriptor() == *descriptor);
+
+    if let Some(method_data) = method_data {
+        parse_attribute(context, class_file, method_data.attributes(), name)
+    } else {
+        Ok(None)
+    }
+}
+
+// Find and parse the attribute named `name` on the given field.
+pub(crate) fn field_attribute(
+    context: &Context,
+    field: FieldTemplate,
+    name: &str,
+) -> Result<Option<AttributeInfo>, Error> {
+    let Some(class_file) = *field.defining_class().class_file() else {
+        return Ok(None);
+    };
+
+    let descriptor = field.descriptor().to_string();
+    let field_data = class_file
+        .fields()
+        .iter()
+        .find(|f| f.name() == field.name() && *f.descriptor() == *descriptor);
+
+    if let Some(field_data) = field_data {
+        parse_attribute(context, class_file, field_data.attributes(), name)
+    } else {
+        Ok(None)
+    }
 }
@@ -0,0 +1,842 @@
+// A filesystem kept entirely in memory, for frontends that have no filesystem
+// of their own. A `MemoryFilesystem` can serve classes and resources to the
+// class loader, as its `LoaderBackend`, and files to `java.io`, by forwarding
+// the file operations of a `Platform` to it.
+
+use crate::platform::{FileMetadata, OpenMode, PlatformError, SeekFrom};
+
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use rjvm_core::{LoaderBackend, ZipFile};
+
+const TAR_BLOCK_SIZE: usize = 512;
+
+/// A filesystem stored in memory, with files and directories that can be
+/// written by both the host and the program running in the VM.
+///
+/// Paths are separated by `/`. Relative paths are resolved against the
+/// working directory of the filesystem, which is `/` by default. Archives can
+/// be mounted as read-only directories with [`MemoryFilesystem::mount_zip`]
+/// and [`MemoryFilesystem::mount_tar`]. For example:
+///
+/// ```ignore
+/// let filesystem = MemoryFilesystem::new();
+/// filesystem.write_file("/Main.class", &class_data)?;
+/// filesystem.mount_zip("/lib", jar_data)?;
+///
+/// Context::init(Box::new(filesystem.clone()));
+///
+/// // Run the program...
+///
+/// let output = filesystem.read_file("/output.txt");
+/// ```
+///
+/// Clones of a `MemoryFilesystem` share the same files; use
+/// [`MemoryFilesystem::snapshot`] to copy them.
+#[derive(Clone)]
+pub struct MemoryFilesystem(Rc<RefCell<FilesystemData>>);
+
+struct FilesystemData {
+    root: Directory,
+
+    // The absolute path of the working directory
+    current_dir: String,
+
+    // The open files, indexed by handle. Closed files are `None`.
+    open_files: Vec<Option<OpenFile>>,
+
+    // Used for the modification times of files
+    clock: Option<Rc<dyn Fn() -> i64>>,
+}
+
+#[derive(Clone)]
+enum Node {
+    File(File),
+    Directory(Directory),
+}
+
+#[derive(Clone)]
+struct File {
+    // Shared with the `OpenFile`s for this file, so that they still work after
+    // the file is deleted or renamed
+    data: Rc<RefCell<FileData>>,
+
+    read_only: bool,
+}
+
+struct FileData {
+    contents: Vec<u8>,
+
+    last_modified: i64,
+}
+
+#[derive(Clone, Default)]
+struct Directory {
+    entries: BTreeMap<String, Node>,
+
+    read_only: bool,
+}
+
+struct OpenFile {
+    data: Rc<RefCell<FileData>>,
+
+    position: usize,
+
+    writable: bool,
+}
+
+impl Node {
+    fn empty_file(read_only: bool, last_modified: i64) -> Self {
+        Node::File(File {
+            data: Rc::new(RefCell::new(FileData {
+                contents: Vec::new(),
+                last_modified,
+            })),
+            read_only,
+        })
+    }
+
+    // A copy of this node that doesn't share its files with this one
+    fn deep_copy(&self) -> Self {
+        match self {
+            Node::File(file) => {
+                let data = file.data.borrow();
+
+                Node::File(File {
+                    data: Rc::new(RefCell::new(FileData {
+                        contents: data.contents.clone(),
+                        last_modified: data.last_modified,
+                    })),
+                    read_only: file.read_only,
+                })
+            }
+            Node::Directory(directory) => Node::Directory(directory.deep_copy()),
+        }
+    }
+}
+
+impl Directory {
+    fn deep_copy(&self) -> Self {
+        Directory {
+            entries: self
+                .entries
+                .iter()
+                .map(|(name, node)| (name.clone(), node.deep_copy()))
+                .collect(),
+            read_only: self.read_only,
+        }
+    }
+
+    fn lookup(&self, components: &[String]) -> Option<&Node> {
+        let (last, parents) = components.split_last()?;
+
+        let mut directory = self;
+        for component in parents {
+            match directory.entries.get(component) {
+                Some(Node::Directory(child)) => directory = child,
+                _ => return None,
+            }
+        }
+
+        directory.entries.get(last)
+    }
+
+    // Get the directory at `components`, which must exist.
+    fn directory_mut(&mut self, components: &[String]) -> Result<&mut Directory, PlatformError> {
+        let mut directory = self;
+        for component in components {
+            match directory.entries.get_mut(component) {
+                Some(Node::Directory(child)) => directory = child,
+                Some(Node::File(_)) => return Err(PlatformError::new("Not a directory")),
+                None => return Err(PlatformError::not_found()),
+            }
+        }
+
+        Ok(directory)
+    }
+
+    // Get the directory at `components`, creating it and its parents if they
+    // don't exist.
+    fn create_dir_all(&mut self, components: &[String]) -> Result<&mut Directory, PlatformError> {
+        let mut directory = self;
+        for component in components {
+            if !directory.entries.contains_key(component) {
+                if directory.read_only {
+                    return Err(read_only_error());
+                }
+
+                directory
+                    .entries
+                    .insert(component.clone(), Node::Directory(Directory::default()));
+            }
+
+            match directory.entries.get_mut(component) {
+                Some(Node::Directory(child)) => directory = child,
+                _ => return Err(PlatformError::new("Not a directory")),
+            }
+        }
+
+        Ok(directory)
+    }
+
+    // Make this directory and everything in it read-only.
+    fn make_read_only(&mut self) {
+        self.read_only = true;
+
+        for node in self.entries.values_mut() {
+            match node {
+                Node::File(file) => file.read_only = true,
+                Node::Directory(directory) => directory.make_read_only(),
+            }
+        }
+    }
+
+    fn collect_file_paths(&self, prefix: &str, paths: &mut Vec<String>) {
+        for (name, node) in &self.entries {
+            let path = format!("{}/{}", prefix, name);
+
+            match node {
+                Node::File(_) => paths.push(path),
+                Node::Directory(directory) => directory.collect_file_paths(&path, paths),
+            }
+        }
+    }
+}
+
+impl MemoryFilesystem {
+    /// Create an empty filesystem, whose working directory is `/`.
+    pub fn new() -> Self {
+        Self(Rc::new(RefCell::new(FilesystemData {
+            root: Directory::default(),
+            current_dir: "/".to_string(),
+            open_files: Vec::new(),
+            clock: None,
+        })))
+    }
+
+    /// Set the clock used for the modification times of files, in
+    /// milliseconds since the epoch. Without a clock, files are modified at
+    /// time 0.
+    pub fn set_clock(&self, clock: impl Fn() -> i64 + 'static) {
+        self.0.borrow_mut().clock = Some(Rc::new(clock));
+    }
+
+    /// The absolute path of the working directory.
+    pub fn current_dir(&self) -> String {
+        self.0.borrow().current_dir.clone()
+    }
+
+    /// Set the working directory, which must be an existing directory.
+    pub fn set_current_dir(&self, path: &str) -> Result<(), PlatformError> {
+        let components = self.resolve(path);
+
+        let mut data = self.0.borrow_mut();
+        data.root.directory_mut(&components)?;
+        data.current_dir = join_components(&components);
+
+        Ok(())
+    }
+
+    /// Create the directory at `path` and any of its parents that don't exist.
+    pub fn create_dir_all(&self, path: &str) -> Result<(), PlatformError> {
+        let components = self.resolve(path);
+
+        self.0.borrow_mut().root.create_dir_all(&components)?;
+
+        Ok(())
+    }
+
+    /// Write `contents` to the file at `path`, creating the file and its
+    /// parent directories if they don't exist.
+    pub fn write_file(&self, path: &str, contents: &[u8]) -> Result<(), PlatformError> {
+        let components = self.resolve(path);
+        let Some((_, parents)) = components.split_last() else {
+            return Err(is_directory_error());
+        };
+
+        self.0.borrow_mut().root.create_dir_all(parents)?;
+
+        let handle = self.open(path, OpenMode::Write)?;
+        let result = self.write(handle, contents);
+        self.close(handle)?;
+
+        result
+    }
+
+    /// Read the contents of the file at `path`, or `None` if there is no
+    /// file there.
+    pub fn read_file(&self, path: &str) -> Option<Vec<u8>> {
+        let components = self.resolve(path);
+
+        match self.0.borrow().root.lookup(&components) {
+            Some(Node::File(file)) => Some(file.data.borrow().contents.clone()),
+            _ => None,
+        }
+    }
+
+    /// The absolute paths of all files in the filesystem, in sorted order.
+    /// Directories aren't included.
+    pub fn file_paths(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        self.0.borrow().root.collect_file_paths("", &mut paths);
+
+        paths
+    }
+
+    /// Mount the contents of the zip (or jar) archive `data` as a read-only
+    /// directory at `path`. Nothing may exist at `path` already, but its
+    /// parent directories are created if they don't exist. An archive with
+    /// both a file and a directory at the same path is rejected.
+    pub fn mount_zip(&self, path: &str, data: Vec<u8>) -> Result<(), PlatformError> {
+        let invalid_archive = |_| PlatformError::new("Invalid zip archive");
+
+        let zip = ZipFile::new(data).map_err(invalid_archive)?;
+
+        let mut names = zip
+            .file_names()
+            .map(|name| String::from_utf8_lossy(name).into_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+
+        let mut tree = Directory::default();
+        for name in names {
+            let components = resolve_components(&[], &name);
+
+            if name.ends_with('/') {
+                tree.create_dir_all(&components)?;
+            } else {
+                let contents = zip.read_file(&name).map_err(invalid_archive)?;
+                insert_archive_file(&mut tree, &components, contents, 0)?;
+            }
+        }
+
+        self.mount(path, tree)
+    }
+
+    /// Mount the contents of the tar archive `data` as a read-only directory
+    /// at `path`, like [`MemoryFilesystem::mount_zip`]. Regular files and
+    /// directories are mounted; links and other special entries are skipped.
+    /// Long names are read from both GNU and pax headers.
+    pub fn mount_tar(&self, path: &str, data: &[u8]) -> Result<(), PlatformError> {
+        let invalid_archive = || PlatformError::new("Invalid tar archive");
+
+        let mut tree = Directory::default();
+
+        let mut long_name = None;
+        let mut offset = 0;
+        while offset + TAR_BLOCK_SIZE <= data.len() {
+            let header = &data[offset..offset + TAR_BLOCK_SIZE];
+
+            // The archive ends with blocks of zeroes
+            if header.iter().all(|b| *b == 0) {
+                break;
+            }
+
+            let size = parse_octal(&header[124..136]).ok_or_else(invalid_archive)? as usize;
+            let last_modified = parse_octal(&header[136..148]).unwrap_or(0) as i64 * 1000;
+            let type_flag = header[156];
+
+            let data_start = offset + TAR_BLOCK_SIZE;
+            let data_end = data_start
+                .checked_add(size)
+                .filter(|end| *end <= data.len())
+                .ok_or_else(invalid_archive)?;
+            let contents = &data[data_start..data_end];
+
+            offset = data_start + size.div_ceil(TAR_BLOCK_SIZE) * TAR_BLOCK_SIZE;
+
+            // A GNU long name entry holds the name of the next entry, as can
+            // the `path` record of a pax extended header
+            match type_flag {
+                b'L' => {
+                    long_name = Some(tar_string(contents));
+                    continue;
+                }
+                b'x' => {
+                    if let Some(path) = pax_path(contents).ok_or_else(invalid_archive)? {
+                        long_name = Some(path);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+
+            let name = long_name.take().unwrap_or_else(|| {
+                let name = tar_string(&header[0..100]);
+                let prefix = if &header[257..262] == b"ustar" {
+                    tar_string(&header[345..500])
+                } else {
+                    String::new()
+                };
+
+                if prefix.is_empty() {
+                    name
+                } else {
+                    format!("{}/{}", prefix, name)
+                }
+            });
+            let components = resolve_components(&[], &name);
+
+            match type_flag {
+                b'0' | 0 => {
+                    insert_archive_file(&mut tree, &components, contents.to_vec(), last_modified)?
+                }
+                b'5' => {
+                    tree.create_dir_all(&components)?;
+                }
+                _ => {}
+            }
+        }
+
+        self.mount(path, tree)
+    }
+
+    fn mount(&self, path: &str, mut tree: Directory) -> Result<(), PlatformError> {
+        tree.make_read_only();
+
+        let components = self.resolve(path);
+        let Some((name, parents)) = components.split_last() else {
+            return Err(PlatformError::new("File exists"));
+        };
+
+        let mut data = self.0.borrow_mut();
+        let parent = data.root.create_dir_all(parents)?;
+        if parent.entries.contains_key(name) {
+            return Err(PlatformError::new("File exists"));
+        }
+
+        parent.entries.insert(name.clone(), Node::Directory(tree));
+
+        Ok(())
+    }
+
+    /// Create a copy of the files and directories of this filesystem, which
+    /// can be inspected or used for another run after this one. Files that
+    /// are open aren't open in the copy.
+    pub fn snapshot(&self) -> MemoryFilesystem {
+        let data = self.0.borrow();
+
+        Self(Rc::new(RefCell::new(FilesystemData {
+            root: data.root.deep_copy(),
+            current_dir: data.current_dir.clone(),
+            open_files: Vec::new(),
+            clock: data.clock.clone(),
+        })))
+    }
+
+    // The operations below match those of `Platform`, so that a `Platform` can
+    // forward its file operations to the filesystem.
+
+    pub fn open(&self, path: &str, mode: OpenMode) -> Result<u32, PlatformError> {
+        let components = self.resolve(path);
+        let Some((name, parents)) = components.split_last() else {
+            return Err(is_directory_error());
+        };
+
+        let time = self.current_time();
+
+        let mut data = self.0.borrow_mut();
+        let parent = data.root.directory_mut(parents)?;
+
+        let file_data = match (parent.entries.get(name), mode) {
+            (Some(Node::Directory(_)), _) => return Err(is_directory_error()),
+            (Some(Node::File(file)), OpenMode::Read) => file.data.clone(),
+            (Some(Node::File(file)), OpenMode::Write) => {
+                if file.read_only {
+                    return Err(read_only_error());
+                }
+
+                let mut file_data = file.data.borrow_mut();
+                file_data.contents.clear();
+                file_data.last_modified = time;
+
+                file.data.clone()
+            }
+            (None, OpenMode::Read) => return Err(PlatformError::not_found()),
+            (None, OpenMode::Write) => {
+                if parent.read_only {
+                    return Err(read_only_error());
+                }
+
+                let node = Node::empty_file(false, time);
+                let Node::File(file) = &node else {
+                    unreachable!();
+                };
+                let file_data = file.data.clone();
+
+                parent.entries.insert(name.clone(), node);
+
+                file_data
+            }
+        };
+
+        data.open_files.push(Some(OpenFile {
+            data: file_data,
+            position: 0,
+            writable: mode == OpenMode::Write,
+        }));
+
+        Ok(data.open_files.len() as u32 - 1)
+    }
+
+    pub fn read(&self, handle: u32, buf: &mut [u8]) -> Result<usize, PlatformError> {
+        self.with_open_file(handle, |file| {
+            let data = file.data.borrow();
+
+            let start = file.position.min(data.contents.len());
+            let count = buf.len().min(data.contents.len() - start);
+            buf[..count].copy_from_slice(&data.contents[start..start + count]);
+
+            file.position += count;
+
+            Ok(count)
+        })
+    }
+
+    pub fn write(&self, handle: u32, bytes: &[u8]) -> Result<(), PlatformError> {
+        let time = self.current_time();
+
+        self.with_open_file(handle, |file| {
+            if !file.writable {
+                return Err(bad_descriptor_error());
+            }
+
+            let mut data = file.data.borrow_mut();
+
+            let end = file.position + bytes.len();
+            if data.contents.len() < end {
+                data.contents.resize(end, 0);
+            }
+            data.contents[file.position..end].copy_from_slice(bytes);
+            data.last_modified = time;
+
+            file.position = end;
+
+            Ok(())
+        })
+    }
+
+    pub fn flush(&self, handle: u32) -> Result<(), PlatformError> {
+        self.with_open_file(handle, |_| Ok(()))
+    }
+
+    pub fn seek(&self, handle: u32, position: SeekFrom) -> Result<u64, PlatformError> {
+        self.with_open_file(handle, |file| {
+            let length = file.data.borrow().contents.len() as i64;
+
+            let new_position = match position {
+                SeekFrom::Start(offset) => i64::try_from(offset).ok(),
+                SeekFrom::End(offset) => length.checked_add(offset),
+                SeekFrom::Current(offset) => (file.position as i64).checked_add(offset),
+            };
+
+            match new_position {
+                Some(new_position) if new_position >= 0 => {
+                    file.position = new_position as usize;
+
+                    Ok(new_position as u64)
+                }
+                _ => Err(PlatformError::new("Invalid argument")),
+            }
+        })
+    }
+
+    pub fn close(&self, handle: u32) -> Result<(), PlatformError> {
+        let mut data = self.0.borrow_mut();
+
+        match data.open_files.get_mut(handle as usize) {
+            Some(file @ Some(_)) => {
+                *file = None;
+
+                Ok(())
+            }
+            _ => Err(bad_descriptor_error()),
+        }
+    }
+
+    pub fn stat(&self, path: &str) -> Option<FileMetadata> {
+        let components = self.resolve(path);
+
+        let data = self.0.borrow();
+        let node = if components.is_empty() {
+            return Some(FileMetadata {
+                is_directory: true,
+                length: 0,
+                last_modified: 0,
+            });
+        } else {
+            data.root.lookup(&components)?
+        };
+
+        Some(match node {
+            Node::File(file) => {
+                let file_data = file.data.borrow();
+
+                FileMetadata {
+                    is_directory: false,
+                    length: file_data.contents.len() as u64,
+                    last_modified: file_data.last_modified,
+                }
+            }
+            Node::Directory(_) => FileMetadata {
+                is_directory: true,
+                length: 0,
+                last_modified: 0,
+            },
+        })
+    }
+
+    pub fn list(&self, path: &str) -> Result<Vec<String>, PlatformError> {
+        let components = self.resolve(path);
+
+        let mut data = self.0.borrow_mut();
+        let directory = data.root.directory_mut(&components)?;
+
+        Ok(directory.entries.keys().cloned().collect())
+    }
+
+    pub fn delete(&self, path: &str) -> Result<(), PlatformError> {
+        let components = self.resolve(path);
+        let Some((name, parents)) = components.split_last() else {
+            return Err(PlatformError::new("Device or resource busy"));
+        };
+
+        let mut data = self.0.borrow_mut();
+        let parent = data.root.directory_mut(parents)?;
+
+        match parent.entries.get(name) {
+            None => return Err(PlatformError::not_found()),
+            Some(Node::Directory(directory)) if !directory.entries.is_empty() => {
+                return Err(PlatformError::new("Directory not empty"));
+            }
+            Some(_) => {}
+        }
+
+        if parent.read_only {
+            return Err(read_only_error());
+        }
+
+        parent.entries.remove(name);
+
+        Ok(())
+    }
+
+    pub fn rename(&self, from: &str, to: &str) -> Result<(), PlatformError> {
+        let from = self.resolve(from);
+        let to = self.resolve(to);
+
+        let (Some((from_name, from_parents)), Some((to_name, to_parents))) =
+            (from.split_last(), to.split_last())
+        else {
+            return Err(PlatformError::new("Device or resource busy"));
+        };
+
+        // A directory can't be moved inside of itself
+        if to.len() > from.len() && to.starts_with(&from) {
+            return Err(PlatformError::new("Invalid argument"));
+        }
+
+        let mut data = self.0.borrow_mut();
+
+        let from_is_directory = match data.root.lookup(&from) {
+            Some(node) => matches!(node, Node::Directory(_)),
+            None => return Err(PlatformError::not_found()),
+        };
+        let to_is_directory = data
+            .root
+            .lookup(&to)
+            .map(|node| matches!(node, Node::Directory(_)));
+
+        match (from_is_directory, to_is_directory) {
+            _ if from == to => return Ok(()),
+            (false, Some(true)) => return Err(is_directory_error()),
+            (true, Some(false)) => return Err(PlatformError::new("Not a directory")),
+            (true, Some(true)) => return Err(PlatformError::new("File exists")),
+            _ => {}
+        }
+
+        if data.root.directory_mut(to_parents)?.read_only {
+            return Err(read_only_error());
+        }
+
+        let from_parent = data.root.directory_mut(from_parents)?;
+        if from_parent.read_only {
+            return Err(read_only_error());
+        }
+        let node = from_parent
+            .entries
+            .remove(from_name)
+            .expect("Source should exist");
+
+        let to_parent = data.root.directory_mut(to_parents)?;
+        to_parent.entries.insert(to_name.clone(), node);
+
+        Ok(())
+    }
+
+    fn resolve(&self, path: &str) -> Vec<String> {
+        let data = self.0.borrow();
+        let current_dir = resolve_components(&[], &data.current_dir);
+
+        resolve_components(&current_dir, path)
+    }
+
+    fn current_time(&self) -> i64 {
+        let clock = self.0.borrow().clock.clone();
+
+        clock.map(|clock| clock()).unwrap_or(0)
+    }
+
+    fn with_open_file<T>(
+        &self,
+        handle: u32,
+        func: impl FnOnce(&mut OpenFile) -> Result<T, PlatformError>,
+    ) -> Result<T, PlatformError> {
+        let mut data = self.0.borrow_mut();
+
+        match data.open_files.get_mut(handle as usize) {
+            Some(Some(file)) => func(file),
+            _ => Err(bad_descriptor_error()),
+        }
+    }
+}
+
+impl Default for MemoryFilesystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoaderBackend for MemoryFilesystem {
+    fn load_filesystem_resource(&self, resource_name: &str) -> Option<Vec<u8>> {
+        self.read_file(resource_name)
+    }
+}
+
+// Resolve `path` against the directory at `base`, returning the components of
+// the resulting absolute path.
+fn resolve_components(base: &[String], path: &str) -> Vec<String> {
+    let mut components = if path.starts_with('/') {
+        Vec::new()
+    } else {
+        base.to_vec()
+    };
+
+    for component in path.split('/') {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            _ => components.push(component.to_string()),
+        }
+    }
+
+    components
+}
+
+fn join_components(components: &[String]) -> String {
+    if components.is_empty() {
+        "/".to_string()
+    } else {
+        components.iter().map(|c| format!("/{}", c)).collect()
+    }
+}
+
+// Add a file from an archive to `tree`, creating its parent directories.
+fn insert_archive_file(
+    tree: &mut Directory,
+    components: &[String],
+    contents: Vec<u8>,
+    last_modified: i64,
+) -> Result<(), PlatformError> {
+    let Some((name, parents)) = components.split_last() else {
+        return Ok(());
+    };
+
+    let file = File {
+        data: Rc::new(RefCell::new(FileData {
+            contents,
+            last_modified,
+        })),
+        read_only: false,
+    };
+
+    let parent = tree.create_dir_all(parents)?;
+    if let Some(Node::Directory(_)) = parent.entries.get(name) {
+        return Err(is_directory_error());
+    }
+
+    parent.entries.insert(name.clone(), Node::File(file));
+
+    Ok(())
+}
+
+// Parse a NUL- or space-terminated octal number from a tar header.
+fn parse_octal(field: &[u8]) -> Option<u64> {
+    let digits = field
+        .iter()
+        .skip_while(|b| **b == b' ')
+        .take_while(|b| **b != 0 && **b != b' ');
+
+    let mut value: u64 = 0;
+    for digit in digits {
+        if !(b'0'..=b'7').contains(digit) {
+            return None;
+        }
+
+        value = value.checked_mul(8)? + (digit - b'0') as u64;
+    }
+
+    Some(value)
+}
+
+// Find the `path` record in the contents of a pax extended header. Each record
+// is formatted as `<length> <key>=<value>\n`, where the length includes the
+// whole record. Returns `None` if the records are malformed.
+fn pax_path(mut records: &[u8]) -> Option<Option<String>> {
+    let mut path = None;
+
+    while !records.is_empty() {
+        let space = records.iter().position(|b| *b == b' ')?;
+        let length = core::str::from_utf8(&records[..space])
+            .ok()?
+            .parse::<usize>()
+            .ok()?;
+        if length <= space + 1 || length > records.len() || records[length - 1] != b'\n' {
+            return None;
+        }
+
+        let record = &records[space + 1..length - 1];
+        let equals = record.iter().position(|b| *b == b'=')?;
+        if &record[..equals] == b"path" {
+            path = Some(String::from_utf8_lossy(&record[equals + 1..]).into_owned());
+        }
+
+        records = &records[length..];
+    }
+
+    Some(path)
+}
+
+// Read a NUL-terminated string from a tar header.
+fn tar_string(field: &[u8]) -> String {
+    let end = field.iter().position(|b| *b == 0).unwrap_or(field.len());
+
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+fn read_only_error() -> PlatformError {
+    PlatformError::new("Read-only file system")
+}
+
+fn is_directory_error() -> PlatformError {
+    PlatformError::new("Is a directory")
+}
+
+fn bad_descriptor_error() -> PlatformError {
+    PlatformError::new("Bad file descriptor")
+}
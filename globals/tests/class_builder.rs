@@ -0,0 +1,151 @@
+mod common;
+
+use common::TestJvm;
+use rjvm_core::{
+    ClassBuilder, ClassFlags, Context, Error, FieldFlags, Method, MethodFlags, Object, Value,
+};
+
+const RUNNER_SOURCE: &str = r#"
+public interface Shape {
+    double area();
+
+    default String describe() {
+        return getClass().getName() + " with area " + area();
+    }
+}
+
+class Runner {
+    static boolean initialized;
+
+    static void initialize() {
+        initialized = true;
+    }
+
+    static String run() throws Exception {
+        Class<?> cls = Class.forName("Square");
+        Shape shape = (Shape) cls.getDeclaredConstructors()[0].newInstance(3.0);
+
+        return initialized + " " + shape.describe() + " " + cls.getMethod("sides").invoke(null);
+    }
+
+    static Object callNext(Object counter) throws Exception {
+        return Class.forName("Counter").getMethod("next").invoke(counter);
+    }
+}
+"#;
+
+fn square_clinit(context: &Context, _args: &[Value]) -> Result<Option<Value>, Error> {
+    context.call_static::<()>("Runner", "initialize", "()V", ())?;
+
+    Ok(None)
+}
+
+fn square_init(context: &Context, this: Object, side: f64) -> Result<(), Error> {
+    this.set_field_by_name(context, "side", side)
+}
+
+fn square_area(context: &Context, this: Object) -> Result<f64, Error> {
+    let side: f64 = this.get_field_by_name(context, "side")?;
+
+    Ok(side * side)
+}
+
+fn square_sides(_context: &Context, _args: &[Value]) -> Result<Option<Value>, Error> {
+    Ok(Some(Value::Integer(4)))
+}
+
+fn counter_next(
+    _context: &Context,
+    _captures: &(),
+    _method: Method,
+    _args: &[Value],
+) -> Result<Option<Value>, Error> {
+    Ok(Some(Value::Integer(7)))
+}
+
+fn square_builder() -> ClassBuilder {
+    let mut builder = ClassBuilder::new(ClassFlags::PUBLIC, "Square");
+    builder.add_interface("Shape");
+    builder.add_field(FieldFlags::PRIVATE, "side", "D");
+    builder.add_method(MethodFlags::STATIC, "<clinit>", "()V", square_clinit);
+    builder.add_typed_method(MethodFlags::PUBLIC, "<init>", "(D)V", square_init);
+    builder.add_typed_method(MethodFlags::PUBLIC, "area", "()D", square_area);
+    builder.add_method(
+        MethodFlags::PUBLIC | MethodFlags::STATIC,
+        "sides",
+        "()I",
+        square_sides,
+    );
+
+    builder
+}
+
+fn error_class(context: &Context, error: Error) -> String {
+    error.info(context).class_name
+}
+
+#[test]
+fn built_classes_are_used_from_java() {
+    TestJvm::compile("class_builder_use", &[("Shape.java", RUNNER_SOURCE)]).run(|context| {
+        square_builder()
+            .define(context, context.system_loader())
+            .unwrap();
+
+        let result: String = context
+            .call_static("Runner", "run", "()Ljava/lang/String;", ())
+            .unwrap();
+        assert_eq!(result, "true Square with area 9.0 4");
+
+        // Interfaces can be built with abstract methods
+        let mut builder = ClassBuilder::new(
+            ClassFlags::PUBLIC | ClassFlags::INTERFACE | ClassFlags::ABSTRACT,
+            "Counter",
+        );
+        builder.add_abstract_method(MethodFlags::PUBLIC, "next", "()I");
+        builder.define(context, context.system_loader()).unwrap();
+
+        let counter = context
+            .implement_interface("Counter", (), counter_next)
+            .unwrap();
+        let next: Object = context
+            .call_static(
+                "Runner",
+                "callNext",
+                "(Ljava/lang/Object;)Ljava/lang/Object;",
+                (counter,),
+            )
+            .unwrap();
+        let next: i32 = next.call_virtual(context, "intValue", "()I", ()).unwrap();
+        assert_eq!(next, 7);
+    });
+}
+
+#[test]
+fn invalid_classes_are_rejected() {
+    TestJvm::compile("class_builder_errors", &[("Shape.java", RUNNER_SOURCE)]).run(|context| {
+        let mut builder = square_builder();
+        builder.add_method(MethodFlags::PUBLIC, "area", "()D", square_sides);
+        let error = builder
+            .define(context, context.system_loader())
+            .unwrap_err();
+        assert_eq!(error_class(context, error), "java.lang.ClassFormatError");
+
+        // `area` takes the receiver, so it can't implement a static method
+        let mut builder = ClassBuilder::new(ClassFlags::PUBLIC, "Mismatched");
+        builder.add_typed_method(
+            MethodFlags::PUBLIC | MethodFlags::STATIC,
+            "area",
+            "()D",
+            square_area,
+        );
+        let error = builder
+            .define(context, context.system_loader())
+            .unwrap_err();
+        assert_eq!(error_class(context, error), "java.lang.LinkageError");
+
+        // Neither failed definition was registered
+        square_builder()
+            .define(context, context.system_loader())
+            .unwrap();
+    });
+}
@@ -0,0 +1,287 @@
+// Shared setup for the tests of the embedding API, which run Java code on a
+// `Context` backed by a `MemoryFilesystem`.
+#![allow(dead_code)]
+
+use rjvm_core::{ConsoleBackend, Context, Jar, ResourceLoadSource};
+use rjvm_globals::platform::{FileMetadata, OpenMode, Platform, PlatformError, SeekFrom};
+use rjvm_globals::vfs::MemoryFilesystem;
+use rjvm_globals::{native_impl, GLOBALS_BASE_JAR, GLOBALS_DESKTOP_JAR};
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::rc::Rc;
+use std::sync::{Mutex, MutexGuard};
+
+// There can only be one `Context` at a time, so tests must not run at the
+// same time as each other.
+static CONTEXT_LOCK: Mutex<()> = Mutex::new(());
+
+/// A platform whose files are kept in a `MemoryFilesystem`.
+pub struct TestPlatform {
+    pub filesystem: MemoryFilesystem,
+
+    // Symbolic links, as `(path, target)` pairs of absolute, canonical paths.
+    // These are only followed when canonicalizing paths.
+    pub links: Vec<(String, String)>,
+}
+
+impl Platform for TestPlatform {
+    fn open(&self, path: &str, mode: OpenMode) -> Result<u32, PlatformError> {
+        self.filesystem.open(path, mode)
+    }
+
+    fn read(&self, handle: u32, buf: &mut [u8]) -> Result<usize, PlatformError> {
+        self.filesystem.read(handle, buf)
+    }
+
+    fn write(&self, handle: u32, data: &[u8]) -> Result<(), PlatformError> {
+        self.filesystem.write(handle, data)
+    }
+
+    fn seek(&self, handle: u32, position: SeekFrom) -> Result<u64, PlatformError> {
+        self.filesystem.seek(handle, position)
+    }
+
+    fn close(&self, handle: u32) -> Result<(), PlatformError> {
+        self.filesystem.close(handle)
+    }
+
+    fn stat(&self, path: &str) -> Option<FileMetadata> {
+        self.filesystem.stat(path)
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<String>, PlatformError> {
+        self.filesystem.list(path)
+    }
+
+    fn delete(&self, path: &str) -> Result<(), PlatformError> {
+        self.filesystem.delete(path)
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), PlatformError> {
+        self.filesystem.rename(from, to)
+    }
+
+    fn current_time_millis(&self) -> i64 {
+        0
+    }
+
+    fn exit(&self, code: i32) -> ! {
+        panic!("System.exit called (code {})", code)
+    }
+
+    fn current_dir(&self) -> String {
+        self.filesystem.current_dir()
+    }
+
+    fn canonicalize(&self, path: &str) -> String {
+        // Links are followed as soon as they're reached, before any `..`
+        // components after them
+        let mut resolved = String::new();
+        for component in path.split('/') {
+            match component {
+                "" | "." => {}
+                ".." => {
+                    let parent = resolved.rfind('/').unwrap_or(0);
+                    resolved.truncate(parent);
+                }
+                name => {
+                    resolved = format!("{}/{}", resolved, name);
+                    if let Some((_, target)) = self.links.iter().find(|(link, _)| *link == resolved)
+                    {
+                        resolved = target.clone();
+                    }
+                }
+            }
+        }
+
+        if resolved.is_empty() {
+            "/".to_string()
+        } else {
+            resolved
+        }
+    }
+}
+
+/// A console that records everything written to it, and reads `System.in`
+/// from a fixed input.
+#[derive(Default)]
+pub struct CapturingConsole {
+    stdout: RefCell<Vec<u8>>,
+    stderr: RefCell<Vec<u8>>,
+    stdin: RefCell<VecDeque<u8>>,
+}
+
+impl CapturingConsole {
+    pub fn with_input(input: &[u8]) -> Self {
+        Self {
+            stdin: RefCell::new(input.iter().copied().collect()),
+            ..Self::default()
+        }
+    }
+
+    pub fn stdout(&self) -> String {
+        String::from_utf8(self.stdout.borrow().clone()).expect("Output should be UTF-8")
+    }
+
+    pub fn stderr(&self) -> String {
+        String::from_utf8(self.stderr.borrow().clone()).expect("Output should be UTF-8")
+    }
+}
+
+impl ConsoleBackend for CapturingConsole {
+    fn write_stdout(&self, data: &[u8]) {
+        self.stdout.borrow_mut().extend_from_slice(data);
+    }
+
+    fn write_stderr(&self, data: &[u8]) {
+        self.stderr.borrow_mut().extend_from_slice(data);
+    }
+
+    fn read_stdin(&self, buf: &mut [u8]) -> usize {
+        let mut stdin = self.stdin.borrow_mut();
+        let count = buf.len().min(stdin.len());
+        for (byte, input) in buf.iter_mut().zip(stdin.drain(..count)) {
+            *byte = input;
+        }
+
+        count
+    }
+
+    fn stdin_available(&self) -> usize {
+        self.stdin.borrow().len()
+    }
+}
+
+/// The Java classes and console used by a test.
+pub struct TestJvm {
+    pub filesystem: MemoryFilesystem,
+    pub console: Rc<CapturingConsole>,
+    links: Vec<(String, String)>,
+}
+
+impl TestJvm {
+    /// Compile the given `(file name, source)` pairs with `javac`, and write
+    /// the resulting class files to the root of a new filesystem.
+    pub fn compile(name: &str, sources: &[(&str, &str)]) -> Self {
+        let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("Test directory should be writable");
+
+        let mut javac = Command::new("javac");
+        javac.arg("-d").arg(&dir);
+        for (file_name, source) in sources {
+            let path = dir.join(file_name);
+            fs::write(&path, source).expect("Test directory should be writable");
+            javac.arg(path);
+        }
+
+        let output = javac.output().expect("javac should be installed");
+        assert!(
+            output.status.success(),
+            "Test sources should compile:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let filesystem = MemoryFilesystem::new();
+        write_classes(&filesystem, &dir, "");
+
+        Self {
+            filesystem,
+            console: Rc::new(CapturingConsole::default()),
+            links: Vec::new(),
+        }
+    }
+
+    /// Provide `input` as the contents of `System.in`.
+    pub fn with_stdin(mut self, input: &[u8]) -> Self {
+        self.console = Rc::new(CapturingConsole::with_input(input));
+
+        self
+    }
+
+    /// Make the platform treat `path` as a symbolic link to `target`.
+    pub fn with_link(mut self, path: &str, target: &str) -> Self {
+        self.links.push((path.to_string(), target.to_string()));
+
+        self
+    }
+
+    /// Create a `Context` that loads classes from the filesystem, and pass it
+    /// to `test`.
+    pub fn run(&self, test: impl FnOnce(&Context)) {
+        let _guard = ContextGuard::new(&self.filesystem);
+
+        Context::with(|context| {
+            context.set_console_backend(self.console.clone());
+
+            let globals_base_jar = Jar::from_bytes(context.gc_ctx(), GLOBALS_BASE_JAR.to_vec())
+                .expect("Builtin globals should be valid");
+            context.add_bootstrap_jar(globals_base_jar);
+            let globals_desktop_jar =
+                Jar::from_bytes(context.gc_ctx(), GLOBALS_DESKTOP_JAR.to_vec())
+                    .expect("Builtin globals should be valid");
+            context.add_bootstrap_jar(globals_desktop_jar);
+
+            let platform = Rc::new(TestPlatform {
+                filesystem: self.filesystem.clone(),
+                links: self.links.clone(),
+            });
+            native_impl::register_native_mappings(context, platform);
+
+            context.load_builtins();
+
+            context
+                .system_loader()
+                .add_source(ResourceLoadSource::FileSystem);
+
+            test(context);
+        });
+    }
+}
+
+fn write_classes(filesystem: &MemoryFilesystem, dir: &PathBuf, prefix: &str) {
+    for entry in fs::read_dir(dir).expect("Test directory should be readable") {
+        let entry = entry.expect("Test directory should be readable");
+        let name = entry.file_name().into_string().expect("Valid file name");
+        let path = format!("{}{}", prefix, name);
+
+        if entry.path().is_dir() {
+            write_classes(filesystem, &entry.path(), &format!("{}/", path));
+        } else if name.ends_with(".class") {
+            let data = fs::read(entry.path()).expect("Class file should be readable");
+            filesystem
+                .write_file(&path, &data)
+                .expect("Filesystem should be writable");
+        }
+    }
+}
+
+// Holds the lock on the `Context` while it exists, and clears it when dropped,
+// even if the test panicked.
+struct ContextGuard {
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl ContextGuard {
+    fn new(filesystem: &MemoryFilesystem) -> Self {
+        // A test that panicked still cleared its `Context`
+        let lock = CONTEXT_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        Context::init(Box::new(filesystem.clone()));
+
+        Self { _lock: lock }
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        // SAFETY: Nothing from the `Context` outlives the test
+        unsafe { Context::clear() };
+    }
+}
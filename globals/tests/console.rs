@@ -0,0 +1,54 @@
+mod common;
+
+use common::TestJvm;
+
+const ECHO_SOURCE: &str = r#"
+import java.io.ByteArrayOutputStream;
+import java.io.IOException;
+
+public class Echo {
+    public static void run() throws IOException {
+        System.out.println("available " + System.in.available());
+        System.out.println("first " + System.in.read());
+
+        ByteArrayOutputStream rest = new ByteArrayOutputStream();
+        byte[] buffer = new byte[4];
+        int count;
+        while ((count = System.in.read(buffer, 0, buffer.length)) != -1) {
+            rest.write(buffer, 0, count);
+        }
+
+        System.out.print("rest " + new String(rest.toByteArray(), 0, rest.size(), "UTF-8"));
+        System.out.println("end " + System.in.read());
+        System.err.println("done");
+    }
+}
+"#;
+
+#[test]
+fn standard_streams_use_the_console_backend() {
+    let jvm = TestJvm::compile("console_streams", &[("Echo.java", ECHO_SOURCE)])
+        .with_stdin(b"first\nsecond line\n");
+
+    jvm.run(|context| {
+        context.call_static::<()>("Echo", "run", "()V", ()).unwrap();
+    });
+
+    assert_eq!(
+        jvm.console.stdout(),
+        "available 18\nfirst 102\nrest irst\nsecond line\nend -1\n"
+    );
+    assert_eq!(jvm.console.stderr(), "done\n");
+}
+
+#[test]
+fn empty_input_ends_immediately() {
+    let jvm = TestJvm::compile("console_empty", &[("Echo.java", ECHO_SOURCE)]);
+
+    jvm.run(|context| {
+        context.call_static::<()>("Echo", "run", "()V", ()).unwrap();
+    });
+
+    assert_eq!(jvm.console.stdout(), "available 0\nfirst -1\nrest end -1\n");
+    assert_eq!(jvm.console.stderr(), "done\n");
+}
@@ -0,0 +1,412 @@
+mod common;
+
+use common::TestJvm;
+use rjvm_core::{Context, Error, JvmString, Object};
+
+const EMBED_SOURCE: &str = r#"
+public class Embed {
+    // Initializing the class allocates, so a GC can run between converting
+    // the arguments of the first call and calling the method
+    static {
+        churn();
+    }
+
+    public int count;
+    public String name;
+
+    public Embed(String name, long count) {
+        churn();
+        this.name = name;
+        this.count = (int) count;
+    }
+
+    static void churn() {
+        for (int i = 0; i < 50; i++) {
+            new StringBuilder().append("garbage").append(i).toString();
+        }
+    }
+
+    public static long wide(long a, int b, double c, long d) {
+        return a + b + (long) c + d;
+    }
+
+    public static String describe(boolean z, byte b, char c, short s, float f) {
+        return z + " " + b + " " + c + " " + s + " " + f;
+    }
+
+    public static int[] squares(int[] values) {
+        int[] result = new int[values.length];
+        for (int i = 0; i < values.length; i++) {
+            result[i] = values[i] * values[i];
+        }
+        return result;
+    }
+
+    public static String[] doubled(String[] values) {
+        String[] result = new String[values.length];
+        for (int i = 0; i < values.length; i++) {
+            result[i] = values[i] + values[i];
+        }
+        return result;
+    }
+
+    public static String echo(String value) {
+        churn();
+        return value;
+    }
+
+    public static String join(String first, Object second) {
+        churn();
+        return first + "+" + second;
+    }
+
+    // `max` is the largest value of its type, passed in so that the
+    // operations aren't folded by javac
+    public static String overflowInt(int max) {
+        int min = max + 1;
+        int incremented = max;
+        incremented++;
+        return min + " " + (max * max) + " " + (min - 1) + " " + (min / -1) + " "
+            + (min % -1) + " " + (-min) + " " + incremented;
+    }
+
+    public static String overflowLong(long max) {
+        long min = max + 1;
+        return min + " " + (max * max) + " " + (min - 1) + " " + (min / -1) + " "
+            + (min % -1) + " " + (-min);
+    }
+
+    public static Object nothing() {
+        return null;
+    }
+
+    public static void fail(String message) {
+        throw new IllegalStateException(message);
+    }
+
+    public String label(String suffix) {
+        churn();
+        return name + ":" + count + suffix;
+    }
+}
+
+class Special extends Embed {
+    public String name = "shadow";
+
+    Special() {
+        super("special", 7);
+    }
+
+    public String label(String suffix) {
+        return "special" + suffix;
+    }
+}
+"#;
+
+fn compile(name: &str) -> TestJvm {
+    TestJvm::compile(name, &[("Embed.java", EMBED_SOURCE)])
+}
+
+fn lookup_class(context: &Context, name: &str) -> rjvm_core::Class {
+    let name = JvmString::new(context.gc_ctx(), name.to_string());
+
+    context
+        .system_loader()
+        .lookup_class(context, name)
+        .expect("Test class should load")
+}
+
+fn error_class(context: &Context, error: Error) -> String {
+    error.info(context).class_name
+}
+
+#[test]
+fn arguments_and_results_are_converted() {
+    compile("embed_conversions").run(|context| {
+        let wide: i64 = context
+            .call_static("Embed", "wide", "(JIDJ)J", (1i64 << 40, 2, 3.5, -1i64))
+            .unwrap();
+        assert_eq!(wide, (1 << 40) + 4);
+
+        let description: String = context
+            .call_static(
+                "Embed",
+                "describe",
+                "(ZBCSF)Ljava/lang/String;",
+                (true, -3i8, 'x' as u16, 300i16, 1.5f32),
+            )
+            .unwrap();
+        assert_eq!(description, "true -3 x 300 1.5");
+
+        let squares: Vec<i32> = context
+            .call_static("Embed", "squares", "([I)[I", (vec![1, 2, 3],))
+            .unwrap();
+        assert_eq!(squares, [1, 4, 9]);
+
+        let doubled: Vec<String> = context
+            .call_static(
+                "Embed",
+                "doubled",
+                "([Ljava/lang/String;)[Ljava/lang/String;",
+                (vec!["a", "bc"],),
+            )
+            .unwrap();
+        assert_eq!(doubled, ["aa", "bcbc"]);
+
+        let joined: String = context
+            .call_static(
+                "Embed",
+                "join",
+                "(Ljava/lang/String;Ljava/lang/Object;)Ljava/lang/String;",
+                ("left".to_string(), None::<Object>),
+            )
+            .unwrap();
+        assert_eq!(joined, "left+null");
+
+        let nothing: Option<Object> = context
+            .call_static("Embed", "nothing", "()Ljava/lang/Object;", ())
+            .unwrap();
+        assert!(nothing.is_none());
+        let nothing: Option<String> = context
+            .call_static(
+                "Embed",
+                "echo",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                (None::<Object>,),
+            )
+            .unwrap();
+        assert!(nothing.is_none());
+    });
+}
+
+#[test]
+fn arithmetic_wraps_on_overflow() {
+    compile("embed_overflow").run(|context| {
+        let result: String = context
+            .call_static("Embed", "overflowInt", "(I)Ljava/lang/String;", (i32::MAX,))
+            .unwrap();
+        assert_eq!(
+            result,
+            "-2147483648 1 2147483647 -2147483648 0 -2147483648 -2147483648"
+        );
+
+        let result: String = context
+            .call_static(
+                "Embed",
+                "overflowLong",
+                "(J)Ljava/lang/String;",
+                (i64::MAX,),
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            "-9223372036854775808 1 9223372036854775807 -9223372036854775808 0 \
+             -9223372036854775808"
+        );
+    });
+}
+
+#[test]
+fn mismatched_types_are_rejected() {
+    compile("embed_mismatches").run(|context| {
+        // An `int` where a `long` is expected
+        let error = context
+            .call_static::<i64>("Embed", "wide", "(JIDJ)J", (1, 2, 3.5, 4i64))
+            .unwrap_err();
+        assert_eq!(
+            error_class(context, error),
+            "java.lang.IllegalArgumentException"
+        );
+
+        let error = context
+            .call_static::<i64>("Embed", "wide", "(JIDJ)J", (1i64, 2))
+            .unwrap_err();
+        assert_eq!(
+            error_class(context, error),
+            "java.lang.IllegalArgumentException"
+        );
+
+        // A `String[]` isn't an `int[]`
+        let error = context
+            .call_static::<Vec<i32>>("Embed", "squares", "([I)[I", (vec!["1"],))
+            .unwrap_err();
+        assert_eq!(
+            error_class(context, error),
+            "java.lang.IllegalArgumentException"
+        );
+
+        let error = context
+            .call_static::<i32>(
+                "Embed",
+                "echo",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                ("a",),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error_class(context, error),
+            "java.lang.IllegalArgumentException"
+        );
+
+        // A non-null result can't be returned as `()`
+        let error = context
+            .call_static::<()>(
+                "Embed",
+                "echo",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                ("a",),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error_class(context, error),
+            "java.lang.IllegalArgumentException"
+        );
+
+        let error = context
+            .call_static::<()>("Embed", "missing", "()V", ())
+            .unwrap_err();
+        assert_eq!(error_class(context, error), "java.lang.NoSuchMethodError");
+
+        let error = context
+            .call_static::<()>("Embed", "fail", "(Ljava/lang/String;)V", ("thrown",))
+            .unwrap_err();
+        let info = error.info(context);
+        assert_eq!(info.class_name, "java.lang.IllegalStateException");
+        assert_eq!(info.message.as_deref(), Some("thrown"));
+    });
+}
+
+#[test]
+fn objects_and_fields_are_accessed_by_name() {
+    compile("embed_objects").run(|context| {
+        let class = lookup_class(context, "Embed");
+        let object = class
+            .new_object(context, "(Ljava/lang/String;J)V", ("first", 5i64))
+            .unwrap();
+
+        let label: String = object
+            .call_virtual(
+                context,
+                "label",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                ("!",),
+            )
+            .unwrap();
+        assert_eq!(label, "first:5!");
+
+        object.set_field_by_name(context, "count", 9).unwrap();
+        object.set_field_by_name(context, "name", "second").unwrap();
+        assert_eq!(
+            object.get_field_by_name::<i32>(context, "count").unwrap(),
+            9
+        );
+        assert_eq!(
+            object.get_field_by_name::<String>(context, "name").unwrap(),
+            "second"
+        );
+
+        let error = object
+            .set_field_by_name(context, "count", "nine")
+            .unwrap_err();
+        assert_eq!(
+            error_class(context, error),
+            "java.lang.IllegalArgumentException"
+        );
+        let error = object
+            .get_field_by_name::<i64>(context, "count")
+            .unwrap_err();
+        assert_eq!(
+            error_class(context, error),
+            "java.lang.IllegalArgumentException"
+        );
+        let error = object
+            .get_field_by_name::<i32>(context, "missing")
+            .unwrap_err();
+        assert_eq!(error_class(context, error), "java.lang.NoSuchFieldError");
+
+        let error = class.new_object(context, "(I)V", (1,)).unwrap_err();
+        assert_eq!(error_class(context, error), "java.lang.NoSuchMethodError");
+
+        // The subclass overrides `label` and shadows `name`
+        let special = lookup_class(context, "Special")
+            .new_object(context, "()V", ())
+            .unwrap();
+        let label: String = special
+            .call_virtual(
+                context,
+                "label",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                ("?",),
+            )
+            .unwrap();
+        assert_eq!(label, "special?");
+        assert_eq!(
+            special
+                .get_field_by_name::<String>(context, "name")
+                .unwrap(),
+            "shadow"
+        );
+        assert_eq!(
+            special.get_field_by_name::<i32>(context, "count").unwrap(),
+            7
+        );
+    });
+}
+
+#[test]
+fn arguments_are_kept_alive_across_a_gc() {
+    compile("embed_gc").run(|context| {
+        context.set_gc_threshold(1);
+
+        // The class is loaded and initialized after the argument is converted
+        let echoed: String = context
+            .call_static(
+                "Embed",
+                "echo",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                ("kept",),
+            )
+            .unwrap();
+        assert_eq!(echoed, "kept");
+
+        let array = vec!["in", "array"]
+            .into_iter()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        let doubled: Vec<String> = context
+            .call_static(
+                "Embed",
+                "doubled",
+                "([Ljava/lang/String;)[Ljava/lang/String;",
+                (array,),
+            )
+            .unwrap();
+        assert_eq!(doubled, ["inin", "arrayarray"]);
+
+        let object = lookup_class(context, "Embed")
+            .new_object(context, "(Ljava/lang/String;J)V", ("constructed", 3i64))
+            .unwrap();
+
+        // Both the receiver and the argument must survive the GC
+        let label: String = object
+            .call_virtual(
+                context,
+                "label",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                ("-suffix",),
+            )
+            .unwrap();
+        assert_eq!(label, "constructed:3-suffix");
+
+        // An object created by Java code, then passed back to it
+        let joined: String = context
+            .call_static(
+                "Embed",
+                "join",
+                "(Ljava/lang/String;Ljava/lang/Object;)Ljava/lang/String;",
+                ("first", object),
+            )
+            .unwrap();
+        assert!(joined.starts_with("first+Embed@"));
+    });
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::TestJvm;
+use rjvm_core::{Context, Error, Object};
+
+const ERRORS_SOURCE: &str = r#"
+public class Errors {
+    public static native void failWithCause();
+
+    public static String describeCause() {
+        try {
+            failWithCause();
+            return "not thrown";
+        } catch (IllegalStateException e) {
+            Throwable cause = e.getCause();
+            return e.getMessage() + " caused by " + cause.getClass().getName() + ": " + cause.getMessage();
+        }
+    }
+
+    public static native void callBack();
+
+    public static void fail() {
+        RuntimeException error = new RuntimeException("outer", new IllegalArgumentException("inner"));
+        error.addSuppressed(new IllegalStateException("suppressed"));
+        throw error;
+    }
+
+    public static void run() {
+        callBack();
+    }
+}
+"#;
+
+fn compile(name: &str) -> TestJvm {
+    TestJvm::compile(name, &[("Errors.java", ERRORS_SOURCE)])
+}
+
+fn fail_with_cause(context: &Context) -> Result<(), Error> {
+    let Error(cause) = context.throw_new("java/io/IOException", "disk on fire");
+
+    Err(context.throw_with_cause("java/lang/IllegalStateException", "outer", cause))
+}
+
+fn call_back(context: &Context) -> Result<(), Error> {
+    context.call_static("Errors", "fail", "()V", ())
+}
+
+#[test]
+fn thrown_errors_have_their_cause() {
+    compile("errors_throw_with_cause").run(|context| {
+        context
+            .register_static_native("Errors.failWithCause.()V", fail_with_cause)
+            .unwrap();
+
+        // The cause must survive the GC run by the constructor of the error
+        context.set_gc_threshold(1);
+
+        let description: String = context
+            .call_static("Errors", "describeCause", "()Ljava/lang/String;", ())
+            .unwrap();
+        assert_eq!(
+            description,
+            "outer caused by java.io.IOException: disk on fire"
+        );
+
+        let Error(cause) = context.throw_new("java/lang/IllegalArgumentException", "inner");
+        let Error(error) = context.throw_with_cause("java/lang/RuntimeException", "outer", cause);
+        let error_cause: Object = error
+            .call_virtual(context, "getCause", "()Ljava/lang/Throwable;", ())
+            .unwrap();
+        assert!(error_cause.ptr_eq(cause));
+
+        let message: String = error_cause
+            .call_virtual(context, "getMessage", "()Ljava/lang/String;", ())
+            .unwrap();
+        assert_eq!(message, "inner");
+    });
+}
+
+#[test]
+fn error_info_describes_the_throwable() {
+    compile("errors_info").run(|context| {
+        context
+            .register_static_native("Errors.callBack.()V", call_back)
+            .unwrap();
+
+        let error = context
+            .call_static::<()>("Errors", "run", "()V", ())
+            .unwrap_err();
+        let info = error.info(context);
+
+        assert_eq!(info.class_name, "java.lang.RuntimeException");
+        assert_eq!(info.message.as_deref(), Some("outer"));
+
+        let methods = info
+            .frames
+            .iter()
+            .map(|frame| frame.method_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(methods, ["fail", "callBack", "run"]);
+
+        // Source positions aren't tracked, so every frame has an unknown line
+        let fail = &info.frames[0];
+        assert_eq!(fail.class_name, "Errors");
+        assert_eq!(fail.file_name, None);
+        assert_eq!(fail.line_number, None);
+        assert!(!fail.is_native);
+
+        let call_back = &info.frames[1];
+        assert_eq!(call_back.class_name, "Errors");
+        assert_eq!(call_back.line_number, None);
+        assert!(call_back.is_native);
+
+        let cause = info.cause.expect("Error should have a cause");
+        assert_eq!(cause.class_name, "java.lang.IllegalArgumentException");
+        assert_eq!(cause.message.as_deref(), Some("inner"));
+        assert!(cause.cause.is_none());
+        assert_eq!(cause.frames[0].method_name, "fail");
+
+        assert_eq!(info.suppressed.len(), 1);
+        let suppressed = &info.suppressed[0];
+        assert_eq!(suppressed.class_name, "java.lang.IllegalStateException");
+        assert_eq!(suppressed.message.as_deref(), Some("suppressed"));
+        assert!(suppressed.suppressed.is_empty());
+    });
+}
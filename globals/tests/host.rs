@@ -0,0 +1,112 @@
+mod common;
+
+use common::TestJvm;
+use rjvm_core::{Context, Error, Method, Object, Value};
+
+const HOST_SOURCE: &str = r#"
+public interface Transformer {
+    String repeat(String value, long times);
+
+    int length(String value);
+
+    default String describe(String value) {
+        return repeat(value, 2) + "/" + length(value);
+    }
+}
+
+class Holder {
+    static Transformer transformer;
+
+    static void store(Transformer value) {
+        transformer = value;
+    }
+
+    static void churn() {
+        for (int i = 0; i < 50; i++) {
+            new StringBuilder().append("garbage").append(i).toString();
+        }
+    }
+
+    static String run(String value) {
+        churn();
+        return transformer.describe(value) + " " + transformer.repeat("ab", 3L);
+    }
+}
+"#;
+
+// Implements `Transformer`, appending `suffix` to the results of `repeat`.
+fn transform(
+    context: &Context,
+    suffix: &Object,
+    method: Method,
+    args: &[Value],
+) -> Result<Option<Value>, Error> {
+    let value = args[0].object().expect("Argument should not be null");
+
+    // Run Java code, and so a GC, before the argument is used
+    context.call_static::<()>("Holder", "churn", "()V", ())?;
+    let value = Context::string_object_to_string(value);
+
+    let result = match &*method.name().to_string() {
+        "repeat" => {
+            // `long` arguments take up two slots
+            let times = args[1].long();
+            assert_eq!(args.len(), 3);
+
+            let suffix = Context::string_object_to_string(*suffix);
+            let repeated = (value.repeat(times as usize) + &suffix)
+                .encode_utf16()
+                .collect::<Vec<_>>();
+            Value::Object(Some(context.create_string(&repeated)))
+        }
+        "length" => Value::Integer(value.len() as i32),
+        other => panic!("Unexpected method {}", other),
+    };
+
+    Ok(Some(result))
+}
+
+fn string(context: &Context, value: &str) -> Object {
+    context.create_string(&value.encode_utf16().collect::<Vec<_>>())
+}
+
+#[test]
+fn implemented_interfaces_survive_a_gc() {
+    TestJvm::compile("host_gc", &[("Transformer.java", HOST_SOURCE)]).run(|context| {
+        context.set_gc_threshold(1);
+
+        // The suffix is only referenced by the captures of the callback
+        let suffix = string(context, "!");
+        let transformer = context
+            .implement_interface("Transformer", suffix, transform)
+            .unwrap();
+        context
+            .call_static::<()>("Holder", "store", "(LTransformer;)V", (transformer,))
+            .unwrap();
+
+        // The object is only referenced by Java code now, and collections run
+        // before it and its captures are used
+        context
+            .call_static::<()>("Holder", "churn", "()V", ())
+            .unwrap();
+
+        let result: String = context
+            .call_static(
+                "Holder",
+                "run",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                ("xyz",),
+            )
+            .unwrap();
+        assert_eq!(result, "xyzxyz!/3 ababab!");
+
+        // Objects created later share the synthesized class
+        let other: Object = context
+            .implement_interface("Transformer", string(context, "?"), transform)
+            .unwrap();
+        let length: i32 = other
+            .call_virtual(context, "length", "(Ljava/lang/String;)I", ("four",))
+            .unwrap();
+        assert_eq!(length, 4);
+    });
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::TestJvm;
+use rjvm_core::{Context, JvmString, Object};
+
+const STREAMS_SOURCE: &str = r#"
+import java.io.FileDescriptor;
+import java.io.FileInputStream;
+import java.io.FileOutputStream;
+import java.io.IOException;
+
+public class Streams {
+    public static String roundTrip() throws IOException {
+        FileOutputStream output = new FileOutputStream("data.bin");
+        output.write(new byte[] { 1, 2, 3 }, 0, 3);
+        output.write(4);
+        output.close();
+
+        FileInputStream input = new FileInputStream("data.bin");
+        String result = input.available() + ":";
+        int value;
+        while ((value = input.read()) != -1) {
+            result += " " + value;
+        }
+        input.close();
+
+        return result;
+    }
+
+    public static String read(FileDescriptor fd) {
+        try {
+            return "read " + new FileInputStream(fd).read();
+        } catch (IOException e) {
+            return "IOException: " + e.getMessage();
+        }
+    }
+
+    public static String write(FileDescriptor fd) {
+        try {
+            new FileOutputStream(fd).write(1);
+            return "written";
+        } catch (IOException e) {
+            return "IOException: " + e.getMessage();
+        }
+    }
+
+    public static String readStandardOutput() {
+        return read(FileDescriptor.out) + ", " + read(FileDescriptor.err);
+    }
+}
+"#;
+
+fn compile(name: &str) -> TestJvm {
+    TestJvm::compile(name, &[("Streams.java", STREAMS_SOURCE)])
+}
+
+// A `FileDescriptor` that claims to be open with the given descriptor.
+fn descriptor(context: &Context, descriptor: i32) -> Object {
+    let name = JvmString::new(context.gc_ctx(), "java/io/FileDescriptor".to_string());
+    let class = context
+        .bootstrap_loader()
+        .lookup_class(context, name)
+        .unwrap();
+
+    let fd = class.new_object(context, "()V", ()).unwrap();
+    fd.set_field_by_name(context, "descriptor", descriptor)
+        .unwrap();
+
+    fd
+}
+
+#[test]
+fn files_are_written_and_read_back() {
+    let jvm = compile("io_round_trip");
+    jvm.run(|context| {
+        let result: String = context
+            .call_static("Streams", "roundTrip", "()Ljava/lang/String;", ())
+            .unwrap();
+        assert_eq!(result, "4: 1 2 3 4");
+    });
+
+    assert_eq!(jvm.filesystem.read_file("data.bin").unwrap(), [1, 2, 3, 4]);
+}
+
+#[test]
+fn output_streams_cannot_be_read() {
+    compile("io_read_output").run(|context| {
+        let result: String = context
+            .call_static("Streams", "readStandardOutput", "()Ljava/lang/String;", ())
+            .unwrap();
+        assert_eq!(
+            result,
+            "IOException: Standard output streams can't be read, \
+             IOException: Standard output streams can't be read"
+        );
+    });
+}
+
+#[test]
+fn negative_descriptors_are_rejected() {
+    compile("io_negative_descriptor").run(|context| {
+        for method in ["read", "write"] {
+            let fd = descriptor(context, -5);
+            let result: String = context
+                .call_static(
+                    "Streams",
+                    method,
+                    "(Ljava/io/FileDescriptor;)Ljava/lang/String;",
+                    (fd,),
+                )
+                .unwrap();
+            assert_eq!(result, "IOException: Invalid file descriptor");
+        }
+    });
+}
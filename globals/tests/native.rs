@@ -0,0 +1,197 @@
+mod common;
+
+use common::TestJvm;
+use rjvm_core::{Context, Error, JvmString, NativeRegistrationError, Object};
+
+const NATIVES_SOURCE: &str = r#"
+public class Natives {
+    int base;
+
+    public Natives(int base) {
+        this.base = base;
+    }
+
+    public static native long add(long a, int b);
+
+    public native int offset(int value);
+
+    public static native int misregistered();
+
+    public static native String greeting(String name);
+
+    public static String run() {
+        return add(1L << 40, 2) + " " + new Natives(10).offset(5);
+    }
+
+    public static String greetAfterGarbage(String name) {
+        for (int i = 0; i < 50; i++) {
+            new StringBuilder().append("garbage").append(i).toString();
+        }
+        return greeting(name);
+    }
+}
+
+class Mismatched {
+    public native int value();
+}
+"#;
+
+fn compile(name: &str) -> TestJvm {
+    TestJvm::compile(name, &[("Natives.java", NATIVES_SOURCE)])
+}
+
+fn add(_context: &Context, a: i64, b: i32) -> Result<i64, Error> {
+    Ok(a + b as i64)
+}
+
+fn offset(context: &Context, this: Object, value: i32) -> Result<i32, Error> {
+    let base: i32 = this.get_field_by_name(context, "base")?;
+
+    Ok(base + value)
+}
+
+fn constant(_context: &Context) -> Result<i32, Error> {
+    Ok(1)
+}
+
+// Prefixes `name` with the captured greeting.
+fn greeting(context: &Context, greeting: &Object, name: Object) -> Result<Object, Error> {
+    let greeting = Context::string_object_to_string(*greeting);
+    let name = Context::string_object_to_string(name);
+    let result = format!("{}, {}", greeting, name)
+        .encode_utf16()
+        .collect::<Vec<_>>();
+
+    Ok(context.create_string(&result))
+}
+
+// An instance method of `Natives` without arguments
+fn offset_free(_context: &Context, _this: Object) -> Result<i32, Error> {
+    Ok(0)
+}
+
+#[test]
+fn signatures_are_checked_on_registration() {
+    compile("native_signatures").run(|context| {
+        assert!(matches!(
+            context.register_static_native("Natives.add", add),
+            Err(NativeRegistrationError::InvalidName(_))
+        ));
+        assert!(matches!(
+            context.register_static_native("Natives.add.(JI", add),
+            Err(NativeRegistrationError::InvalidDescriptor(_))
+        ));
+        assert!(matches!(
+            context.register_static_native("Natives.add.(J)J", add),
+            Err(NativeRegistrationError::ArgumentCount {
+                expected: 1,
+                found: 2
+            })
+        ));
+        assert!(matches!(
+            context.register_static_native("Natives.add.(JJ)J", add),
+            Err(NativeRegistrationError::ArgumentType { index: 1, .. })
+        ));
+        assert!(matches!(
+            context.register_static_native("Natives.add.(JI)I", add),
+            Err(NativeRegistrationError::ReturnType { .. })
+        ));
+
+        // Instance methods take the receiver first
+        assert!(matches!(
+            context.register_instance_native("Natives.offset.(II)I", offset),
+            Err(NativeRegistrationError::ArgumentCount {
+                expected: 2,
+                found: 2
+            })
+        ));
+        assert!(matches!(
+            context.register_instance_native("Natives.add.(I)J", add),
+            Err(NativeRegistrationError::ArgumentType { index: 0, .. })
+        ));
+        assert!(matches!(
+            context.register_static_native("Natives.offset.(I)I", offset),
+            Err(NativeRegistrationError::ArgumentCount {
+                expected: 1,
+                found: 2
+            })
+        ));
+    });
+}
+
+#[test]
+fn registered_natives_are_called() {
+    compile("native_calls").run(|context| {
+        context
+            .register_static_native("Natives.add.(JI)J", add)
+            .unwrap();
+        context
+            .register_instance_native("Natives.offset.(I)I", offset)
+            .unwrap();
+
+        let result: String = context
+            .call_static("Natives", "run", "()Ljava/lang/String;", ())
+            .unwrap();
+        assert_eq!(result, format!("{} 15", (1i64 << 40) + 2));
+    });
+}
+
+#[test]
+fn captured_objects_survive_a_gc() {
+    compile("native_captures").run(|context| {
+        context.set_gc_threshold(1);
+
+        // The greeting is only referenced by the captures of the native
+        let greeting_object = context.create_string(&"Hello".encode_utf16().collect::<Vec<_>>());
+        context
+            .register_static_native_with(
+                "Natives.greeting.(Ljava/lang/String;)Ljava/lang/String;",
+                greeting_object,
+                greeting,
+            )
+            .unwrap();
+
+        let result: String = context
+            .call_static(
+                "Natives",
+                "greetAfterGarbage",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                ("world",),
+            )
+            .unwrap();
+        assert_eq!(result, "Hello, world");
+    });
+}
+
+#[test]
+fn mismatched_static_natives_fail_to_link() {
+    compile("native_mismatches").run(|context| {
+        // Both signatures are valid, but are registered as the wrong kind
+        context
+            .register_instance_native("Natives.misregistered.()I", offset_free)
+            .unwrap();
+        context
+            .register_static_native("Mismatched.value.()I", constant)
+            .unwrap();
+
+        for (class_name, registered_as) in [
+            ("Natives", "an instance method"),
+            ("Mismatched", "a static method"),
+        ] {
+            let name = JvmString::new(context.gc_ctx(), class_name.to_string());
+            let error = context
+                .system_loader()
+                .lookup_class(context, name)
+                .unwrap_err();
+
+            let info = error.info(context);
+            assert_eq!(info.class_name, "java.lang.LinkageError");
+            let message = info.message.unwrap();
+            assert!(
+                message.ends_with(&format!("was registered as {}", registered_as)),
+                "unexpected message {}",
+                message
+            );
+        }
+    });
+}
@@ -0,0 +1,293 @@
+mod common;
+
+use common::TestJvm;
+use rjvm_core::{
+    ClassBuilder, ClassFlags, Context, Error, Method, MethodFlags, Object, Operation,
+    SecurityPolicy, Value,
+};
+
+const SANDBOX_SOURCE: &str = r#"
+import java.io.ByteArrayOutputStream;
+import java.io.File;
+import java.io.FileInputStream;
+import java.io.IOException;
+import java.security.AccessControlException;
+import java.security.AccessController;
+import java.security.PrivilegedAction;
+
+public class Sandbox {
+    static String denied(AccessControlException e) {
+        return "denied " + e.getMessage();
+    }
+
+    public static String exists(String path) {
+        try {
+            return "exists " + new File(path).exists();
+        } catch (AccessControlException e) {
+            return denied(e);
+        }
+    }
+
+    public static String getenv(String name) {
+        try {
+            return "getenv " + System.getenv(name);
+        } catch (AccessControlException e) {
+            return denied(e);
+        }
+    }
+
+    public static String exit(int status) {
+        try {
+            System.exit(status);
+            return "exited";
+        } catch (AccessControlException e) {
+            return denied(e);
+        }
+    }
+
+    public static String define(String name) throws IOException {
+        FileInputStream input = new FileInputStream("/" + name + ".class");
+        ByteArrayOutputStream data = new ByteArrayOutputStream();
+        byte[] buffer = new byte[256];
+        int count;
+        while ((count = input.read(buffer, 0, buffer.length)) != -1) {
+            data.write(buffer, 0, count);
+        }
+        input.close();
+
+        byte[] bytes = data.toByteArray();
+        try {
+            return "defined " + new PluginLoader().define(name, bytes).getName();
+        } catch (AccessControlException e) {
+            return denied(e);
+        }
+    }
+
+    public static String privileged(PrivilegedAction<Object> action) {
+        try {
+            return "privileged " + AccessController.doPrivileged(action);
+        } catch (AccessControlException e) {
+            return denied(e);
+        }
+    }
+
+    public static PrivilegedAction<Object> envAction() {
+        return new EnvAction();
+    }
+}
+
+class EnvAction implements PrivilegedAction<Object> {
+    public Object run() {
+        return System.getenv("SECRET");
+    }
+}
+
+class PluginLoader extends ClassLoader {
+    Class<?> define(String name, byte[] data) {
+        return defineClass(name, data, 0, data.length);
+    }
+}
+
+class Plugin {
+}
+"#;
+
+fn compile(name: &str) -> TestJvm {
+    TestJvm::compile(name, &[("Sandbox.java", SANDBOX_SOURCE)])
+}
+
+fn call(context: &Context, method: &str, arg: &str) -> String {
+    context
+        .call_static(
+            "Sandbox",
+            method,
+            "(Ljava/lang/String;)Ljava/lang/String;",
+            (arg,),
+        )
+        .unwrap()
+}
+
+fn denied(permission: &str) -> String {
+    format!("denied access denied ({})", permission)
+}
+
+#[test]
+fn denied_operations_throw() {
+    let jvm = compile("security_denied")
+        .with_link("/data/escape", "/secret/dir")
+        .with_link("/data/shared", "/public/files");
+    jvm.filesystem
+        .write_file("/data/public/file.txt", b"")
+        .unwrap();
+    jvm.filesystem.write_file("/secret/key.txt", b"").unwrap();
+    jvm.filesystem
+        .write_file("/public/files/notes.txt", b"")
+        .unwrap();
+
+    jvm.run(|context| {
+        let mut policy = SecurityPolicy::deny_all();
+        policy.allow(Operation::ReadFile, "/data/");
+        policy.deny(Operation::ReadFile, "/data/private/");
+        policy.allow(Operation::ReadFile, "/data/private/shared/");
+        policy.allow(Operation::ReadFile, "/public/");
+        // Of rules with the same prefix, the last one wins
+        policy.allow(Operation::ReadEnvironment, "APP_");
+        policy.deny(Operation::ReadEnvironment, "APP_");
+        policy.allow(Operation::ReadEnvironment, "PUBLIC_");
+        policy.allow(Operation::ReadFile, "/Plugin.class");
+        context.set_security_policy(policy);
+
+        // The rule with the longest matching prefix decides
+        assert_eq!(
+            call(context, "exists", "/data/public/file.txt"),
+            "exists true"
+        );
+        assert_eq!(
+            call(context, "exists", "/data/private/file.txt"),
+            denied("\"java.io.FilePermission\" \"/data/private/file.txt\" \"read\"")
+        );
+        assert_eq!(
+            call(context, "exists", "/data/private/shared/file.txt"),
+            "exists false"
+        );
+        assert_eq!(
+            call(context, "exists", "/data/public/../private/file.txt"),
+            denied("\"java.io.FilePermission\" \"/data/private/file.txt\" \"read\"")
+        );
+        assert_eq!(
+            call(context, "exists", "/secret/key.txt"),
+            denied("\"java.io.FilePermission\" \"/secret/key.txt\" \"read\"")
+        );
+
+        // Links are resolved before the policy is checked, and before the
+        // `..` components after them
+        assert_eq!(
+            call(context, "exists", "/data/escape/key.txt"),
+            denied("\"java.io.FilePermission\" \"/secret/dir/key.txt\" \"read\"")
+        );
+        assert_eq!(
+            call(context, "exists", "/data/escape/../key.txt"),
+            denied("\"java.io.FilePermission\" \"/secret/key.txt\" \"read\"")
+        );
+
+        // The platform is given the resolved path that was checked
+        assert_eq!(
+            call(context, "exists", "/data/shared/notes.txt"),
+            "exists true"
+        );
+
+        assert_eq!(
+            call(context, "getenv", "APP_TOKEN"),
+            denied("\"java.lang.RuntimePermission\" \"getenv.APP_TOKEN\"")
+        );
+        assert_eq!(call(context, "getenv", "PUBLIC_NAME"), "getenv null");
+
+        let result: String = context
+            .call_static("Sandbox", "exit", "(I)Ljava/lang/String;", (3,))
+            .unwrap();
+        assert_eq!(
+            result,
+            denied("\"java.lang.RuntimePermission\" \"exitVM.3\"")
+        );
+
+        assert_eq!(
+            call(context, "define", "Plugin"),
+            denied("\"java.lang.RuntimePermission\" \"defineClass\" \"Plugin\"")
+        );
+
+        let mut policy = SecurityPolicy::deny_all();
+        policy.allow(Operation::ReadFile, "/Plugin.class");
+        policy.allow(Operation::DefineClass, "Plugin");
+        context.set_security_policy(policy);
+        assert_eq!(call(context, "define", "Plugin"), "defined Plugin");
+    });
+}
+
+// java.security.PrivilegedAction : Object run()
+fn read_secret(
+    context: &Context,
+    _captures: &(),
+    _method: Method,
+    _args: &[Value],
+) -> Result<Option<Value>, Error> {
+    let value: Option<Object> = context.call_static(
+        "java/lang/System",
+        "getenv",
+        "(Ljava/lang/String;)Ljava/lang/String;",
+        ("SECRET",),
+    )?;
+
+    Ok(Some(Value::Object(value)))
+}
+
+// Trusted : static Object run(PrivilegedAction)
+fn trusted_run(context: &Context, action: Object) -> Result<Option<Object>, Error> {
+    context.call_static(
+        "java/security/AccessController",
+        "doPrivileged",
+        "(Ljava/security/PrivilegedAction;)Ljava/lang/Object;",
+        (action,),
+    )
+}
+
+#[test]
+fn only_trusted_code_is_privileged() {
+    compile("security_privileged").run(|context| {
+        context.set_security_policy(SecurityPolicy::deny_all());
+
+        // A class loaded by the bootstrap loader, which calls `doPrivileged`
+        let mut builder = ClassBuilder::new(ClassFlags::PUBLIC, "Trusted");
+        builder.add_typed_method(
+            MethodFlags::PUBLIC | MethodFlags::STATIC,
+            "run",
+            "(Ljava/security/PrivilegedAction;)Ljava/lang/Object;",
+            trusted_run,
+        );
+        builder.define(context, context.bootstrap_loader()).unwrap();
+
+        let secret_denied = denied("\"java.lang.RuntimePermission\" \"getenv.SECRET\"");
+        let run_trusted = |action: Object| -> Result<Option<Object>, Error> {
+            context.call_static(
+                "Trusted",
+                "run",
+                "(Ljava/security/PrivilegedAction;)Ljava/lang/Object;",
+                (action,),
+            )
+        };
+
+        // Trusted code running a trusted action is privileged. The class of
+        // the action is defined by the bootstrap loader, like its interface.
+        let trusted_action = context
+            .implement_interface("java/security/PrivilegedAction", (), read_secret)
+            .unwrap();
+        assert!(run_trusted(trusted_action).unwrap().is_none());
+
+        // Code loaded by the system loader can't use `doPrivileged` itself
+        let result: String = context
+            .call_static(
+                "Sandbox",
+                "privileged",
+                "(Ljava/security/PrivilegedAction;)Ljava/lang/String;",
+                (trusted_action,),
+            )
+            .unwrap();
+        assert_eq!(result, secret_denied);
+
+        // ...nor get trusted code to run it with `doPrivileged`
+        let untrusted_action: Object = context
+            .call_static(
+                "Sandbox",
+                "envAction",
+                "()Ljava/security/PrivilegedAction;",
+                (),
+            )
+            .unwrap();
+        let error = run_trusted(untrusted_action).unwrap_err();
+        let info = error.info(context);
+        assert_eq!(info.class_name, "java.security.AccessControlException");
+        assert_eq!(
+            info.message.as_deref(),
+            Some("access denied (\"java.lang.RuntimePermission\" \"getenv.SECRET\")")
+        );
+    });
+}
@@ -0,0 +1,409 @@
+mod common;
+
+use common::TestJvm;
+use rjvm_globals::platform::{OpenMode, PlatformError};
+use rjvm_globals::vfs::MemoryFilesystem;
+
+const GREETER_SOURCE: &str = r#"
+public class Greeter {
+    public static String greet(String name) {
+        return "Hello, " + name;
+    }
+}
+"#;
+
+// Build a zip archive with the given `(name, contents)` entries, which are
+// stored without compression. Names ending in `/` are directories.
+fn zip_archive(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for (name, contents) in entries {
+        let offset = data.len() as u32;
+        let crc = crc32(contents);
+
+        data.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        data.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        data.extend_from_slice(&crc.to_le_bytes());
+        data.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        data.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        data.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        data.extend_from_slice(&[0, 0]);
+        data.extend_from_slice(name.as_bytes());
+        data.extend_from_slice(contents);
+
+        central_directory.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        central_directory.extend_from_slice(&[20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        central_directory.extend_from_slice(&crc.to_le_bytes());
+        central_directory.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        central_directory.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        central_directory.extend_from_slice(&[0; 12]);
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+    }
+
+    let central_directory_offset = data.len() as u32;
+    data.extend_from_slice(&central_directory);
+
+    data.extend_from_slice(&0x06054b50u32.to_le_bytes());
+    data.extend_from_slice(&[0; 4]);
+    data.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    data.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    data.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+    data.extend_from_slice(&central_directory_offset.to_le_bytes());
+    data.extend_from_slice(&[0, 0]);
+
+    data
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+// A tar entry, with the fields of its header that the tests use.
+struct TarEntry<'a> {
+    name: &'a str,
+    prefix: &'a str,
+    type_flag: u8,
+    contents: &'a [u8],
+}
+
+impl<'a> TarEntry<'a> {
+    fn file(name: &'a str, contents: &'a [u8]) -> Self {
+        Self {
+            name,
+            prefix: "",
+            type_flag: b'0',
+            contents,
+        }
+    }
+
+    fn directory(name: &'a str) -> Self {
+        Self {
+            name,
+            prefix: "",
+            type_flag: b'5',
+            contents: &[],
+        }
+    }
+}
+
+// Build a ustar archive from `entries`, all modified at second 1000.
+fn tar_archive(entries: &[TarEntry]) -> Vec<u8> {
+    let mut data = Vec::new();
+
+    for entry in entries {
+        let mut header = [0u8; 512];
+        header[..entry.name.len()].copy_from_slice(entry.name.as_bytes());
+        header[100..107].copy_from_slice(b"0000644");
+        header[124..135].copy_from_slice(format!("{:011o}", entry.contents.len()).as_bytes());
+        header[136..147].copy_from_slice(format!("{:011o}", 1000).as_bytes());
+        header[148..156].copy_from_slice(b"        ");
+        header[156] = entry.type_flag;
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        header[345..345 + entry.prefix.len()].copy_from_slice(entry.prefix.as_bytes());
+
+        let checksum = header.iter().map(|b| *b as u32).sum::<u32>();
+        header[148..155].copy_from_slice(format!("{:06o}\0", checksum).as_bytes());
+
+        data.extend_from_slice(&header);
+        data.extend_from_slice(entry.contents);
+        data.resize(data.len().div_ceil(512) * 512, 0);
+    }
+
+    data.resize(data.len() + 1024, 0);
+
+    data
+}
+
+fn reason<T>(result: Result<T, PlatformError>) -> String {
+    match result {
+        Ok(_) => panic!("Operation should fail"),
+        Err(error) => error.reason().to_string(),
+    }
+}
+
+#[test]
+fn zip_archives_are_mounted_read_only() {
+    let filesystem = MemoryFilesystem::new();
+    let archive = zip_archive(&[
+        ("docs/", b""),
+        ("docs/readme.txt", b"read me"),
+        ("data/values.bin", &[1, 2, 3]),
+    ]);
+    filesystem.mount_zip("/mnt/archive", archive).unwrap();
+
+    assert_eq!(
+        filesystem
+            .read_file("/mnt/archive/docs/readme.txt")
+            .unwrap(),
+        b"read me"
+    );
+    assert_eq!(filesystem.list("/mnt/archive").unwrap(), ["data", "docs"]);
+    assert!(filesystem.stat("/mnt/archive/data").unwrap().is_directory);
+    assert_eq!(
+        filesystem.file_paths(),
+        [
+            "/mnt/archive/data/values.bin",
+            "/mnt/archive/docs/readme.txt"
+        ]
+    );
+
+    // Nothing in the archive can be changed
+    let read_only = "Read-only file system";
+    assert_eq!(
+        reason(filesystem.write_file("/mnt/archive/docs/readme.txt", b"changed")),
+        read_only
+    );
+    assert_eq!(
+        reason(filesystem.write_file("/mnt/archive/docs/new.txt", b"new")),
+        read_only
+    );
+    assert_eq!(
+        reason(filesystem.create_dir_all("/mnt/archive/docs/more")),
+        read_only
+    );
+    assert_eq!(
+        reason(filesystem.delete("/mnt/archive/docs/readme.txt")),
+        read_only
+    );
+    assert_eq!(
+        reason(filesystem.rename("/mnt/archive/docs/readme.txt", "/readme.txt")),
+        read_only
+    );
+    filesystem.write_file("/outside.txt", b"outside").unwrap();
+    assert_eq!(
+        reason(filesystem.rename("/outside.txt", "/mnt/archive/outside.txt")),
+        read_only
+    );
+    assert_eq!(
+        filesystem
+            .read_file("/mnt/archive/docs/readme.txt")
+            .unwrap(),
+        b"read me"
+    );
+
+    // The directory containing the mount point can still be changed
+    filesystem.write_file("/mnt/notes.txt", b"notes").unwrap();
+
+    assert_eq!(
+        reason(filesystem.mount_zip("/mnt/archive", zip_archive(&[]))),
+        "File exists"
+    );
+    assert_eq!(
+        reason(filesystem.mount_zip("/other", b"not a zip".to_vec())),
+        "Invalid zip archive"
+    );
+}
+
+#[test]
+fn tar_archives_are_mounted_read_only() {
+    let filesystem = MemoryFilesystem::new();
+
+    let long_name = format!("long/{}.txt", "n".repeat(120));
+    let pax_record = format!("path={}\n", long_name);
+    let pax_header = format!("{} {}", pax_record.len() + 4, pax_record);
+
+    let archive = tar_archive(&[
+        TarEntry::directory("empty/"),
+        TarEntry::file("top.txt", b"top"),
+        TarEntry {
+            prefix: "prefixed/dir",
+            ..TarEntry::file("inner.txt", b"inner")
+        },
+        TarEntry {
+            type_flag: b'x',
+            ..TarEntry::file("PaxHeaders/truncated", pax_header.as_bytes())
+        },
+        TarEntry::file("truncated", b"long"),
+        TarEntry {
+            type_flag: b'2',
+            ..TarEntry::file("link", b"")
+        },
+    ]);
+    filesystem.mount_tar("/mnt", &archive).unwrap();
+
+    assert_eq!(
+        filesystem.file_paths(),
+        [
+            format!("/mnt/{}", long_name),
+            "/mnt/prefixed/dir/inner.txt".to_string(),
+            "/mnt/top.txt".to_string(),
+        ]
+    );
+    assert!(filesystem.stat("/mnt/empty").unwrap().is_directory);
+    assert_eq!(
+        filesystem
+            .read_file(&format!("/mnt/{}", long_name))
+            .unwrap(),
+        b"long"
+    );
+
+    let metadata = filesystem.stat("/mnt/top.txt").unwrap();
+    assert_eq!(metadata.length, 3);
+    assert_eq!(metadata.last_modified, 1_000_000);
+
+    assert_eq!(
+        reason(filesystem.open("/mnt/top.txt", OpenMode::Write)),
+        "Read-only file system"
+    );
+    assert_eq!(
+        reason(filesystem.delete("/mnt/empty")),
+        "Read-only file system"
+    );
+
+    let truncated = tar_archive(&[TarEntry::file("file.txt", b"contents")]);
+    assert_eq!(
+        reason(filesystem.mount_tar("/other", &truncated[..515])),
+        "Invalid tar archive"
+    );
+}
+
+#[test]
+fn conflicting_archive_entries_are_rejected() {
+    let filesystem = MemoryFilesystem::new();
+
+    let zip = zip_archive(&[("a", b"file"), ("a/b", b"nested")]);
+    assert_eq!(reason(filesystem.mount_zip("/zip", zip)), "Not a directory");
+
+    let tar = tar_archive(&[TarEntry::directory("a/"), TarEntry::file("a", b"file")]);
+    assert_eq!(reason(filesystem.mount_tar("/tar", &tar)), "Is a directory");
+
+    // Nothing was mounted
+    assert!(filesystem.list("/").unwrap().is_empty());
+}
+
+#[test]
+fn snapshots_do_not_share_files() {
+    let filesystem = MemoryFilesystem::new();
+    filesystem
+        .write_file("/data/file.txt", b"original")
+        .unwrap();
+    filesystem
+        .mount_zip("/lib", zip_archive(&[("lib.txt", b"library")]))
+        .unwrap();
+    filesystem.set_current_dir("/data").unwrap();
+    let handle = filesystem.open("file.txt", OpenMode::Read).unwrap();
+
+    let snapshot = filesystem.snapshot();
+    assert_eq!(snapshot.current_dir(), "/data");
+
+    filesystem.write_file("file.txt", b"changed").unwrap();
+    filesystem.write_file("added.txt", b"added").unwrap();
+    assert_eq!(snapshot.read_file("file.txt").unwrap(), b"original");
+    assert!(snapshot.read_file("added.txt").is_none());
+
+    snapshot.delete("file.txt").unwrap();
+    assert_eq!(filesystem.read_file("file.txt").unwrap(), b"changed");
+
+    // Open files aren't copied, and mounted archives stay read-only
+    let mut buf = [0; 8];
+    assert_eq!(
+        reason(snapshot.read(handle, &mut buf)),
+        "Bad file descriptor"
+    );
+    assert_eq!(
+        reason(snapshot.write_file("/lib/lib.txt", b"changed")),
+        "Read-only file system"
+    );
+}
+
+#[test]
+fn renames_and_deletes_check_their_targets() {
+    let filesystem = MemoryFilesystem::new();
+    filesystem.write_file("/dir/file.txt", b"file").unwrap();
+    filesystem.write_file("/other.txt", b"other").unwrap();
+    filesystem.create_dir_all("/empty").unwrap();
+
+    assert_eq!(
+        reason(filesystem.rename("/dir", "/dir/inner")),
+        "Invalid argument"
+    );
+    assert_eq!(
+        reason(filesystem.rename("/other.txt", "/dir")),
+        "Is a directory"
+    );
+    assert_eq!(
+        reason(filesystem.rename("/dir", "/other.txt")),
+        "Not a directory"
+    );
+    assert_eq!(reason(filesystem.rename("/dir", "/empty")), "File exists");
+    assert_eq!(
+        reason(filesystem.rename("/missing", "/found")),
+        "No such file or directory"
+    );
+    assert_eq!(
+        reason(filesystem.rename("/other.txt", "/missing/other.txt")),
+        "No such file or directory"
+    );
+    filesystem.rename("/dir", "/dir").unwrap();
+
+    // Renaming a file over another replaces it
+    filesystem.rename("/other.txt", "/dir/file.txt").unwrap();
+    assert_eq!(filesystem.read_file("/dir/file.txt").unwrap(), b"other");
+    assert!(filesystem.stat("/other.txt").is_none());
+
+    filesystem.rename("/dir", "/moved").unwrap();
+    assert_eq!(filesystem.read_file("/moved/file.txt").unwrap(), b"other");
+
+    assert_eq!(reason(filesystem.delete("/moved")), "Directory not empty");
+    assert_eq!(reason(filesystem.delete("/")), "Device or resource busy");
+    assert_eq!(
+        reason(filesystem.delete("/missing")),
+        "No such file or directory"
+    );
+    filesystem.delete("/empty").unwrap();
+
+    // Open files can still be used after they're deleted
+    let handle = filesystem.open("/moved/file.txt", OpenMode::Read).unwrap();
+    filesystem.delete("/moved/file.txt").unwrap();
+    let mut buf = [0; 8];
+    assert_eq!(filesystem.read(handle, &mut buf).unwrap(), 5);
+    assert_eq!(&buf[..5], b"other");
+    filesystem.close(handle).unwrap();
+
+    filesystem.delete("/moved").unwrap();
+    assert!(filesystem.list("/").unwrap().is_empty());
+}
+
+#[test]
+fn classes_are_loaded_from_mounted_jars() {
+    let jvm = TestJvm::compile("vfs_mounted_jar", &[("Greeter.java", GREETER_SOURCE)]);
+
+    // Move the compiled class into a jar
+    let class_data = jvm.filesystem.read_file("/Greeter.class").unwrap();
+    jvm.filesystem.delete("/Greeter.class").unwrap();
+    let jar = zip_archive(&[
+        ("META-INF/", b""),
+        ("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\n"),
+        ("Greeter.class", &class_data),
+    ]);
+    jvm.filesystem.mount_zip("/app/greeter.jar", jar).unwrap();
+    jvm.filesystem.set_current_dir("/app/greeter.jar").unwrap();
+
+    jvm.run(|context| {
+        let greeting: String = context
+            .call_static(
+                "Greeter",
+                "greet",
+                "(Ljava/lang/String;)Ljava/lang/String;",
+                ("jar",),
+            )
+            .unwrap();
+        assert_eq!(greeting, "Hello, jar");
+    });
+}
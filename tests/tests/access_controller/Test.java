@@ -0,0 +1,29 @@
+import java.security.AccessController;
+import java.security.PrivilegedAction;
+
+public class Test {
+    public static void main(String[] args) {
+        String result = AccessController.doPrivileged(new PrivilegedAction<String>() {
+            public String run() {
+                return "run " + System.getenv("RJVM_UNSET_VARIABLE");
+            }
+        });
+        System.out.println(result);
+
+        try {
+            AccessController.doPrivileged(new PrivilegedAction<Object>() {
+                public Object run() {
+                    throw new IllegalStateException("from action");
+                }
+            });
+        } catch (IllegalStateException e) {
+            System.out.println("Caught " + e.getMessage());
+        }
+
+        try {
+            AccessController.doPrivileged((PrivilegedAction<Object>) null);
+        } catch (NullPointerException e) {
+            System.out.println("Caught NullPointerException");
+        }
+    }
+}
@@ -0,0 +1,126 @@
+import java.lang.annotation.Annotation;
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Inherited;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.lang.reflect.Constructor;
+import java.lang.reflect.Field;
+import java.lang.reflect.Method;
+
+public class Test {
+    @Retention(RetentionPolicy.RUNTIME)
+    @interface Tag {
+        String value();
+    }
+
+    @Retention(RetentionPolicy.RUNTIME)
+    @Inherited
+    @interface Info {
+        int number() default 7;
+        long big() default 5L;
+        float ratio() default 1.5f;
+        char letter() default 'q';
+        byte small() default 3;
+        String name();
+        ElementType kind() default ElementType.FIELD;
+        Class<?> type() default String.class;
+        int[] numbers() default {1, 2, 3};
+        Tag tag() default @Tag("nested");
+        ElementType[] kinds() default {};
+    }
+
+    @interface NotRetained { }
+
+    @Info(name = "parent", numbers = {4, 5})
+    static class Parent { }
+
+    @Tag("child")
+    static class Child extends Parent { }
+
+    @Tag("field")
+    @NotRetained
+    public int annotatedField;
+
+    public int plainField;
+
+    @Info(name = "method", kind = ElementType.METHOD, kinds = {ElementType.TYPE, ElementType.METHOD})
+    public void annotatedMethod(@Tag("first") int a, int b, @Tag("third") @Info(name = "param") String c) { }
+
+    @Tag("ctor")
+    public Test() { }
+
+    public static void main(String[] args) throws Exception {
+        printAnnotations(Parent.class.getDeclaredAnnotations());
+        printAnnotations(Child.class.getDeclaredAnnotations());
+        printAnnotations(Child.class.getAnnotations());
+
+        Info info = Parent.class.getAnnotation(Info.class);
+        System.out.println(info.number());
+        System.out.println(info.big());
+        System.out.println(info.ratio());
+        System.out.println(String.valueOf(info.letter()));
+        System.out.println(info.small());
+        System.out.println(info.name());
+        System.out.println(info.kind());
+        System.out.println(info.type());
+        System.out.println(info.numbers().length);
+        System.out.println(info.tag().value());
+        System.out.println(info.annotationType() == Info.class);
+
+        // Returned arrays should be copies
+        info.numbers()[0] = 100;
+        System.out.println(info.numbers()[0]);
+
+        System.out.println(info.equals(Parent.class.getAnnotation(Info.class)));
+        System.out.println(info.hashCode() == Parent.class.getAnnotation(Info.class).hashCode());
+        System.out.println(info.equals(Child.class.getAnnotation(Tag.class)));
+
+        System.out.println(Child.class.isAnnotationPresent(Info.class));
+        System.out.println(Child.class.isAnnotationPresent(Retention.class));
+        System.out.println(Child.class.getDeclaredAnnotation(Info.class) == null);
+        System.out.println(Info.class.isAnnotation());
+        System.out.println(Info.class.isAnnotationPresent(Inherited.class));
+
+        Field field = findField("annotatedField");
+        printAnnotations(field.getDeclaredAnnotations());
+        printAnnotations(findField("plainField").getAnnotations());
+
+        Method method = Test.class.getMethod("annotatedMethod", int.class, int.class, String.class);
+        printAnnotations(method.getDeclaredAnnotations());
+        Annotation[][] parameterAnnotations = method.getParameterAnnotations();
+        System.out.println(parameterAnnotations.length);
+        for (int i = 0; i < parameterAnnotations.length; i ++) {
+            printAnnotations(parameterAnnotations[i]);
+        }
+
+        Constructor<?> ctor = Test.class.getDeclaredConstructors()[0];
+        System.out.println(ctor.getAnnotation(Tag.class).value());
+
+        System.out.println(Info.class.getMethod("number").getDefaultValue());
+        System.out.println(Info.class.getMethod("name").getDefaultValue());
+        System.out.println(Info.class.getMethod("tag").getDefaultValue());
+    }
+
+    static Field findField(String name) {
+        Field[] fields = Test.class.getDeclaredFields();
+        for (int i = 0; i < fields.length; i ++) {
+            if (fields[i].getName().equals(name)) {
+                return fields[i];
+            }
+        }
+        return null;
+    }
+
+    static void printAnnotations(Annotation[] annotations) {
+        System.out.println(annotations.length);
+        for (int i = 0; i < annotations.length; i ++) {
+            // Member order in `toString` is unspecified, so only print it for
+            // single-member annotations
+            if (annotations[i] instanceof Tag) {
+                System.out.println(annotations[i]);
+            } else {
+                System.out.println(annotations[i].annotationType().getName());
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+import java.io.*;
+
+public class Test {
+    public static void main(String[] args) {
+        try {
+            new FileInputStream("does_not_exist.txt");
+            System.out.println("Opened missing file");
+        } catch (FileNotFoundException e) {
+            System.out.println("Caught " + e);
+        }
+
+        try {
+            new FileInputStream(new File("."));
+            System.out.println("Opened directory");
+        } catch (FileNotFoundException e) {
+            System.out.println("Caught " + e);
+        }
+
+        try {
+            new FileOutputStream("missing_dir/out.txt");
+            System.out.println("Created file in missing directory");
+        } catch (FileNotFoundException e) {
+            System.out.println("Caught " + e);
+        }
+
+        try {
+            new FileReader("does_not_exist.txt");
+            System.out.println("Opened missing file");
+        } catch (IOException e) {
+            System.out.println("Caught " + e.getClass().getName() + ": " + e.getMessage());
+        }
+    }
+}
@@ -0,0 +1,65 @@
+import java.io.*;
+
+public class Test {
+    public static void main(String[] args) throws IOException {
+        File dir = new File("scratch_dir");
+        System.out.println("dir exists: " + dir.exists());
+        System.out.println("dir list: " + dir.list());
+
+        File file = new File("scratch_file.txt");
+        FileOutputStream out = new FileOutputStream(file);
+        out.write('h');
+        out.write("ello".getBytes(), 0, 4);
+        out.flush();
+        out.close();
+
+        try {
+            out.write('!');
+            System.out.println("Wrote to closed stream");
+        } catch (IOException e) {
+            System.out.println("Caught IOException");
+        }
+
+        System.out.println("exists: " + file.exists());
+        System.out.println("isFile: " + file.isFile());
+        System.out.println("isDirectory: " + file.isDirectory());
+        System.out.println("length: " + file.length());
+
+        FileInputStream in = new FileInputStream(file);
+        System.out.println("available: " + in.available());
+        System.out.println("first: " + (char) in.read());
+        System.out.println("available: " + in.available());
+
+        byte[] buffer = new byte[10];
+        int read = in.read(buffer, 2, 8);
+        System.out.println("read: " + read);
+        StringBuilder contents = new StringBuilder();
+        for (int i = 2; i < 2 + read; i++) {
+            contents.append((char) buffer[i]);
+        }
+        System.out.println(contents);
+        System.out.println("at end: " + in.read() + " " + in.read(buffer, 0, 10));
+        in.close();
+
+        File renamed = new File("scratch_renamed.txt");
+        System.out.println("renamed: " + file.renameTo(renamed));
+        System.out.println("old exists: " + file.exists());
+        System.out.println("new exists: " + renamed.exists());
+
+        System.out.println("deleted: " + renamed.delete());
+        System.out.println("deleted again: " + renamed.delete());
+        System.out.println("exists after delete: " + renamed.exists());
+
+        File relative = new File("a/../b/./c");
+        String cwd = System.getProperty("user.dir");
+        System.out.println("absolute: " + relative.getAbsolutePath().equals(cwd + "/a/../b/./c"));
+        System.out.println("canonical: " + relative.getCanonicalPath().equals(cwd + "/b/c"));
+        System.out.println("root canonical: " + new File("/..").getCanonicalPath());
+
+        System.out.println("missing env: " + System.getenv("RJVM_SURELY_NOT_SET"));
+
+        long start = System.nanoTime();
+        long end = System.nanoTime();
+        System.out.println("monotonic: " + (end >= start));
+    }
+}
@@ -0,0 +1,404 @@
+import java.io.*;
+import java.lang.invoke.*;
+import java.util.ArrayList;
+
+// javac never emits `ldc` of method handles, method types, or dynamic
+// constants, so this test assembles a class that does.
+class ClassAssembler {
+    private ByteArrayOutputStream poolBytes = new ByteArrayOutputStream();
+    private DataOutputStream pool = new DataOutputStream(poolBytes);
+    private int poolCount = 1;
+
+    private ArrayList<int[]> bootstrapMethods = new ArrayList<int[]>();
+
+    private ByteArrayOutputStream methodBytes = new ByteArrayOutputStream();
+    private DataOutputStream methods = new DataOutputStream(methodBytes);
+    private int methodCount = 0;
+
+    private String superName;
+
+    ClassAssembler(String superName) {
+        this.superName = superName;
+    }
+
+    int utf8(String string) throws IOException {
+        pool.writeByte(1);
+        pool.writeUTF(string);
+        return poolCount++;
+    }
+
+    int integer(int value) throws IOException {
+        pool.writeByte(3);
+        pool.writeInt(value);
+        return poolCount++;
+    }
+
+    int longValue(long value) throws IOException {
+        pool.writeByte(5);
+        pool.writeLong(value);
+        int index = poolCount;
+        poolCount += 2;
+        return index;
+    }
+
+    int string(String value) throws IOException {
+        int utf8 = utf8(value);
+        pool.writeByte(8);
+        pool.writeShort(utf8);
+        return poolCount++;
+    }
+
+    int cls(String name) throws IOException {
+        int utf8 = utf8(name);
+        pool.writeByte(7);
+        pool.writeShort(utf8);
+        return poolCount++;
+    }
+
+    int nameAndType(String name, String descriptor) throws IOException {
+        int nameIndex = utf8(name);
+        int descriptorIndex = utf8(descriptor);
+        pool.writeByte(12);
+        pool.writeShort(nameIndex);
+        pool.writeShort(descriptorIndex);
+        return poolCount++;
+    }
+
+    int member(int tag, String owner, String name, String descriptor) throws IOException {
+        int ownerIndex = cls(owner);
+        int natIndex = nameAndType(name, descriptor);
+        pool.writeByte(tag);
+        pool.writeShort(ownerIndex);
+        pool.writeShort(natIndex);
+        return poolCount++;
+    }
+
+    int methodHandle(int kind, int tag, String owner, String name, String descriptor) throws IOException {
+        int reference = member(tag, owner, name, descriptor);
+        pool.writeByte(15);
+        pool.writeByte(kind);
+        pool.writeShort(reference);
+        return poolCount++;
+    }
+
+    int methodType(String descriptor) throws IOException {
+        int utf8 = utf8(descriptor);
+        pool.writeByte(16);
+        pool.writeShort(utf8);
+        return poolCount++;
+    }
+
+    int dynamic(String name, String descriptor, int bootstrap, int[] args) throws IOException {
+        int[] bootstrapMethod = new int[args.length + 1];
+        bootstrapMethod[0] = bootstrap;
+        System.arraycopy(args, 0, bootstrapMethod, 1, args.length);
+        bootstrapMethods.add(bootstrapMethod);
+
+        int natIndex = nameAndType(name, descriptor);
+        pool.writeByte(17);
+        pool.writeShort(bootstrapMethods.size() - 1);
+        pool.writeShort(natIndex);
+        return poolCount++;
+    }
+
+    // Add a static method that loads the constant at `index` and returns it.
+    void constantMethod(String name, String descriptor, int index, int returnOpcode) throws IOException {
+        int nameIndex = utf8(name);
+        int descriptorIndex = utf8(descriptor);
+        int codeIndex = utf8("Code");
+
+        boolean wide = returnOpcode == 0xad;
+
+        methods.writeShort(0x0009);
+        methods.writeShort(nameIndex);
+        methods.writeShort(descriptorIndex);
+        methods.writeShort(1);
+        methods.writeShort(codeIndex);
+        methods.writeInt(12 + 4);
+        methods.writeShort(2);
+        methods.writeShort(0);
+        methods.writeInt(4);
+        methods.writeByte(wide ? 0x14 : 0x13);
+        methods.writeShort(index);
+        methods.writeByte(returnOpcode);
+        methods.writeShort(0);
+        methods.writeShort(0);
+        methodCount++;
+    }
+
+    // Add a public instance method with the given bytecode.
+    void instanceMethod(String name, String descriptor, int maxStack, byte[] code) throws IOException {
+        int nameIndex = utf8(name);
+        int descriptorIndex = utf8(descriptor);
+        int codeIndex = utf8("Code");
+
+        methods.writeShort(0x0001);
+        methods.writeShort(nameIndex);
+        methods.writeShort(descriptorIndex);
+        methods.writeShort(1);
+        methods.writeShort(codeIndex);
+        methods.writeInt(12 + code.length);
+        methods.writeShort(maxStack);
+        methods.writeShort(1);
+        methods.writeInt(code.length);
+        methods.write(code);
+        methods.writeShort(0);
+        methods.writeShort(0);
+        methodCount++;
+    }
+
+    byte[] finish(String name) throws IOException {
+        int thisIndex = cls(name);
+        int superIndex = cls(superName);
+        int bootstrapIndex = utf8("BootstrapMethods");
+
+        ByteArrayOutputStream bytes = new ByteArrayOutputStream();
+        DataOutputStream out = new DataOutputStream(bytes);
+        out.writeInt(0xCAFEBABE);
+        out.writeShort(0);
+        out.writeShort(55);
+        out.writeShort(poolCount);
+        out.write(poolBytes.toByteArray());
+        out.writeShort(0x0021);
+        out.writeShort(thisIndex);
+        out.writeShort(superIndex);
+        out.writeShort(0);
+        out.writeShort(0);
+        out.writeShort(methodCount);
+        out.write(methodBytes.toByteArray());
+
+        int attributeLength = 2;
+        for (int i = 0; i < bootstrapMethods.size(); i++) {
+            attributeLength += 2 + bootstrapMethods.get(i).length * 2;
+        }
+
+        out.writeShort(1);
+        out.writeShort(bootstrapIndex);
+        out.writeInt(attributeLength);
+        out.writeShort(bootstrapMethods.size());
+        for (int i = 0; i < bootstrapMethods.size(); i++) {
+            int[] bootstrapMethod = bootstrapMethods.get(i);
+            out.writeShort(bootstrapMethod[0]);
+            out.writeShort(bootstrapMethod.length - 1);
+            for (int j = 1; j < bootstrapMethod.length; j++) {
+                out.writeShort(bootstrapMethod[j]);
+            }
+        }
+
+        return bytes.toByteArray();
+    }
+}
+
+class Loader extends ClassLoader {
+    Loader() {
+        super(Test.class.getClassLoader());
+    }
+
+    Class<?> define(String name, byte[] data) {
+        return defineClass(name, data, 0, data.length);
+    }
+}
+
+public class Test {
+    public static class Base {
+        public String describe() {
+            return "base";
+        }
+    }
+
+    public static int counter = 5;
+    static int bootstrapCalls = 0;
+
+    public static int add(int a, int b) {
+        return a + b;
+    }
+
+    public static long square(long value) {
+        return value * value;
+    }
+
+    public static Object makeName(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        bootstrapCalls++;
+        System.out.println(lookup.lookupClass().getName());
+        System.out.println(type.getName());
+
+        StringBuilder result = new StringBuilder();
+        result.append("made ");
+        result.append(name);
+        return result.toString();
+    }
+
+    public static Object fail(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        throw new IllegalStateException("bootstrap failed");
+    }
+
+    public static Object wrongType(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        return "not an int";
+    }
+
+    static Object call(Class<?> cls, String name) throws Throwable {
+        try {
+            return cls.getMethod(name).invoke(null);
+        } catch (java.lang.reflect.InvocationTargetException e) {
+            throw e.getCause();
+        }
+    }
+
+    public static void main(String[] args) throws Throwable {
+        final int GET_STATIC = 2;
+        final int PUT_STATIC = 4;
+        final int INVOKE_VIRTUAL = 5;
+        final int INVOKE_STATIC = 6;
+        final int INVOKE_SPECIAL = 7;
+        final int NEW_INVOKE_SPECIAL = 8;
+        final int FIELD = 9;
+        final int METHOD = 10;
+
+        String lookup = "Ljava/lang/invoke/MethodHandles$Lookup;";
+        StringBuilder bootstrapDescriptor = new StringBuilder();
+        bootstrapDescriptor.append('(');
+        bootstrapDescriptor.append(lookup);
+        bootstrapDescriptor.append("Ljava/lang/String;Ljava/lang/Class;)Ljava/lang/Object;");
+
+        StringBuilder invokeDescriptor = new StringBuilder();
+        invokeDescriptor.append('(');
+        invokeDescriptor.append(lookup);
+        invokeDescriptor.append("Ljava/lang/String;Ljava/lang/Class;Ljava/lang/invoke/MethodHandle;[Ljava/lang/Object;)Ljava/lang/Object;");
+
+        ClassAssembler assembler = new ClassAssembler("Test$Base");
+
+        // Constants() { super(); }
+        int superConstructor = assembler.member(METHOD, "Test$Base", "<init>", "()V");
+        assembler.instanceMethod("<init>", "()V", 1, new byte[] {
+            0x2a,
+            (byte) 0xb7, (byte) (superConstructor >> 8), (byte) superConstructor,
+            (byte) 0xb1,
+        });
+
+        // String describe() { return "constants"; }
+        int describeString = assembler.string("constants");
+        assembler.instanceMethod("describe", "()Ljava/lang/String;", 1, new byte[] {
+            0x13, (byte) (describeString >> 8), (byte) describeString,
+            (byte) 0xb0,
+        });
+
+        int special = assembler.methodHandle(INVOKE_SPECIAL, METHOD, "Test$Base", "describe", "()Ljava/lang/String;");
+        assembler.constantMethod("special", "()Ljava/lang/Object;", special, 0xb0);
+
+        int add = assembler.methodHandle(INVOKE_STATIC, METHOD, "Test", "add", "(II)I");
+        assembler.constantMethod("add", "()Ljava/lang/Object;", add, 0xb0);
+
+        int counterGetter = assembler.methodHandle(GET_STATIC, FIELD, "Test", "counter", "I");
+        assembler.constantMethod("counterGetter", "()Ljava/lang/Object;", counterGetter, 0xb0);
+
+        int counterSetter = assembler.methodHandle(PUT_STATIC, FIELD, "Test", "counter", "I");
+        assembler.constantMethod("counterSetter", "()Ljava/lang/Object;", counterSetter, 0xb0);
+
+        int length = assembler.methodHandle(INVOKE_VIRTUAL, METHOD, "java/lang/String", "length", "()I");
+        assembler.constantMethod("length", "()Ljava/lang/Object;", length, 0xb0);
+
+        int constructor = assembler.methodHandle(NEW_INVOKE_SPECIAL, METHOD, "java/lang/StringBuilder", "<init>", "(Ljava/lang/String;)V");
+        assembler.constantMethod("constructor", "()Ljava/lang/Object;", constructor, 0xb0);
+
+        int type = assembler.methodType("(ILjava/lang/String;[J)V");
+        assembler.constantMethod("type", "()Ljava/lang/Object;", type, 0xb0);
+
+        int makeName = assembler.methodHandle(INVOKE_STATIC, METHOD, "Test", "makeName", bootstrapDescriptor.toString());
+        int name = assembler.dynamic("someName", "Ljava/lang/String;", makeName, new int[0]);
+        assembler.constantMethod("name", "()Ljava/lang/Object;", name, 0xb0);
+        assembler.constantMethod("nameAgain", "()Ljava/lang/Object;", name, 0xb0);
+
+        int invoke = assembler.methodHandle(INVOKE_STATIC, METHOD, "java/lang/invoke/ConstantBootstraps", "invoke", invokeDescriptor.toString());
+        int sum = assembler.dynamic("sum", "I", invoke, new int[] { add, assembler.integer(30), assembler.integer(12) });
+        assembler.constantMethod("sum", "()I", sum, 0xac);
+
+        int square = assembler.methodHandle(INVOKE_STATIC, METHOD, "Test", "square", "(J)J");
+        int squared = assembler.dynamic("squared", "J", invoke, new int[] { square, assembler.longValue(3000000000L) });
+        assembler.constantMethod("squared", "()J", squared, 0xad);
+
+        int build = assembler.dynamic("built", "Ljava/lang/Object;", invoke, new int[] { constructor, assembler.string("built") });
+        assembler.constantMethod("built", "()Ljava/lang/Object;", build, 0xb0);
+
+        int nullConstant = assembler.methodHandle(INVOKE_STATIC, METHOD, "java/lang/invoke/ConstantBootstraps", "nullConstant", bootstrapDescriptor.toString());
+        int nothing = assembler.dynamic("nothing", "Ljava/lang/Runnable;", nullConstant, new int[0]);
+        assembler.constantMethod("nothing", "()Ljava/lang/Object;", nothing, 0xb0);
+
+        int fail = assembler.methodHandle(INVOKE_STATIC, METHOD, "Test", "fail", bootstrapDescriptor.toString());
+        int failed = assembler.dynamic("failed", "Ljava/lang/Object;", fail, new int[0]);
+        assembler.constantMethod("failed", "()Ljava/lang/Object;", failed, 0xb0);
+
+        int wrongType = assembler.methodHandle(INVOKE_STATIC, METHOD, "Test", "wrongType", bootstrapDescriptor.toString());
+        int wrong = assembler.dynamic("wrong", "I", wrongType, new int[0]);
+        assembler.constantMethod("wrong", "()I", wrong, 0xac);
+
+        Class<?> cls = new Loader().define("Constants", assembler.finish("Constants"));
+
+        MethodHandle addHandle = (MethodHandle) call(cls, "add");
+        System.out.println(addHandle.type());
+        System.out.println(addHandle);
+        System.out.println(addHandle.invokeWithArguments(2, 3));
+        System.out.println(addHandle == call(cls, "add"));
+
+        MethodHandle getter = (MethodHandle) call(cls, "counterGetter");
+        MethodHandle setter = (MethodHandle) call(cls, "counterSetter");
+        System.out.println(getter.type());
+        System.out.println(setter.type());
+        System.out.println(getter.invokeWithArguments());
+        setter.invokeWithArguments(17);
+        System.out.println(counter);
+
+        MethodHandle lengthHandle = (MethodHandle) call(cls, "length");
+        System.out.println(lengthHandle.type());
+        System.out.println(lengthHandle.invokeWithArguments("four"));
+
+        MethodHandle constructorHandle = (MethodHandle) call(cls, "constructor");
+        System.out.println(constructorHandle.type());
+        System.out.println(constructorHandle.invokeWithArguments("new"));
+
+        try {
+            addHandle.invokeWithArguments(1);
+        } catch (WrongMethodTypeException e) {
+            System.out.println("WrongMethodTypeException");
+        }
+
+        // `invokeSpecial` handles call the referenced method even when the
+        // receiver overrides it
+        MethodHandle specialHandle = (MethodHandle) call(cls, "special");
+        System.out.println(specialHandle.type());
+        Base instance = (Base) cls.getDeclaredConstructors()[0].newInstance();
+        System.out.println(instance.describe());
+        System.out.println(specialHandle.invokeWithArguments(instance));
+
+        MethodType methodType = (MethodType) call(cls, "type");
+        System.out.println(methodType);
+        System.out.println(methodType.toMethodDescriptorString());
+        System.out.println(methodType.parameterCount());
+        System.out.println(methodType.returnType());
+        System.out.println(methodType == call(cls, "type"));
+        System.out.println(methodType.equals(MethodType.methodType(void.class, int.class, String.class, long[].class)));
+
+        Object madeName = call(cls, "name");
+        System.out.println(madeName);
+        System.out.println(madeName == call(cls, "name"));
+        System.out.println(madeName == call(cls, "nameAgain"));
+        System.out.println(bootstrapCalls);
+
+        System.out.println(call(cls, "sum"));
+        System.out.println(call(cls, "squared"));
+        System.out.println(call(cls, "built"));
+        System.out.println(call(cls, "nothing"));
+
+        try {
+            call(cls, "failed");
+        } catch (BootstrapMethodError e) {
+            System.out.println(e.getCause().getClass().getName());
+            System.out.println(e.getCause().getMessage());
+        }
+
+        try {
+            call(cls, "wrong");
+        } catch (BootstrapMethodError e) {
+            System.out.println(e.getCause().getClass().getName());
+        }
+    }
+}
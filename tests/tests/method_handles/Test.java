@@ -0,0 +1,241 @@
+import java.lang.invoke.*;
+import java.lang.reflect.*;
+import java.util.Arrays;
+
+public class Test {
+    public static final int CONSTANT = 7;
+    public static int counter = 3;
+
+    public int value;
+    public String name;
+
+    public Test(int value) {
+        this.value = value;
+    }
+
+    public static int add(int a, int b) {
+        return a + b;
+    }
+
+    public static long widen(long a, double b) {
+        return a + (long) b;
+    }
+
+    public static String join(String separator, String... parts) {
+        StringBuilder result = new StringBuilder();
+        for (int i = 0; i < parts.length; i ++) {
+            if (i != 0) {
+                result.append(separator);
+            }
+
+            result.append(parts[i]);
+        }
+
+        return result.toString();
+    }
+
+    public int scaled(int factor) {
+        return this.value * factor;
+    }
+
+    public static void bump() {
+        counter ++;
+    }
+
+    private static int secret() {
+        return 42;
+    }
+
+    public static void main(String[] args) throws Throwable {
+        MethodHandles.Lookup lookup = MethodHandles.lookup();
+        System.out.println(lookup.lookupClass().getName());
+
+        // Static methods, with exact and converting invocation
+        MethodHandle add = lookup.findStatic(Test.class, "add", MethodType.methodType(int.class, int.class, int.class));
+        System.out.println(add);
+        int sum = (int) add.invokeExact(2, 3);
+        System.out.println(sum);
+        Object boxed = add.invoke((Object) 4, (Object) 5);
+        System.out.println(boxed);
+        long widened = (long) add.invoke((short) 6, 'a');
+        System.out.println(widened);
+        System.out.println(add.invokeWithArguments(1, 2));
+        System.out.println(add.invokeWithArguments(Arrays.asList(10, 20)));
+
+        MethodHandle widen = lookup.findStatic(Test.class, "widen", MethodType.methodType(long.class, long.class, double.class));
+        long wide = (long) widen.invokeExact(100L, 2.5);
+        System.out.println(wide);
+        System.out.println(widen.invoke(1, 2));
+
+        MethodHandle bump = lookup.findStatic(Test.class, "bump", MethodType.methodType(void.class));
+        bump.invokeExact();
+        bump.invoke();
+        System.out.println(counter);
+
+        // Virtual methods and constructors
+        MethodHandle scaled = lookup.findVirtual(Test.class, "scaled", MethodType.methodType(int.class, int.class));
+        System.out.println(scaled);
+        Test test = new Test(6);
+        int product = (int) scaled.invokeExact(test, 7);
+        System.out.println(product);
+        System.out.println(scaled.bindTo(test).invoke(3));
+
+        MethodHandle length = lookup.findVirtual(String.class, "length", MethodType.methodType(int.class));
+        System.out.println((int) length.invokeExact("hello"));
+
+        MethodHandle charSequenceLength = lookup.findVirtual(CharSequence.class, "length", MethodType.methodType(int.class));
+        System.out.println((int) charSequenceLength.invokeExact((CharSequence) "four"));
+
+        MethodHandle constructor = lookup.findConstructor(StringBuilder.class, MethodType.methodType(void.class, String.class));
+        System.out.println(constructor);
+        StringBuilder builder = (StringBuilder) constructor.invokeExact("built");
+        System.out.println(builder.append('!'));
+
+        MethodHandle testConstructor = lookup.findConstructor(Test.class, MethodType.methodType(void.class, int.class));
+        Test created = (Test) testConstructor.invoke(11);
+        System.out.println(created.value);
+
+        // Fields
+        MethodHandle getter = lookup.findGetter(Test.class, "value", int.class);
+        System.out.println(getter);
+        System.out.println((int) getter.invokeExact(test));
+        MethodHandle setter = lookup.findSetter(Test.class, "name", String.class);
+        setter.invokeExact(test, "named");
+        System.out.println(test.name);
+        MethodHandle staticGetter = lookup.findStaticGetter(Test.class, "CONSTANT", int.class);
+        System.out.println((int) staticGetter.invokeExact());
+        MethodHandle staticSetter = lookup.findStaticSetter(Test.class, "counter", int.class);
+        staticSetter.invokeExact(50);
+        System.out.println(counter);
+
+        // Private members are accessible from the lookup class
+        MethodHandle secret = lookup.findStatic(Test.class, "secret", MethodType.methodType(int.class));
+        System.out.println((int) secret.invokeExact());
+
+        // Adapters
+        MethodHandle addTen = MethodHandles.insertArguments(add, 1, 10);
+        System.out.println(addTen);
+        System.out.println(addTen.invoke(5));
+        MethodHandle dropped = MethodHandles.dropArguments(add, 0, String.class, Object.class);
+        System.out.println(dropped);
+        System.out.println(dropped.invoke("ignored", null, 1, 2));
+        MethodHandle asObject = add.asType(MethodType.methodType(Object.class, Object.class, int.class));
+        System.out.println(asObject);
+        System.out.println(asObject.invoke((Object) 8, 9));
+
+        MethodHandle join = lookup.findStatic(Test.class, "join", MethodType.methodType(String.class, String.class, String[].class));
+        System.out.println(join.isVarargsCollector());
+        System.out.println(join.asFixedArity().isVarargsCollector());
+        System.out.println((String) join.invoke("-", "a", "b", "c"));
+        System.out.println((String) join.invoke("-"));
+        System.out.println((String) join.invoke("+", new String[] { "x", "y" }));
+
+        // Reflection
+        Method scaledMethod = Test.class.getMethod("scaled", int.class);
+        MethodHandle unreflected = lookup.unreflect(scaledMethod);
+        System.out.println(unreflected.invoke(new Test(2), 21));
+        Constructor<?>[] constructors = Test.class.getConstructors();
+        System.out.println(lookup.unreflectConstructor(constructors[0]).invoke(99).getClass().getName());
+        Field[] fields = Test.class.getDeclaredFields();
+        for (int i = 0; i < fields.length; i ++) {
+            if (fields[i].getName().equals("value")) {
+                MethodHandle fieldSetter = lookup.unreflectSetter(fields[i]);
+                fieldSetter.invoke(test, 123);
+                System.out.println(lookup.unreflectGetter(fields[i]).invoke(test));
+            }
+        }
+
+        try {
+            MethodHandle.class.getMethod("invoke", Object[].class).invoke(add, new Object[] { new Object[0] });
+        } catch (InvocationTargetException e) {
+            System.out.println(e.getCause().getClass().getName());
+        }
+
+        // Method types
+        MethodType parsed = MethodType.fromMethodDescriptorString("(ILjava/lang/String;[J)V", null);
+        System.out.println(parsed);
+        System.out.println(parsed.toMethodDescriptorString());
+        System.out.println(MethodType.genericMethodType(2));
+        System.out.println(parsed.dropParameterTypes(0, 1).appendParameterTypes(char.class));
+        System.out.println(parsed.insertParameterTypes(1, Object.class).changeParameterType(0, long.class));
+
+        // Errors
+        try {
+            int wrong = (int) add.invokeExact((Object) 1, (Object) 2);
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            add.invoke("a", 2);
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            add.invoke((Object) null, 2);
+        } catch (NullPointerException e) {
+            System.out.println(e.getClass().getName());
+        }
+
+        try {
+            MethodHandles.insertArguments(add, 0, "nope");
+        } catch (ClassCastException e) {
+            System.out.println(e.getClass().getName());
+        }
+
+        try {
+            add.bindTo(1);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            add.invokeWithArguments(1);
+        } catch (WrongMethodTypeException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            lookup.findStatic(Test.class, "nope", MethodType.methodType(void.class));
+        } catch (NoSuchMethodException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            lookup.findGetter(Test.class, "nope", int.class);
+        } catch (NoSuchFieldException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            lookup.findVirtual(Test.class, "add", MethodType.methodType(int.class, int.class, int.class));
+        } catch (IllegalAccessException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            lookup.findStatic(String.class, "length", MethodType.methodType(int.class));
+        } catch (IllegalAccessException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            lookup.findStaticSetter(Test.class, "CONSTANT", int.class);
+        } catch (IllegalAccessException e) {
+            System.out.println(e.getClass().getName());
+        }
+
+        try {
+            MethodType.fromMethodDescriptorString("(Lnope/Nope;)V", null);
+        } catch (TypeNotPresentException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            MethodType.fromMethodDescriptorString("(I", null);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+    }
+}
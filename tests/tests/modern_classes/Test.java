@@ -0,0 +1,215 @@
+import java.io.InputStream;
+import java.lang.reflect.RecordComponent;
+
+public class Test {
+    record Point(int x, int y) {
+        Point {
+            if (x < 0) {
+                throw new IllegalArgumentException("negative x");
+            }
+        }
+
+        int sum() {
+            return this.x + this.y;
+        }
+    }
+
+    record Named(String name, long id, double weight, Object tag) { }
+
+    record Empty() { }
+
+    sealed interface Shape permits Circle, Square, Polygon { }
+
+    static final class Circle implements Shape { }
+
+    static final class Square implements Shape { }
+
+    static non-sealed class Polygon implements Shape { }
+
+    static class Triangle extends Polygon { }
+
+    interface Greeter {
+        default String greet(String name) {
+            return this.prefix() + name + suffix(name);
+        }
+
+        private String prefix() {
+            return "Hello, ";
+        }
+
+        private static String suffix(String name) {
+            return name.length() > 3 ? "!" : ".";
+        }
+    }
+
+    static class SimpleGreeter implements Greeter { }
+
+    private int secret = 42;
+
+    private int secretMethod() {
+        return this.secret * 2;
+    }
+
+    private static String staticSecret() {
+        return "static secret";
+    }
+
+    class Inner {
+        int read() {
+            return Test.this.secret + Test.this.secretMethod();
+        }
+
+        void write(int value) {
+            Test.this.secret = value;
+        }
+
+        private int innerSecret() {
+            return 7;
+        }
+    }
+
+    static class Nested {
+        private String hidden = "hidden";
+
+        String peek(Test test) {
+            return test.secret + " " + staticSecret();
+        }
+    }
+
+    static class Base {
+        private String who() {
+            return "Base";
+        }
+
+        String callWho() {
+            return this.who();
+        }
+    }
+
+    static class Derived extends Base {
+        String who() {
+            return "Derived";
+        }
+    }
+
+    public static void main(String[] args) throws Exception {
+        testRecords();
+        testSealed();
+        testNestmates();
+        testPrivateInterfaceMethods();
+        testUnsupportedVersion();
+    }
+
+    static void testRecords() {
+        Point p = new Point(1, 2);
+        Point q = new Point(1, 2);
+        Point r = new Point(2, 1);
+
+        System.out.println(p);
+        System.out.println(p.x() + " " + p.y() + " " + p.sum());
+        System.out.println(p.equals(q) + " " + p.equals(r) + " " + p.equals(null) + " " + p.equals("x"));
+        System.out.println((p.hashCode() == q.hashCode()) + " " + p.hashCode());
+
+        try {
+            new Point(-1, 0);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e.getMessage());
+        }
+
+        Named n = new Named("abc", 5L, 0.5, null);
+        Named m = new Named("abc", 5L, 0.5, null);
+        System.out.println(n);
+        System.out.println(n.equals(m) + " " + (n.hashCode() == m.hashCode()));
+        System.out.println(n.equals(new Named("abc", 6L, 0.5, null)));
+
+        Empty e = new Empty();
+        System.out.println(e + " " + e.equals(new Empty()) + " " + e.hashCode());
+
+        System.out.println(Point.class.isRecord() + " " + Test.class.isRecord() + " " + Record.class.isRecord());
+        System.out.println(Point.class.getSuperclass().getName());
+        System.out.println(Test.class.getRecordComponents() == null);
+
+        RecordComponent[] components = Named.class.getRecordComponents();
+        System.out.println(components.length);
+        for (RecordComponent component : components) {
+            System.out.println(component.getName() + " " + component.getType().getName() + " " + component.getAccessor().getName() + " " + (component.getDeclaringRecord() == Named.class));
+        }
+        System.out.println(Empty.class.getRecordComponents().length);
+    }
+
+    static void testSealed() {
+        System.out.println(Shape.class.isSealed() + " " + Circle.class.isSealed() + " " + Polygon.class.isSealed() + " " + Triangle.class.isSealed());
+
+        Class<?>[] permitted = Shape.class.getPermittedSubclasses();
+        System.out.println(permitted.length);
+        for (Class<?> c : permitted) {
+            System.out.println(c.getName());
+        }
+        System.out.println(Circle.class.getPermittedSubclasses() == null);
+
+        Shape[] shapes = new Shape[] { new Circle(), new Square(), new Triangle() };
+        for (Shape shape : shapes) {
+            System.out.println(shape.getClass().getSimpleName());
+        }
+    }
+
+    static void testNestmates() {
+        Test test = new Test();
+        Inner inner = test.new Inner();
+        System.out.println(inner.read());
+        inner.write(10);
+        System.out.println(inner.read());
+        System.out.println(inner.innerSecret());
+
+        Nested nested = new Nested();
+        System.out.println(nested.peek(test) + " " + nested.hidden);
+
+        System.out.println(Inner.class.getNestHost().getName());
+        System.out.println(Test.class.getNestHost().getName());
+        System.out.println(Inner.class.isNestmateOf(Nested.class));
+        System.out.println(Test.class.isNestmateOf(String.class));
+
+        // Private methods aren't overridden
+        System.out.println(new Derived().callWho());
+    }
+
+    static void testPrivateInterfaceMethods() {
+        Greeter greeter = new SimpleGreeter();
+        System.out.println(greeter.greet("Bob"));
+        System.out.println(greeter.greet("Alice"));
+    }
+
+    static class BytesLoader extends ClassLoader {
+        Class<?> define(byte[] data) {
+            return this.defineClass("Test$Empty", data, 0, data.length);
+        }
+    }
+
+    static void testUnsupportedVersion() throws Exception {
+        byte[] data = new byte[0];
+        InputStream stream = Test.class.getResourceAsStream("Test$Empty.class");
+        int b;
+        while ((b = stream.read()) != -1) {
+            byte[] grown = new byte[data.length + 1];
+            System.arraycopy(data, 0, grown, 0, data.length);
+            grown[data.length] = (byte) b;
+            data = grown;
+        }
+        stream.close();
+
+        BytesLoader loader = new BytesLoader();
+        System.out.println(loader.define(data).getName());
+
+        // Bump the major version far past anything supported
+        data[6] = 0x01;
+        data[7] = 0x00;
+
+        try {
+            new BytesLoader().define(data);
+            System.out.println("loaded");
+        } catch (UnsupportedClassVersionError e) {
+            System.out.println("UnsupportedClassVersionError");
+            System.out.println(e instanceof ClassFormatError);
+        }
+    }
+}
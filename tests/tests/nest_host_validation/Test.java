@@ -0,0 +1,103 @@
+import java.io.*;
+import java.util.HashMap;
+
+// Assembles classes whose `NestHost` and `NestMembers` attributes don't agree,
+// or that claim a nest host in another package. Such classes are the hosts of
+// their own nest.
+public class Test {
+    static byte[] assemble(String name, String attributeName, String[] classes) throws IOException {
+        ByteArrayOutputStream poolBytes = new ByteArrayOutputStream();
+        DataOutputStream pool = new DataOutputStream(poolBytes);
+        int poolCount = 1;
+
+        int[] classIndices = new int[classes.length + 2];
+        String[] names = new String[classes.length + 2];
+        names[0] = name;
+        names[1] = "java/lang/Object";
+        System.arraycopy(classes, 0, names, 2, classes.length);
+
+        for (int i = 0; i < names.length; i++) {
+            pool.writeByte(1);
+            pool.writeUTF(names[i]);
+            pool.writeByte(7);
+            pool.writeShort(poolCount);
+            classIndices[i] = poolCount + 1;
+            poolCount += 2;
+        }
+
+        pool.writeByte(1);
+        pool.writeUTF(attributeName);
+        int attributeIndex = poolCount++;
+
+        ByteArrayOutputStream bytes = new ByteArrayOutputStream();
+        DataOutputStream out = new DataOutputStream(bytes);
+        out.writeInt(0xCAFEBABE);
+        out.writeShort(0);
+        out.writeShort(55);
+        out.writeShort(poolCount);
+        out.write(poolBytes.toByteArray());
+        out.writeShort(0x0021);
+        out.writeShort(classIndices[0]);
+        out.writeShort(classIndices[1]);
+        out.writeShort(0);
+        out.writeShort(0);
+        out.writeShort(0);
+
+        out.writeShort(1);
+        out.writeShort(attributeIndex);
+        if (attributeName.equals("NestHost")) {
+            out.writeInt(2);
+            out.writeShort(classIndices[2]);
+        } else {
+            out.writeInt(2 + 2 * classes.length);
+            out.writeShort(classes.length);
+            for (int i = 0; i < classes.length; i++) {
+                out.writeShort(classIndices[i + 2]);
+            }
+        }
+
+        return bytes.toByteArray();
+    }
+
+    static class Loader extends ClassLoader {
+        private HashMap<String, byte[]> classes = new HashMap<String, byte[]>();
+
+        Loader() {
+            super(Test.class.getClassLoader());
+        }
+
+        void add(String name, byte[] data) {
+            classes.put(name, data);
+        }
+
+        @SuppressWarnings("deprecation")
+        protected Class<?> findClass(String name) throws ClassNotFoundException {
+            byte[] data = classes.get(name);
+            if (data == null) {
+                throw new ClassNotFoundException(name);
+            }
+
+            return defineClass(data, 0, data.length);
+        }
+    }
+
+    public static void main(String[] args) throws Exception {
+        Loader loader = new Loader();
+        loader.add("Host", assemble("Host", "NestMembers", new String[] { "Member", "pkg/Stranger" }));
+        loader.add("Member", assemble("Member", "NestHost", new String[] { "Host" }));
+        loader.add("pkg.Stranger", assemble("pkg/Stranger", "NestHost", new String[] { "Host" }));
+        loader.add("Liar", assemble("Liar", "NestHost", new String[] { "Host" }));
+        loader.add("Orphan", assemble("Orphan", "NestHost", new String[] { "Missing" }));
+
+        String[] names = { "Host", "Member", "pkg.Stranger", "Liar", "Orphan" };
+        for (int i = 0; i < names.length; i++) {
+            Class<?> cls = loader.loadClass(names[i]);
+            System.out.println(cls.getName() + " -> " + cls.getNestHost().getName());
+            System.out.println(cls.getNestHost() == cls.getNestHost());
+        }
+
+        Class<?> host = loader.loadClass("Host");
+        System.out.println(loader.loadClass("Member").isNestmateOf(host));
+        System.out.println(loader.loadClass("pkg.Stranger").isNestmateOf(host));
+    }
+}
@@ -0,0 +1,203 @@
+import java.lang.reflect.Field;
+
+public class Test {
+    private int count;
+    private long total;
+    private double ratio;
+    private boolean flag;
+    private byte small;
+    private char letter;
+    private short medium;
+    private String name;
+    private Object anything;
+    private final int fixed;
+
+    private static int staticCount;
+    private static final String CONSTANT = String.valueOf("constant");
+
+    public Test() {
+        this.fixed = 1;
+    }
+
+    public static void main(String[] args) throws Exception {
+        Test test = new Test();
+
+        Field count = findField(Test.class, "count");
+        count.setInt(test, 5);
+        System.out.println(test.count);
+        count.set(test, Integer.valueOf(7));
+        System.out.println(count.get(test));
+        count.setShort(test, (short) 8);
+        System.out.println(test.count);
+        count.setChar(test, 'A');
+        System.out.println(test.count);
+
+        // Widening conversions
+        Field total = findField(Test.class, "total");
+        total.setByte(test, (byte) -3);
+        System.out.println(test.total);
+        total.setInt(test, 123456);
+        System.out.println(test.total);
+        total.set(test, Short.valueOf((short) 12));
+        System.out.println(test.total);
+
+        Field ratio = findField(Test.class, "ratio");
+        ratio.setFloat(test, 1.5f);
+        System.out.println(test.ratio);
+        ratio.setLong(test, 4000000L);
+        System.out.println(test.ratio);
+        ratio.set(test, Integer.valueOf(3));
+        System.out.println(ratio.get(test));
+
+        // Subword types
+        Field flag = findField(Test.class, "flag");
+        flag.setBoolean(test, true);
+        System.out.println(test.flag);
+        flag.set(test, Boolean.FALSE);
+        System.out.println(flag.get(test));
+
+        Field small = findField(Test.class, "small");
+        small.setByte(test, (byte) -100);
+        System.out.println(small.get(test));
+
+        Field letter = findField(Test.class, "letter");
+        letter.setChar(test, 'z');
+        System.out.println(letter.get(test));
+
+        Field medium = findField(Test.class, "medium");
+        medium.set(test, Byte.valueOf((byte) 42));
+        System.out.println(medium.get(test));
+
+        // References
+        Field name = findField(Test.class, "name");
+        name.set(test, "hello");
+        System.out.println(test.name);
+        name.set(test, null);
+        System.out.println(test.name);
+
+        Field anything = findField(Test.class, "anything");
+        anything.set(test, Integer.valueOf(9));
+        System.out.println(test.anything);
+
+        // Statics
+        Field staticCount = findField(Test.class, "staticCount");
+        staticCount.setInt(null, 11);
+        System.out.println(Test.staticCount);
+
+        Field value = findField(Holder.class, "value");
+        value.set(null, "replaced");
+        System.out.println(Holder.value);
+
+        // Narrowing and mismatched types
+        expectFailure(count, test, Long.valueOf(1));
+        expectFailure(count, test, "string");
+        expectFailure(count, test, null);
+        expectFailure(small, test, Integer.valueOf(1));
+        expectFailure(flag, test, Integer.valueOf(1));
+        expectFailure(name, test, Integer.valueOf(1));
+
+        try {
+            total.setDouble(test, 1.0);
+            System.out.println("no exception");
+        } catch (IllegalArgumentException e) {
+            System.out.println("IllegalArgumentException");
+        }
+
+        try {
+            name.setInt(test, 1);
+            System.out.println("no exception");
+        } catch (IllegalArgumentException e) {
+            System.out.println("IllegalArgumentException");
+        }
+
+        // Receivers
+        expectFailure(count, "wrong receiver", Integer.valueOf(1));
+
+        try {
+            count.setInt(null, 1);
+            System.out.println("no exception");
+        } catch (NullPointerException e) {
+            System.out.println("NullPointerException");
+        }
+
+        // Final fields
+        Field fixed = findField(Test.class, "fixed");
+        expectFailure(fixed, test, Integer.valueOf(2));
+        System.out.println(test.fixed);
+        fixed.setAccessible(true);
+        fixed.set(test, Integer.valueOf(3));
+        System.out.println(test.fixed);
+
+        Field constant = findField(Test.class, "CONSTANT");
+        constant.setAccessible(true);
+        expectFailure(constant, null, "other");
+        System.out.println(CONSTANT);
+
+        // Private fields of other classes
+        Other other = new Other();
+        Field secret = findField(Other.class, "secret");
+        expectFailure(secret, other, Integer.valueOf(1));
+
+        try {
+            secret.get(other);
+            System.out.println("no exception");
+        } catch (IllegalAccessException e) {
+            System.out.println("IllegalAccessException");
+        }
+
+        System.out.println(secret.isAccessible());
+        secret.setAccessible(true);
+        System.out.println(secret.isAccessible());
+        secret.set(other, Integer.valueOf(99));
+        System.out.println(secret.get(other));
+
+        Field visible = findField(Other.class, "visible");
+        visible.setInt(other, 4);
+        System.out.println(visible.get(other));
+
+        // Private fields of nestmates
+        Inner inner = new Inner();
+        Field hidden = findField(Inner.class, "hidden");
+        hidden.setLong(inner, 77L);
+        System.out.println(hidden.get(inner));
+    }
+
+    static void expectFailure(Field field, Object receiver, Object value) {
+        try {
+            field.set(receiver, value);
+            System.out.println("no exception");
+        } catch (IllegalArgumentException e) {
+            System.out.println("IllegalArgumentException");
+        } catch (IllegalAccessException e) {
+            System.out.println("IllegalAccessException");
+        }
+    }
+
+    static Field findField(Class<?> cls, String name) {
+        Field[] fields = cls.getDeclaredFields();
+        for (int i = 0; i < fields.length; i ++) {
+            if (fields[i].getName().equals(name)) {
+                return fields[i];
+            }
+        }
+
+        return null;
+    }
+
+    static class Inner {
+        private long hidden;
+    }
+}
+
+class Holder {
+    static String value = String.valueOf("original");
+
+    static {
+        System.out.println("Holder initialized");
+    }
+}
+
+class Other {
+    private int secret;
+    int visible;
+}
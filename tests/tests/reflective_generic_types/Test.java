@@ -0,0 +1,125 @@
+import java.lang.reflect.Field;
+import java.lang.reflect.GenericArrayType;
+import java.lang.reflect.Method;
+import java.lang.reflect.ParameterizedType;
+import java.lang.reflect.Type;
+import java.lang.reflect.TypeVariable;
+import java.lang.reflect.WildcardType;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+
+public class Test {
+    static class Box<T extends Comparable<T>> {
+        public T value;
+    }
+
+    static class Pair<A, B extends Number & Comparable<B>> implements Comparable<Pair<A, B>> {
+        public int compareTo(Pair<A, B> other) {
+            return 0;
+        }
+    }
+
+    class Inner<X> {
+        public X item;
+    }
+
+    static class StringList extends ArrayList<String> { }
+
+    public List<String> names;
+    public Map<String, List<Integer>> lookup;
+    public List<? extends Number> numbers;
+    public List<? super Integer> sinks;
+    public List<?> anything;
+    public List<String>[] nameArrays;
+    public int[] plainInts;
+    public Test.Inner<String> inner;
+    public Map.Entry<String, Integer> entry;
+    public String plain;
+
+    public <K, V extends List<K>> Map<K, V> method(K key, V[] values, int count) throws IllegalStateException {
+        return null;
+    }
+
+    public void plainMethod(String s) { }
+
+    public static void main(String[] args) throws Exception {
+        Field[] fields = Test.class.getDeclaredFields();
+        for (int i = 0; i < fields.length; i ++) {
+            describe(fields[i].getGenericType());
+        }
+
+        TypeVariable<?>[] boxParams = Box.class.getTypeParameters();
+        System.out.println(boxParams.length);
+        System.out.println(boxParams[0]);
+        describe(boxParams[0].getBounds()[0]);
+        System.out.println(boxParams[0].getGenericDeclaration() == Box.class);
+        System.out.println(boxParams[0].equals(Box.class.getTypeParameters()[0]));
+
+        TypeVariable<?>[] pairParams = Pair.class.getTypeParameters();
+        System.out.println(pairParams.length);
+        System.out.println(pairParams[1].getBounds().length);
+        describe(pairParams[1].getBounds()[0]);
+        describe(pairParams[1].getBounds()[1]);
+
+        describe(Pair.class.getGenericSuperclass());
+        Type[] interfaces = Pair.class.getGenericInterfaces();
+        System.out.println(interfaces.length);
+        describe(interfaces[0]);
+
+        describe(StringList.class.getGenericSuperclass());
+        System.out.println(Object.class.getGenericSuperclass() == null);
+        System.out.println(Test.class.getTypeParameters().length);
+        System.out.println(int[].class.getTypeName());
+
+        Method[] methods = Test.class.getDeclaredMethods();
+        for (int i = 0; i < methods.length; i ++) {
+            if (methods[i].getName().equals("method")) {
+                Method method = methods[i];
+                System.out.println(method.getTypeParameters().length);
+                describe(method.getGenericReturnType());
+                Type[] params = method.getGenericParameterTypes();
+                for (int j = 0; j < params.length; j ++) {
+                    describe(params[j]);
+                }
+                describe(method.getTypeParameters()[1].getBounds()[0]);
+            } else if (methods[i].getName().equals("plainMethod")) {
+                describe(methods[i].getGenericReturnType());
+                describe(methods[i].getGenericParameterTypes()[0]);
+            }
+        }
+    }
+
+    static void describe(Type type) {
+        System.out.println(type.getTypeName());
+
+        if (type instanceof ParameterizedType) {
+            ParameterizedType parameterized = (ParameterizedType) type;
+            System.out.print(" raw: ");
+            System.out.println(((Class<?>) parameterized.getRawType()).getName());
+            System.out.print(" owner: ");
+            System.out.println(parameterized.getOwnerType());
+            Type[] arguments = parameterized.getActualTypeArguments();
+            for (int i = 0; i < arguments.length; i ++) {
+                System.out.print(" arg: ");
+                System.out.println(arguments[i]);
+            }
+        } else if (type instanceof WildcardType) {
+            WildcardType wildcard = (WildcardType) type;
+            System.out.print(" upper: ");
+            System.out.println(wildcard.getUpperBounds().length);
+            System.out.print(" lower: ");
+            System.out.println(wildcard.getLowerBounds().length);
+        } else if (type instanceof GenericArrayType) {
+            System.out.print(" component: ");
+            System.out.println(((GenericArrayType) type).getGenericComponentType());
+        } else if (type instanceof TypeVariable) {
+            System.out.print(" variable: ");
+            System.out.println(((TypeVariable<?>) type).getName());
+        } else {
+            System.out.print(" class: ");
+            System.out.println(type);
+        }
+    }
+}
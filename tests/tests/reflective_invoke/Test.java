@@ -0,0 +1,242 @@
+import java.lang.reflect.Constructor;
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Method;
+
+public class Test {
+    private int value;
+
+    public Test(int value) {
+        this.value = value;
+    }
+
+    public Test(long a, String b, double c) {
+        this.value = (int) (a + b.length() + (long) c);
+    }
+
+    public Test(String s) {
+        throw new IllegalStateException(s);
+    }
+
+    public int getValue() {
+        return this.value;
+    }
+
+    public static long addLong(long a, int b, long c) {
+        return a + b + c;
+    }
+
+    public static double scale(double d, float f) {
+        return d * f;
+    }
+
+    public static boolean negate(boolean b) {
+        return !b;
+    }
+
+    public static char next(char c) {
+        return (char) (c + 1);
+    }
+
+    public static byte half(byte b) {
+        return (byte) (b / 2);
+    }
+
+    public static short twice(short s) {
+        return (short) (s * 2);
+    }
+
+    public static float third(float f) {
+        return f / 3.0f;
+    }
+
+    public static String describe(Object o, int i) {
+        StringBuilder builder = new StringBuilder();
+        builder.append(o);
+        builder.append(':');
+        builder.append(i);
+        return builder.toString();
+    }
+
+    public static void nothing() { }
+
+    public static void fail(String message) {
+        throw new UnsupportedOperationException(message);
+    }
+
+    public static void main(String[] args) throws Exception {
+        // Return values are boxed
+        Method addLong = findMethod(Test.class, "addLong");
+        System.out.println(addLong.invoke(null, Long.valueOf(1), Integer.valueOf(2), Long.valueOf(3)));
+        System.out.println(findMethod(Test.class, "scale").invoke(null, Double.valueOf(1.5), Float.valueOf(2.0f)));
+        System.out.println(findMethod(Test.class, "negate").invoke(null, Boolean.TRUE));
+        System.out.println(findMethod(Test.class, "next").invoke(null, Character.valueOf('a')));
+        System.out.println(findMethod(Test.class, "half").invoke(null, Byte.valueOf((byte) 10)));
+        System.out.println(findMethod(Test.class, "twice").invoke(null, Short.valueOf((short) 21)));
+        System.out.println(findMethod(Test.class, "third").invoke(null, Float.valueOf(1.5f)));
+        System.out.println(findMethod(Test.class, "nothing").invoke(null));
+
+        Object boxed = addLong.invoke(null, Long.valueOf(1), Integer.valueOf(1), Long.valueOf(1));
+        System.out.println(boxed.getClass().getName());
+
+        // Widening conversions
+        System.out.println(addLong.invoke(null, Integer.valueOf(10), Short.valueOf((short) 20), Byte.valueOf((byte) 30)));
+        System.out.println(addLong.invoke(null, Character.valueOf('A'), Character.valueOf('B'), Integer.valueOf(0)));
+        System.out.println(findMethod(Test.class, "scale").invoke(null, Integer.valueOf(3), Long.valueOf(2)));
+        System.out.println(findMethod(Test.class, "third").invoke(null, Integer.valueOf(9)));
+        System.out.println(findMethod(Test.class, "twice").invoke(null, Byte.valueOf((byte) 4)));
+        System.out.println(findMethod(Test.class, "describe").invoke(null, "x", Character.valueOf('0')));
+        System.out.println(findMethod(Test.class, "describe").invoke(null, null, Short.valueOf((short) 5)));
+
+        // Mismatched arguments
+        expectFailure(addLong, null, new Object[] { Double.valueOf(1), Integer.valueOf(2), Long.valueOf(3) });
+        expectFailure(addLong, null, new Object[] { Long.valueOf(1), Long.valueOf(2), Long.valueOf(3) });
+        expectFailure(addLong, null, new Object[] { Long.valueOf(1), null, Long.valueOf(3) });
+        expectFailure(addLong, null, new Object[] { Long.valueOf(1), Integer.valueOf(2) });
+        expectFailure(addLong, null, new Object[] { "1", Integer.valueOf(2), Long.valueOf(3) });
+        expectFailure(findMethod(Test.class, "negate"), null, new Object[] { Integer.valueOf(1) });
+        expectFailure(findMethod(Test.class, "next"), null, new Object[] { Integer.valueOf(1) });
+        expectFailure(findMethod(Test.class, "half"), null, new Object[] { Short.valueOf((short) 1) });
+
+        // A null argument array is the same as an empty one
+        System.out.println(findMethod(Test.class, "nothing").invoke(null, (Object[]) null));
+        expectFailure(addLong, null, null);
+
+        // Receivers
+        Test test = new Test(42);
+        Method getValue = findMethod(Test.class, "getValue");
+        System.out.println(getValue.invoke(test));
+        System.out.println(getValue.invoke(test, (Object[]) null));
+        expectFailure(getValue, "not a Test", new Object[0]);
+
+        try {
+            getValue.invoke(null);
+            System.out.println("no exception");
+        } catch (NullPointerException e) {
+            System.out.println("NullPointerException");
+        }
+
+        // Virtual dispatch
+        Method speak = findMethod(Animal.class, "speak");
+        System.out.println(speak.invoke(new Animal()));
+        System.out.println(speak.invoke(new Dog()));
+        System.out.println(findMethod(Named.class, "name").invoke(new Dog()));
+
+        // Exceptions thrown by the target are wrapped
+        try {
+            findMethod(Test.class, "fail").invoke(null, "boom");
+            System.out.println("no exception");
+        } catch (InvocationTargetException e) {
+            System.out.println("InvocationTargetException");
+            System.out.println(e.getCause().getClass().getName());
+            System.out.println(e.getCause().getMessage());
+            System.out.println(e.getTargetException() == e.getCause());
+        }
+
+        // Constructors
+        Constructor<?> intConstructor = findConstructor(Test.class, int.class);
+        Test created = (Test) intConstructor.newInstance(Short.valueOf((short) 7));
+        System.out.println(created.value);
+
+        try {
+            intConstructor.newInstance(Long.valueOf(7));
+            System.out.println("no exception");
+        } catch (IllegalArgumentException e) {
+            System.out.println("IllegalArgumentException");
+        }
+
+        Object animal = Animal.class.getDeclaredConstructors()[0].newInstance((Object[]) null);
+        System.out.println(animal.getClass().getName());
+
+        created = (Test) findConstructor(Test.class, long.class).newInstance(Integer.valueOf(1), "abc", Float.valueOf(4.0f));
+        System.out.println(created.value);
+
+        try {
+            findConstructor(Test.class, String.class).newInstance("ctor failed");
+            System.out.println("no exception");
+        } catch (InvocationTargetException e) {
+            System.out.println(e.getCause().getClass().getName());
+            System.out.println(e.getCause().getMessage());
+        }
+
+        try {
+            Shape.class.getDeclaredConstructors()[0].newInstance();
+            System.out.println("no exception");
+        } catch (InstantiationException e) {
+            System.out.println("InstantiationException");
+        }
+
+        // Access checks
+        Method secret = findMethod(Other.class, "secret");
+        try {
+            secret.invoke(null);
+            System.out.println("no exception");
+        } catch (IllegalAccessException e) {
+            System.out.println("IllegalAccessException");
+        }
+        secret.setAccessible(true);
+        System.out.println(secret.invoke(null));
+    }
+
+    static void expectFailure(Method method, Object receiver, Object[] args) throws Exception {
+        try {
+            method.invoke(receiver, args);
+            System.out.println("no exception");
+        } catch (IllegalArgumentException e) {
+            System.out.println("IllegalArgumentException");
+        }
+    }
+
+    // Find the constructor whose first parameter has the given type
+    static Constructor<?> findConstructor(Class<?> cls, Class<?> firstParameterType) {
+        Constructor<?>[] constructors = cls.getDeclaredConstructors();
+        for (int i = 0; i < constructors.length; i ++) {
+            Class<?>[] parameterTypes = constructors[i].getParameterTypes();
+            if (parameterTypes.length != 0 && parameterTypes[0] == firstParameterType) {
+                return constructors[i];
+            }
+        }
+
+        return null;
+    }
+
+    static Method findMethod(Class<?> cls, String name) {
+        Method[] methods = cls.getDeclaredMethods();
+        for (int i = 0; i < methods.length; i ++) {
+            if (methods[i].getName().equals(name)) {
+                return methods[i];
+            }
+        }
+
+        return null;
+    }
+}
+
+interface Named {
+    String name();
+}
+
+class Animal {
+    public String speak() {
+        return "...";
+    }
+}
+
+class Dog extends Animal implements Named {
+    public String speak() {
+        return "Woof";
+    }
+
+    public String name() {
+        return "Rex";
+    }
+}
+
+abstract class Shape {
+    public Shape() { }
+}
+
+class Other {
+    private static String secret() {
+        return "secret";
+    }
+}
@@ -0,0 +1,217 @@
+import java.io.IOException;
+import java.lang.reflect.InvocationHandler;
+import java.lang.reflect.Method;
+import java.lang.reflect.Proxy;
+import java.lang.reflect.UndeclaredThrowableException;
+
+public class Test {
+    public interface Calculator {
+        int add(int a, int b);
+        long scale(long value, double factor);
+        boolean check(char c, byte b, short s, float f);
+        String describe(Object o);
+        void reset();
+        int[] values();
+    }
+
+    public interface Reader {
+        String read() throws IOException;
+    }
+
+    interface Greeter {
+        String greet(String name);
+
+        default String greetTwice(String name) {
+            return "unused";
+        }
+    }
+
+    interface Named {
+        String greet(String name);
+    }
+
+    static class LoggingHandler implements InvocationHandler {
+        public Object invoke(Object proxy, Method method, Object[] args) throws Throwable {
+            System.out.print(method.getName());
+            System.out.print(" declared by ");
+            System.out.print(method.getDeclaringClass().getName());
+            System.out.print(" with ");
+            if (args == null) {
+                System.out.println("no args");
+            } else {
+                for (int i = 0; i < args.length; i ++) {
+                    if (i != 0) {
+                        System.out.print(", ");
+                    }
+                    if (args[i] == null) {
+                        System.out.print("null");
+                    } else if (args[i] instanceof Proxy) {
+                        System.out.print("proxy");
+                    } else {
+                        System.out.print(args[i].getClass().getName());
+                        System.out.print(" ");
+                        System.out.print(args[i]);
+                    }
+                }
+                System.out.println();
+            }
+
+            String name = method.getName();
+            if (name.equals("add")) {
+                return Integer.valueOf(((Integer) args[0]).intValue() + ((Integer) args[1]).intValue());
+            } else if (name.equals("scale")) {
+                return Long.valueOf((long) (((Long) args[0]).longValue() * ((Double) args[1]).doubleValue()));
+            } else if (name.equals("check")) {
+                return Boolean.TRUE;
+            } else if (name.equals("describe")) {
+                return "described";
+            } else if (name.equals("values")) {
+                return new int[] { 1, 2, 3 };
+            } else if (name.equals("hashCode")) {
+                return Integer.valueOf(1234);
+            } else if (name.equals("equals")) {
+                return Boolean.valueOf(proxy == args[0]);
+            } else if (name.equals("toString")) {
+                return "CalculatorProxy";
+            } else if (name.equals("greet")) {
+                return "Hello";
+            } else if (name.equals("greetTwice")) {
+                return "Hello Hello";
+            }
+
+            return null;
+        }
+    }
+
+    static class BadHandler implements InvocationHandler {
+        private Object result;
+        private Throwable error;
+
+        BadHandler(Object result, Throwable error) {
+            this.result = result;
+            this.error = error;
+        }
+
+        public Object invoke(Object proxy, Method method, Object[] args) throws Throwable {
+            if (this.error != null) {
+                throw this.error;
+            }
+            return this.result;
+        }
+    }
+
+    public static void main(String[] args) throws Exception {
+        ClassLoader loader = Test.class.getClassLoader();
+        LoggingHandler handler = new LoggingHandler();
+
+        Calculator calculator = (Calculator) Proxy.newProxyInstance(loader, new Class<?>[] { Calculator.class }, handler);
+        System.out.println(calculator.add(2, 3));
+        System.out.println(calculator.scale(10L, 2.5));
+        System.out.println(calculator.check('x', (byte) 1, (short) 2, 3.5f));
+        System.out.println(calculator.describe(null));
+        calculator.reset();
+        System.out.println(calculator.values().length);
+
+        // Methods of `Object`
+        System.out.println(calculator.hashCode());
+        System.out.println(calculator.equals(calculator));
+        System.out.println(calculator.toString());
+
+        // Proxy classes and instances
+        System.out.println(Proxy.isProxyClass(calculator.getClass()));
+        System.out.println(Proxy.isProxyClass(Test.class));
+        System.out.println(Proxy.getInvocationHandler(calculator) == handler);
+        System.out.println(calculator instanceof Proxy);
+        System.out.println(calculator.getClass().getInterfaces()[0] == Calculator.class);
+
+        Object other = Proxy.newProxyInstance(loader, new Class<?>[] { Calculator.class }, new LoggingHandler());
+        System.out.println(other.getClass() == calculator.getClass());
+
+        try {
+            Proxy.getInvocationHandler("not a proxy");
+            System.out.println("no exception");
+        } catch (IllegalArgumentException e) {
+            System.out.println("IllegalArgumentException");
+        }
+
+        // Multiple interfaces, default methods, and non-public interfaces
+        Object multi = Proxy.newProxyInstance(loader, new Class<?>[] { Greeter.class, Named.class, Reader.class }, handler);
+        System.out.println(((Greeter) multi).greet("a"));
+        System.out.println(((Named) multi).greet("b"));
+        System.out.println(((Greeter) multi).greetTwice("c"));
+        System.out.println(multi.getClass().getPackageName().equals(Test.class.getPackageName()));
+
+        // Invalid interfaces
+        try {
+            Proxy.newProxyInstance(loader, new Class<?>[] { String.class }, handler);
+            System.out.println("no exception");
+        } catch (IllegalArgumentException e) {
+            System.out.println("IllegalArgumentException");
+        }
+
+        try {
+            Proxy.newProxyInstance(loader, new Class<?>[] { Calculator.class, Calculator.class }, handler);
+            System.out.println("no exception");
+        } catch (IllegalArgumentException e) {
+            System.out.println("IllegalArgumentException");
+        }
+
+        try {
+            Proxy.newProxyInstance(loader, new Class<?>[] { Calculator.class }, null);
+            System.out.println("no exception");
+        } catch (NullPointerException e) {
+            System.out.println("NullPointerException");
+        }
+
+        // Bad return values
+        Calculator nullReturning = (Calculator) Proxy.newProxyInstance(loader, new Class<?>[] { Calculator.class }, new BadHandler(null, null));
+        try {
+            nullReturning.add(1, 2);
+            System.out.println("no exception");
+        } catch (NullPointerException e) {
+            System.out.println("NullPointerException");
+        }
+
+        Calculator wrongReturning = (Calculator) Proxy.newProxyInstance(loader, new Class<?>[] { Calculator.class }, new BadHandler(Long.valueOf(1), null));
+        try {
+            wrongReturning.add(1, 2);
+            System.out.println("no exception");
+        } catch (ClassCastException e) {
+            System.out.println("ClassCastException");
+        }
+        try {
+            wrongReturning.describe("x");
+            System.out.println("no exception");
+        } catch (ClassCastException e) {
+            System.out.println("ClassCastException");
+        }
+
+        // Exceptions
+        IOException ioException = new IOException("io");
+        Reader reader = (Reader) Proxy.newProxyInstance(loader, new Class<?>[] { Reader.class }, new BadHandler(null, ioException));
+        try {
+            reader.read();
+            System.out.println("no exception");
+        } catch (IOException e) {
+            System.out.println(e == ioException);
+        }
+
+        Calculator throwing = (Calculator) Proxy.newProxyInstance(loader, new Class<?>[] { Calculator.class }, new BadHandler(null, ioException));
+        try {
+            throwing.reset();
+            System.out.println("no exception");
+        } catch (UndeclaredThrowableException e) {
+            System.out.println(e.getUndeclaredThrowable() == ioException);
+            System.out.println(e.getCause() == ioException);
+        }
+
+        IllegalStateException stateException = new IllegalStateException("state");
+        Calculator unchecked = (Calculator) Proxy.newProxyInstance(loader, new Class<?>[] { Calculator.class }, new BadHandler(null, stateException));
+        try {
+            unchecked.reset();
+            System.out.println("no exception");
+        } catch (IllegalStateException e) {
+            System.out.println(e == stateException);
+        }
+    }
+}
@@ -0,0 +1,104 @@
+import java.lang.invoke.*;
+
+public class Test {
+    private String name;
+
+    Test(String name) {
+        this.name = name;
+    }
+
+    public String toString() {
+        return "Test[" + this.name + "]";
+    }
+
+    static class NullString {
+        public String toString() {
+            return null;
+        }
+    }
+
+    static class Noisy {
+        public String toString() {
+            System.out.println("Noisy.toString");
+            return "noisy";
+        }
+    }
+
+    static class Throwing {
+        public String toString() {
+            throw new IllegalStateException("no string");
+        }
+    }
+
+    public static void main(String[] args) throws Throwable {
+        int i = -42;
+        long l = 9000000000L;
+        short s = 7;
+        byte b = -3;
+        char c = 'x';
+        boolean z = true;
+        float f = 1.5f;
+        double d = 0.25;
+        String str = "str";
+        String nullString = null;
+        Object object = new Test("a");
+        int[] array = new int[0];
+
+        System.out.println("int " + i + ", long " + l + ", short " + s + ", byte " + b);
+        System.out.println("char " + c + ", boolean " + z + ", float " + f + ", double " + d);
+        System.out.println("string " + str + ", null " + nullString + ", object " + object);
+        System.out.println(i + l + str);
+        System.out.println(str + i + l);
+        System.out.println("" + c + c + c);
+        System.out.println("array " + (array.getClass() == int[].class));
+        System.out.println("toString null " + new NullString());
+        System.out.println("float " + Float.NaN + " " + Double.NEGATIVE_INFINITY + " " + 1e20 + " " + 100.0f);
+        String unicode = "caf\u00e9 " + c + "\ud83d\ude00";
+        System.out.println(unicode.length() + " " + (int) unicode.charAt(3) + " " + (int) unicode.charAt(7));
+
+        // Recipe markers in literals are passed as constants
+        System.out.println("marker \u0001 and \u0002 " + i);
+
+        // Long concatenations
+        String all = "a" + 1 + "b" + 2 + "c" + 3 + "d" + 4 + "e" + 5 + "f" + 6 + "g" + 7 + "h" + 8 + "i" + 9 + "j" + 10;
+        System.out.println(all);
+
+        String accumulated = "";
+        for (int n = 0; n < 5; n ++) {
+            accumulated += n;
+        }
+        System.out.println(accumulated);
+
+        // Arguments are converted in order
+        System.out.println("first " + new Noisy() + " second " + new Noisy());
+
+        try {
+            System.out.println("throws " + new Throwing());
+        } catch (IllegalStateException e) {
+            System.out.println(e.getMessage());
+        }
+
+        // The bootstrap methods can also be called directly
+        MethodHandles.Lookup lookup = MethodHandles.lookup();
+        MethodType type = MethodType.methodType(String.class, int.class, String.class);
+        CallSite site = StringConcatFactory.makeConcatWithConstants(lookup, "concat", type, "[\u0001|\u0002|\u0001]", "const");
+        MethodHandle target = site.getTarget();
+        System.out.println(target.type());
+        System.out.println((String) target.invokeExact(5, "five"));
+
+        CallSite plain = StringConcatFactory.makeConcat(lookup, "concat", MethodType.methodType(String.class, char.class, double.class));
+        System.out.println((String) plain.getTarget().invokeExact('q', 2.5));
+
+        try {
+            StringConcatFactory.makeConcatWithConstants(lookup, "concat", type, "\u0001");
+        } catch (StringConcatException e) {
+            System.out.println(e.getMessage());
+        }
+
+        try {
+            StringConcatFactory.makeConcatWithConstants(lookup, "concat", type, "\u0001\u0001\u0002");
+        } catch (StringConcatException e) {
+            System.out.println(e.getClass().getName());
+        }
+    }
+}
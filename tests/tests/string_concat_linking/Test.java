@@ -0,0 +1,155 @@
+import java.io.*;
+import java.lang.reflect.InvocationTargetException;
+
+// Assembles a class with string concatenation call sites, one of which has a
+// recipe that doesn't match its descriptor. Like other call sites, they
+// should only be linked when they're first executed.
+public class Test {
+    static ByteArrayOutputStream poolBytes = new ByteArrayOutputStream();
+    static DataOutputStream pool = new DataOutputStream(poolBytes);
+    static int poolCount = 1;
+
+    static int utf8(String string) throws IOException {
+        pool.writeByte(1);
+        pool.writeUTF(string);
+        return poolCount++;
+    }
+
+    static int ref(int tag, int index) throws IOException {
+        pool.writeByte(tag);
+        pool.writeShort(index);
+        return poolCount++;
+    }
+
+    static int pair(int tag, int first, int second) throws IOException {
+        pool.writeByte(tag);
+        pool.writeShort(first);
+        pool.writeShort(second);
+        return poolCount++;
+    }
+
+    static void method(DataOutputStream out, int codeIndex, String name, String descriptor, byte[] code) throws IOException {
+        out.writeShort(0x0009);
+        out.writeShort(utf8(name));
+        out.writeShort(utf8(descriptor));
+        out.writeShort(1);
+        out.writeShort(codeIndex);
+        out.writeInt(12 + code.length);
+        out.writeShort(2);
+        out.writeShort(0);
+        out.writeInt(code.length);
+        out.write(code);
+        out.writeShort(0);
+        out.writeShort(0);
+    }
+
+    static byte[] assemble() throws IOException {
+        int thisClass = ref(7, utf8("Concat"));
+        int superClass = ref(7, utf8("java/lang/Object"));
+        int codeIndex = utf8("Code");
+        int bootstrapIndex = utf8("BootstrapMethods");
+
+        int testClass = ref(7, utf8("Test"));
+        int before = pair(10, testClass, pair(12, utf8("before"), utf8("()V")));
+
+        int factory = ref(7, utf8("java/lang/invoke/StringConcatFactory"));
+        int bootstrapType = pair(12, utf8("makeConcatWithConstants"), utf8("(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;Ljava/lang/invoke/MethodType;Ljava/lang/String;[Ljava/lang/Object;)Ljava/lang/invoke/CallSite;"));
+        int bootstrapRef = pair(10, factory, bootstrapType);
+        pool.writeByte(15);
+        pool.writeByte(6);
+        pool.writeShort(bootstrapRef);
+        int bootstrapHandle = poolCount++;
+
+        int goodRecipe = ref(8, utf8("value=\u0001"));
+        int badRecipe = ref(8, utf8("\u0001 and \u0001"));
+
+        int concatType = pair(12, utf8("concat"), utf8("(I)Ljava/lang/String;"));
+        int goodSite = pair(18, 0, concatType);
+        int badSite = pair(18, 1, concatType);
+
+        ByteArrayOutputStream methodBytes = new ByteArrayOutputStream();
+        DataOutputStream methods = new DataOutputStream(methodBytes);
+
+        // static String works() { before(); return "value=" + 5; }
+        method(methods, codeIndex, "works", "()Ljava/lang/String;", new byte[] {
+            (byte) 0xb8, (byte) (before >> 8), (byte) before,
+            0x08,
+            (byte) 0xba, (byte) (goodSite >> 8), (byte) goodSite, 0, 0,
+            (byte) 0xb0,
+        });
+
+        // static String broken() { before(); return <concat with a bad recipe>(1); }
+        method(methods, codeIndex, "broken", "()Ljava/lang/String;", new byte[] {
+            (byte) 0xb8, (byte) (before >> 8), (byte) before,
+            0x04,
+            (byte) 0xba, (byte) (badSite >> 8), (byte) badSite, 0, 0,
+            (byte) 0xb0,
+        });
+
+        ByteArrayOutputStream bytes = new ByteArrayOutputStream();
+        DataOutputStream out = new DataOutputStream(bytes);
+        out.writeInt(0xCAFEBABE);
+        out.writeShort(0);
+        out.writeShort(55);
+        out.writeShort(poolCount);
+        out.write(poolBytes.toByteArray());
+        out.writeShort(0x0021);
+        out.writeShort(thisClass);
+        out.writeShort(superClass);
+        out.writeShort(0);
+        out.writeShort(0);
+        out.writeShort(2);
+        out.write(methodBytes.toByteArray());
+
+        out.writeShort(1);
+        out.writeShort(bootstrapIndex);
+        out.writeInt(2 + 2 * 6);
+        out.writeShort(2);
+        out.writeShort(bootstrapHandle);
+        out.writeShort(1);
+        out.writeShort(goodRecipe);
+        out.writeShort(bootstrapHandle);
+        out.writeShort(1);
+        out.writeShort(badRecipe);
+
+        return bytes.toByteArray();
+    }
+
+    public static void before() {
+        System.out.println("before");
+    }
+
+    static class Loader extends ClassLoader {
+        Loader() {
+            super(Test.class.getClassLoader());
+        }
+
+        Class<?> define(String name, byte[] data) {
+            return defineClass(name, data, 0, data.length);
+        }
+    }
+
+    static Object call(Class<?> cls, String name) throws Throwable {
+        try {
+            return cls.getMethod(name).invoke(null);
+        } catch (InvocationTargetException e) {
+            throw e.getCause();
+        }
+    }
+
+    public static void main(String[] args) throws Throwable {
+        Class<?> cls = new Loader().define("Concat", assemble());
+
+        System.out.println(call(cls, "works"));
+        System.out.println(call(cls, "works"));
+
+        for (int i = 0; i < 2; i++) {
+            try {
+                call(cls, "broken");
+                System.out.println("linked");
+            } catch (BootstrapMethodError e) {
+                System.out.println("BootstrapMethodError");
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+public class Test {
+    public static void main(String[] args) {
+        // Characters outside the BMP are stored in the class file as a
+        // surrogate pair, with each surrogate encoded separately
+        String emoji = "😀";
+        System.out.println(emoji.length());
+        System.out.println((int) emoji.charAt(0));
+        System.out.println((int) emoji.charAt(1));
+
+        String mixed = "a𐐷b\u0000c𝄞";
+        System.out.println(mixed.length());
+        for (int i = 0; i < mixed.length(); i ++) {
+            System.out.println((int) mixed.charAt(i));
+        }
+
+        System.out.println(emoji.equals("\uD83D" + "\uDE00"));
+    }
+}
@@ -0,0 +1,254 @@
+import java.io.*;
+import java.lang.reflect.InvocationTargetException;
+import java.lang.reflect.Method;
+import java.util.ArrayList;
+
+// javac only emits calls to `SwitchBootstraps` for pattern switches, which
+// need a newer Java version, so this test assembles a class that calls them.
+class ClassAssembler {
+    private ByteArrayOutputStream poolBytes = new ByteArrayOutputStream();
+    private DataOutputStream pool = new DataOutputStream(poolBytes);
+    private int poolCount = 1;
+
+    private ArrayList<int[]> bootstrapMethods = new ArrayList<int[]>();
+
+    private ByteArrayOutputStream methodBytes = new ByteArrayOutputStream();
+    private DataOutputStream methods = new DataOutputStream(methodBytes);
+    private int methodCount = 0;
+
+    int utf8(String string) throws IOException {
+        pool.writeByte(1);
+        pool.writeUTF(string);
+        return poolCount++;
+    }
+
+    int integer(int value) throws IOException {
+        pool.writeByte(3);
+        pool.writeInt(value);
+        return poolCount++;
+    }
+
+    int string(String value) throws IOException {
+        int utf8 = utf8(value);
+        pool.writeByte(8);
+        pool.writeShort(utf8);
+        return poolCount++;
+    }
+
+    int cls(String name) throws IOException {
+        int utf8 = utf8(name);
+        pool.writeByte(7);
+        pool.writeShort(utf8);
+        return poolCount++;
+    }
+
+    int nameAndType(String name, String descriptor) throws IOException {
+        int nameIndex = utf8(name);
+        int descriptorIndex = utf8(descriptor);
+        pool.writeByte(12);
+        pool.writeShort(nameIndex);
+        pool.writeShort(descriptorIndex);
+        return poolCount++;
+    }
+
+    int member(int tag, String owner, String name, String descriptor) throws IOException {
+        int ownerIndex = cls(owner);
+        int natIndex = nameAndType(name, descriptor);
+        pool.writeByte(tag);
+        pool.writeShort(ownerIndex);
+        pool.writeShort(natIndex);
+        return poolCount++;
+    }
+
+    int methodHandle(int kind, int tag, String owner, String name, String descriptor) throws IOException {
+        int reference = member(tag, owner, name, descriptor);
+        pool.writeByte(15);
+        pool.writeByte(kind);
+        pool.writeShort(reference);
+        return poolCount++;
+    }
+
+    int invokeDynamic(String name, String descriptor, int bootstrap, int[] args) throws IOException {
+        int[] bootstrapMethod = new int[args.length + 1];
+        bootstrapMethod[0] = bootstrap;
+        System.arraycopy(args, 0, bootstrapMethod, 1, args.length);
+        bootstrapMethods.add(bootstrapMethod);
+
+        int natIndex = nameAndType(name, descriptor);
+        pool.writeByte(18);
+        pool.writeShort(bootstrapMethods.size() - 1);
+        pool.writeShort(natIndex);
+        return poolCount++;
+    }
+
+    // Add a static method that passes its two arguments, a reference and an
+    // int, to the call site at `index` and returns the result.
+    void switchMethod(String name, String descriptor, int index) throws IOException {
+        int nameIndex = utf8(name);
+        int descriptorIndex = utf8(descriptor);
+        int codeIndex = utf8("Code");
+
+        methods.writeShort(0x0009);
+        methods.writeShort(nameIndex);
+        methods.writeShort(descriptorIndex);
+        methods.writeShort(1);
+        methods.writeShort(codeIndex);
+        methods.writeInt(12 + 8);
+        methods.writeShort(2);
+        methods.writeShort(2);
+        methods.writeInt(8);
+        methods.writeByte(0x2a);
+        methods.writeByte(0x1b);
+        methods.writeByte(0xba);
+        methods.writeShort(index);
+        methods.writeShort(0);
+        methods.writeByte(0xac);
+        methods.writeShort(0);
+        methods.writeShort(0);
+        methodCount++;
+    }
+
+    byte[] finish(String name) throws IOException {
+        int thisIndex = cls(name);
+        int superIndex = cls("java/lang/Object");
+        int bootstrapIndex = utf8("BootstrapMethods");
+
+        ByteArrayOutputStream bytes = new ByteArrayOutputStream();
+        DataOutputStream out = new DataOutputStream(bytes);
+        out.writeInt(0xCAFEBABE);
+        out.writeShort(0);
+        out.writeShort(61);
+        out.writeShort(poolCount);
+        out.write(poolBytes.toByteArray());
+        out.writeShort(0x0021);
+        out.writeShort(thisIndex);
+        out.writeShort(superIndex);
+        out.writeShort(0);
+        out.writeShort(0);
+        out.writeShort(methodCount);
+        out.write(methodBytes.toByteArray());
+
+        int attributeLength = 2;
+        for (int i = 0; i < bootstrapMethods.size(); i++) {
+            attributeLength += 2 + bootstrapMethods.get(i).length * 2;
+        }
+
+        out.writeShort(1);
+        out.writeShort(bootstrapIndex);
+        out.writeInt(attributeLength);
+        out.writeShort(bootstrapMethods.size());
+        for (int i = 0; i < bootstrapMethods.size(); i++) {
+            int[] bootstrapMethod = bootstrapMethods.get(i);
+            out.writeShort(bootstrapMethod[0]);
+            out.writeShort(bootstrapMethod.length - 1);
+            for (int j = 1; j < bootstrapMethod.length; j++) {
+                out.writeShort(bootstrapMethod[j]);
+            }
+        }
+
+        return bytes.toByteArray();
+    }
+}
+
+class Loader extends ClassLoader {
+    Loader() {
+        super(Test.class.getClassLoader());
+    }
+
+    Class<?> define(String name, byte[] data) {
+        return defineClass(name, data, 0, data.length);
+    }
+}
+
+public class Test {
+    public enum Color {
+        RED, GREEN, BLUE
+    }
+
+    static Class<?> cls;
+
+    static void print(String name, Class<?> type, Object target, int restart) throws Throwable {
+        Method method = cls.getMethod(name, type, int.class);
+
+        try {
+            System.out.println(method.invoke(null, target, restart));
+        } catch (InvocationTargetException e) {
+            if (e.getCause() instanceof BootstrapMethodError) {
+                System.out.println(e.getCause().getCause().getClass().getName());
+            } else {
+                throw e.getCause();
+            }
+        }
+    }
+
+    public static void main(String[] args) throws Throwable {
+        final int INVOKE_STATIC = 6;
+        final int METHOD = 10;
+
+        StringBuilder bootstrapDescriptor = new StringBuilder();
+        bootstrapDescriptor.append("(Ljava/lang/invoke/MethodHandles$Lookup;Ljava/lang/String;");
+        bootstrapDescriptor.append("Ljava/lang/invoke/MethodType;[Ljava/lang/Object;)Ljava/lang/invoke/CallSite;");
+
+        ClassAssembler assembler = new ClassAssembler();
+
+        int typeSwitch = assembler.methodHandle(INVOKE_STATIC, METHOD, "java/lang/runtime/SwitchBootstraps", "typeSwitch", bootstrapDescriptor.toString());
+        int enumSwitch = assembler.methodHandle(INVOKE_STATIC, METHOD, "java/lang/runtime/SwitchBootstraps", "enumSwitch", bootstrapDescriptor.toString());
+
+        int types = assembler.invokeDynamic("typeSwitch", "(Ljava/lang/Object;I)I", typeSwitch, new int[] {
+            assembler.cls("java/lang/String"),
+            assembler.cls("java/lang/CharSequence"),
+            assembler.integer(7),
+            assembler.string("text"),
+            assembler.cls("java/lang/Number"),
+        });
+        assembler.switchMethod("types", "(Ljava/lang/Object;I)I", types);
+
+        int noLabels = assembler.invokeDynamic("typeSwitch", "(Ljava/lang/Object;I)I", typeSwitch, new int[0]);
+        assembler.switchMethod("noLabels", "(Ljava/lang/Object;I)I", noLabels);
+
+        int colors = assembler.invokeDynamic("enumSwitch", "(LTest$Color;I)I", enumSwitch, new int[] {
+            assembler.string("BLUE"),
+            assembler.string("RED"),
+            assembler.cls("Test$Color"),
+        });
+        assembler.switchMethod("colors", "(LTest$Color;I)I", colors);
+
+        int badLabel = assembler.invokeDynamic("typeSwitch", "(Ljava/lang/Object;I)I", typeSwitch, new int[] {
+            assembler.methodHandle(INVOKE_STATIC, METHOD, "Test", "main", "([Ljava/lang/String;)V"),
+        });
+        assembler.switchMethod("badLabel", "(Ljava/lang/Object;I)I", badLabel);
+
+        int notEnum = assembler.invokeDynamic("enumSwitch", "(Ljava/lang/Object;I)I", enumSwitch, new int[0]);
+        assembler.switchMethod("notEnum", "(Ljava/lang/Object;I)I", notEnum);
+
+        cls = new Loader().define("Switches", assembler.finish("Switches"));
+
+        print("types", Object.class, "text", 0);
+        print("types", Object.class, "text", 1);
+        print("types", Object.class, "text", 2);
+        print("types", Object.class, "text", 4);
+        print("types", Object.class, new StringBuilder("sb"), 0);
+        print("types", Object.class, 7, 0);
+        print("types", Object.class, 7, 3);
+        print("types", Object.class, (short) 7, 0);
+        print("types", Object.class, (char) 7, 0);
+        print("types", Object.class, 7L, 0);
+        print("types", Object.class, 8, 0);
+        print("types", Object.class, 1.5, 0);
+        print("types", Object.class, new Object(), 0);
+        print("types", Object.class, null, 0);
+        print("types", Object.class, "text", 5);
+
+        print("noLabels", Object.class, "text", 0);
+        print("noLabels", Object.class, null, 0);
+
+        print("colors", Color.class, Color.RED, 0);
+        print("colors", Color.class, Color.GREEN, 0);
+        print("colors", Color.class, Color.BLUE, 0);
+        print("colors", Color.class, Color.BLUE, 1);
+        print("colors", Color.class, null, 0);
+
+        print("badLabel", Object.class, "text", 0);
+        print("notEnum", Object.class, "text", 0);
+    }
+}
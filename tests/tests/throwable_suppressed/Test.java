@@ -0,0 +1,113 @@
+import java.io.*;
+
+public class Test {
+    static class Resource implements AutoCloseable {
+        private String name;
+
+        Resource(String name) {
+            this.name = name;
+            System.out.println("Opened " + name);
+        }
+
+        public void close() {
+            System.out.println("Closing " + this.name);
+            throw new IllegalStateException("Failed to close " + this.name);
+        }
+    }
+
+    static class LocalizedException extends RuntimeException {
+        LocalizedException(String message) {
+            super(message);
+        }
+
+        public String getLocalizedMessage() {
+            return "localized: " + this.getMessage();
+        }
+    }
+
+    public static void main(String[] args) {
+        try {
+            Resource first = new Resource("first");
+            Resource second = new Resource("second");
+            Exception thrown = null;
+            try {
+                throw new UnsupportedOperationException("body");
+            } catch (Exception e) {
+                thrown = e;
+            }
+
+            try {
+                second.close();
+            } catch (Exception e) {
+                thrown.addSuppressed(e);
+            }
+
+            try {
+                first.close();
+            } catch (Exception e) {
+                thrown.addSuppressed(e);
+            }
+
+            throw thrown;
+        } catch (Exception e) {
+            printThrowable(e);
+        }
+
+        try (Resource resource = new Resource("resource")) {
+            System.out.println("In body");
+        } catch (IllegalStateException e) {
+            printThrowable(e);
+        }
+
+        RuntimeException empty = new RuntimeException("empty");
+        System.out.println(empty.getSuppressed().length);
+
+        try {
+            empty.addSuppressed(empty);
+        } catch (IllegalArgumentException e) {
+            System.out.println(e + " caused by " + e.getCause());
+        }
+
+        try {
+            empty.addSuppressed(null);
+        } catch (NullPointerException e) {
+            System.out.println(e);
+        }
+
+        Throwable[] copy = empty.getSuppressed();
+        empty.addSuppressed(new Error("added"));
+        System.out.println(copy.length + " " + empty.getSuppressed().length);
+
+        LocalizedException localized = new LocalizedException("message");
+        System.out.println(localized.getMessage());
+        System.out.println(localized);
+
+        RuntimeException outer = new RuntimeException("outer");
+        RuntimeException inner = new RuntimeException("inner", outer);
+        outer.initCause(inner);
+        outer.addSuppressed(new Error("suppressed", new LocalizedException("deep")));
+        printThrowable(outer);
+    }
+
+    // Print the output of `printStackTrace`, without the frames of stack
+    // traces, since those contain line numbers
+    static void printThrowable(Throwable t) {
+        ByteArrayOutputStream output = new ByteArrayOutputStream();
+        t.printStackTrace(new PrintStream(output));
+
+        // The output is ASCII
+        byte[] bytes = output.toByteArray();
+        StringBuilder builder = new StringBuilder();
+        for (int i = 0; i < bytes.length; i ++) {
+            builder.append((char) bytes[i]);
+        }
+
+        String[] lines = builder.toString().split("\n");
+        for (int i = 0; i < lines.length; i ++) {
+            String trimmed = lines[i].trim();
+            if (!trimmed.startsWith("at ") && !trimmed.startsWith("...")) {
+                System.out.println(lines[i]);
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::output;
+use crate::output_to_err;
+
+use rjvm_core::ConsoleBackend;
+
+use std::cell::Cell;
+
+// The program runs without yielding to the page, so input can't be typed while
+// it's running; instead, all of `System.in` is provided before it starts.
+pub struct WebConsoleBackend {
+    stdin: Vec<u8>,
+    stdin_position: Cell<usize>,
+}
+
+impl WebConsoleBackend {
+    pub fn new(stdin: Vec<u8>) -> Self {
+        Self {
+            stdin,
+            stdin_position: Cell::new(0),
+        }
+    }
+}
+
+impl ConsoleBackend for WebConsoleBackend {
+    fn write_stdout(&self, data: &[u8]) {
+        // `output` does not buffer, so no need to flush
+        output(&String::from_utf8_lossy(data));
+    }
+
+    fn write_stderr(&self, data: &[u8]) {
+        // `output_to_err` does not buffer, so no need to flush
+        output_to_err(&String::from_utf8_lossy(data));
+    }
+
+    fn read_stdin(&self, buf: &mut [u8]) -> usize {
+        let position = self.stdin_position.get();
+        let remaining = &self.stdin[position..];
+
+        let count = buf.len().min(remaining.len());
+        buf[..count].copy_from_slice(&remaining[..count]);
+        self.stdin_position.set(position + count);
+
+        count
+    }
+
+    fn stdin_available(&self) -> usize {
+        self.stdin.len() - self.stdin_position.get()
+    }
+}
@@ -1,7 +1,7 @@
 use wasm_bindgen::prelude::*;
 
-mod loader_backend;
-mod native_impl;
+mod console_backend;
+mod platform;
 mod runner;
 
 #[wasm_bindgen]
@@ -17,7 +17,7 @@ extern "C" {
 }
 
 #[wasm_bindgen(js_name = "fileLoaded")]
-pub fn file_loaded(name: &str, data: &[u8], args: Vec<String>) {
+pub fn file_loaded(name: &str, data: &[u8], args: Vec<String>, stdin: &str) {
     let is_jar = name.ends_with(".jar");
 
     output("rjvm ");
@@ -31,7 +31,7 @@ pub fn file_loaded(name: &str, data: &[u8], args: Vec<String>) {
     }
     output("\n");
 
-    runner::run_file(name, data, args);
+    runner::run_file(name, data, args, stdin.as_bytes().to_vec());
     output("$ ");
 }
 
@@ -0,0 +1,79 @@
+use rjvm_globals::platform::{FileMetadata, OpenMode, Platform, PlatformError, SeekFrom};
+use rjvm_globals::vfs::MemoryFilesystem;
+
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date, js_name = "now")]
+    fn date_now() -> f64;
+}
+
+// There's no filesystem on web, so files are kept in a `MemoryFilesystem`,
+// which is also used to load classes
+pub struct WebPlatform {
+    filesystem: MemoryFilesystem,
+}
+
+impl WebPlatform {
+    pub fn new(filesystem: MemoryFilesystem) -> Self {
+        filesystem.set_clock(|| date_now() as i64);
+
+        Self { filesystem }
+    }
+}
+
+impl Platform for WebPlatform {
+    fn open(&self, path: &str, mode: OpenMode) -> Result<u32, PlatformError> {
+        self.filesystem.open(path, mode)
+    }
+
+    fn read(&self, handle: u32, buf: &mut [u8]) -> Result<usize, PlatformError> {
+        self.filesystem.read(handle, buf)
+    }
+
+    fn write(&self, handle: u32, data: &[u8]) -> Result<(), PlatformError> {
+        self.filesystem.write(handle, data)
+    }
+
+    fn flush(&self, handle: u32) -> Result<(), PlatformError> {
+        self.filesystem.flush(handle)
+    }
+
+    fn seek(&self, handle: u32, position: SeekFrom) -> Result<u64, PlatformError> {
+        self.filesystem.seek(handle, position)
+    }
+
+    fn close(&self, handle: u32) -> Result<(), PlatformError> {
+        self.filesystem.close(handle)
+    }
+
+    fn stat(&self, path: &str) -> Option<FileMetadata> {
+        self.filesystem.stat(path)
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<String>, PlatformError> {
+        self.filesystem.list(path)
+    }
+
+    fn delete(&self, path: &str) -> Result<(), PlatformError> {
+        self.filesystem.delete(path)
+    }
+
+    fn rename(&self, from: &str, to: &str) -> Result<(), PlatformError> {
+        self.filesystem.rename(from, to)
+    }
+
+    fn current_time_millis(&self) -> i64 {
+        date_now() as i64
+    }
+
+    fn exit(&self, code: i32) -> ! {
+        // No exit function on web
+        panic!("System.exit called (code {})", code)
+    }
+
+    fn current_dir(&self) -> String {
+        self.filesystem.current_dir()
+    }
+}
@@ -1,11 +1,13 @@
-use crate::loader_backend;
-use crate::native_impl;
+use crate::console_backend;
 use crate::output_to_err;
+use crate::platform;
 
 use rjvm_core::{
     Class, Context, Jar, JvmString, MethodDescriptor, Object, ResourceLoadSource, Value,
 };
+use rjvm_globals::vfs::MemoryFilesystem;
 use rjvm_globals::{GLOBALS_BASE_JAR, GLOBALS_DESKTOP_JAR, native_impl as base_native_impl};
+use std::rc::Rc;
 
 fn init_main_class(
     context: &Context,
@@ -69,14 +71,21 @@ fn init_main_class(
         .map_err(|e| e.display(context))
 }
 
-pub(crate) fn run_file(class_name: &str, class_data: &[u8], args: Vec<String>) {
+pub(crate) fn run_file(class_name: &str, class_data: &[u8], args: Vec<String>, stdin: Vec<u8>) {
     let is_jar = class_name.ends_with(".jar");
 
+    // The uploaded file is the only file in the filesystem to begin with
+    let filesystem = MemoryFilesystem::new();
+    filesystem
+        .write_file(class_name, class_data)
+        .expect("Filesystem should be writable");
+
     // Initialize JVM
-    let loader = loader_backend::WebLoaderBackend::new(class_name, class_data);
-    Context::init(Box::new(loader));
+    Context::init(Box::new(filesystem.clone()));
 
     Context::with(|context| {
+        context.set_console_backend(Rc::new(console_backend::WebConsoleBackend::new(stdin)));
+
         // Load globals
         let globals_base_jar = Jar::from_bytes(context.gc_ctx(), GLOBALS_BASE_JAR.to_vec())
             .expect("Builtin globals should be valid");
@@ -85,8 +94,8 @@ pub(crate) fn run_file(class_name: &str, class_data: &[u8], args: Vec<String>) {
             .expect("Builtin globals should be valid");
         context.add_bootstrap_jar(globals_desktop_jar);
 
-        base_native_impl::register_native_mappings(&context);
-        native_impl::register_native_mappings(&context);
+        let platform = Rc::new(platform::WebPlatform::new(filesystem));
+        base_native_impl::register_native_mappings(&context, platform);
 
         context.load_builtins();
 
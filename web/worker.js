@@ -24,11 +24,12 @@ self.addEventListener("message", function(e) {
 
         case "runFile":
             let args = e.data.args;
+            let stdin = e.data.stdin;
             if (currentFileName == null || currentFileData == null) {
                 throw new Error("Called \"runFile\" with null name or data");
             }
 
-            fileLoaded(currentFileName, currentFileData, args);
+            fileLoaded(currentFileName, currentFileData, args, stdin);
             break;
     }
 });